@@ -52,7 +52,7 @@ fn benchmark_http(c: &mut Criterion) {
                                     max_bytes: Some(num_lines * line_size),
                                     ..Default::default()
                                 },
-                                encoding: sinks::http::Encoding::Text.into(),
+                                encoding: sinks::util::encoding::StandardEncodings::Text.into(),
                                 request: Default::default(),
                                 tls: Default::default(),
                             },
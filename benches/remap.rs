@@ -50,6 +50,7 @@ fn benchmark_remap(c: &mut Criterion) {
                 .to_string(),
                 drop_on_error: true,
                 drop_on_abort: true,
+                reroute_dropped: false,
             })
             .unwrap(),
         );
@@ -111,6 +112,7 @@ fn benchmark_remap(c: &mut Criterion) {
                 source: ".bar = parse_json!(string!(.foo))".to_owned(),
                 drop_on_error: true,
                 drop_on_abort: true,
+                reroute_dropped: false,
             })
             .unwrap(),
         );
@@ -181,6 +183,7 @@ fn benchmark_remap(c: &mut Criterion) {
                 .to_owned(),
                 drop_on_error: true,
                 drop_on_abort: true,
+                reroute_dropped: false,
             })
             .unwrap(),
         );
@@ -85,6 +85,7 @@ pub fn add_fields(criterion: &mut Criterion) {
                     .to_string(),
                     drop_on_error: true,
                     drop_on_abort: true,
+                    reroute_dropped: false,
                 })
                 .unwrap(),
             ),
@@ -50,6 +50,17 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		reload: {
+			common:   false
+			required: false
+			type: bool: default: false
+			description: """
+				Whether the `reloadConfig` mutation is enabled. It replaces the
+				running configuration, and the secret backends of the new one
+				are queried, which may run commands, so only enable it if the
+				API can't be reached by untrusted clients.
+				"""
+		}
 	}
 
 	endpoints: {
@@ -58,6 +69,24 @@ api: {
 				description: """
 					Main endpoint for receiving and processing
 					GraphQL queries.
+
+					The `reloadConfig` mutation, enabled by the `reload`
+					option, validates a new config the same way as
+					`vector validate` and applies it. With `dryRun: true` it
+					only builds the new components, and returns which
+					components would be added, changed, reconfigured or
+					removed. Secrets aren't retrieved and healthchecks aren't
+					run for a dry run. Errors are reported per
+					component, and the running config is restored if the new
+					one fails its healthchecks. No healthchecks run if they
+					are disabled by `healthchecks.enabled`, so the new config
+					is then kept even if its sinks are unhealthy.
+
+					The `componentMetricHistory` query returns the
+					throughput and errors of components for each of the
+					last 300 seconds, with errors broken down by metric
+					and `error_type`. The history is kept in memory and
+					starts empty whenever Vector starts.
 					"""
 				responses: {
 					"200": {
@@ -177,6 +177,77 @@ cli: {
 	}
 
 	commands: {
+		"buffer": {
+			description: """
+				Inspect and repair the disk buffers of a stopped Vector instance. The
+				`list` subcommand lists the buffers of all sinks with their number of
+				events and bytes, `dump <sink>` prints events as JSON along with their
+				keys, `truncate <sink>` deletes a range of keys and `skip-corrupted <sink>`
+				deletes the records which can't be decoded.
+				"""
+
+			flags: _default_flags
+
+			options: {
+				"config": {
+					_short: "c"
+					description: """
+						Read the `data_dir` from one or more configuration files.
+						"""
+					type:    "string"
+					default: "/etc/vector/vector.toml"
+					env_var: "VECTOR_CONFIG"
+				}
+				"data-dir": {
+					description: "The data directory to use instead of the `data_dir` of the configuration"
+					type:        "string"
+				}
+			}
+		}
+
+		"config": {
+			description: """
+				Print the configuration that results from merging the configuration
+				files, interpolating environment variables and secrets, and expanding
+				component templates and overlays, then exit. The result is checked the
+				same way as `vector validate` checks the topology. Secrets are redacted,
+				as are credentials such as `password` and `token` options wherever their
+				value comes from, and keys are sorted, so the output of two deployments
+				can be compared.
+				"""
+
+			flags: _default_flags & {
+				"include-defaults": {
+					description: "Include the options left to their default value"
+				}
+				"print": {
+					description: "Print the merged configuration, which is always done. Kept for compatibility"
+				}
+			}
+
+			options: {
+				"config": {
+					_short: "c"
+					description: """
+						Read configuration from one or more files. Wildcard paths are
+						supported. File format is detected from the file name.
+						"""
+					type:    "string"
+					default: "/etc/vector/vector.toml"
+					env_var: "VECTOR_CONFIG"
+				}
+				"format": {
+					description: "Format of the printed configuration"
+					default:     "toml"
+					enum: {
+						toml: "Output the configuration as TOML"
+						json: "Output the configuration as JSON"
+						yaml: "Output the configuration as YAML"
+					}
+				}
+			}
+		}
+
 		"generate": {
 			description: "Generate a Vector configuration containing a list of components"
 
@@ -203,6 +274,61 @@ cli: {
 			}
 		}
 
+		"generate-schema": {
+			description: """
+				Print the JSON Schema of Vector configuration files, then exit. It covers
+				the global options and every available source, transform and sink.
+				The global options and some components, such as the `socket` source and
+				sink, are described from their configuration types, with every option and
+				its default. The options of other components are inferred from their
+				example configuration, which doesn't show every option, so the schema is
+				meant for completion and documentation in editors: a configuration
+				matching it may still be invalid, use `vector validate` to check it.
+				"""
+
+			flags: _default_flags
+
+			options: {
+				"file": {
+					description: "Write the schema to a file instead of printing it"
+					type:        "string"
+					example:     "vector.schema.json"
+				}
+			}
+		}
+
+		"graph": {
+			description: """
+				Render the topology of a configuration as a DOT or Mermaid graph, then
+				exit. Nodes are colored by component kind and edges are labelled with
+				the type of their events, prefixed by the name of the output they come
+				from for named outputs.
+				"""
+
+			flags: _default_flags
+
+			options: {
+				"config": {
+					_short: "c"
+					description: """
+						Read configuration from one or more files. Wildcard paths are
+						supported. File format is detected from the file name.
+						"""
+					type:    "string"
+					default: "/etc/vector/vector.toml"
+					env_var: "VECTOR_CONFIG"
+				}
+				"format": {
+					description: "Format of the graph"
+					default:     "dot"
+					enum: {
+						dot:     "Output the graph in the Graphviz DOT language"
+						mermaid: "Output the graph as a Mermaid flowchart"
+					}
+				}
+			}
+		}
+
 		"help": {
 			description: "Prints this message or the help of the given subcommand(s)"
 		}
@@ -230,6 +356,12 @@ cli: {
 				Run Vector config unit tests, then exit. This command is experimental and
 				therefore subject to change. For guidance on how to write unit tests check
 				out: \(urls.vector_unit_testing)
+
+				Inputs are inserted on a simulated clock starting at the Unix epoch, and
+				are timestamped with it. Transforms follow it when they rely on timers,
+				like the flushes of `reduce`, but not when they read the current time,
+				like the `now` function of VRL. Delaying inputs with `delay_ms` requires
+				Vector to be built with the `unit-test-clock` feature.
 				"""
 
 			options: {
@@ -257,6 +389,29 @@ cli: {
 					type:    "string"
 					env_var: "VECTOR_CONFIG_YAML"
 				}
+				"junit-report": {
+					description: "Write a JUnit XML report of the test results to this file"
+					type:        "string"
+				}
+				"json-report": {
+					description: "Write a JSON report of the test results to this file"
+					type:        "string"
+				}
+				"snapshot-dir": {
+					description: """
+						Compare the outputs of each test with the snapshot recorded in
+						this directory. Snapshots missing from it are recorded instead.
+						Log fields listed in the `snapshot_ignore` option of a test, such
+						as a UUID added by a transform, are left out of its snapshot.
+						"""
+					type: "string"
+				}
+			}
+
+			flags: _default_flags & {
+				"update-snapshots": {
+					description: "Record the snapshots of all tests again, instead of comparing with them"
+				}
 			}
 
 			args: {
@@ -321,6 +476,13 @@ cli: {
 			description: """
 				Display topology and metrics in the console, for a local or remote Vector
 				instance
+
+				Components can be sorted by any column with the arrow keys or `1`-`7`,
+				and filtered with `/` by name, kind or type, using `name:`, `kind:` and
+				`type:` to narrow a term. `e` shows only the components that reported
+				errors. `Enter` opens a detail pane for the selected component, with its
+				throughput over the last minutes, the fill level of its buffer, its
+				in-flight requests and adaptive concurrency limit, and its errors by type.
 				"""
 
 			flags: _default_flags & {
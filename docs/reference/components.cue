@@ -717,6 +717,119 @@ components: {
 				}
 			}
 
+			_decoding: {
+				common:      false
+				description: "Configures how incoming data is split into frames and how each frame is turned into events."
+				required:    false
+				warnings: []
+				type: object: {
+					examples: []
+					options: {
+						framing: {
+							common:      false
+							description: "How incoming data is split into frames. Each source defaults to the framing it uses without a `decoding` option."
+							required:    false
+							warnings: []
+							type: object: {
+								examples: []
+								options: {
+									method: {
+										description: "The framing method."
+										required:    true
+										warnings: []
+										type: string: {
+											enum: {
+												bytes:               "Each received message, such as a datagram or request body, is a single frame. On a stream, the frame is everything received until the stream is closed."
+												newline_delimited:   "Frames are separated by a newline."
+												character_delimited: "Frames are separated by `delimiter`."
+												length_delimited:    "Frames are prefixed with their length as a 32-bit big-endian integer."
+											}
+											syntax: "literal"
+										}
+									}
+									delimiter: {
+										description:   "The ASCII character separating frames."
+										relevant_when: "method = \"character_delimited\""
+										required:      true
+										warnings: []
+										type: string: {
+											examples: [",", "\t"]
+											syntax: "literal"
+										}
+									}
+									max_length: {
+										common:        false
+										description:   "The maximum length of a frame, in bytes. Longer frames are discarded. Sources reading streams, such as the `tcp` mode of the `socket` source, cap `bytes` frames at their own `max_length` unless this is set."
+										relevant_when: "method = \"bytes\" or method = \"newline_delimited\" or method = \"character_delimited\""
+										required:      false
+										warnings: []
+										type: uint: {
+											default: null
+											unit:    "bytes"
+										}
+									}
+								}
+							}
+						}
+						codec: {
+							common:      false
+							description: "How each frame is turned into events. Frames which fail to parse are reported and skipped."
+							required:    false
+							warnings: []
+							type: string: {
+								default: "bytes"
+								enum: {
+									bytes:  "The frame is used as the message of a log event."
+									json:   "The frame is a JSON object, or an array of JSON objects, whose fields become the fields of the log events."
+									syslog: "The frame is a RFC 3164 or RFC 5424 syslog message. Requires the `codecs-syslog` feature, which builds including the `syslog` source have."
+									native: "The frame is an event in Vector's native protobuf format."
+								}
+								syntax: "literal"
+							}
+						}
+					}
+				}
+			}
+
+			_decompression: {
+				common:      false
+				description: "Limits on request bodies decompressed according to their `Content-Encoding` header, which may be `gzip`, `deflate`, `zstd`, `snappy`, `x-snappy-framed` or `lz4`. Requests exceeding a limit are rejected with a `413 Payload Too Large` response."
+				required:    false
+				warnings: []
+				type: object: {
+					examples: []
+					options: {
+						max_bytes: {
+							common:      false
+							description: "The maximum size of a request body after each decoding step."
+							required:    false
+							warnings: []
+							type: uint: {
+								default: 104857600
+								unit:    "bytes"
+							}
+						}
+						max_bytes_per_encoding: {
+							common:      false
+							description: "Overrides `max_bytes` for individual encodings."
+							required:    false
+							warnings: []
+							type: object: {
+								examples: [{zstd: 52428800}]
+								options: {
+									"*": {
+										description: "The maximum size of a request body after decoding it with this encoding."
+										required:    true
+										warnings: []
+										type: uint: unit: "bytes"
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+
 			_http_basic_auth: {
 				common:      false
 				description: "Options for HTTP Basic Authentication."
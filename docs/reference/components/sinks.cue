@@ -10,6 +10,13 @@ components: sinks: [Name=string]: {
 	features: _
 
 	configuration: {
+		dead_letter: {
+			common:      false
+			description: "Send copies of the events this sink rejects to its `<name>.dead_letter` output, which other components can take as input."
+			required:    false
+			type: bool: default: false
+		}
+
 		if features.send != _|_ && features.send.batch != _|_ {
 			if features.send.batch.enabled {
 				batch: {
@@ -66,11 +73,22 @@ components: sinks: [Name=string]: {
 				type: object: {
 					examples: []
 					options: {
+						max_bytes: {
+							common:        false
+							description:   "The maximum size of the [events][docs.data-model] allowed in the buffer, measured in their encoded form. By default only `max_events` limits the buffer."
+							required:      false
+							relevant_when: "type = \"memory\" or type = \"overflow\""
+							type: uint: {
+								default: null
+								examples: [104900000]
+								unit: "bytes"
+							}
+						}
 						max_events: {
 							common:        true
-							description:   "The maximum number of [events][docs.data-model] allowed in the buffer."
+							description:   "The maximum number of [events][docs.data-model] allowed in the buffer. For `overflow` buffers this is the number of events kept in memory."
 							required:      false
-							relevant_when: "type = \"memory\""
+							relevant_when: "type = \"memory\" or type = \"overflow\""
 							type: uint: {
 								default: 500
 								unit:    "events"
@@ -79,7 +97,7 @@ components: sinks: [Name=string]: {
 						max_size: {
 							description:   "The maximum size of the buffer on the disk."
 							required:      true
-							relevant_when: "type = \"disk\""
+							relevant_when: "type = \"disk\" or type = \"overflow\""
 							type: uint: {
 								examples: [104900000]
 								unit: "bytes"
@@ -100,6 +118,13 @@ components: sinks: [Name=string]: {
 									WARNING: This may stall the sink if disk performance isn't on par with the throughput.
 									For comparison, AWS gp2 volumes are usually too slow for common cases.
 									"""
+									overflow: """
+									Stores the sink's buffer in memory until it is full, then writes to a disk buffer.
+									Events are read back in the order they were received, so once events have been
+									written to disk new events follow them there until the disk buffer is drained.
+									This gives bursts the durability of a disk buffer without its cost in the steady state.
+									Events that are still in memory will be lost if Vector is restarted forcefully.
+									"""
 								}
 								syntax: "literal"
 							}
@@ -113,6 +138,7 @@ components: sinks: [Name=string]: {
 								enum: {
 									block:       "Applies back pressure when the buffer is full. This prevents data loss, but will cause data to pile up on the edge."
 									drop_newest: "Drops new data as it's received. This data is lost. This should be used when performance is the highest priority."
+									drop_oldest: "Evicts the oldest buffered data to make room for new data. This data is lost. This should be used when fresh data is more valuable than stale data, for example during long outages of a downstream service."
 								}
 								syntax: "literal"
 							}
@@ -143,6 +169,15 @@ components: sinks: [Name=string]: {
 							if list.Contains(features.send.compression.algorithms, "gzip") {
 								gzip: "[Gzip](\(urls.gzip)) standard DEFLATE compression."
 							}
+							if list.Contains(features.send.compression.algorithms, "zstd") {
+								zstd: "[Zstandard](\(urls.zstd)) compression, with levels from 1 to 21."
+							}
+							if list.Contains(features.send.compression.algorithms, "snappy") {
+								snappy: "[Snappy](\(urls.snappy)) compression, using the framing format."
+							}
+							if list.Contains(features.send.compression.algorithms, "lz4") {
+								lz4: "[LZ4](\(urls.lz4)) compression, using the frame format."
+							}
 						}
 						syntax: "literal"
 					}
@@ -408,6 +443,33 @@ components: sinks: [Name=string]: {
 	}
 
 	how_it_works: {
+		reloading: {
+			title: "Reloading"
+			body: """
+				When the configuration is reloaded and the options of this sink changed, the
+				sink is rebuilt, starting over with new connections. Only options a sink
+				documents as reconfigurable are updated in place, keeping its buffer and
+				connections. Request settings, such as `request.concurrency`,
+				`request.rate_limit_num` and `request.timeout_secs`, are never reconfigurable,
+				so changing them always rebuilds the sink.
+				"""
+		}
+
+		dead_letter: {
+			title: "Dead-letter output"
+			body: """
+				With `dead_letter` enabled, the events for which the downstream service
+				answered with a permanent failure, such as an HTTP 4xx status, are sent to
+				the `<name>.dead_letter` output. They carry a `dead_letter` object with the
+				`reason` (`rejected`), a `message` with the response, such as its status
+				and body, and the `component_kind`, `component_name` and `component_type` of
+				the sink. Metrics carry the same information as `dead_letter_*` tags.
+
+				The sink keeps a copy of each event until it's delivered, so this increases
+				its memory usage.
+				"""
+		}
+
 		if features.buffer.enabled {
 			if features.send != _|_ {
 				if features.send.batch != _|_ {
@@ -587,7 +649,9 @@ components: sinks: [Name=string]: {
 	}
 
 	telemetry: metrics: {
-		events_in_total:  components.sources.internal_metrics.output.metrics.events_in_total
-		events_out_total: components.sources.internal_metrics.output.metrics.events_out_total
+		buffer_corrupted_records_total: components.sources.internal_metrics.output.metrics.buffer_corrupted_records_total
+		buffer_discarded_events_total:  components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
+		events_in_total:                components.sources.internal_metrics.output.metrics.events_in_total
+		events_out_total:               components.sources.internal_metrics.output.metrics.events_out_total
 	}
 }
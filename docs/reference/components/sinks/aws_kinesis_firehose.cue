@@ -34,7 +34,7 @@ components: sinks: aws_kinesis_firehose: components._aws & {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
 			}
 			request: {
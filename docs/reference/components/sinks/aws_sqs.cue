@@ -22,7 +22,7 @@ components: sinks: aws_sqs: components._aws & {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
 			}
 			request: {
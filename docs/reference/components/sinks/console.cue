@@ -22,7 +22,7 @@ components: sinks: console: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
 			}
 			request: enabled: false
@@ -30,7 +30,11 @@ components: sinks: elasticsearch: {
 			}
 			encoding: {
 				enabled: true
-				codec: enabled: false
+				codec: {
+					enabled: true
+					default: null
+					enum: ["json", "ndjson"]
+				}
 			}
 			request: {
 				enabled:                    true
@@ -20,15 +20,15 @@ components: sinks: file: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["none", "gzip"]
-				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+				algorithms: ["none", "gzip", "zstd", "snappy", "lz4"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
 			}
 			encoding: {
 				enabled: true
 				codec: {
 					enabled: true
 					default: null
-					enum: ["ndjson", "text"]
+					enum: ["csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
 			}
 			request: enabled: false
@@ -89,6 +89,16 @@ components: sinks: file: {
 				to create and write to files in the specified directories.
 				"""
 		}
+
+		reconfiguration: {
+			title: "Reconfiguration"
+			body: """
+				When the configuration is reloaded and only the `path`, `idle_timeout_secs`
+				or `encoding` options of the sink changed, the sink is reconfigured in place
+				instead of being rebuilt. Its buffer and open files are kept, and open files
+				are written with the new encoding until they go idle.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -25,15 +25,15 @@ components: sinks: http: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["none", "gzip"]
-				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+				algorithms: ["none", "gzip", "zstd", "snappy", "lz4"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
 			}
 			encoding: {
 				enabled: true
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "ndjson", "text"]
+					enum: ["csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
 			}
 			request: {
@@ -126,6 +126,18 @@ components: sinks: http: {
 		metrics: null
 	}
 
+	how_it_works: {
+		reconfiguration: {
+			title: "Reconfiguration"
+			body: """
+				When the configuration is reloaded and only the `batch` options of the sink
+				changed, the sink is reconfigured in place instead of being rebuilt. Its
+				buffer and connections are kept, and batches started from then on use the
+				new settings. Changing the `request` options still rebuilds the sink.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		events_discarded_total:  components.sources.internal_metrics.output.metrics.events_discarded_total
 		http_bad_requests_total: components.sources.internal_metrics.output.metrics.http_bad_requests_total
@@ -22,7 +22,7 @@ components: sinks: papertrail: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
 			}
 			send_buffer_bytes: enabled: true
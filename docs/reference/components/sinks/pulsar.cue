@@ -22,7 +22,7 @@ components: sinks: pulsar: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["text", "json"]
+					enum: ["avro", "csv", "json", "logfmt", "native", "ndjson", "text"]
 				}
 			}
 			request: enabled: false
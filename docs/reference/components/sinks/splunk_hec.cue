@@ -33,7 +33,7 @@ components: sinks: splunk_hec: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["csv", "json", "logfmt", "ndjson", "text"]
 				}
 			}
 			request: {
@@ -68,6 +68,7 @@ components: sources: aws_kinesis_firehose: {
 	}
 
 	configuration: {
+		decompression: configuration._decompression
 		address: {
 			description: "The address to listen for connections on"
 			required:    true
@@ -47,6 +47,7 @@ components: sources: aws_s3: components._aws & {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
 		strategy: {
 			common:      false
 			description: "The strategy to use to consume objects from AWS S3."
@@ -274,6 +275,7 @@ components: sources: aws_s3: components._aws & {
 	]
 
 	telemetry: metrics: {
+		batches_not_delivered_total:            components.sources.internal_metrics.output.metrics.batches_not_delivered_total
 		events_in_total:                        components.sources.internal_metrics.output.metrics.events_in_total
 		processed_bytes_total:                  components.sources.internal_metrics.output.metrics.processed_bytes_total
 		sqs_message_delete_failed_total:        components.sources.internal_metrics.output.metrics.sqs_message_delete_failed_total
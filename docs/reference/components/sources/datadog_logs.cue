@@ -64,6 +64,7 @@ components: sources: datadog_logs: {
 	configuration: {
 		acknowledgements: configuration._acknowledgements
 		address:          sources.http.configuration.address
+		decompression:    configuration._decompression
 		store_api_key: {
 			common:      false
 			description: "When incoming events contain a Datadog API key, if this setting is set to `true` the key will kept in the event metadata and will be used if the event is sent to a Datadog sink."
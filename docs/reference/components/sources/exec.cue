@@ -44,6 +44,7 @@ components: sources: exec: {
 	}
 
 	configuration: {
+		decoding: configuration._decoding
 		mode: {
 			description: "The type of exec mechanism."
 			required:    true
@@ -57,6 +57,8 @@ components: sources: file: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
+		decoding:         configuration._decoding
 		exclude: {
 			common:      false
 			description: "Array of file patterns to exclude. [Globbing](#globbing) is supported.*Takes precedence over the [`include` option](#include).*"
@@ -544,6 +546,10 @@ components: sources: file: {
 				behavior, you can set the `ignore_checkpoints` option to `true`.  This
 				will cause Vector to disregard existing checkpoints when determining the
 				starting read position of a file.
+
+				With `acknowledgements` enabled, checkpoints only advance past lines
+				once all sinks delivered their events, so lines that weren't delivered
+				are read again after a restart.
 				"""
 		}
 
@@ -588,6 +594,7 @@ components: sources: file: {
 	}
 
 	telemetry: metrics: {
+		batches_not_delivered_total:   components.sources.internal_metrics.output.metrics.batches_not_delivered_total
 		events_in_total:               components.sources.internal_metrics.output.metrics.events_in_total
 		checkpoint_write_errors_total: components.sources.internal_metrics.output.metrics.checkpoint_write_errors_total
 		checkpoints_total:             components.sources.internal_metrics.output.metrics.checkpoints_total
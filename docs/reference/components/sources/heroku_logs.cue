@@ -71,6 +71,7 @@ components: sources: heroku_logs: {
 		acknowledgements: configuration._acknowledgements
 		address:          sources.http.configuration.address
 		auth:             sources.http.configuration.auth
+		decompression:    configuration._decompression
 		query_parameters: sources.http.configuration.query_parameters
 	}
 
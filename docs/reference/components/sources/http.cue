@@ -60,6 +60,8 @@ components: sources: http: {
 	}
 
 	configuration: {
+		decoding:         configuration._decoding
+		decompression:    configuration._decompression
 		acknowledgements: configuration._acknowledgements
 		address: {
 			description: "The address to accept connections on. The address _must_ include a port."
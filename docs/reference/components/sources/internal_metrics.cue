@@ -178,6 +178,42 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		batches_not_delivered_total: {
+			description:       "The total number of batches of events a source didn't acknowledge upstream because not all sinks delivered them."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				status: {
+					description: "The status reported for the batch."
+					required:    true
+					enum: {
+						errored: "Some of the events failed to be delivered, but may succeed when retried."
+						failed:  "Some of the events were rejected by a sink."
+					}
+				}
+			}
+		}
+		buffer_corrupted_records_total: {
+			description:       "The total number of records skipped by a disk buffer because they couldn't be decoded."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_discarded_events_total: {
+			description:       "The total number of events dropped by a full buffer, either because they were new or evicted as the oldest."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				strategy: {
+					description: "The `when_full` strategy of the buffer."
+					required:    true
+					enum: {
+						"drop_newest": "New events were dropped."
+						"drop_oldest": "The oldest events were evicted."
+					}
+				}
+			}
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints."
 			type:              "counter"
@@ -34,6 +34,8 @@ components: sources: kafka: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
+		decoding:         configuration._decoding
 		auto_offset_reset: {
 			common:      false
 			description: "If offsets for consumer group do not exist, set them using this strategy. [librdkafka documentation][urls.librdkafka_config] for `auto.offset.reset` option for explanation."
@@ -249,6 +251,7 @@ components: sources: kafka: {
 	}
 
 	telemetry: metrics: {
+		batches_not_delivered_total:          components.sources.internal_metrics.output.metrics.batches_not_delivered_total
 		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
 		consumer_offset_updates_failed_total: components.sources.internal_metrics.output.metrics.consumer_offset_updates_failed_total
 		events_failed_total:                  components.sources.internal_metrics.output.metrics.events_failed_total
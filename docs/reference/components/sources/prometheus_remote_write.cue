@@ -60,6 +60,7 @@ components: sources: prometheus_remote_write: {
 
 	configuration: {
 		acknowledgements: configuration._acknowledgements
+		decompression:    configuration._decompression
 		address: {
 			description: "The address to accept connections on. The address _must_ include a port."
 			required:    true
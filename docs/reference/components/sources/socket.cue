@@ -61,6 +61,7 @@ components: sources: socket: {
 	}
 
 	configuration: {
+		decoding: configuration._decoding
 		address: {
 			description:   "The address to listen for connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
 			relevant_when: "mode = `tcp` or `udp`"
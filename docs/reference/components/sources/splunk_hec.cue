@@ -62,6 +62,8 @@ components: sources: splunk_hec: {
 	}
 
 	configuration: {
+		acknowledgements: configuration._acknowledgements
+		decompression:    configuration._decompression
 		address: {
 			common:      true
 			description: "The address to accept connections on."
@@ -98,9 +100,23 @@ components: sources: splunk_hec: {
 		}
 	}
 
+	how_it_works: {
+		indexer_acknowledgements: {
+			title: "Indexer Acknowledgements"
+			body: """
+				With `acknowledgements` enabled, requests must name a channel and are
+				answered with an `ackId`. Clients query the `/services/collector/ack`
+				endpoint of the same channel with these ids to learn which requests had
+				all of their events delivered by the sinks, and resend the others.
+				Delivered and failed ids are forgotten once reported.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
-		events_in_total:           components.sources.internal_metrics.output.metrics.events_in_total
-		http_request_errors_total: components.sources.internal_metrics.output.metrics.http_request_errors_total
-		requests_received_total:   components.sources.internal_metrics.output.metrics.requests_received_total
+		batches_not_delivered_total: components.sources.internal_metrics.output.metrics.batches_not_delivered_total
+		events_in_total:             components.sources.internal_metrics.output.metrics.events_in_total
+		http_request_errors_total:   components.sources.internal_metrics.output.metrics.http_request_errors_total
+		requests_received_total:     components.sources.internal_metrics.output.metrics.requests_received_total
 	}
 }
@@ -45,6 +45,7 @@ components: sources: stdin: {
 	}
 
 	configuration: {
+		decoding: configuration._decoding
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -413,6 +413,14 @@ components: transforms: log_to_metric: {
 				will not be emitted.
 				"""
 		}
+		unmatched_events: {
+			title: "Unmatched Events"
+			body: """
+				Log events from which no metric could be derived, for example because none of
+				the configured fields are present, are sent to the `<transform_name>.unmatched`
+				output. Other components can take it as input to process these logs further.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
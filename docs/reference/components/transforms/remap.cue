@@ -79,6 +79,16 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: true
 		}
+		reroute_dropped: {
+			common:   false
+			required: false
+			description: """
+				Send the events dropped because of `drop_on_error` or `drop_on_abort` to the
+				`<name>.dropped` output, which other components can take as input, instead of
+				discarding them.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -138,6 +148,17 @@ components: transforms: "remap": {
 				reference](\#(urls.vrl_runtime_errors)).
 				"""#
 		}
+		dropped_events: {
+			title: "Dropped Events"
+			body:  #"""
+				With `reroute_dropped` set, the events dropped by `drop_on_error` or
+				`drop_on_abort` are sent unchanged to the `<name>.dropped` output instead.
+				They carry a `dead_letter` object with the `reason` (`error` or `abort`),
+				the error `message`, and the `component_kind`, `component_name` and
+				`component_type` of the transform, so they can be stored and replayed.
+				Metrics carry the same information as `dead_letter_*` tags.
+				"""#
+		}
 		emitting_multiple_events: {
 			title: "Emitting multiple log events"
 			body: #"""
@@ -213,6 +213,107 @@ configuration: {
 				```
 				"""
 		}
+		secrets: {
+			title: "Secrets"
+			body: """
+				Vector can retrieve sensitive values, such as API keys, from secret backends declared in the
+				`secret` table of any of your configuration files. They are referenced with the
+				`SECRET[<backend>.<key>]` syntax in string values. Unlike environment variables, they are
+				interpolated once the file is parsed, so secrets never need to be escaped:
+
+				```toml title="vector.toml"
+				[secret.files]
+				  type = "directory"
+				  path = "/run/secrets"
+
+				[secret.helper]
+				  type = "exec"
+				  command = ["/usr/local/bin/get-secret", "--format", "raw"]
+				  timeout_secs = 5 # default
+
+				[sinks.datadog]
+				  type = "datadog_logs"
+				  inputs = ["in"]
+				  api_key = "SECRET[files.datadog_api_key]"
+				```
+
+				Resolved values are redacted from the output of `vector validate` and `vector config`, from configuration errors
+				and from debug output. Values are only redacted where they aren't part of a longer word.
+				"""
+
+			sub_sections: [
+				{
+					title: "Directory backend"
+					body: """
+						Each secret is read from the file named after its key in `path`. A trailing newline
+						is removed. Keys may not refer to files outside of `path`.
+						"""
+				},
+				{
+					title: "Exec backend"
+					body: """
+						The `command` is run with the key as its last argument and the secret is read from its
+						standard output, without the trailing newline. The command must exit successfully
+						within `timeout_secs` seconds.
+						"""
+				},
+			]
+		}
+		templates: {
+			title: "Templates and overlays"
+			body: """
+				Components that only differ by a few options can be declared once as a template, in the
+				`templates` table, with `%{<param>}` placeholders. Each entry of the `instances` table adds a
+				component made from a template and its parameters:
+
+				```toml title="vector.toml"
+				[templates.sinks.regional]
+				  type = "http"
+				  inputs = ["logs"]
+				  uri = "https://%{region}.logs.example.com"
+				  batch.max_events = "%{batch_size}"
+				  encoding.codec = "json"
+
+				[instances.logs_us_east]
+				  template = "regional"
+				  params = { region = "us-east-1", batch_size = 1000 }
+
+				[instances.logs_eu_west]
+				  template = "regional"
+				  params = { region = "eu-west-1", batch_size = 1000 }
+				```
+
+				Overlays, in the `overlays` table, patch fields of a component declared in any of the
+				configuration files, such as a file specific to an environment:
+
+				```toml title="production.toml"
+				[overlays.sinks.logs_eu_west]
+				  batch.max_events = 5000
+				```
+
+				Use `vector config` to inspect the merged configuration.
+				"""
+
+			sub_sections: [
+				{
+					title: "Parameters"
+					body: """
+						A string made of a single placeholder takes the value of the parameter as is, so that
+						parameters can be numbers, booleans or arrays. Placeholders within longer strings are
+						replaced by the parameter's text.
+						"""
+				},
+				{
+					title: "Merging"
+					body: """
+						Templates and instances are expanded once all the configuration files are merged,
+						then overlays are applied. Tables of an overlay are merged with those of the component
+						while other values replace them. Overlays of the same component in several files are
+						applied in the order of the files.
+						"""
+				},
+			]
+		}
 		wildcards: {
 			title: "Wildcards in identifiers"
 			body: """
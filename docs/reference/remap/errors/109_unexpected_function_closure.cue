@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "109": {
+	title:       "Unexpected function closure"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#function-call) passes a closure to a function that
+		doesn't accept one.
+		"""
+	resolution: """
+		Remove the closure, or call a function that iterates over its argument, such as `map_values`.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				upcase(.message) -> |value| { value }
+				"""#
+			diff: #"""
+				-upcase(.message) -> |value| { value }
+				+upcase(.message)
+				"""#
+		},
+	]
+}
@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "111": {
+	title:       "Missing function closure"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#function-call) invokes a function that requires a
+		closure without passing one.
+		"""
+	resolution: """
+		Add a closure to the function call, as documented by the function.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				map_values(.tags)
+				"""#
+			diff: #"""
+				-map_values(.tags)
+				+map_values(.tags) -> |value| { downcase(value) ?? value }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "120": {
+	title:       "Function closure arity mismatch"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#function-call) passes a closure with more variables
+		than the function provides for each item.
+		"""
+	resolution: """
+		Remove the extra variables to adhere to the function's documented closure.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				map_keys(.) -> |key, value| { downcase(key) }
+				"""#
+			diff: #"""
+				-map_keys(.) -> |key, value| { downcase(key) }
+				+map_keys(.) -> |key| { downcase(key) }
+				"""#
+		},
+	]
+}
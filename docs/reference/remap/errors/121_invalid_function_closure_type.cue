@@ -0,0 +1,29 @@
+package metadata
+
+remap: errors: "121": {
+	title:       "Invalid function closure type"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#function-call) passes a closure that resolves to a
+		type the function doesn't expect.
+		"""
+	rationale: """
+		Functions rely on the result of their closure, for example to decide which items to keep. Checking its type at
+		compile time ensures the function can't fail because of it.
+		"""
+	resolution: """
+		Change the closure so that it resolves to the documented type, potentially by coercing its result.
+		"""
+
+	examples: [
+		{
+			"title": title
+			source: #"""
+				filter(.tags) -> |_key, value| { value }
+				"""#
+			diff: #"""
+				-filter(.tags) -> |_key, value| { value }
+				+filter(.tags) -> |_key, value| { value != null }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,31 @@
+package metadata
+
+remap: errors: "631": {
+	title: "Fallible function closure"
+	description: """
+		You've passed a closure that can fail to a function.
+		"""
+
+	rationale: """
+		In VRL, closures passed to functions need to be infallible themselves. Otherwise, the function could fail for
+		some items of the collection, but not for others.
+		"""
+
+	resolution: """
+		Make the expressions in the closure infallible, potentially by coalescing errors using `??`, or via some other
+		method.
+		"""
+
+	examples: [
+		{
+			"title": "\(title)"
+			source: #"""
+				map_values(.counts) -> |value| { to_int(value) }
+				"""#
+			diff: #"""
+				-map_values(.counts) -> |value| { to_int(value) }
+				+map_values(.counts) -> |value| { to_int(value) ?? 0 }
+				"""#
+		},
+	]
+}
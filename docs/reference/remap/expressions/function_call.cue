@@ -15,7 +15,7 @@ remap: expressions: function_call: {
 
 	grammar: {
 		source: """
-			function ~ abort? ~ "(" ~ arguments? ~ ")"
+			function ~ abort? ~ "(" ~ arguments? ~ ")" ~ closure?
 			"""
 		definitions: {
 			function: {
@@ -95,6 +95,20 @@ remap: expressions: function_call: {
 					}
 				}
 			}
+			closure: {
+				description: """
+					The `closure` is a block of expressions that functions iterating over a collection, such as
+					[`map_values`](\(urls.vrl_functions)#map_values), resolve once for each item. It's prefixed
+					with `->` and lists the variables receiving each item between pipes:
+
+					```vrl
+					map_values(.tags) -> |value| { downcase(value) ?? value }
+					```
+
+					The variables are only defined inside the closure, and their type is derived from the
+					collection. A closure can't fail, and only functions that document a closure accept one.
+					"""
+			}
 		}
 	}
 
@@ -120,5 +134,12 @@ remap: expressions: function_call: {
 				"""#
 			return: ["hello", "world!"]
 		},
+		{
+			title: "Function invocation with a closure"
+			source: #"""
+				filter([1, null, 2]) -> |_index, value| { value != null }
+				"""#
+			return: [1, 2]
+		},
 	]
 }
@@ -19,6 +19,7 @@ remap: {
 		notices:     [string, ...string] | *[]
 
 		arguments: [...#Argument]
+		closure?:  #Closure
 		return: {
 			types: [remap.#Type, ...remap.#Type]
 			rules?: [string, ...string]
@@ -27,6 +28,12 @@ remap: {
 		examples?: [remap.#Example, ...remap.#Example]
 	}
 
+	#Closure: {
+		description: string
+		variables: [string, ...string]
+		return: [remap.#Type, ...remap.#Type]
+	}
+
 	#FunctionCategory: "Array" | "Codec" | "Coerce" | "Convert" | "Debug" | "Enumerate" | "Event" | "Hash" | "IP" | "Number" | "Object" | "Parse" | "Random" | "String" | "System" | "Timestamp" | "Type"
 
 	functions: [Name=string]: #Function & {
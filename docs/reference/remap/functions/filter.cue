@@ -0,0 +1,41 @@
+package metadata
+
+remap: functions: filter: {
+	category: "Enumerate"
+	description: """
+		Filters the items of `value`, keeping only the ones for which the closure returns `true`.
+
+		Because `value` is evaluated before iterating over it, the filtering is guaranteed to terminate.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to filter."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	closure: {
+		description: "Resolved once for each item, returning whether to keep it."
+		variables: ["key", "value"]
+		return: ["boolean"]
+	}
+	internal_failure_reasons: []
+	return: {
+		types: ["array", "object"]
+		rules: [
+			"The return type matches the `value` type.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Drop null values from an array"
+			source: #"""
+				filter(["a", null, "b", null]) -> |_index, value| { value != null }
+				"""#
+			return: ["a", "b"]
+		},
+	]
+}
@@ -0,0 +1,41 @@
+package metadata
+
+remap: functions: for_each: {
+	category: "Enumerate"
+	description: """
+		Iterates over the items of `value`, resolving the closure once for each item.
+
+		The closure receives the key (for objects) or index (for arrays) and the value of each item. Because `value`
+		is evaluated before iterating over it, the iteration is guaranteed to terminate.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to iterate over."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	closure: {
+		description: "Resolved once for each item. Its result is ignored."
+		variables: ["key", "value"]
+		return: ["any"]
+	}
+	internal_failure_reasons: []
+	return: types: ["null"]
+
+	examples: [
+		{
+			title: "Sum the values of an object"
+			source: #"""
+				sum = 0
+				for_each({ "a": 1, "b": 2, "c": 3 }) -> |_key, value| {
+					sum = sum + value
+				}
+				sum
+				"""#
+			return: 6
+		},
+	]
+}
@@ -0,0 +1,44 @@
+package metadata
+
+remap: functions: map_keys: {
+	category: "Enumerate"
+	description: """
+		Maps the keys of the `value` object, replacing each one with the result of the closure.
+
+		Because `value` is evaluated before iterating over it, the mapping is guaranteed to terminate.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object to map the keys of."
+			required:    true
+			type: ["object"]
+		},
+	]
+	closure: {
+		description: "Resolved once for each key, returning the new key."
+		variables: ["key"]
+		return: ["string"]
+	}
+	internal_failure_reasons: []
+	return: {
+		types: ["object"]
+		rules: [
+			"If multiple keys map to the same key, the value of the last key in alphabetical order is kept.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Downcase the keys of an object"
+			source: #"""
+				map_keys({ "Foo": 1, "BAR": 2 }) -> |key| { downcase(key) }
+				"""#
+			return: {
+				bar: 2
+				foo: 1
+			}
+		},
+	]
+}
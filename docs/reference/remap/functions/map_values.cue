@@ -0,0 +1,44 @@
+package metadata
+
+remap: functions: map_values: {
+	category: "Enumerate"
+	description: """
+		Maps the values of `value`, replacing each one with the result of the closure.
+
+		Because `value` is evaluated before iterating over it, the mapping is guaranteed to terminate.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to map the values of."
+			required:    true
+			type: ["array", "object"]
+		},
+	]
+	closure: {
+		description: "Resolved once for each value, returning the new value."
+		variables: ["value"]
+		return: ["any"]
+	}
+	internal_failure_reasons: []
+	return: {
+		types: ["array", "object"]
+		rules: [
+			"The return type matches the `value` type.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Upcase the values of an object"
+			source: #"""
+				map_values({ "a": "foo", "b": "bar" }) -> |value| { upcase(value) }
+				"""#
+			return: {
+				a: "FOO"
+				b: "BAR"
+			}
+		},
+	]
+}
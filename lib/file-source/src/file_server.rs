@@ -3,7 +3,7 @@ use crate::{
     checkpointer::{Checkpointer, CheckpointsView},
     file_watcher::FileWatcher,
     fingerprinter::{FileFingerprint, Fingerprinter},
-    FileSourceInternalEvents, ReadFrom,
+    FileSourceInternalEvents, Line, ReadFrom,
 };
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
@@ -44,11 +44,14 @@ where
     pub ignore_before: Option<DateTime<Utc>>,
     pub max_line_bytes: usize,
     pub line_delimiter: Bytes,
-    pub data_dir: PathBuf,
     pub glob_minimum_cooldown: Duration,
     pub fingerprinter: Fingerprinter,
     pub oldest_first: bool,
     pub remove_after: Option<Duration>,
+    /// When set, read positions are not checkpointed by the server. Instead
+    /// the receiver of the lines updates the checkpoints once it's done with
+    /// them, using the `CheckpointsView` of the `Checkpointer`.
+    pub acknowledgements: bool,
     pub emitter: E,
     pub handle: tokio::runtime::Handle,
 }
@@ -75,10 +78,11 @@ where
         self,
         mut chans: C,
         shutdown: S,
-    ) -> Result<Shutdown, <C as Sink<Vec<(Bytes, String)>>>::Error>
+        mut checkpointer: Checkpointer,
+    ) -> Result<Shutdown, <C as Sink<Vec<Line>>>::Error>
     where
-        C: Sink<Vec<(Bytes, String)>> + Unpin,
-        <C as Sink<Vec<(Bytes, String)>>>::Error: std::error::Error,
+        C: Sink<Vec<Line>> + Unpin,
+        <C as Sink<Vec<Line>>>::Error: std::error::Error,
         S: Future + Unpin + Send + 'static,
         <S as Future>::Output: Clone + Send + Sync,
     {
@@ -89,7 +93,6 @@ where
         let mut backoff_cap: usize = 1;
         let mut lines = Vec::new();
 
-        let mut checkpointer = Checkpointer::new(&self.data_dir);
         checkpointer.read_checkpoints(self.ignore_before);
 
         let mut known_small_files = HashSet::new();
@@ -284,10 +287,12 @@ where
 
                     bytes_read += sz;
 
-                    lines.push((
-                        line,
-                        watcher.path.to_str().expect("not a valid path").to_owned(),
-                    ));
+                    lines.push(Line {
+                        text: line,
+                        filename: watcher.path.to_str().expect("not a valid path").to_owned(),
+                        file_id,
+                        offset: watcher.get_file_position(),
+                    });
 
                     if bytes_read > self.max_read_bytes {
                         maxed_out_reading_single_file = true;
@@ -298,7 +303,9 @@ where
 
                 if bytes_read > 0 {
                     global_bytes_read = global_bytes_read.saturating_add(bytes_read);
-                    checkpoints.update(file_id, watcher.get_file_position());
+                    if !self.acknowledgements {
+                        checkpoints.update(file_id, watcher.get_file_position());
+                    }
                 } else {
                     // Should the file be removed
                     if let Some(grace_period) = self.remove_after {
@@ -12,12 +12,25 @@ mod internal_events;
 mod metadata_ext;
 pub mod paths_provider;
 
+pub use self::checkpointer::{Checkpointer, CheckpointsView};
 pub use self::file_server::{FileServer, Shutdown as FileServerShutdown};
-pub use self::fingerprinter::{FingerprintStrategy, Fingerprinter};
+pub use self::fingerprinter::{FileFingerprint, FingerprintStrategy, Fingerprinter};
 pub use self::internal_events::FileSourceInternalEvents;
 
+use bytes::Bytes;
+
 pub type FilePosition = u64;
 
+/// A line read by the `FileServer`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line {
+    pub text: Bytes,
+    pub filename: String,
+    pub file_id: FileFingerprint,
+    /// The position in the file right after this line.
+    pub offset: FilePosition,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ReadFrom {
     Beginning,
@@ -1,6 +1,7 @@
 //! Metrics queries/subscriptions.
 
-use crate::BoxedSubscription;
+use crate::{BoxedSubscription, QueryResult};
+use async_trait::async_trait;
 use graphql_client::GraphQLQuery;
 
 /// UptimeSubscription returns uptime metrics to determine how long the Vector
@@ -163,6 +164,52 @@ pub struct ComponentEventsOutThroughputsSubscription;
 )]
 pub struct ComponentEventsOutTotalsSubscription;
 
+/// ComponentErrorsTotalsSubscription contains metrics on the number of errors
+/// of each of the `*_errors_total` metrics, against specific components.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_errors_totals.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentErrorsTotalsSubscription;
+
+/// ComponentMetricHistoryQuery returns the recent per-second samples of the
+/// throughput, errors, buffer and concurrency of components.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/queries/component_metric_history.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentMetricHistoryQuery;
+
+/// Extension methods for metrics queries
+#[async_trait]
+pub trait MetricsQueryExt {
+    /// Executes a component metric history query, for every component unless
+    /// `component_names` is given.
+    async fn component_metric_history_query(
+        &self,
+        component_names: Option<Vec<String>>,
+    ) -> QueryResult<ComponentMetricHistoryQuery>;
+}
+
+#[async_trait]
+impl MetricsQueryExt for crate::Client {
+    async fn component_metric_history_query(
+        &self,
+        component_names: Option<Vec<String>>,
+    ) -> QueryResult<ComponentMetricHistoryQuery> {
+        let request_body =
+            ComponentMetricHistoryQuery::build_query(component_metric_history_query::Variables {
+                component_names,
+            });
+        self.query::<ComponentMetricHistoryQuery>(&request_body)
+            .await
+    }
+}
+
 /// Extension methods for metrics subscriptions
 pub trait MetricsSubscriptionExt {
     /// Executes an uptime metrics subscription.
@@ -257,6 +304,12 @@ pub trait MetricsSubscriptionExt {
         &self,
         interval: i64,
     ) -> crate::BoxedSubscription<ComponentEventsOutThroughputsSubscription>;
+
+    /// Executes an component errors totals subscription.
+    fn component_errors_totals_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentErrorsTotalsSubscription>;
 }
 
 impl MetricsSubscriptionExt for crate::SubscriptionClient {
@@ -448,4 +501,16 @@ impl MetricsSubscriptionExt for crate::SubscriptionClient {
 
         self.start::<ComponentEventsOutThroughputsSubscription>(&request_body)
     }
+
+    /// Executes an all component errors totals subscription.
+    fn component_errors_totals_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentErrorsTotalsSubscription> {
+        let request_body = ComponentErrorsTotalsSubscription::build_query(
+            component_errors_totals_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentErrorsTotalsSubscription>(&request_body)
+    }
 }
@@ -56,13 +56,36 @@ impl output_events_subscription::OutputEventsSubscriptionOutputEvents {
             _ => None,
         }
     }
+
+    pub fn as_metric(
+        &self,
+    ) -> Option<&output_events_subscription::OutputEventsSubscriptionOutputEventsOnMetric> {
+        match self {
+            output_events_subscription::OutputEventsSubscriptionOutputEvents::Metric(ev) => {
+                Some(ev)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Which of the tapped events are sent by the server.
+#[derive(Debug, Clone, Default)]
+pub struct TapFilter {
+    /// VRL condition the events have to match
+    pub condition: Option<String>,
+    /// Maximum number of events sent each second
+    pub rate_limit: Option<i64>,
 }
 
 pub trait TapSubscriptionExt {
-    /// Executes an output events subscription.
+    /// Executes an output events subscription, tapping the output of the components matching
+    /// `component_names` and the input of those matching `input_names`.
     fn output_events_subscription(
         &self,
         component_names: Vec<String>,
+        input_names: Vec<String>,
+        filter: TapFilter,
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
@@ -74,6 +97,8 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
     fn output_events_subscription(
         &self,
         component_names: Vec<String>,
+        input_names: Vec<String>,
+        filter: TapFilter,
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
@@ -81,9 +106,12 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
         let request_body =
             OutputEventsSubscription::build_query(output_events_subscription::Variables {
                 component_names,
+                input_names,
                 limit,
                 interval,
                 encoding: encoding.into(),
+                filter: filter.condition,
+                rate_limit: filter.rate_limit,
             });
 
         self.start::<OutputEventsSubscription>(&request_body)
@@ -27,6 +27,7 @@ macro_rules! experiment {
                         || {
                             let variant = Variant::Memory {
                                 max_events: *max_events,
+                                max_bytes: None,
                                 when_full: WhenFull::DropNewest,
                             };
                             crate::common::setup::<$width>(*max_events, variant)
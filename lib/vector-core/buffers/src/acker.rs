@@ -1,11 +1,19 @@
+#[cfg(feature = "disk-buffer")]
+use crate::overflow::InFlight;
 use futures::task::AtomicWaker;
 use metrics::counter;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+#[cfg(feature = "disk-buffer")]
+use std::sync::Mutex;
 
 #[derive(Debug, Clone)]
 pub enum Acker {
     Disk(Arc<AtomicUsize>, Arc<AtomicWaker>),
+    /// Passes on acks to the disk buffer of an overflowing buffer, for the
+    /// events read from it.
+    #[cfg(feature = "disk-buffer")]
+    Overflow(Arc<Mutex<InFlight>>, Box<Acker>),
     Null,
 }
 
@@ -19,13 +27,7 @@ impl Acker {
     pub fn ack(&self, num: usize) {
         // Only ack items if the amount to ack is larger than zero.
         if num > 0 {
-            match self {
-                Acker::Null => {}
-                Acker::Disk(counter, notifier) => {
-                    counter.fetch_add(num, Ordering::Relaxed);
-                    notifier.wake();
-                }
-            }
+            self.ack_buffer(num);
 
             // WARN this string "events_out_total" is a duplicate of the metric
             // name in `ROOT/src/internal_events/topology.rs`. `Acker` had a
@@ -39,6 +41,23 @@ impl Acker {
         }
     }
 
+    fn ack_buffer(&self, num: usize) {
+        match self {
+            Acker::Null => {}
+            Acker::Disk(counter, notifier) => {
+                counter.fetch_add(num, Ordering::Relaxed);
+                notifier.wake();
+            }
+            #[cfg(feature = "disk-buffer")]
+            Acker::Overflow(in_flight, disk) => {
+                let from_disk = in_flight.lock().unwrap().ack(num);
+                if from_disk > 0 {
+                    disk.ack_buffer(from_disk);
+                }
+            }
+        }
+    }
+
     #[must_use]
     pub fn new_for_testing() -> (Self, Arc<AtomicUsize>) {
         let ack_counter = Arc::new(AtomicUsize::new(0));
@@ -0,0 +1,184 @@
+//! Offline inspection and repair of disk buffers
+//!
+//! Leveldb locks its store, so a buffer can only be opened here while no
+//! Vector instance is using it.
+
+use super::Key;
+use crate::bytes::DecodeBytes;
+use crate::disk::{DataDirError, Open};
+use bytes::Bytes;
+use leveldb::database::{
+    batch::{Batch, Writebatch},
+    compaction::Compaction,
+    iterator::{Iterable, LevelDBIterator},
+    options::{Options, ReadOptions, WriteOptions},
+    Database,
+};
+use snafu::ResultExt;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// Suffix of the directories of disk buffers within the data dir.
+const BUFFER_DIR_SUFFIX: &str = "_buffer";
+
+/// A record of a disk buffer.
+#[derive(Debug)]
+pub struct Record<T> {
+    /// The key of the record, records are read in the order of their keys.
+    pub key: usize,
+    /// The encoded size of the record in bytes.
+    pub size: usize,
+    /// The decoded event, or why it couldn't be decoded.
+    pub event: Result<T, String>,
+}
+
+/// Counts of the records in a disk buffer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub events: usize,
+    pub bytes: usize,
+    pub corrupted: usize,
+    pub first_key: Option<usize>,
+    pub last_key: Option<usize>,
+}
+
+/// Lists the names of the sinks with a disk buffer in `data_dir`.
+///
+/// # Errors
+///
+/// Function will fail if `data_dir` can't be read.
+pub fn list(data_dir: &Path) -> io::Result<Vec<String>> {
+    let mut names = Vec::new();
+    for entry in fs::read_dir(data_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Some(name) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_suffix(BUFFER_DIR_SUFFIX))
+        {
+            names.push(name.to_owned());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// The directory of the disk buffer of sink `name`.
+#[must_use]
+pub fn buffer_dir(data_dir: &Path, name: &str) -> PathBuf {
+    data_dir.join(format!("{}{}", name, BUFFER_DIR_SUFFIX))
+}
+
+/// A disk buffer opened for inspection.
+pub struct Inspector {
+    db: Database<Key>,
+}
+
+impl Inspector {
+    /// Opens the disk buffer of sink `name` in `data_dir`.
+    ///
+    /// # Errors
+    ///
+    /// Function will fail if there is no such buffer, or it's in use.
+    pub fn open(data_dir: &Path, name: &str) -> Result<Self, DataDirError> {
+        let db = Database::open(&buffer_dir(data_dir, name), Options::new())
+            .with_context(|| Open { data_dir })?;
+        Ok(Self { db })
+    }
+
+    /// Iterates over all records in order.
+    #[must_use]
+    pub fn records<'a, T>(&'a self) -> impl Iterator<Item = Record<T>> + 'a
+    where
+        T: DecodeBytes<T> + 'a,
+        <T as DecodeBytes<T>>::Error: Display,
+    {
+        self.db
+            .iter(ReadOptions::new())
+            .map(|(key, value)| Record {
+                key: key.0,
+                size: value.len(),
+                event: T::decode(Bytes::from(value)).map_err(|error| error.to_string()),
+            })
+    }
+
+    /// Counts the records, decoding them to find corrupted ones.
+    #[must_use]
+    pub fn stats<T>(&self) -> Stats
+    where
+        T: DecodeBytes<T>,
+        <T as DecodeBytes<T>>::Error: Display,
+    {
+        self.records::<T>().fold(Stats::default(), |mut stats, record| {
+            if record.event.is_ok() {
+                stats.events += 1;
+            } else {
+                stats.corrupted += 1;
+            }
+            stats.bytes += record.size;
+            stats.first_key = stats.first_key.or(Some(record.key));
+            stats.last_key = Some(record.key);
+            stats
+        })
+    }
+
+    /// Deletes the records with keys from `from` up to, not including, `to`.
+    /// Returns the number of deleted records.
+    ///
+    /// # Panics
+    ///
+    /// Function will panic if leveldb fails to write.
+    #[must_use]
+    pub fn truncate(&self, from: Option<usize>, to: Option<usize>) -> usize {
+        let keys = self
+            .db
+            .keys_iter(ReadOptions::new())
+            .map(|key| key.0)
+            .filter(|key| from.map_or(true, |from| *key >= from))
+            .take_while(|key| to.map_or(true, |to| *key < to));
+        self.delete(keys)
+    }
+
+    /// Deletes the records which can't be decoded. Returns the number of
+    /// deleted records.
+    ///
+    /// # Panics
+    ///
+    /// Function will panic if leveldb fails to write.
+    #[must_use]
+    pub fn skip_corrupted<T>(&self) -> usize
+    where
+        T: DecodeBytes<T>,
+        <T as DecodeBytes<T>>::Error: Display,
+    {
+        let keys = self
+            .records::<T>()
+            .filter(|record| record.event.is_err())
+            .map(|record| record.key)
+            .collect::<Vec<_>>();
+        self.delete(keys)
+    }
+
+    fn delete(&self, keys: impl IntoIterator<Item = usize>) -> usize {
+        let mut batch = Writebatch::new();
+        let mut first = None;
+        let mut last = 0;
+        let mut count = 0;
+        for key in keys {
+            batch.delete(Key(key));
+            first = first.or(Some(key));
+            last = key;
+            count += 1;
+        }
+
+        if let Some(first) = first {
+            self.db.write(WriteOptions::new(), &batch).unwrap();
+            self.db.compact(&Key(first), &Key(last));
+        }
+        count
+    }
+}
@@ -1,10 +1,11 @@
+pub mod inspect;
 mod key;
 mod reader;
 mod writer;
 
 use super::{DataDirError, Open};
 use crate::bytes::{DecodeBytes, EncodeBytes};
-use crate::Acker;
+use crate::{Acker, WhenFull};
 use futures::task::AtomicWaker;
 use key::Key;
 use leveldb::database::{
@@ -33,7 +34,10 @@ pub struct Buffer<T> {
     phantom: PhantomData<T>,
 }
 
-/// Read the byte size of the database
+/// Read the number of events and the byte size of the database
+///
+/// Records aren't decoded, so corrupted ones are counted until the reader
+/// reaches and skips them.
 ///
 /// There is a mismatch between leveldb's mechanism and vector's. While vector
 /// would prefer to keep as little in-memory as possible leveldb, being a
@@ -49,13 +53,15 @@ pub struct Buffer<T> {
 /// This function does not solve the problem -- leveldb will still map 1000
 /// files if it wants -- but we at least avoid forcing this to happen at the
 /// start of vector.
-fn db_initial_size(path: &Path) -> Result<usize, DataDirError> {
+fn db_initial_size(path: &Path) -> Result<(usize, usize), DataDirError> {
     let mut options = Options::new();
     options.create_if_missing = true;
     let db: Database<Key> = Database::open(&path, options).with_context(|| Open {
         data_dir: path.parent().expect("always a parent"),
     })?;
-    Ok(db.value_iter(ReadOptions::new()).map(|v| v.len()).sum())
+    Ok(db
+        .value_iter(ReadOptions::new())
+        .fold((0, 0), |(events, size), v| (events + 1, size + v.len())))
 }
 
 impl<T> Buffer<T>
@@ -66,6 +72,9 @@ where
 {
     /// Build a new `DiskBuffer` rooted at `path`
     ///
+    /// With [`WhenFull::DropOldest`] writers evict the oldest unread events
+    /// to make room for new ones. Any other `when_full` makes them wait.
+    ///
     /// # Errors
     ///
     /// Function will fail if the permissions of `path` are not correct, if
@@ -74,13 +83,14 @@ where
     pub fn build(
         path: &Path,
         max_size: usize,
+        when_full: WhenFull,
     ) -> Result<(Writer<T>, Reader<T>, Acker), DataDirError> {
         // New `max_size` of the buffer is used for storing the unacked events.
         // The rest is used as a buffer which when filled triggers compaction.
         let max_uncompacted_size = max_size / MAX_UNCOMPACTED_DENOMINATOR;
         let max_size = max_size - max_uncompacted_size;
 
-        let initial_size = db_initial_size(&path)?;
+        let (initial_events, initial_size) = db_initial_size(&path)?;
 
         let mut options = Options::new();
         options.create_if_missing = true;
@@ -101,6 +111,10 @@ where
 
         let current_size = Arc::new(AtomicUsize::new(initial_size));
 
+        let unread_events = Arc::new(AtomicUsize::new(initial_events));
+
+        let read_offset = Arc::new(Mutex::new(head));
+
         let write_notifier = Arc::new(AtomicWaker::new());
 
         let blocked_write_tasks = Arc::new(Mutex::new(Vec::new()));
@@ -116,7 +130,10 @@ where
             writebatch: Writebatch::new(),
             batch_size: 0,
             max_size,
+            when_full,
             current_size: Arc::clone(&current_size),
+            unread_events: Arc::clone(&unread_events),
+            read_offset: Arc::clone(&read_offset),
             slot: None,
         };
 
@@ -124,15 +141,17 @@ where
             db: Arc::clone(&db),
             write_notifier: Arc::clone(&write_notifier),
             blocked_write_tasks,
-            read_offset: head,
+            read_offset,
             compacted_offset: 0,
             acked: 0,
+            acked_offset: head,
             delete_offset: head,
             current_size,
+            unread_events,
             ack_counter,
             max_uncompacted_size,
             uncompacted_size: 0,
-            unacked_sizes: VecDeque::new(),
+            unacked: VecDeque::new(),
             buffer: VecDeque::new(),
             last_compaction: Instant::now(),
             phantom: PhantomData,
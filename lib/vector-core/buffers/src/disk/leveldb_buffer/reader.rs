@@ -1,5 +1,6 @@
 use super::Key;
 use crate::bytes::DecodeBytes;
+use crate::internal_events::BufferCorruptedRecordsSkipped;
 use bytes::Bytes;
 use futures::{task::AtomicWaker, Stream};
 use leveldb::database::{
@@ -31,6 +32,9 @@ const MIN_UNCOMPACTED_SIZE: usize = 4 * 1024 * 1024;
 /// Reader maintains/manages events thorugh several stages.
 /// Unread -> Read -> Deleted -> Compacted
 ///
+/// Writers may also delete unread events when evicting the oldest ones, which
+/// leaves gaps in the keys.
+///
 /// So the disk buffer (indices/keys) is separated into following regions.
 /// |--Compacted--|--Deleted--|--Read--|--Unread
 ///  ^             ^   ^       ^        ^
@@ -46,7 +50,8 @@ where
     /// Shared with Writers.
     pub(crate) db: Arc<Database<Key>>,
     /// First unread key
-    pub(crate) read_offset: usize,
+    /// Shared with Writers, which advance it when evicting events.
+    pub(crate) read_offset: Arc<Mutex<usize>>,
     /// First uncompacted key
     pub(crate) compacted_offset: usize,
     /// First not deleted key
@@ -54,6 +59,8 @@ where
     /// Number of acked events that haven't been deleted from
     /// database. Used for batching deletes.
     pub(crate) acked: usize,
+    /// One past the last acked key.
+    pub(crate) acked_offset: usize,
     /// Reader is notified by Writers through this Waker.
     /// Shared with Writers.
     pub(crate) write_notifier: Arc<AtomicWaker>,
@@ -63,15 +70,18 @@ where
     /// Size of unread events in bytes.
     /// Shared with Writers.
     pub(crate) current_size: Arc<AtomicUsize>,
+    /// Number of unread events.
+    /// Shared with Writers.
+    pub(crate) unread_events: Arc<AtomicUsize>,
     /// Number of oldest read, not deleted, events that have been acked by the consumer.
     /// Shared with consumer.
     pub(crate) ack_counter: Arc<AtomicUsize>,
     /// Size of deleted, not compacted, events in bytes.
     pub(crate) uncompacted_size: usize,
-    /// Sizes in bytes of read, not acked/deleted, events.
-    pub(crate) unacked_sizes: VecDeque<usize>,
+    /// Keys and sizes in bytes of read, not acked/deleted, events.
+    pub(crate) unacked: VecDeque<(usize, usize)>,
     /// Buffer for internal use.
-    pub(crate) buffer: VecDeque<Vec<u8>>,
+    pub(crate) buffer: VecDeque<(usize, Vec<u8>)>,
     /// Limit on uncompacted_size after which we trigger compaction.
     pub(crate) max_uncompacted_size: usize,
     /// Last time that compaction was triggered.
@@ -104,38 +114,61 @@ where
             this.flush(unread_size);
         }
 
-        if this.buffer.is_empty() {
-            // This will usually complete instantly, but in the case of a large
-            // queue (or a fresh launch of the app), this will have to go to
-            // disk.
-            tokio::task::block_in_place(|| {
-                this.buffer.extend(
-                    this.db
-                        .value_iter(ReadOptions::new())
-                        .from(&Key(this.read_offset))
-                        .to(&Key(this.read_offset + 100)),
-                );
-            });
-        }
+        // Corrupted records are skipped until one can be decoded.
+        loop {
+            let mut read_offset = this.read_offset.lock().unwrap();
+
+            // Writers may have evicted some of the buffered events.
+            while matches!(this.buffer.front(), Some((key, _)) if *key < *read_offset) {
+                this.buffer.pop_front();
+            }
+
+            if this.buffer.is_empty() {
+                // This will usually complete instantly, but in the case of a large
+                // queue (or a fresh launch of the app), this will have to go to
+                // disk.
+                let db = &this.db;
+                let buffer = &mut this.buffer;
+                let offset = *read_offset;
+                tokio::task::block_in_place(|| {
+                    buffer.extend(
+                        db.iter(ReadOptions::new())
+                            .from(&Key(offset))
+                            .to(&Key(offset + 100))
+                            .map(|(key, value)| (key.0, value)),
+                    );
+                });
+            }
+
+            let next = this.buffer.pop_front();
+            if let Some((key, _)) = next {
+                *read_offset = key + 1;
+            }
+            drop(read_offset);
 
-        if let Some(value) = this.buffer.pop_front() {
-            this.unacked_sizes.push_back(value.len());
-            this.read_offset += 1;
+            let (key, value) = match next {
+                Some(next) => next,
+                // There are no writers left
+                None if Arc::strong_count(&this.db) == 1 => return Poll::Ready(None),
+                None => return Poll::Pending,
+            };
+            this.unread_events.fetch_sub(1, Ordering::AcqRel);
 
+            let size = value.len();
             let buffer: Bytes = Bytes::from(value);
             match T::decode(buffer) {
-                Ok(event) => Poll::Ready(Some(event)),
+                Ok(event) => {
+                    this.unacked.push_back((key, size));
+                    return Poll::Ready(Some(event));
+                }
                 Err(error) => {
+                    // The record is never handed out, so it won't be acked.
+                    // It's deleted along with the acked records around it.
                     error!(message = "Error deserializing event.", %error);
-                    debug_assert!(false);
-                    Pin::new(this).poll_next(cx)
+                    this.current_size.fetch_sub(size, Ordering::Release);
+                    BufferCorruptedRecordsSkipped { count: 1 }.emit();
                 }
             }
-        } else if Arc::strong_count(&this.db) == 1 {
-            // There are no writers left
-            Poll::Ready(None)
-        } else {
-            Poll::Pending
         }
     }
 }
@@ -159,7 +192,11 @@ where
         let num_to_delete = self.ack_counter.swap(0, Ordering::Relaxed);
 
         let unread_size = if num_to_delete > 0 {
-            let size_deleted = self.unacked_sizes.drain(..num_to_delete).sum();
+            let mut size_deleted = 0;
+            for (key, size) in self.unacked.drain(..num_to_delete) {
+                self.acked_offset = key + 1;
+                size_deleted += size;
+            }
             let unread_size =
                 self.current_size.fetch_sub(size_deleted, Ordering::Release) - size_deleted;
 
@@ -180,9 +217,11 @@ where
 
     fn flush(&mut self, unread_size: usize) {
         if self.acked > 0 {
-            let new_offset = self.delete_offset + self.acked;
+            // Keys evicted by Writers are already deleted, deleting them
+            // again is a no-op.
+            let new_offset = self.acked_offset;
             assert!(
-                new_offset <= self.read_offset,
+                new_offset <= *self.read_offset.lock().unwrap(),
                 "Tried to ack beyond read offset"
             );
 
@@ -1,10 +1,13 @@
 use super::Key;
 use crate::bytes::{DecodeBytes, EncodeBytes};
+use crate::internal_events::BufferEventsDropped;
+use crate::WhenFull;
 use bytes::BytesMut;
 use futures::{task::AtomicWaker, Sink};
 use leveldb::database::{
     batch::{Batch, Writebatch},
-    options::WriteOptions,
+    iterator::{Iterable, LevelDBIterator},
+    options::{ReadOptions, WriteOptions},
     Database,
 };
 use std::fmt::Debug;
@@ -40,9 +43,17 @@ where
     pub(crate) batch_size: usize,
     /// Max size of unread events in bytes.
     pub(crate) max_size: usize,
+    /// What to do when `max_size` is reached.
+    pub(crate) when_full: WhenFull,
     /// Size of unread events in bytes.
     /// Shared with Reader.
     pub(crate) current_size: Arc<AtomicUsize>,
+    /// Number of unread events, including batched ones.
+    /// Shared with Reader.
+    pub(crate) unread_events: Arc<AtomicUsize>,
+    /// First unread key. Evicting events moves it past them.
+    /// Shared with Reader.
+    pub(crate) read_offset: Arc<Mutex<usize>>,
     /// Buffer for internal use.
     pub(crate) slot: Option<T>,
 }
@@ -72,7 +83,10 @@ where
             writebatch: Writebatch::new(),
             batch_size: 0,
             max_size: self.max_size,
+            when_full: self.when_full,
             current_size: Arc::clone(&self.current_size),
+            unread_events: Arc::clone(&self.unread_events),
+            read_offset: Arc::clone(&self.read_offset),
             slot: None,
         }
     }
@@ -145,6 +159,11 @@ where
     <T as EncodeBytes<T>>::Error: Debug,
     <T as DecodeBytes<T>>::Error: Debug,
 {
+    /// Number of events that have been written, but not yet read.
+    pub(crate) fn unread_events(&self) -> usize {
+        self.unread_events.load(Ordering::Acquire)
+    }
+
     fn try_send(&mut self, event: T) -> Option<T> {
         let mut buffer: BytesMut = BytesMut::with_capacity(64);
         T::encode(event, &mut buffer).unwrap();
@@ -157,10 +176,14 @@ where
 
             self.flush();
 
-            return Some(T::decode(buffer).unwrap());
+            if self.when_full != WhenFull::DropOldest || !self.evict_oldest(event_size) {
+                return Some(T::decode(buffer).unwrap());
+            }
+            self.current_size.fetch_add(event_size, Ordering::Relaxed);
         }
 
         let key = self.offset.fetch_add(1, Ordering::Relaxed);
+        self.unread_events.fetch_add(1, Ordering::AcqRel);
 
         self.writebatch.put(Key(key), &buffer);
         self.batch_size += 1;
@@ -172,6 +195,45 @@ where
         None
     }
 
+    /// Deletes the oldest unread events until an event of `event_size` fits.
+    ///
+    /// Returns `false` if it still doesn't fit, which happens when the buffer
+    /// is taken up by events that have been read but not yet acked.
+    fn evict_oldest(&mut self, event_size: usize) -> bool {
+        let fits = |current_size: usize| current_size + (event_size / 2) <= self.max_size;
+
+        let db = self.db.as_ref().unwrap();
+        let mut read_offset = self.read_offset.lock().unwrap();
+        let start = Key(*read_offset);
+
+        let mut batch = Writebatch::new();
+        let mut count = 0;
+        let mut evicted_size = 0;
+        let current_size = self.current_size.load(Ordering::Acquire);
+        for (key, value) in db.iter(ReadOptions::new()).from(&start) {
+            if fits(current_size.saturating_sub(evicted_size)) {
+                break;
+            }
+            *read_offset = key.0 + 1;
+            batch.delete(key);
+            count += 1;
+            evicted_size += value.len();
+        }
+
+        if count > 0 {
+            db.write(WriteOptions::new(), &batch).unwrap();
+            self.current_size.fetch_sub(evicted_size, Ordering::Release);
+            self.unread_events.fetch_sub(count, Ordering::AcqRel);
+            BufferEventsDropped {
+                count,
+                when_full: WhenFull::DropOldest,
+            }
+            .emit();
+        }
+
+        fits(self.current_size.load(Ordering::Acquire))
+    }
+
     fn flush(&mut self) {
         // This doesn't write all the way through to disk and doesn't need to be
         // wrapped with `blocking`. (It does get written to a memory mapped
@@ -1,4 +1,5 @@
 use crate::bytes::{DecodeBytes, EncodeBytes};
+use crate::WhenFull;
 use futures::{Sink, Stream};
 use pin_project::pin_project;
 use snafu::Snafu;
@@ -43,6 +44,18 @@ where
     inner: leveldb_buffer::Writer<T>,
 }
 
+impl<T> Writer<T>
+where
+    T: Send + Sync + Unpin + Clone + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    /// Number of events that have been written, but not yet read.
+    pub(crate) fn unread_events(&self) -> usize {
+        self.inner.unread_events()
+    }
+}
+
 impl<T> Sink<T> for Writer<T>
 where
     T: Send + Sync + Unpin + Clone + EncodeBytes<T> + DecodeBytes<T>,
@@ -77,6 +90,7 @@ pub fn open<'a, T>(
     data_dir: &Path,
     name: &str,
     max_size: usize,
+    when_full: WhenFull,
 ) -> Result<
     (
         Writer<T>,
@@ -116,6 +130,6 @@ where
             }
         })?;
 
-    let (writer, reader, acker) = leveldb_buffer::Buffer::build(&path, max_size)?;
+    let (writer, reader, acker) = leveldb_buffer::Buffer::build(&path, max_size, when_full)?;
     Ok((Writer { inner: writer }, Box::new(reader), acker))
 }
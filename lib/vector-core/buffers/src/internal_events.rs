@@ -0,0 +1,69 @@
+//! Internal events emitted by the buffers.
+//!
+//! These mirror the `InternalEvent`s of the vector crate, which this crate
+//! can't depend on. Metrics are tagged with the component of the surrounding
+//! span the same way.
+
+use crate::WhenFull;
+use metrics::{counter, gauge};
+
+/// Events were dropped because the buffer was full.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BufferEventsDropped {
+    pub(crate) count: usize,
+    pub(crate) when_full: WhenFull,
+}
+
+impl BufferEventsDropped {
+    pub(crate) fn emit(self) {
+        let strategy = match self.when_full {
+            WhenFull::Block => "block",
+            WhenFull::DropNewest => "drop_newest",
+            WhenFull::DropOldest => "drop_oldest",
+        };
+        debug!(
+            message = "Shedding load; dropping events.",
+            count = self.count,
+            strategy,
+            internal_log_rate_secs = 10
+        );
+        counter!(
+            "buffer_discarded_events_total", self.count as u64,
+            "strategy" => strategy,
+        );
+    }
+}
+
+/// Number of events waiting in a memory buffer. It's emitted in the span of
+/// its reader, so the gauges are tagged with the sink.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BufferEventsQueued {
+    pub(crate) count: usize,
+    pub(crate) max_events: usize,
+}
+
+impl BufferEventsQueued {
+    // Gauges are floats, and no buffer holds anywhere near 2^52 events.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn emit(self) {
+        gauge!("buffer_events", self.count as f64);
+        gauge!("buffer_max_events", self.max_events as f64);
+    }
+}
+
+/// Records of a disk buffer were skipped because they couldn't be decoded.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BufferCorruptedRecordsSkipped {
+    pub(crate) count: usize,
+}
+
+impl BufferCorruptedRecordsSkipped {
+    pub(crate) fn emit(self) {
+        error!(
+            message = "Skipped corrupted records in disk buffer.",
+            count = self.count,
+            internal_log_rate_secs = 10
+        );
+        counter!("buffer_corrupted_records_total", self.count as u64);
+    }
+}
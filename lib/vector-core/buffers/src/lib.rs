@@ -1,7 +1,9 @@
 //! The Vector Core buffer
 //!
 //! This library implements a channel like functionality, one variant which is
-//! solely in-memory and the other that is on-disk. Both variants are bounded.
+//! solely in-memory and the other that is on-disk. Both variants are bounded,
+//! and when full they either block, drop the newest event or evict the oldest
+//! ones.
 
 #![deny(clippy::all)]
 #![deny(clippy::pedantic)]
@@ -15,11 +17,16 @@ mod acker;
 pub mod bytes;
 #[cfg(feature = "disk-buffer")]
 pub mod disk;
+mod internal_events;
+pub mod memory;
+#[cfg(feature = "disk-buffer")]
+pub mod overflow;
 #[cfg(test)]
 mod test;
 mod variant;
 
 use crate::bytes::{DecodeBytes, EncodeBytes};
+use crate::internal_events::BufferEventsDropped;
 pub use acker::Acker;
 use futures::{channel::mpsc, Sink, SinkExt, Stream};
 use pin_project::pin_project;
@@ -63,21 +70,57 @@ where
         } => {
             let buffer_dir = format!("{}_buffer", name);
 
-            let (tx, rx, acker) =
-                disk::open(&data_dir, &buffer_dir, max_size).map_err(|error| error.to_string())?;
+            let (tx, rx, acker) = disk::open(&data_dir, &buffer_dir, max_size, when_full)
+                .map_err(|error| error.to_string())?;
 
             let tx = BufferInputCloner::Disk(tx, when_full);
             Ok((tx, rx, acker))
         }
-        Variant::Memory {
+        #[cfg(feature = "disk-buffer")]
+        Variant::Overflow {
             max_events,
+            max_bytes,
+            max_size,
             when_full,
+            data_dir,
+            name,
         } => {
+            let buffer_dir = format!("{}_buffer", name);
+
+            let (tx, rx, acker) = overflow::open(
+                max_events,
+                max_bytes,
+                &data_dir,
+                &buffer_dir,
+                max_size,
+                when_full,
+            )
+            .map_err(|error| error.to_string())?;
+
+            let tx = BufferInputCloner::Overflow(tx);
+            Ok((tx, Box::new(rx), acker))
+        }
+        Variant::Memory {
+            max_events,
+            max_bytes: None,
+            when_full,
+        } if when_full != WhenFull::DropOldest => {
             let (tx, rx) = mpsc::channel(max_events);
             let tx = BufferInputCloner::Memory(tx, when_full);
             let rx = Box::new(rx);
             Ok((tx, rx, Acker::Null))
         }
+        // Limiting bytes and evicting events isn't possible with `mpsc`.
+        Variant::Memory {
+            max_events,
+            max_bytes,
+            when_full,
+        } => {
+            let (tx, rx) = memory::channel(max_events, max_bytes, when_full);
+            let tx = BufferInputCloner::MemoryQueue(tx);
+            let rx = Box::new(rx);
+            Ok((tx, rx, Acker::Null))
+        }
     }
 }
 
@@ -86,6 +129,7 @@ where
 pub enum WhenFull {
     Block,
     DropNewest,
+    DropOldest,
 }
 
 impl Default for WhenFull {
@@ -97,10 +141,10 @@ impl Default for WhenFull {
 #[cfg(test)]
 impl Arbitrary for WhenFull {
     fn arbitrary(g: &mut Gen) -> Self {
-        if bool::arbitrary(g) {
-            WhenFull::Block
-        } else {
-            WhenFull::DropNewest
+        match u8::arbitrary(g) % 3 {
+            0 => WhenFull::Block,
+            1 => WhenFull::DropNewest,
+            _ => WhenFull::DropOldest,
         }
     }
 }
@@ -117,8 +161,11 @@ where
     <T as DecodeBytes<T>>::Error: Debug,
 {
     Memory(mpsc::Sender<T>, WhenFull),
+    MemoryQueue(memory::Sender<T>),
     #[cfg(feature = "disk-buffer")]
     Disk(disk::Writer<T>, WhenFull),
+    #[cfg(feature = "disk-buffer")]
+    Overflow(overflow::Writer<T>),
 }
 
 impl<'a, T> BufferInputCloner<T>
@@ -141,6 +188,8 @@ where
                 }
             }
 
+            BufferInputCloner::MemoryQueue(tx) => Box::new(tx.clone()),
+
             #[cfg(feature = "disk-buffer")]
             BufferInputCloner::Disk(writer, when_full) => {
                 let inner: disk::Writer<T> = (*writer).clone();
@@ -150,6 +199,9 @@ where
                     Box::new(inner)
                 }
             }
+
+            #[cfg(feature = "disk-buffer")]
+            BufferInputCloner::Overflow(writer) => Box::new(writer.clone()),
         }
     }
 }
@@ -187,10 +239,11 @@ impl<T, S: Sink<T> + Unpin> Sink<T> for DropWhenFull<S> {
 
     fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
         if self.drop {
-            debug!(
-                message = "Shedding load; dropping event.",
-                internal_log_rate_secs = 10
-            );
+            BufferEventsDropped {
+                count: 1,
+                when_full: WhenFull::DropNewest,
+            }
+            .emit();
             Ok(())
         } else {
             self.project().inner.start_send(item)
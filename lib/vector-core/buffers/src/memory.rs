@@ -0,0 +1,282 @@
+//! A bounded in-memory channel
+//!
+//! Unlike `futures::channel::mpsc` this channel can be limited by the encoded
+//! byte size of the buffered events as well as by their number, and it can
+//! make room for new events by evicting the oldest ones.
+
+use crate::bytes::EncodeBytes;
+use crate::internal_events::{BufferEventsDropped, BufferEventsQueued};
+use crate::WhenFull;
+use futures::{Sink, Stream};
+use std::collections::VecDeque;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use tracing::Span;
+
+/// The buffer gauges are emitted once every this many changes to the queue,
+/// and whenever it becomes empty or full, so busy buffers don't pay for them
+/// on every event.
+const GAUGE_INTERVAL: usize = 128;
+
+struct State<T> {
+    /// Buffered events along with their byte size.
+    queue: VecDeque<(T, usize)>,
+    /// Byte size of all buffered events.
+    bytes: usize,
+    /// Number of live `Sender`s.
+    senders: usize,
+    /// Set once the `Receiver` is dropped.
+    closed: bool,
+    /// Receiver waiting for events.
+    read_waker: Option<Waker>,
+    /// Senders waiting for room, only used when blocking.
+    blocked_senders: Vec<Waker>,
+    /// Span the `Receiver` is first polled in, which the buffer gauges are
+    /// emitted in.
+    reader_span: Option<Span>,
+    /// Changes to the queue since the buffer gauges were last emitted.
+    changes: usize,
+    /// Number of events the buffer gauges were last emitted with.
+    gauged: usize,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    max_events: usize,
+    max_bytes: Option<usize>,
+    when_full: WhenFull,
+}
+
+impl<T> Shared<T> {
+    fn is_full(&self, state: &State<T>) -> bool {
+        state.queue.len() >= self.max_events
+            || self.max_bytes.map_or(false, |max| state.bytes >= max)
+    }
+
+    /// Counts a change to the queue, returning the number of queued events if
+    /// the buffer gauges are due.
+    fn gauge_due(&self, state: &mut State<T>) -> Option<usize> {
+        let count = state.queue.len();
+        state.changes += 1;
+        let edge = (count == 0 || self.is_full(state)) && count != state.gauged;
+        (edge || state.changes >= GAUGE_INTERVAL).then(|| {
+            state.changes = 0;
+            state.gauged = count;
+            count
+        })
+    }
+
+    fn emit_gauge(&self, count: usize) {
+        BufferEventsQueued {
+            count,
+            max_events: self.max_events,
+        }
+        .emit();
+    }
+}
+
+/// Create a new channel holding at most `max_events` events and, if set,
+/// `max_bytes` bytes.
+///
+/// Like `mpsc` a blocked sender may push one event past the limits once it
+/// has been signaled ready.
+#[must_use]
+pub fn channel<T>(
+    max_events: usize,
+    max_bytes: Option<usize>,
+    when_full: WhenFull,
+) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            bytes: 0,
+            senders: 1,
+            closed: false,
+            read_waker: None,
+            blocked_senders: Vec::new(),
+            reader_span: None,
+            changes: 0,
+            gauged: 0,
+        }),
+        max_events,
+        max_bytes,
+        when_full,
+    });
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+/// The sending half of a [`channel`].
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.shared.state.lock().unwrap();
+            state.senders -= 1;
+            state.read_waker.take()
+        };
+        // The receiver has to learn there are no more senders.
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T: EncodeBytes<T>> Sink<T> for Sender<T> {
+    type Error = ();
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let mut state = self.shared.state.lock().unwrap();
+        if state.closed {
+            error!(message = "Sender error.", error = "Receiver is gone.");
+            Poll::Ready(Err(()))
+        } else if self.shared.when_full == WhenFull::Block && self.shared.is_full(&state) {
+            state.blocked_senders.push(cx.waker().clone());
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        // Sizing an event may be costly, so only do so when it's limited.
+        let size = self
+            .shared
+            .max_bytes
+            .map_or(0, |_| item.encoded_size().unwrap_or_else(mem::size_of::<T>));
+
+        let mut state = self.shared.state.lock().unwrap();
+        if state.closed {
+            return Err(());
+        }
+
+        if self.shared.is_full(&state) {
+            match self.shared.when_full {
+                // The sender was signaled ready, so it may exceed the limits.
+                WhenFull::Block => {}
+                WhenFull::DropNewest => {
+                    drop(state);
+                    BufferEventsDropped {
+                        count: 1,
+                        when_full: WhenFull::DropNewest,
+                    }
+                    .emit();
+                    return Ok(());
+                }
+                WhenFull::DropOldest => {
+                    let mut count = 0;
+                    while self.shared.is_full(&state) {
+                        match state.queue.pop_front() {
+                            Some((_, evicted)) => {
+                                state.bytes -= evicted;
+                                count += 1;
+                            }
+                            None => break,
+                        }
+                    }
+                    if count > 0 {
+                        BufferEventsDropped {
+                            count,
+                            when_full: WhenFull::DropOldest,
+                        }
+                        .emit();
+                    }
+                }
+            }
+        }
+
+        state.bytes += size;
+        state.queue.push_back((item, size));
+        let waker = state.read_waker.take();
+        // Also emitted here, so the buffer of a stalled sink shows as full.
+        let gauge = self
+            .shared
+            .gauge_due(&mut state)
+            .and_then(|count| Some((count, state.reader_span.clone()?)));
+        drop(state);
+
+        if let Some((count, span)) = gauge {
+            span.in_scope(|| self.shared.emit_gauge(count));
+        }
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// The receiving half of a [`channel`].
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let blocked = {
+            let mut state = self.shared.state.lock().unwrap();
+            state.closed = true;
+            mem::take(&mut state.blocked_senders)
+        };
+        for waker in blocked {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.shared.state.lock().unwrap();
+        if state.reader_span.is_none() {
+            state.reader_span = Some(Span::current());
+        }
+        if let Some((item, size)) = state.queue.pop_front() {
+            state.bytes -= size;
+            let blocked = mem::take(&mut state.blocked_senders);
+            let gauge = self.shared.gauge_due(&mut state);
+            drop(state);
+
+            if let Some(count) = gauge {
+                self.shared.emit_gauge(count);
+            }
+
+            for waker in blocked {
+                waker.wake();
+            }
+            Poll::Ready(Some(item))
+        } else if state.senders == 0 {
+            Poll::Ready(None)
+        } else {
+            state.read_waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
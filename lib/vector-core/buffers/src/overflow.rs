@@ -0,0 +1,233 @@
+//! A memory buffer that overflows into a disk buffer
+//!
+//! Events stay in memory until it is full, only then they are written to the
+//! disk buffer. Once events have overflowed, new events follow them onto disk
+//! until the reader has drained the disk buffer, so that events are read in
+//! the order they were written.
+
+use crate::bytes::{DecodeBytes, EncodeBytes};
+use crate::internal_events::BufferEventsDropped;
+use crate::{disk, memory, WhenFull};
+use futures::{Sink, Stream};
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// Where read, not yet acked, events came from, so that acks can be passed on
+/// to the disk buffer for just the events read from it.
+#[derive(Debug, Default)]
+pub struct InFlight {
+    /// Runs of consecutive events, `true` for those read from disk.
+    runs: VecDeque<(bool, usize)>,
+}
+
+impl InFlight {
+    fn push(&mut self, from_disk: bool) {
+        match self.runs.back_mut() {
+            Some((disk, count)) if *disk == from_disk => *count += 1,
+            _ => self.runs.push_back((from_disk, 1)),
+        }
+    }
+
+    /// Removes the oldest `num` events and returns how many of them came from
+    /// disk.
+    pub(crate) fn ack(&mut self, mut num: usize) -> usize {
+        let mut from_disk = 0;
+        while num > 0 {
+            let (disk, count) = match self.runs.front_mut() {
+                Some(run) => run,
+                None => break,
+            };
+            let acked = num.min(*count);
+            if *disk {
+                from_disk += acked;
+            }
+            *count -= acked;
+            num -= acked;
+            if *count == 0 {
+                self.runs.pop_front();
+            }
+        }
+        from_disk
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Memory,
+    Disk,
+    Drop,
+}
+
+/// The writer side of an overflowing buffer.
+pub struct Writer<T>
+where
+    T: Send + Sync + Unpin + Clone + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    memory: memory::Sender<T>,
+    disk: disk::Writer<T>,
+    when_full: WhenFull,
+    /// Where the next event goes, decided by `poll_ready`.
+    target: Target,
+}
+
+impl<T> Clone for Writer<T>
+where
+    T: Send + Sync + Unpin + Clone + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug,
+{
+    fn clone(&self) -> Self {
+        Self {
+            memory: self.memory.clone(),
+            disk: self.disk.clone(),
+            when_full: self.when_full,
+            target: Target::Memory,
+        }
+    }
+}
+
+impl<T> Sink<T> for Writer<T>
+where
+    T: Send + Sync + Unpin + Clone + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug + Display,
+{
+    type Error = ();
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        // Events written to disk but not yet read have to be read first.
+        if this.disk.unread_events() == 0 {
+            match Pin::new(&mut this.memory).poll_ready(cx) {
+                Poll::Ready(Ok(())) => {
+                    this.target = Target::Memory;
+                    return Poll::Ready(Ok(()));
+                }
+                error @ Poll::Ready(Err(())) => return error,
+                Poll::Pending => {}
+            }
+        }
+
+        match Pin::new(&mut this.disk).poll_ready(cx) {
+            Poll::Ready(result) => {
+                this.target = Target::Disk;
+                Poll::Ready(result)
+            }
+            Poll::Pending if this.when_full == WhenFull::DropNewest => {
+                this.target = Target::Drop;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        match this.target {
+            Target::Memory => Pin::new(&mut this.memory).start_send(item),
+            Target::Disk => Pin::new(&mut this.disk).start_send(item),
+            Target::Drop => {
+                BufferEventsDropped {
+                    count: 1,
+                    when_full: WhenFull::DropNewest,
+                }
+                .emit();
+                Ok(())
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().disk).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.get_mut().disk).poll_close(cx)
+    }
+}
+
+/// The reader side of an overflowing buffer.
+pub struct Reader<'a, T> {
+    memory: memory::Receiver<T>,
+    memory_done: bool,
+    disk: Box<dyn Stream<Item = T> + 'a + Unpin + Send>,
+    /// Shared with the `Acker`.
+    in_flight: Arc<Mutex<InFlight>>,
+}
+
+impl<'a, T> Stream for Reader<'a, T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        // Everything in memory was written before anything that is on disk.
+        if !this.memory_done {
+            match Pin::new(&mut this.memory).poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.in_flight.lock().unwrap().push(false);
+                    return Poll::Ready(Some(item));
+                }
+                Poll::Ready(None) => this.memory_done = true,
+                Poll::Pending => {}
+            }
+        }
+
+        match Pin::new(&mut this.disk).poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                this.in_flight.lock().unwrap().push(true);
+                Poll::Ready(Some(item))
+            }
+            // Writers are dropped from both buffers at once, so this is only
+            // a moment away.
+            Poll::Ready(None) if !this.memory_done => Poll::Pending,
+            poll => poll,
+        }
+    }
+}
+
+/// Open an overflowing buffer, keeping at most `max_events` events and, if
+/// set, `max_bytes` bytes in memory before writing to the disk buffer.
+///
+/// # Errors
+///
+/// This function will fail with [`disk::DataDirError`] if the disk buffer
+/// can't be opened.
+pub fn open<'a, T>(
+    max_events: usize,
+    max_bytes: Option<usize>,
+    data_dir: &std::path::Path,
+    name: &str,
+    max_size: usize,
+    when_full: WhenFull,
+) -> Result<(Writer<T>, Reader<'a, T>, super::Acker), disk::DataDirError>
+where
+    T: 'a + Send + Sync + Unpin + Clone + EncodeBytes<T> + DecodeBytes<T>,
+    <T as EncodeBytes<T>>::Error: Debug,
+    <T as DecodeBytes<T>>::Error: Debug + Display,
+{
+    let (disk_tx, disk_rx, disk_acker) = disk::open(data_dir, name, max_size, when_full)?;
+    let (memory_tx, memory_rx) = memory::channel(max_events, max_bytes, WhenFull::Block);
+
+    let in_flight = Arc::new(Mutex::new(InFlight::default()));
+
+    let writer = Writer {
+        memory: memory_tx,
+        disk: disk_tx,
+        when_full,
+        target: Target::Memory,
+    };
+    let reader = Reader {
+        memory: memory_rx,
+        memory_done: false,
+        disk: disk_rx,
+        in_flight: Arc::clone(&in_flight),
+    };
+    let acker = super::Acker::Overflow(in_flight, Box::new(disk_acker));
+
+    Ok((writer, reader, acker))
+}
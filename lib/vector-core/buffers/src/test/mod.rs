@@ -1,7 +1,8 @@
 mod common;
 mod model;
 
-use crate::{Acker, DropWhenFull};
+use crate::test::common::Message;
+use crate::{memory, Acker, DropWhenFull, WhenFull};
 use futures::task::Poll;
 use futures::{channel::mpsc, future, task::AtomicWaker};
 use futures::{Sink, Stream};
@@ -34,6 +35,116 @@ async fn drop_when_full() {
     .await;
 }
 
+#[tokio::test]
+async fn memory_drop_oldest() {
+    future::lazy(|cx| {
+        let (tx, rx) = memory::channel(2, None, WhenFull::DropOldest);
+
+        let mut tx = Box::pin(tx);
+
+        for id in 1..=4 {
+            assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+            assert_eq!(tx.as_mut().start_send(Message::new(id)), Ok(()));
+        }
+
+        let mut rx = Box::pin(rx);
+
+        assert_eq!(
+            rx.as_mut().poll_next(cx),
+            Poll::Ready(Some(Message::new(3)))
+        );
+        assert_eq!(
+            rx.as_mut().poll_next(cx),
+            Poll::Ready(Some(Message::new(4)))
+        );
+        assert_eq!(rx.as_mut().poll_next(cx), Poll::Pending);
+
+        drop(tx);
+        assert_eq!(rx.as_mut().poll_next(cx), Poll::Ready(None));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn memory_max_bytes() {
+    future::lazy(|cx| {
+        // Each `Message` encodes into 8 bytes.
+        let (tx, rx) = memory::channel(100, Some(16), WhenFull::Block);
+
+        let mut tx = Box::pin(tx);
+
+        assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+        assert_eq!(tx.as_mut().start_send(Message::new(1)), Ok(()));
+        assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+        assert_eq!(tx.as_mut().start_send(Message::new(2)), Ok(()));
+        assert_eq!(tx.as_mut().poll_ready(cx), Poll::Pending);
+
+        let mut rx = Box::pin(rx);
+
+        assert_eq!(
+            rx.as_mut().poll_next(cx),
+            Poll::Ready(Some(Message::new(1)))
+        );
+        assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+    })
+    .await;
+}
+
+#[cfg(feature = "disk-buffer")]
+#[test]
+fn overflow_reads_in_order() {
+    use crate::overflow::Writer;
+    use futures::task::{noop_waker, Context};
+    use std::pin::Pin;
+
+    fn send(tx: &mut Pin<Box<Writer<Message>>>, cx: &mut Context<'_>, id: u64) {
+        assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+        assert_eq!(tx.as_mut().start_send(Message::new(id)), Ok(()));
+        assert_eq!(tx.as_mut().poll_flush(cx), Poll::Ready(Ok(())));
+    }
+
+    let data_dir = tempdir::TempDir::new("overflow").unwrap();
+    let (tx, rx, acker) = crate::overflow::open::<Message>(
+        2,
+        None,
+        data_dir.path(),
+        "overflow_buffer",
+        1_000_000,
+        WhenFull::Block,
+    )
+    .unwrap();
+
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut tx = Box::pin(tx);
+    let mut rx = Box::pin(rx);
+
+    // The first two stay in memory, the rest overflow to disk.
+    for id in 1..=5 {
+        send(&mut tx, &mut cx, id);
+    }
+    for id in 1..=3 {
+        assert_eq!(
+            rx.as_mut().poll_next(&mut cx),
+            Poll::Ready(Some(Message::new(id)))
+        );
+    }
+
+    // Memory has room again, but this has to be read after those on disk.
+    send(&mut tx, &mut cx, 6);
+    for id in 4..=6 {
+        assert_eq!(
+            rx.as_mut().poll_next(&mut cx),
+            Poll::Ready(Some(Message::new(id)))
+        );
+    }
+    assert_eq!(rx.as_mut().poll_next(&mut cx), Poll::Pending);
+
+    acker.ack(6);
+    drop(tx);
+    assert_eq!(rx.as_mut().poll_next(&mut cx), Poll::Ready(None));
+}
+
 #[test]
 fn ack_with_none() {
     let counter = Arc::new(AtomicUsize::new(0));
@@ -1,5 +1,5 @@
 use crate::test::model::{Message, Model};
-use crate::{Variant, WhenFull};
+use crate::{EncodeBytes, Variant, WhenFull};
 use std::collections::VecDeque;
 
 use super::Progress;
@@ -8,8 +8,11 @@ use super::Progress;
 pub(crate) struct InMemory {
     inner: VecDeque<Message>,
     when_full: WhenFull,
-    num_senders: usize,
     capacity: usize,
+    /// Items the buffer accepts beyond `capacity`.
+    slack: usize,
+    max_bytes: Option<usize>,
+    current_bytes: usize,
 }
 
 impl InMemory {
@@ -17,11 +20,19 @@ impl InMemory {
         match variant {
             Variant::Memory {
                 max_events,
+                max_bytes,
                 when_full,
             } => InMemory {
                 inner: VecDeque::with_capacity(*max_events),
                 capacity: *max_events,
-                num_senders,
+                // Only the `mpsc` based buffer reserves a slot per sender.
+                slack: if max_bytes.is_none() && *when_full != WhenFull::DropOldest {
+                    num_senders
+                } else {
+                    0
+                },
+                max_bytes: *max_bytes,
+                current_bytes: 0,
                 when_full: *when_full,
             },
             #[cfg(feature = "disk-buffer")]
@@ -30,23 +41,49 @@ impl InMemory {
     }
 }
 
+impl InMemory {
+    fn byte_size(&self, item: &Message) -> usize {
+        self.max_bytes
+            .map_or(0, |_| EncodeBytes::encoded_size(item).unwrap())
+    }
+
+    fn push(&mut self, item: Message) {
+        self.current_bytes += self.byte_size(&item);
+        self.inner.push_back(item);
+    }
+
+    fn is_at_limit(&self) -> bool {
+        self.inner.len() >= (self.capacity + self.slack)
+            || self
+                .max_bytes
+                .map_or(false, |max| self.current_bytes >= max)
+    }
+}
+
 impl Model for InMemory {
     fn send(&mut self, item: Message) -> Progress {
         match self.when_full {
             WhenFull::DropNewest => {
-                if self.inner.len() >= (self.capacity + self.num_senders) {
+                if self.is_at_limit() {
                     // DropNewest never blocks, instead it silently drops the
                     // item pushed in when the buffer is too full.
                 } else {
-                    self.inner.push_back(item);
+                    self.push(item);
                 }
                 Progress::Advanced
             }
+            WhenFull::DropOldest => {
+                // DropOldest never blocks either, instead it evicts the
+                // oldest items until the new one fits.
+                while self.is_at_limit() && self.recv().is_some() {}
+                self.push(item);
+                Progress::Advanced
+            }
             WhenFull::Block => {
-                if self.inner.len() >= (self.capacity + self.num_senders) {
+                if self.is_at_limit() {
                     Progress::Blocked(item)
                 } else {
-                    self.inner.push_back(item);
+                    self.push(item);
                     Progress::Advanced
                 }
             }
@@ -54,11 +91,17 @@ impl Model for InMemory {
     }
 
     fn recv(&mut self) -> Option<Message> {
-        self.inner.pop_front()
+        self.inner.pop_front().map(|msg| {
+            self.current_bytes -= self.byte_size(&msg);
+            msg
+        })
     }
 
     fn is_full(&self) -> bool {
         self.inner.len() >= self.capacity
+            || self
+                .max_bytes
+                .map_or(false, |max| self.current_bytes >= max)
     }
 
     fn is_empty(&self) -> bool {
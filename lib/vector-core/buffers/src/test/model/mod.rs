@@ -46,6 +46,9 @@ fn check(variant: &Variant) -> bool {
 
             data_dir.starts_with(prefix)
         }
+        // Not generated by `arbitrary`, there is no model for it.
+        #[cfg(feature = "disk-buffer")]
+        Variant::Overflow { .. } => false,
     }
 }
 
@@ -80,6 +83,8 @@ impl VariantGuard {
                     },
                 }
             }
+            #[cfg(feature = "disk-buffer")]
+            Variant::Overflow { .. } => unreachable!(),
         }
     }
 }
@@ -95,7 +100,7 @@ impl Drop for VariantGuard {
         match &self.inner {
             Variant::Memory { .. } => { /* nothing to clean up */ }
             #[cfg(feature = "disk-buffer")]
-            Variant::Disk { data_dir, .. } => {
+            Variant::Disk { data_dir, .. } | Variant::Overflow { data_dir, .. } => {
                 // SAFETY: Here we clean up the data_dir of the inner `Variant`,
                 // see note in the constructor for this type.
                 std::fs::remove_dir_all(data_dir).unwrap();
@@ -124,6 +129,8 @@ fn model_check() {
             Variant::Memory { .. } => Box::new(InMemory::new(guard.as_ref(), 1)),
             #[cfg(feature = "disk-buffer")]
             Variant::Disk { .. } => Box::new(OnDisk::new(guard.as_ref())),
+            #[cfg(feature = "disk-buffer")]
+            Variant::Overflow { .. } => unreachable!(),
         };
 
         let rcv_waker = noop_waker();
@@ -17,7 +17,7 @@ pub(crate) struct OnDisk {
 impl OnDisk {
     pub(crate) fn new(variant: &Variant) -> Self {
         match variant {
-            Variant::Memory { .. } => unreachable!(),
+            Variant::Memory { .. } | Variant::Overflow { .. } => unreachable!(),
             #[cfg(feature = "disk-buffer")]
             Variant::Disk {
                 max_size,
@@ -38,6 +38,14 @@ impl Model for OnDisk {
     fn send(&mut self, item: Message) -> Progress {
         let byte_size = EncodeBytes::encoded_size(&item).unwrap();
         match self.when_full {
+            WhenFull::DropOldest => {
+                // DropOldest never blocks, instead it evicts the oldest items
+                // until the new one fits.
+                while self.is_full() && self.recv().is_some() {}
+                self.current_bytes += byte_size;
+                self.inner.push_back(item);
+                Progress::Advanced
+            }
             WhenFull::DropNewest => {
                 if self.is_full() {
                     // DropNewest never blocks, instead it silently drops the
@@ -15,6 +15,7 @@ const ALPHABET: [&str; 27] = [
 pub enum Variant {
     Memory {
         max_events: usize,
+        max_bytes: Option<usize>,
         when_full: WhenFull,
     },
     Disk {
@@ -23,6 +24,15 @@ pub enum Variant {
         data_dir: PathBuf,
         name: String,
     },
+    /// Keeps events in memory, only writing them to disk once that is full.
+    Overflow {
+        max_events: usize,
+        max_bytes: Option<usize>,
+        max_size: usize,
+        when_full: WhenFull,
+        data_dir: PathBuf,
+        name: String,
+    },
 }
 
 #[cfg(test)]
@@ -50,6 +60,7 @@ impl Arbitrary for Variant {
         if bool::arbitrary(g) {
             Variant::Memory {
                 max_events: u16::arbitrary(g) as usize, // u16 avoids allocation failures
+                max_bytes: Option::<u16>::arbitrary(g).map(usize::from),
                 when_full: WhenFull::arbitrary(g),
             }
         } else {
@@ -66,11 +77,14 @@ impl Arbitrary for Variant {
         match self {
             Variant::Memory {
                 max_events,
+                max_bytes,
                 when_full,
             } => {
+                let max_bytes = *max_bytes;
                 let when_full = *when_full;
                 Box::new(max_events.shrink().map(move |me| Variant::Memory {
                     max_events: me,
+                    max_bytes,
                     when_full,
                 }))
             }
@@ -91,6 +105,8 @@ impl Arbitrary for Variant {
                     data_dir: data_dir.clone(),
                 }))
             }
+            // Not generated by `arbitrary`.
+            Variant::Overflow { .. } => Box::new(std::iter::empty()),
         }
     }
 }
@@ -6,6 +6,7 @@ use quickcheck::{Arbitrary, Gen};
 pub enum Variant {
     Memory {
         max_events: usize,
+        max_bytes: Option<usize>,
         when_full: WhenFull,
     },
 }
@@ -15,6 +16,7 @@ impl Arbitrary for Variant {
     fn arbitrary(g: &mut Gen) -> Self {
         Variant::Memory {
             max_events: u16::arbitrary(g) as usize, // u16 avoids allocation failures
+            max_bytes: Option::<u16>::arbitrary(g).map(usize::from),
             when_full: WhenFull::arbitrary(g),
         }
     }
@@ -23,9 +25,11 @@ impl Arbitrary for Variant {
         match *self {
             Variant::Memory {
                 max_events,
+                max_bytes,
                 when_full,
             } => Box::new(max_events.shrink().map(move |me| Variant::Memory {
                 max_events: me,
+                max_bytes,
                 when_full,
             })),
         }
@@ -1,5 +1,6 @@
 use getset::{Getters, Setters};
 use once_cell::sync::OnceCell;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 static LOG_SCHEMA: OnceCell<LogSchema> = OnceCell::new();
@@ -42,7 +43,7 @@ pub fn log_schema() -> &'static LogSchema {
     LOG_SCHEMA.get().unwrap_or(&LOG_SCHEMA_DEFAULT)
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Getters, Setters)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Getters, Setters, JsonSchema)]
 #[serde(default)]
 pub struct LogSchema {
     #[serde(default = "LogSchema::default_message_key")]
@@ -7,7 +7,10 @@ use std::future::Future;
 use std::iter::{self, ExactSizeIterator};
 use std::pin::Pin;
 use std::task::Poll;
-use std::{cmp, mem, sync::Arc};
+use std::{
+    cmp, mem,
+    sync::{Arc, Mutex},
+};
 use tokio::sync::oneshot;
 
 type ImmutVec<T> = Box<[T]>;
@@ -87,6 +90,13 @@ impl EventFinalizers {
         }
     }
 
+    /// Mark all finalizers in this set as failed, for the given reason.
+    pub fn reject(&self, reason: &str) {
+        for finalizer in self.0.iter() {
+            finalizer.reject(reason);
+        }
+    }
+
     /// Update all sources for this finalizer with the current
     /// status. This *drops* the finalizer array elements so they may
     /// immediately signal the source batch.
@@ -129,6 +139,13 @@ impl EventFinalizer {
             .unwrap_or_else(|_| unreachable!());
     }
 
+    /// Mark this event as failed, recording `reason` as the cause of the
+    /// failure of its batch unless another event already gave one.
+    pub fn reject(&self, reason: &str) {
+        self.update_status(EventStatus::Failed);
+        self.batch.set_reason(reason);
+    }
+
     /// Update the batch for this event with this finalizer's
     /// status, and mark this event as no longer requiring update.
     #[allow(clippy::missing_panics_doc)] // Panic is unreachable
@@ -152,14 +169,14 @@ impl Drop for EventFinalizer {
 /// A convenience newtype wrapper for the one-shot receiver for an
 /// individual batch status.
 #[pin_project::pin_project]
-pub struct BatchStatusReceiver(oneshot::Receiver<BatchStatus>);
+pub struct BatchStatusReceiver(oneshot::Receiver<(BatchStatus, Option<String>)>);
 
 impl Future for BatchStatusReceiver {
     type Output = BatchStatus;
     fn poll(mut self: Pin<&mut Self>, ctx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
         match self.0.poll_unpin(ctx) {
             Poll::Pending => Poll::Pending,
-            Poll::Ready(Ok(status)) => Poll::Ready(status),
+            Poll::Ready(Ok((status, _reason))) => Poll::Ready(status),
             Poll::Ready(Err(error)) => {
                 error!(message = "Batch status receiver dropped before sending.", %error);
                 Poll::Ready(BatchStatus::Errored)
@@ -176,7 +193,16 @@ impl BatchStatusReceiver {
     /// - `TryRecvError::Empty` if no value has been sent yet.
     /// - `TryRecvError::Closed` if the sender has dropped without sending a value.
     pub fn try_recv(&mut self) -> Result<BatchStatus, oneshot::error::TryRecvError> {
-        self.0.try_recv()
+        self.0.try_recv().map(|(status, _reason)| status)
+    }
+
+    /// Wait for the status of the batch, along with the reason given for
+    /// rejecting it if any of its events were rejected.
+    pub async fn recv_with_reason(self) -> (BatchStatus, Option<String>) {
+        self.0.await.unwrap_or_else(|error| {
+            error!(message = "Batch status receiver dropped before sending.", %error);
+            (BatchStatus::Errored, None)
+        })
     }
 }
 
@@ -186,7 +212,8 @@ impl BatchStatusReceiver {
 #[derive(Debug)]
 pub struct BatchNotifier {
     status: Atomic<BatchStatus>,
-    notifier: Option<oneshot::Sender<BatchStatus>>,
+    reason: Mutex<Option<String>>,
+    notifier: Option<oneshot::Sender<(BatchStatus, Option<String>)>>,
 }
 
 impl BatchNotifier {
@@ -196,6 +223,7 @@ impl BatchNotifier {
         let (sender, receiver) = oneshot::channel();
         let notifier = Self {
             status: Atomic::new(BatchStatus::Delivered),
+            reason: Mutex::new(None),
             notifier: Some(sender),
         };
         (Arc::new(notifier), BatchStatusReceiver(receiver))
@@ -215,13 +243,29 @@ impl BatchNotifier {
         }
     }
 
+    /// Record why an event of this batch was rejected, keeping the first reason.
+    fn set_reason(&self, reason: &str) {
+        let mut current = self
+            .reason
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        if current.is_none() {
+            *current = Some(reason.to_owned());
+        }
+    }
+
     /// Send this notifier's status up to the source.
     fn send_status(&mut self) {
         if let Some(notifier) = self.notifier.take() {
             let status = self.status.load(Ordering::Relaxed);
+            let reason = self
+                .reason
+                .get_mut()
+                .unwrap_or_else(|error| error.into_inner())
+                .take();
             // Ignore the error case, as it will happen during normal
             // source shutdown and we can't detect that here.
-            let _ = notifier.send(status);
+            let _ = notifier.send((status, reason));
         }
     }
 }
@@ -425,6 +469,22 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
     }
 
+    #[test]
+    fn reports_first_rejection_reason() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event1 = EventFinalizers::new(EventFinalizer::new(Arc::clone(&batch)));
+        let event2 = EventFinalizers::new(EventFinalizer::new(batch));
+
+        event1.reject("first");
+        event2.reject("second");
+        drop(event1);
+        drop(event2);
+        assert_eq!(
+            tokio_test::block_on(receiver.recv_with_reason()),
+            (BatchStatus::Failed, Some("first".into()))
+        );
+    }
+
     fn make_finalizer() -> (EventFinalizers, BatchStatusReceiver) {
         let (batch, receiver) = BatchNotifier::new_with_receiver();
         let finalizer = EventFinalizers::new(EventFinalizer::new(batch));
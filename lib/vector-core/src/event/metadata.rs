@@ -46,6 +46,16 @@ impl EventMetadata {
         self.finalizers.update_status(status);
     }
 
+    /// Mark the finalizer(s) as failed for the given reason.
+    pub fn reject(&self, reason: &str) {
+        self.finalizers.reject(reason);
+    }
+
+    /// Take the finalizers, leaving this metadata without any.
+    pub fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+
     /// Update the finalizers' sources.
     pub fn update_sources(&mut self) {
         self.finalizers.update_sources();
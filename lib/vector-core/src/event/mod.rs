@@ -403,6 +403,10 @@ impl EncodeBytes<Event> for Event {
     {
         proto::EventWrapper::from(self).encode(buffer)
     }
+
+    fn encoded_size(&self) -> Option<usize> {
+        Some(proto::EventWrapper::from(self.clone()).encoded_len())
+    }
 }
 
 impl DecodeBytes<Event> for Event {
@@ -103,10 +103,43 @@ impl Transform {
 ///   `TaskTransform` or vice versa.
 pub trait FunctionTransform: Send + dyn_clone::DynClone + Sync {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event);
+
+    /// Like `transform`, but the transform may also push events to its named
+    /// outputs through `named`. This is only called for transforms whose
+    /// configuration declares named outputs.
+    fn transform_with_outputs(
+        &mut self,
+        output: &mut Vec<Event>,
+        _named: &mut NamedOutputs,
+        event: Event,
+    ) {
+        self.transform(output, event);
+    }
 }
 
 dyn_clone::clone_trait_object!(FunctionTransform);
 
+/// The events a [`FunctionTransform`] pushed to its named outputs.
+#[derive(Debug, Default)]
+pub struct NamedOutputs {
+    buffers: Vec<(String, Vec<Event>)>,
+}
+
+impl NamedOutputs {
+    /// Pushes `event` to the named output `output`.
+    pub fn push(&mut self, output: &str, event: Event) {
+        match self.buffers.iter_mut().find(|(name, _)| name == output) {
+            Some((_, events)) => events.push(event),
+            None => self.buffers.push((output.to_owned(), vec![event])),
+        }
+    }
+
+    /// Takes the events pushed so far, by output.
+    pub fn drain(&mut self) -> impl Iterator<Item = (String, Vec<Event>)> + '_ {
+        self.buffers.drain(..)
+    }
+}
+
 /// Transforms that tend to be more complicated runtime style components.
 ///
 /// These require coordination and map a stream of some `T` to some `U`.
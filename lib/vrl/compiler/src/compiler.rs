@@ -1,5 +1,7 @@
+use crate::expression::assignment::Details;
 use crate::expression::*;
-use crate::{Function, Program, State, Value};
+use crate::function::{ClosureDefinition, ClosureVariable, Parameter};
+use crate::{Function, Program, State, TypeDef, Value};
 use chrono::{TimeZone, Utc};
 use diagnostic::DiagnosticError;
 use ordered_float::NotNan;
@@ -329,12 +331,20 @@ impl<'a> Compiler<'a> {
             ident,
             abort_on_error,
             arguments,
+            closure,
         } = node.into_inner();
 
         let arguments = arguments
             .into_iter()
             .map(|node| Node::new(node.span(), self.compile_function_argument(node)))
-            .collect();
+            .collect::<Vec<_>>();
+
+        let closure = closure.map(|node| {
+            let span = node.span();
+            let closure = self.compile_function_closure(node, ident.as_ref().as_ref(), &arguments);
+
+            Node::new(span, closure)
+        });
 
         if abort_on_error {
             self.fallible = true;
@@ -345,6 +355,7 @@ impl<'a> Compiler<'a> {
             ident,
             abort_on_error,
             arguments,
+            closure,
             self.fns,
             self.state,
         )
@@ -360,6 +371,71 @@ impl<'a> Compiler<'a> {
         FunctionArgument::new(ident, expr)
     }
 
+    /// Compile the closure passed to function `ident`.
+    ///
+    /// The closure variables are only defined while compiling the closure
+    /// block. They are typed after the keys and values of the collection the
+    /// function iterates over, as defined by its [`ClosureDefinition`].
+    ///
+    /// Any error in the closure signature itself is reported by
+    /// [`FunctionCall::new`].
+    fn compile_function_closure(
+        &mut self,
+        node: Node<ast::FunctionClosure>,
+        ident: &str,
+        arguments: &[Node<FunctionArgument>],
+    ) -> FunctionClosure {
+        let ast::FunctionClosure { variables, block } = node.into_inner();
+
+        let function = self.fns.iter().find(|f| f.identifier() == ident);
+        let definition = function.and_then(|f| f.closure());
+
+        let collection = match (function, definition) {
+            (Some(function), Some(definition)) => {
+                closure_collection(function.parameters(), definition, arguments)
+                    .map(|argument| argument.type_def(self.state))
+                    .unwrap_or_default()
+            }
+            _ => TypeDef::new().unknown(),
+        };
+
+        let kinds = definition.map(|d| d.variables).unwrap_or(&[]);
+
+        let shadowed = variables
+            .iter()
+            .enumerate()
+            .map(|(i, node)| {
+                let ident = node.as_ref().clone();
+                let type_def = match kinds.get(i) {
+                    Some(ClosureVariable::Key) => collection.collection_key(),
+                    Some(ClosureVariable::Value) => collection.collection_value(),
+                    None => TypeDef::new().unknown(),
+                };
+
+                let previous = self.state.variable(&ident).cloned();
+                let details = Details {
+                    type_def,
+                    value: None,
+                };
+                self.state.insert_variable(ident.clone(), details);
+
+                (ident, previous)
+            })
+            .collect::<Vec<_>>();
+
+        let block = self.compile_block(block);
+        let type_def = block.type_def(self.state);
+
+        for (ident, previous) in shadowed.into_iter().rev() {
+            match previous {
+                Some(details) => self.state.insert_variable(ident, details),
+                None => self.state.remove_variable(&ident),
+            }
+        }
+
+        FunctionClosure::new(variables, block, type_def)
+    }
+
     fn compile_variable(&mut self, node: Node<ast::Ident>) -> Variable {
         Variable::new(node.into_inner(), &self.state)
     }
@@ -394,3 +470,33 @@ impl<'a> Compiler<'a> {
         self.errors.push(Box::new(error))
     }
 }
+
+/// Find the argument holding the collection a function closure iterates over.
+///
+/// Positional arguments are mapped to their parameter the same way
+/// [`FunctionCall::new`] does.
+fn closure_collection<'a>(
+    parameters: &[Parameter],
+    definition: ClosureDefinition,
+    arguments: &'a [Node<FunctionArgument>],
+) -> Option<&'a Node<FunctionArgument>> {
+    let mut index = 0;
+
+    arguments.iter().find(|argument| {
+        let keyword = match argument.keyword() {
+            Some(keyword) => {
+                if parameters.get(index).map(|p| p.keyword) == Some(keyword) {
+                    index += 1;
+                }
+
+                Some(keyword)
+            }
+            None => {
+                index += 1;
+                parameters.get(index - 1).map(|p| p.keyword)
+            }
+        };
+
+        keyword == Some(definition.parameter)
+    })
+}
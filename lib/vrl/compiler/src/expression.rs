@@ -7,6 +7,7 @@ mod abort;
 mod array;
 mod block;
 mod function_argument;
+mod function_closure;
 mod group;
 mod if_statement;
 mod levenstein;
@@ -32,6 +33,7 @@ pub use container::Container;
 pub use container::Variant;
 pub use function_argument::FunctionArgument;
 pub use function_call::FunctionCall;
+pub use function_closure::FunctionClosure;
 pub use group::Group;
 pub use if_statement::IfStatement;
 pub use literal::Literal;
@@ -1,4 +1,4 @@
-use crate::expression::{levenstein, ExpressionError, FunctionArgument, Noop};
+use crate::expression::{levenstein, ExpressionError, FunctionArgument, FunctionClosure, Noop};
 use crate::function::{ArgumentList, Parameter};
 use crate::parser::{Ident, Node};
 use crate::{value::Kind, Context, Expression, Function, Resolved, Span, State, TypeDef};
@@ -22,6 +22,8 @@ pub struct FunctionCall {
     // This allows us to keep the arguments non-cloneable.
     arguments_fmt: Vec<String>,
     arguments_dbg: Vec<String>,
+    closure_fmt: Option<String>,
+    closure_dbg: Option<String>,
 
     // used for equality check
     ident: &'static str,
@@ -33,6 +35,7 @@ impl FunctionCall {
         ident: Node<Ident>,
         abort_on_error: bool,
         arguments: Vec<Node<FunctionArgument>>,
+        closure: Option<Node<FunctionClosure>>,
         funcs: &[Box<dyn Function>],
         state: &State,
     ) -> Result<Self, Error> {
@@ -162,6 +165,54 @@ impl FunctionCall {
                 })
             })?;
 
+        let closure_fmt = closure.as_ref().map(|closure| closure.to_string());
+        let closure_dbg = closure.as_ref().map(|closure| format!("{:?}", closure));
+
+        // Check the closure against the one the function accepts.
+        match (function.closure(), closure) {
+            (None, None) => {}
+            (None, Some(closure)) => {
+                return Err(Error::UnexpectedClosure {
+                    ident_span,
+                    closure_span: closure.span(),
+                })
+            }
+            (Some(_), None) => return Err(Error::MissingClosure { call_span }),
+            (Some(definition), Some(closure)) => {
+                let (closure_span, closure) = closure.take();
+
+                let max = definition.variables.len();
+                if closure.variables().len() > max {
+                    let variables_span = {
+                        let start = closure.variables()[max].span().start();
+                        let end = closure.variables().last().unwrap().span().end();
+
+                        Span::new(start, end)
+                    };
+
+                    return Err(Error::ClosureArity {
+                        variables_span,
+                        max,
+                    });
+                }
+
+                let type_def = closure.type_def();
+                if type_def.is_fallible() {
+                    return Err(Error::FallibleClosure { closure_span });
+                }
+
+                if !definition.output().contains(type_def.kind()) {
+                    return Err(Error::InvalidClosureKind {
+                        closure_span,
+                        expected: definition.output(),
+                        got: type_def.kind(),
+                    });
+                }
+
+                list.set_closure(closure);
+            }
+        }
+
         let expr = function
             .compile(list)
             .map_err(|error| Error::Compilation { call_span, error })?;
@@ -183,6 +234,8 @@ impl FunctionCall {
             span: call_span,
             arguments_fmt,
             arguments_dbg,
+            closure_fmt,
+            closure_dbg,
             ident: function.identifier(),
         })
     }
@@ -197,6 +250,8 @@ impl FunctionCall {
             span: Span::default(),
             arguments_fmt: vec![],
             arguments_dbg: vec![],
+            closure_fmt: None,
+            closure_dbg: None,
             ident: "noop",
         }
     }
@@ -209,7 +264,9 @@ impl Expression for FunctionCall {
                 use ExpressionError::*;
 
                 match err {
-                    Abort => panic!("abort errors must only be defined by `abort` statement"),
+                    // An `abort` statement inside a function closure aborts
+                    // the program as a whole.
+                    Abort => Abort,
                     Error {
                         message,
                         labels,
@@ -315,7 +372,14 @@ impl fmt::Display for FunctionCall {
             }
         }
 
-        f.write_str(")")
+        f.write_str(")")?;
+
+        if let Some(closure) = &self.closure_fmt {
+            f.write_str(" ")?;
+            f.write_str(closure)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -335,7 +399,14 @@ impl fmt::Debug for FunctionCall {
             }
         }
 
-        f.write_str("))")
+        f.write_str(")")?;
+
+        if let Some(closure) = &self.closure_dbg {
+            f.write_str(" ")?;
+            f.write_str(closure)?;
+        }
+
+        f.write_str(")")
     }
 }
 
@@ -396,6 +467,28 @@ pub enum Error {
 
     #[error("fallible argument")]
     FallibleArgument { expr_span: Span },
+
+    #[error("unexpected function closure")]
+    UnexpectedClosure {
+        ident_span: Span,
+        closure_span: Span,
+    },
+
+    #[error("missing function closure")]
+    MissingClosure { call_span: Span },
+
+    #[error("wrong number of closure variables")]
+    ClosureArity { variables_span: Span, max: usize },
+
+    #[error("fallible closure")]
+    FallibleClosure { closure_span: Span },
+
+    #[error("invalid closure return type")]
+    InvalidClosureKind {
+        closure_span: Span,
+        expected: Kind,
+        got: Kind,
+    },
 }
 
 impl DiagnosticError for Error {
@@ -411,12 +504,27 @@ impl DiagnosticError for Error {
             AbortInfallible { .. } => 620,
             InvalidArgumentKind { .. } => 110,
             FallibleArgument { .. } => 630,
+            UnexpectedClosure { .. } => 109,
+            MissingClosure { .. } => 111,
+            ClosureArity { .. } => 120,
+            InvalidClosureKind { .. } => 121,
+            FallibleClosure { .. } => 631,
         }
     }
 
     fn labels(&self) -> Vec<Label> {
         use Error::*;
 
+        let kind_str = |kind: &Kind| {
+            if kind.is_any() {
+                kind.to_string()
+            } else if !kind.is_many() {
+                format!(r#"the exact type {}"#, kind)
+            } else {
+                format!("one of {}", kind)
+            }
+        };
+
         match self {
             Undefined {
                 ident_span,
@@ -525,17 +633,6 @@ impl DiagnosticError for Error {
                 let expected = parameter.kind();
                 let expr_span = argument.span();
 
-                // TODO: extract this out into a helper
-                let kind_str = |kind: &Kind| {
-                    if kind.is_any() {
-                        kind.to_string()
-                    } else if !kind.is_many() {
-                        format!(r#"the exact type {}"#, kind)
-                    } else {
-                        format!("one of {}", kind)
-                    }
-                };
-
                 vec![
                     Label::primary(
                         format!("this expression resolves to {}", kind_str(got)),
@@ -559,6 +656,54 @@ impl DiagnosticError for Error {
                     expr_span,
                 ),
             ],
+
+            UnexpectedClosure {
+                ident_span,
+                closure_span,
+            } => vec![
+                Label::primary("unexpected closure", closure_span),
+                Label::context("this function doesn't accept a closure", ident_span),
+            ],
+
+            MissingClosure { call_span } => vec![Label::primary(
+                "this function requires a closure, e.g. `-> |key, value| { ... }`",
+                call_span,
+            )],
+
+            ClosureArity {
+                variables_span,
+                max,
+            } => {
+                let variable = if *max == 1 { "variable" } else { "variables" };
+
+                vec![
+                    Label::primary("too many closure variables", variables_span),
+                    Label::context(
+                        format!("this closure takes a maximum of {} {}", max, variable),
+                        variables_span,
+                    ),
+                ]
+            }
+
+            FallibleClosure { closure_span } => vec![
+                Label::primary("this closure can fail", closure_span),
+                Label::context("handle the errors inside the closure block", closure_span),
+            ],
+
+            InvalidClosureKind {
+                closure_span,
+                expected,
+                got,
+            } => vec![
+                Label::primary(
+                    format!("this closure resolves to {}", kind_str(got)),
+                    closure_span,
+                ),
+                Label::context(
+                    format!("but the function expects {}", kind_str(expected)),
+                    closure_span,
+                ),
+            ],
         }
     }
 
@@ -570,7 +715,9 @@ impl DiagnosticError for Error {
                 "function arguments".to_owned(),
                 Urls::expression_docs_url("#arguments"),
             )],
-            AbortInfallible { .. } | FallibleArgument { .. } => vec![Note::SeeErrorDocs],
+            AbortInfallible { .. } | FallibleArgument { .. } | FallibleClosure { .. } => {
+                vec![Note::SeeErrorDocs]
+            }
             InvalidArgumentKind {
                 function_ident,
                 abort_on_error,
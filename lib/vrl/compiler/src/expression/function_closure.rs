@@ -0,0 +1,92 @@
+use crate::expression::{Block, Resolved};
+use crate::parser::{Ident, Node};
+use crate::{Context, Expression, TypeDef, Value};
+use std::fmt;
+
+/// A closure passed to a function call.
+///
+/// The closure is never resolved on its own. Instead, the function it is
+/// passed to calls it once for each item of the collection it iterates over.
+///
+/// For example:
+///
+/// ```text
+/// for_each(.tags) -> |key, value| { .tag_count = int!(.tag_count) + 1 }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionClosure {
+    variables: Vec<Node<Ident>>,
+    block: Block,
+
+    // The type definition of the block, computed at compile-time while the
+    // closure variables are defined.
+    type_def: TypeDef,
+}
+
+impl FunctionClosure {
+    pub(crate) fn new(variables: Vec<Node<Ident>>, block: Block, type_def: TypeDef) -> Self {
+        Self {
+            variables,
+            block,
+            type_def,
+        }
+    }
+
+    pub(crate) fn variables(&self) -> &[Node<Ident>] {
+        &self.variables
+    }
+
+    /// The type definition of the value the closure block resolves to.
+    pub fn type_def(&self) -> TypeDef {
+        self.type_def.clone()
+    }
+
+    /// Resolve the closure block for a single item of a collection.
+    ///
+    /// The items are bound to the closure variables in the order of the
+    /// function's [`ClosureDefinition`](crate::function::ClosureDefinition).
+    /// Variables shadowed by the closure variables are restored once the block
+    /// is resolved.
+    pub fn call(&self, ctx: &mut Context, items: &[Value]) -> Resolved {
+        let shadowed = self
+            .variables
+            .iter()
+            .zip(items)
+            .map(|(ident, item)| {
+                let ident = ident.as_ref();
+                let previous = ctx.state().variable(ident).cloned();
+                ctx.state_mut().insert_variable(ident.clone(), item.clone());
+
+                (ident, previous)
+            })
+            .collect::<Vec<_>>();
+
+        let resolved = self.block.resolve(ctx);
+
+        for (ident, previous) in shadowed.into_iter().rev() {
+            match previous {
+                Some(value) => ctx.state_mut().insert_variable(ident.clone(), value),
+                None => ctx.state_mut().remove_variable(ident),
+            }
+        }
+
+        resolved
+    }
+}
+
+impl fmt::Display for FunctionClosure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("-> |")?;
+
+        let mut iter = self.variables.iter().peekable();
+        while let Some(variable) = iter.next() {
+            variable.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        write!(f, "| {}", self.block)
+    }
+}
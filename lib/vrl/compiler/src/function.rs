@@ -1,5 +1,6 @@
 use crate::expression::{
-    container::Variant, Container, Expr, Expression, FunctionArgument, Literal, Query,
+    container::Variant, Container, Expr, Expression, FunctionArgument, FunctionClosure, Literal,
+    Query,
 };
 use crate::parser::Node;
 use crate::value::Kind;
@@ -48,6 +49,14 @@ pub trait Function: Sync + fmt::Debug {
     fn parameters(&self) -> &'static [Parameter] {
         &[]
     }
+
+    /// The closure the function accepts, if any.
+    ///
+    /// This is used at compile-time to type the closure variables, and to
+    /// check the closure arity and the kind of value it returns.
+    fn closure(&self) -> Option<ClosureDefinition> {
+        None
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -90,8 +99,50 @@ impl Parameter {
 
 // -----------------------------------------------------------------------------
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ClosureDefinition {
+    /// The keyword of the parameter holding the collection the closure
+    /// iterates over.
+    ///
+    /// The closure variables are typed after the keys and values of this
+    /// collection.
+    pub parameter: &'static str,
+
+    /// The variables the closure receives for each item, in order.
+    ///
+    /// A closure can bind fewer variables than listed here, in which case the
+    /// trailing ones are left out.
+    pub variables: &'static [ClosureVariable],
+
+    /// The type kind(s) the closure block has to resolve to.
+    ///
+    /// If the block resolves to any other kind, the compiler will return a
+    /// compile-time error.
+    pub output: u16,
+}
+
+impl ClosureDefinition {
+    pub fn output(&self) -> Kind {
+        Kind::new(self.output)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ClosureVariable {
+    /// The key of an object item, or the index of an array item.
+    Key,
+
+    /// The value of the item.
+    Value,
+}
+
+// -----------------------------------------------------------------------------
+
 #[derive(Debug, Default)]
-pub struct ArgumentList(HashMap<&'static str, Expr>);
+pub struct ArgumentList {
+    arguments: HashMap<&'static str, Expr>,
+    closure: Option<FunctionClosure>,
+}
 
 impl ArgumentList {
     pub fn optional(&mut self, keyword: &'static str) -> Option<Box<dyn Expression>> {
@@ -211,16 +262,28 @@ impl ArgumentList {
         Ok(required(self.optional_array(keyword)?))
     }
 
+    pub fn optional_closure(&mut self) -> Option<FunctionClosure> {
+        self.closure.take()
+    }
+
+    pub fn required_closure(&mut self) -> FunctionClosure {
+        required(self.optional_closure())
+    }
+
     pub(crate) fn keywords(&self) -> Vec<&'static str> {
-        self.0.keys().copied().collect::<Vec<_>>()
+        self.arguments.keys().copied().collect::<Vec<_>>()
     }
 
     pub(crate) fn insert(&mut self, k: &'static str, v: Expr) {
-        self.0.insert(k, v);
+        self.arguments.insert(k, v);
+    }
+
+    pub(crate) fn set_closure(&mut self, closure: FunctionClosure) {
+        self.closure = Some(closure);
     }
 
     fn optional_expr(&mut self, keyword: &'static str) -> Option<Expr> {
-        self.0.remove(keyword)
+        self.arguments.remove(keyword)
     }
 
     fn required_expr(&mut self, keyword: &'static str) -> Expr {
@@ -234,11 +297,15 @@ fn required<T>(argument: Option<T>) -> T {
 
 impl From<HashMap<&'static str, Value>> for ArgumentList {
     fn from(map: HashMap<&'static str, Value>) -> Self {
-        Self(
-            map.into_iter()
-                .map(|(k, v)| (k, v.into_expr()))
-                .collect::<HashMap<_, _>>(),
-        )
+        let arguments = map
+            .into_iter()
+            .map(|(k, v)| (k, v.into_expr()))
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            arguments,
+            closure: None,
+        }
     }
 }
 
@@ -256,7 +323,10 @@ impl From<Vec<Node<FunctionArgument>>> for ArgumentList {
             })
             .collect::<HashMap<_, _>>();
 
-        Self(arguments)
+        Self {
+            arguments,
+            closure: None,
+        }
     }
 }
 
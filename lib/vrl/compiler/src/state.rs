@@ -36,6 +36,10 @@ impl Compiler {
         self.variables.insert(ident, details);
     }
 
+    pub(crate) fn remove_variable(&mut self, ident: &Ident) {
+        self.variables.remove(ident);
+    }
+
     pub(crate) fn target(&self) -> Option<&assignment::Details> {
         self.target.as_ref()
     }
@@ -87,4 +91,8 @@ impl Runtime {
     pub(crate) fn insert_variable(&mut self, ident: Ident, value: Value) {
         self.variables.insert(ident, value);
     }
+
+    pub(crate) fn remove_variable(&mut self, ident: &Ident) {
+        self.variables.remove(ident);
+    }
 }
@@ -827,6 +827,65 @@ impl TypeDef {
         self
     }
 
+    /// The type definition of the keys of the collections (array, object)
+    /// this type definition represents, that is, strings for object fields and
+    /// integers for array indices.
+    ///
+    /// Used to type the variables of a closure iterating over a collection.
+    pub fn collection_key(&self) -> Self {
+        let kind = self.kind();
+        let mut key = Kind::empty();
+
+        if kind.contains_object() {
+            key |= Kind::Bytes;
+        }
+        if kind.contains_array() {
+            key |= Kind::Integer;
+        }
+
+        key.into()
+    }
+
+    /// The type definition of the values of the collections (array, object)
+    /// this type definition represents, merged into a single type.
+    ///
+    /// Used to type the variables of a closure iterating over a collection.
+    pub fn collection_value(&self) -> Self {
+        let set = match &self.kind {
+            KindInfo::Unknown => return Self::new().unknown(),
+            KindInfo::Known(set) => set,
+        };
+
+        let values = set
+            .iter()
+            .flat_map(|kind| match kind {
+                TypeKind::Array(array) => array.values().collect::<Vec<_>>(),
+                TypeKind::Object(object) => object.values().collect::<Vec<_>>(),
+                _ => vec![],
+            })
+            .collect::<Vec<_>>();
+
+        // Any value of unknown type makes the merged type unknown as well,
+        // which `KindInfo::merge` alone doesn't guarantee. The same goes for
+        // collections of which no value is known, such as empty ones.
+        let kind =
+            if values.is_empty() || values.iter().any(|info| matches!(info, KindInfo::Unknown)) {
+                KindInfo::Unknown
+            } else {
+                values
+                    .into_iter()
+                    .cloned()
+                    .fold(KindInfo::Known(BTreeSet::default()), |acc, info| {
+                        acc.merge(info, false, false)
+                    })
+            };
+
+        Self {
+            fallible: false,
+            kind,
+        }
+    }
+
     #[inline]
     pub fn is_unknown(&self) -> bool {
         matches!(self.kind, KindInfo::Unknown)
@@ -1005,6 +1064,34 @@ mod tests {
         assert_eq!(kind, expected);
     }
 
+    #[test]
+    fn collection_items() {
+        let object = TypeDef::new()
+            .object::<&'static str, Kind>(map! { "foo": Kind::Integer, "bar": Kind::Bytes });
+        assert_eq!(object.collection_key(), TypeDef::new().bytes());
+        assert_eq!(
+            object.collection_value(),
+            TypeDef::new().integer().add_bytes()
+        );
+
+        let array = TypeDef::new().array_mapped::<i32, Kind>(map! { 0: Kind::Boolean });
+        let both = array
+            .clone()
+            .add_object::<(), Kind>(map! { (): Kind::Null });
+        assert_eq!(both.collection_key(), TypeDef::new().bytes().add_integer());
+        assert_eq!(both.collection_value(), TypeDef::new().boolean().add_null());
+
+        // Any value of unknown type makes all values unknown.
+        let unknown = array.add_object::<(), Kind>(map! { (): Kind::all() });
+        assert!(unknown.collection_value().is_unknown());
+
+        assert!(TypeDef::new()
+            .array::<TypeDef>(vec![])
+            .collection_value()
+            .is_unknown());
+        assert!(TypeDef::new().unknown().collection_value().is_unknown());
+    }
+
     mod kind_info {
         use super::*;
 
@@ -24,7 +24,10 @@ pub use std::fmt;
 
 // commonly used function types
 
-pub use compiler::function::{ArgumentList, Compiled, Example, Parameter};
+pub use compiler::expression::FunctionClosure;
+pub use compiler::function::{
+    ArgumentList, ClosureDefinition, ClosureVariable, Compiled, Example, Parameter,
+};
 
 // commonly used macros
 pub use compiler::{bench_function, expr, func_args, map, test_function, test_type_def, value};
@@ -266,6 +266,7 @@ pub enum Token<S> {
     MergeEquals,
     Bang,
     Question,
+    Arrow,
 
     /// The {L,R}Query token is an "instruction" token. It does not represent
     /// any character in the source, instead it represents the start or end of a
@@ -347,6 +348,7 @@ impl<S> Token<S> {
             MergeEquals => MergeEquals,
             Bang => Bang,
             Question => Question,
+            Arrow => Arrow,
 
             LQuery => LQuery,
             RQuery => RQuery,
@@ -401,6 +403,7 @@ where
             MergeEquals => "MergeEquals",
             Bang => "Bang",
             Question => "Question",
+            Arrow => "Arrow",
 
             LQuery => "LQuery",
             RQuery => "RQuery",
@@ -980,12 +983,20 @@ impl<'input> Lexer<'input> {
     }
 
     fn operator(&mut self, start: usize) -> Spanned<'input, usize> {
+        // The closure parameters following an arrow start with `|`, which
+        // isn't part of the arrow, even without whitespace in between.
+        if self.input[start..].starts_with("->") {
+            self.bump();
+            return (start, Token::Arrow, start + 2);
+        }
+
         let (end, op) = self.take_while(start, is_operator);
 
         let token = match op {
             "=" => Token::Equals,
             "|=" => Token::MergeEquals,
             "?" => Token::Question,
+            "->" => Token::Arrow,
             op => Token::Operator(op),
         };
 
@@ -1839,6 +1850,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn arrow_before_closure_parameters() {
+        test(
+            data("f(1) ->|x| 2"),
+            vec![
+                ("~           ", FunctionCall("f")),
+                (" ~          ", LParen),
+                ("  ~         ", IntegerLiteral(1)),
+                ("   ~        ", RParen),
+                ("     ~~     ", Arrow),
+                ("       ~    ", Operator("|")),
+                ("        ~   ", Identifier("x")),
+                ("         ~  ", Operator("|")),
+                ("           ~", IntegerLiteral(2)),
+            ],
+        );
+    }
+
     #[test]
     fn multi_byte_character_1() {
         use StringLiteral as S;
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Filter;
+
+impl Function for Filter {
+    fn identifier(&self) -> &'static str {
+        "filter"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn closure(&self) -> Option<ClosureDefinition> {
+        Some(ClosureDefinition {
+            parameter: "value",
+            variables: &[ClosureVariable::Key, ClosureVariable::Value],
+            output: kind::BOOLEAN,
+        })
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: r#"filter({ "a": 1, "b": null, "c": 3 }) -> |_key, value| { !is_null(value) }"#,
+                result: Ok(r#"{ "a": 1, "c": 3 }"#),
+            },
+            Example {
+                title: "array",
+                source: r#"filter(["a", null, "b", null]) -> |_index, value| { value != null }"#,
+                result: Ok(r#"["a", "b"]"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(FilterFn { value, closure }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FilterFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for FilterFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(object) => {
+                let mut filtered = BTreeMap::new();
+                for (key, value) in object {
+                    let keep = self
+                        .closure
+                        .call(ctx, &[key.as_str().into(), value.clone()])?;
+                    if keep.try_boolean()? {
+                        filtered.insert(key, value);
+                    }
+                }
+
+                Ok(filtered.into())
+            }
+            Value::Array(array) => {
+                let mut filtered = Vec::new();
+                for (index, value) in array.into_iter().enumerate() {
+                    let keep = self.closure.call(ctx, &[index.into(), value.clone()])?;
+                    if keep.try_boolean()? {
+                        filtered.push(value);
+                    }
+                }
+
+                Ok(filtered.into())
+            }
+            value => Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::Object | Kind::Array,
+            }
+            .into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        // Filtering changes the indices of array items, and drops object
+        // fields, so only the type of the values as a whole is known.
+        let type_def = self.value.type_def(state);
+        let kind = type_def.kind();
+        let value = type_def.collection_value();
+        let mut type_def = TypeDef::new();
+
+        if kind.contains_object() {
+            type_def = type_def.add_object::<(), TypeDef>(map! { (): value.clone() });
+        }
+        if kind.contains_array() {
+            type_def = type_def.add_array_mapped::<(), TypeDef>(map! { (): value });
+        }
+
+        type_def
+    }
+}
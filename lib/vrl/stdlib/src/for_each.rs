@@ -0,0 +1,96 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ForEach;
+
+impl Function for ForEach {
+    fn identifier(&self) -> &'static str {
+        "for_each"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn closure(&self) -> Option<ClosureDefinition> {
+        Some(ClosureDefinition {
+            parameter: "value",
+            variables: &[ClosureVariable::Key, ClosureVariable::Value],
+            output: kind::ANY,
+        })
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: indoc! {r#"
+                    sum = 0
+                    for_each({ "a": 1, "b": 2, "c": 3 }) -> |_key, value| {
+                        sum = sum + value
+                    }
+                    sum
+                "#},
+                result: Ok("6"),
+            },
+            Example {
+                title: "array",
+                source: indoc! {r#"
+                    count = 0
+                    for_each([true, false, true]) -> |index, value| {
+                        if value { count = count + index }
+                    }
+                    count
+                "#},
+                result: Ok("2"),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(ForEachFn { value, closure }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ForEachFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for ForEachFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(object) => {
+                for (key, value) in object {
+                    self.closure.call(ctx, &[key.into(), value])?;
+                }
+            }
+            Value::Array(array) => {
+                for (index, value) in array.into_iter().enumerate() {
+                    self.closure.call(ctx, &[index.into(), value])?;
+                }
+            }
+            value => {
+                return Err(value::Error::Expected {
+                    got: value.kind(),
+                    expected: Kind::Object | Kind::Array,
+                }
+                .into())
+            }
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().null()
+    }
+}
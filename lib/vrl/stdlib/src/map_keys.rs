@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapKeys;
+
+impl Function for MapKeys {
+    fn identifier(&self) -> &'static str {
+        "map_keys"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT,
+            required: true,
+        }]
+    }
+
+    fn closure(&self) -> Option<ClosureDefinition> {
+        Some(ClosureDefinition {
+            parameter: "value",
+            variables: &[ClosureVariable::Key],
+            output: kind::BYTES,
+        })
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "downcase keys",
+                source: r#"map_keys({ "Foo": 1, "BAR": 2 }) -> |key| { downcase(key) }"#,
+                result: Ok(r#"{ "bar": 2, "foo": 1 }"#),
+            },
+            Example {
+                title: "rename matching keys",
+                source: r#"map_keys({ "tag_a": 1, "b": 2 }) -> |key| { replace(key, r'^tag_', "") }"#,
+                result: Ok(r#"{ "a": 1, "b": 2 }"#),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(MapKeysFn { value, closure }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapKeysFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for MapKeysFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let object = self.value.resolve(ctx)?.try_object()?;
+
+        // Keys are mapped in order, so if two keys map to the same key, the
+        // value of the last one is kept.
+        let mut mapped = BTreeMap::new();
+        for (key, value) in object {
+            let key = self.closure.call(ctx, &[key.into()])?;
+            mapped.insert(key.try_bytes_utf8_lossy()?.into_owned(), value);
+        }
+
+        Ok(mapped.into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let value = self.value.type_def(state).collection_value();
+
+        TypeDef::new().object::<(), TypeDef>(map! { (): value })
+    }
+}
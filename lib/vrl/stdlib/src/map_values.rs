@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapValues;
+
+impl Function for MapValues {
+    fn identifier(&self) -> &'static str {
+        "map_values"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn closure(&self) -> Option<ClosureDefinition> {
+        Some(ClosureDefinition {
+            parameter: "value",
+            variables: &[ClosureVariable::Value],
+            output: kind::ANY,
+        })
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "object",
+                source: r#"map_values({ "a": "foo", "b": "bar" }) -> |value| { upcase(value) }"#,
+                result: Ok(r#"{ "a": "FOO", "b": "BAR" }"#),
+            },
+            Example {
+                title: "array",
+                source: r#"map_values([1, 2, 3]) -> |value| { value * 2 }"#,
+                result: Ok("[2, 4, 6]"),
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure();
+
+        Ok(Box::new(MapValuesFn { value, closure }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapValuesFn {
+    value: Box<dyn Expression>,
+    closure: FunctionClosure,
+}
+
+impl Expression for MapValuesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        match self.value.resolve(ctx)? {
+            Value::Object(object) => object
+                .into_iter()
+                .map(|(key, value)| Ok((key, self.closure.call(ctx, &[value])?)))
+                .collect::<Result<BTreeMap<_, _>>>()
+                .map(Into::into),
+            Value::Array(array) => array
+                .into_iter()
+                .map(|value| self.closure.call(ctx, &[value]))
+                .collect::<Result<Vec<_>>>()
+                .map(Into::into),
+            value => Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::Object | Kind::Array,
+            }
+            .into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let kind = self.value.type_def(state).kind();
+        let value = self.closure.type_def();
+        let mut type_def = TypeDef::new();
+
+        if kind.contains_object() {
+            type_def = type_def.add_object::<(), TypeDef>(map! { (): value.clone() });
+        }
+        if kind.contains_array() {
+            type_def = type_def.add_array_mapped::<(), TypeDef>(map! { (): value });
+        }
+
+        type_def
+    }
+}
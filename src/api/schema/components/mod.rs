@@ -268,6 +268,14 @@ pub fn update_config(config: &Config) {
                 name: name.to_owned(),
                 component_type: transform.inner.transform_type().to_string(),
                 inputs: transform.inputs.clone(),
+                outputs: transform
+                    .named_outputs(name)
+                    .into_iter()
+                    .map(|(name, data_type)| transform::Output {
+                        name,
+                        output_type: data_type.into(),
+                    })
+                    .collect(),
             })),
         );
     }
@@ -340,6 +348,7 @@ mod tests {
                 name: "parse_json".to_string(),
                 component_type: "json".to_string(),
                 inputs: vec!["gen1".to_string(), "gen2".to_string()],
+                outputs: vec![],
             })),
             Component::Sink(sink::Sink(sink::Data {
                 name: "devnull".to_string(),
@@ -479,6 +488,7 @@ mod tests {
                 name: "c".to_string(),
                 component_type: "json".to_string(),
                 inputs: vec!["gen1".to_string(), "gen2".to_string()],
+                outputs: vec![],
             })),
             Component::Source(source::Source(source::Data {
                 name: "e".to_string(),
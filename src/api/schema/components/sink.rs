@@ -88,7 +88,7 @@ impl Sink {
         self.0
             .inputs
             .iter()
-            .filter_map(|name| match state::component_by_name(name) {
+            .filter_map(|name| match state::component_by_input(name) {
                 Some(Component::Source(s)) => Some(s),
                 _ => None,
             })
@@ -100,7 +100,7 @@ impl Sink {
         self.0
             .inputs
             .iter()
-            .filter_map(|name| match state::component_by_name(name) {
+            .filter_map(|name| match state::component_by_input(name) {
                 Some(Component::Transform(t)) => Some(t),
                 _ => None,
             })
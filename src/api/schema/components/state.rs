@@ -66,6 +66,20 @@ pub fn component_by_name(name: &str) -> Option<Component> {
     Some(COMPONENTS.read().expect(INVARIANT).get(name)?.clone())
 }
 
+/// Gets the component an input refers to, by its name or one of its named outputs
+pub fn component_by_input(input: &str) -> Option<Component> {
+    let components = COMPONENTS.read().expect(INVARIANT);
+    components
+        .get(input)
+        .or_else(|| {
+            components.values().find(|component| match component {
+                Component::Transform(t) => t.has_output(input),
+                _ => false,
+            })
+        })
+        .cloned()
+}
+
 /// Overwrites component state with new components.
 pub fn update(new_components: HashMap<String, Component>) {
     *COMPONENTS.write().expect(INVARIANT) = new_components
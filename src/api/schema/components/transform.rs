@@ -1,4 +1,8 @@
-use super::{sink, source, state, Component};
+use super::{
+    sink,
+    source::{self, SourceOutputType},
+    state, Component,
+};
 use crate::{
     api::schema::{
         filter,
@@ -7,7 +11,7 @@ use crate::{
     },
     filter_check,
 };
-use async_graphql::{Enum, InputObject, Object};
+use async_graphql::{Enum, InputObject, Object, SimpleObject};
 use std::cmp;
 
 #[derive(Debug, Clone)]
@@ -15,6 +19,16 @@ pub struct Data {
     pub name: String,
     pub component_type: String,
     pub inputs: Vec<String>,
+    pub outputs: Vec<Output>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Output {
+    /// Output name, as used in the inputs of other components
+    pub name: String,
+
+    /// Output type
+    pub output_type: SourceOutputType,
 }
 
 #[derive(Debug, Clone)]
@@ -27,6 +41,11 @@ impl Transform {
     pub fn get_component_type(&self) -> &str {
         self.0.component_type.as_str()
     }
+
+    /// Whether `input` refers to the default or a named output of the transform
+    pub fn has_output(&self, input: &str) -> bool {
+        input == self.0.name || self.0.outputs.iter().any(|output| output.name == input)
+    }
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
@@ -63,17 +82,24 @@ impl Transform {
         self.0
             .inputs
             .iter()
-            .filter_map(|name| match state::component_by_name(name) {
+            .filter_map(|name| match state::component_by_input(name) {
                 Some(Component::Source(s)) => Some(s),
                 _ => None,
             })
             .collect()
     }
 
+    /// Named outputs, besides the default one
+    pub async fn outputs(&self) -> Vec<Output> {
+        self.0.outputs.clone()
+    }
+
     /// Transform outputs
     pub async fn transforms(&self) -> Vec<Transform> {
         state::filter_components(|(_name, components)| match components {
-            Component::Transform(t) if t.0.inputs.contains(&self.0.name) => Some(t.clone()),
+            Component::Transform(t) if t.0.inputs.iter().any(|i| self.has_output(i)) => {
+                Some(t.clone())
+            }
             _ => None,
         })
     }
@@ -81,7 +107,7 @@ impl Transform {
     /// Sink outputs
     pub async fn sinks(&self) -> Vec<sink::Sink> {
         state::filter_components(|(_name, components)| match components {
-            Component::Sink(s) if s.0.inputs.contains(&self.0.name) => Some(s.clone()),
+            Component::Sink(s) if s.0.inputs.iter().any(|i| self.has_output(i)) => Some(s.clone()),
             _ => None,
         })
     }
@@ -127,16 +153,19 @@ mod tests {
                 name: "parse_json".to_string(),
                 component_type: "json".to_string(),
                 inputs: vec![],
+                outputs: vec![],
             }),
             Transform(Data {
                 name: "field_adder".to_string(),
                 component_type: "add_fields".to_string(),
                 inputs: vec![],
+                outputs: vec![],
             }),
             Transform(Data {
                 name: "append".to_string(),
                 component_type: "concat".to_string(),
                 inputs: vec![],
+                outputs: vec![],
             }),
         ]
     }
@@ -0,0 +1,226 @@
+use crate::{
+    config::{self, secret, ConfigDiff},
+    signal::{SignalTo, SignalTx},
+};
+use async_graphql::{Context, Enum, Object, SimpleObject};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashSet;
+use tokio::{sync::oneshot, task::spawn_blocking};
+
+const RELOAD_DISABLED: &str =
+    "Config reloads are disabled, enable them by setting `api.reload` to `true`.";
+
+lazy_static! {
+    static ref COMPONENT_ERROR: Regex =
+        Regex::new(r#"^(?:Source|Transform|Sink) "([^"]+)": (?s)(.*)$"#).unwrap();
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl From<ConfigFormat> for config::Format {
+    fn from(format: ConfigFormat) -> Self {
+        match format {
+            ConfigFormat::Toml => config::Format::Toml,
+            ConfigFormat::Json => config::Format::Json,
+            ConfigFormat::Yaml => config::Format::Yaml,
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ReloadError {
+    /// Name of the component the error is about, if any
+    pub component_name: Option<String>,
+
+    /// Error message
+    pub message: String,
+}
+
+impl From<String> for ReloadError {
+    fn from(error: String) -> Self {
+        let error = secret::redact(&error);
+        match COMPONENT_ERROR.captures(&error) {
+            Some(caps) => Self {
+                component_name: Some(caps[1].to_owned()),
+                message: caps[2].to_owned(),
+            },
+            None => Self {
+                component_name: None,
+                message: error,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, SimpleObject)]
+pub struct ComponentsDiff {
+    /// Components that would be or were added
+    pub added: Vec<String>,
+
+    /// Components that would be or were rebuilt
+    pub changed: Vec<String>,
+
+    /// Components that would be or were updated without being rebuilt
+    pub reconfigured: Vec<String>,
+
+    /// Components that would be or were removed
+    pub removed: Vec<String>,
+}
+
+impl From<&config::Difference> for ComponentsDiff {
+    fn from(difference: &config::Difference) -> Self {
+        let sorted = |names: &HashSet<String>| {
+            let mut names = names.iter().cloned().collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+
+        Self {
+            added: sorted(&difference.to_add),
+            changed: sorted(&difference.to_change),
+            reconfigured: sorted(&difference.to_reconfigure),
+            removed: sorted(&difference.to_remove),
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct TopologyDiff {
+    pub sources: ComponentsDiff,
+    pub transforms: ComponentsDiff,
+    pub sinks: ComponentsDiff,
+}
+
+impl From<&ConfigDiff> for TopologyDiff {
+    fn from(diff: &ConfigDiff) -> Self {
+        Self {
+            sources: (&diff.sources).into(),
+            transforms: (&diff.transforms).into(),
+            sinks: (&diff.sinks).into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ConfigReload {
+    /// Whether the config is now running. Always false for a dry run
+    pub applied: bool,
+
+    /// Difference between the running config and the new one. Missing if the
+    /// new config is invalid
+    pub diff: Option<TopologyDiff>,
+
+    /// Warnings about the new config
+    pub warnings: Vec<String>,
+
+    /// Errors preventing the new config from being applied
+    pub errors: Vec<ReloadError>,
+}
+
+impl ConfigReload {
+    fn failed(warnings: Vec<String>, errors: Vec<String>) -> Self {
+        Self {
+            applied: false,
+            diff: None,
+            warnings,
+            errors: errors.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ConfigMutation;
+
+#[Object]
+impl ConfigMutation {
+    /// Validates a new config the same way as `vector validate` does, and
+    /// applies it unless `dryRun` is set. The previous config is restored if
+    /// the new one fails to start or its sinks are unhealthy. Secrets aren't
+    /// retrieved for a dry run. Requires `api.reload` to be enabled.
+    async fn reload_config(
+        &self,
+        ctx: &Context<'_>,
+        config: String,
+        #[graphql(default_with = "ConfigFormat::Toml")] format: ConfigFormat,
+        #[graphql(default)] dry_run: bool,
+    ) -> ConfigReload {
+        let signal_tx = match ctx.data_opt::<SignalTx>() {
+            Some(signal_tx) => signal_tx,
+            None => return ConfigReload::failed(vec![], vec![RELOAD_DISABLED.to_owned()]),
+        };
+
+        // Secret backends may run commands, so they're only queried for a
+        // config that is going to be applied. They wait on the commands
+        // synchronously, so loading runs off the runtime's worker threads.
+        let format: config::FormatHint = Some(format.into());
+        let loaded = spawn_blocking(move || {
+            if dry_run {
+                config::load_without_secrets(config.as_bytes(), format)
+            } else {
+                config::load(config.as_bytes(), format)
+            }
+        })
+        .await;
+        let (builder, mut warnings) = match loaded {
+            Ok(Ok(loaded)) => loaded,
+            Ok(Err(errors)) => return ConfigReload::failed(vec![], errors),
+            Err(error) => {
+                return ConfigReload::failed(vec![], vec![format!("Loading failed: {}", error)])
+            }
+        };
+        let new_config = match builder.build_with_warnings() {
+            Ok((new_config, build_warnings)) => {
+                warnings.extend(build_warnings);
+                new_config
+            }
+            Err(errors) => return ConfigReload::failed(warnings, errors),
+        };
+        let warnings = warnings
+            .iter()
+            .map(|warning| secret::redact(warning))
+            .collect::<Vec<_>>();
+
+        let (reply, rx) = oneshot::channel();
+        let signal = SignalTo::ReloadFromApi {
+            config: new_config,
+            dry_run,
+            reply,
+        };
+        if signal_tx.send(signal).await.is_err() {
+            return ConfigReload::failed(warnings, vec!["Vector is shutting down.".to_owned()]);
+        }
+
+        match rx.await {
+            Ok(Ok(diff)) => ConfigReload {
+                applied: !dry_run,
+                diff: Some((&diff).into()),
+                warnings,
+                errors: vec![],
+            },
+            Ok(Err(errors)) => ConfigReload::failed(warnings, errors),
+            Err(_) => ConfigReload::failed(warnings, vec!["Vector is shutting down.".to_owned()]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_errors_to_components() {
+        let error = ReloadError::from(r#"Sink "out": unexpected field"#.to_owned());
+        assert_eq!(error.component_name.as_deref(), Some("out"));
+        assert_eq!(error.message, "unexpected field");
+
+        let error = ReloadError::from("No sources defined in the config.".to_owned());
+        assert_eq!(error.component_name, None);
+        assert_eq!(error.message, "No sources defined in the config.");
+    }
+}
@@ -1,5 +1,8 @@
 use super::EventEncodingType;
-use crate::event::{self, Value};
+use crate::{
+    api::tap::TapSide,
+    event::{self, Value},
+};
 
 use async_graphql::Object;
 use chrono::{DateTime, Utc};
@@ -7,13 +10,15 @@ use chrono::{DateTime, Utc};
 #[derive(Debug)]
 pub struct Log {
     component_name: String,
+    side: TapSide,
     event: event::LogEvent,
 }
 
 impl Log {
-    pub fn new(component_name: &str, event: event::LogEvent) -> Self {
+    pub fn new(component_name: &str, side: TapSide, event: event::LogEvent) -> Self {
         Self {
             component_name: component_name.to_string(),
+            side,
             event,
         }
     }
@@ -35,6 +40,11 @@ impl Log {
         &self.component_name
     }
 
+    /// Whether the log event was tapped from the input or the output of the component
+    async fn side(&self) -> TapSide {
+        self.side
+    }
+
     /// Log message
     async fn message(&self) -> Option<String> {
         self.get_message()
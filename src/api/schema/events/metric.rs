@@ -0,0 +1,61 @@
+use super::EventEncodingType;
+use crate::{api::tap::TapSide, event};
+
+use async_graphql::Object;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug)]
+pub struct Metric {
+    component_name: String,
+    side: TapSide,
+    event: event::Metric,
+}
+
+impl Metric {
+    pub fn new(component_name: &str, side: TapSide, event: event::Metric) -> Self {
+        Self {
+            component_name: component_name.to_string(),
+            side,
+            event,
+        }
+    }
+}
+
+#[Object]
+/// Metric event with fields for querying metric data
+impl Metric {
+    /// Name of the component associated with the metric event
+    async fn component_name(&self) -> &str {
+        &self.component_name
+    }
+
+    /// Whether the metric event was tapped from the input or the output of the component
+    async fn side(&self) -> TapSide {
+        self.side
+    }
+
+    /// Metric name
+    async fn name(&self) -> &str {
+        self.event.name()
+    }
+
+    /// Metric namespace
+    async fn namespace(&self) -> Option<&str> {
+        self.event.namespace()
+    }
+
+    /// Metric timestamp
+    async fn timestamp(&self) -> Option<&DateTime<Utc>> {
+        self.event.data.timestamp.as_ref()
+    }
+
+    /// Metric event as an encoded string format
+    async fn string(&self, encoding: EventEncodingType) -> String {
+        match encoding {
+            EventEncodingType::Json => serde_json::to_string(&self.event)
+                .expect("JSON serialization of metric event failed. Please report."),
+            EventEncodingType::Yaml => serde_yaml::to_string(&self.event)
+                .expect("YAML serialization of metric event failed. Please report."),
+        }
+    }
+}
@@ -1,12 +1,16 @@
 mod encoding;
 mod log;
+mod metric;
 mod notification;
 mod output;
 
 use encoding::EventEncodingType;
 use output::OutputEventsPayload;
 
-use crate::{api::tap::TapController, topology::WatchRx};
+use crate::{
+    api::tap::{TapCondition, TapController, TapFilter, TapPatterns},
+    topology::WatchRx,
+};
 
 use async_graphql::{validators::IntRange, Context, Subscription};
 use futures::Stream;
@@ -20,27 +24,49 @@ pub struct EventsSubscription;
 
 #[Subscription]
 impl EventsSubscription {
-    /// A stream of events emitted from matched component(s)
+    /// A stream of events emitted from matched component(s), or received by them. Events can
+    /// be selected with a VRL `filter` condition, and capped to `rateLimit` events per second from
+    /// each component.
+    #[allow(clippy::too_many_arguments)]
     pub async fn output_events<'a>(
         &'a self,
         ctx: &'a Context<'a>,
         component_names: Vec<String>,
+        #[graphql(default)] input_names: Vec<String>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(IntRange(min = "1", max = "10_000")))] limit: u32,
-    ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
+        filter: Option<String>,
+        #[graphql(validator(IntRange(min = "1", max = "1_000_000")))] rate_limit: Option<u32>,
+    ) -> async_graphql::Result<impl Stream<Item = Vec<OutputEventsPayload>> + 'a> {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
 
+        let condition = filter
+            .map(|source| TapCondition::new(&source))
+            .transpose()
+            .map_err(|error| format!("Invalid filter: {}", error))?;
+        let patterns = TapPatterns {
+            outputs: component_names.into_iter().collect(),
+            inputs: input_names.into_iter().collect(),
+        };
+
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, component_names, interval as u64, limit as usize)
+        Ok(create_events_stream(
+            watch_rx,
+            patterns,
+            TapFilter::new(condition, rate_limit),
+            interval as u64,
+            limit as usize,
+        ))
     }
 }
 
-/// Creates an events stream based on component names, and a provided interval. Will emit
-/// control messages that bubble up the application if the sink goes away. The stream contains
-/// all matching events; filtering should be done at the caller level.
+/// Creates an events stream based on component name patterns, and a provided interval. Will
+/// emit control messages that bubble up the application if the sink goes away. The stream
+/// contains the matching events that pass `filter`.
 fn create_events_stream(
     watch_rx: WatchRx,
-    component_names: Vec<String>,
+    patterns: TapPatterns,
+    filter: TapFilter,
     interval: u64,
     limit: usize,
 ) -> impl Stream<Item = Vec<OutputEventsPayload>> {
@@ -56,7 +82,7 @@ fn create_events_stream(
     tokio::spawn(async move {
         // Create a tap controller. When this drops out of scope, clean up will be performed on the
         // event handlers and topology observation that the tap controller provides.
-        let _tap_controller = TapController::new(watch_rx, tap_tx, &component_names);
+        let _tap_controller = TapController::new(watch_rx, tap_tx, patterns, filter);
 
         // A tick interval to represent when to 'cut' the results back to the client.
         let mut interval = time::interval(time::Duration::from_millis(interval));
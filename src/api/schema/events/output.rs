@@ -1,5 +1,6 @@
 use super::{
     log::Log,
+    metric::Metric,
     notification::{EventNotification, EventNotificationType},
 };
 use crate::api::tap::{TapNotification, TapPayload};
@@ -12,6 +13,9 @@ pub enum OutputEventsPayload {
     /// Log event
     Log(Log),
 
+    /// Metric event
+    Metric(Metric),
+
     // Notification
     Notification(EventNotification),
 }
@@ -20,7 +24,8 @@ pub enum OutputEventsPayload {
 impl From<TapPayload> for OutputEventsPayload {
     fn from(t: TapPayload) -> Self {
         match t {
-            TapPayload::Log(name, ev) => Self::Log(Log::new(&name, ev)),
+            TapPayload::Log(name, side, ev) => Self::Log(Log::new(&name, side, ev)),
+            TapPayload::Metric(name, side, ev) => Self::Metric(Metric::new(&name, side, ev)),
             TapPayload::Notification(name, n) => match n {
                 TapNotification::Matched => Self::Notification(EventNotification::new(
                     &name,
@@ -31,7 +36,6 @@ impl From<TapPayload> for OutputEventsPayload {
                     EventNotificationType::NotMatched,
                 )),
             },
-            _ => unreachable!("TODO: implement metrics"),
         }
     }
 }
@@ -0,0 +1,442 @@
+//! A bounded history of the throughput and errors of every component, sampled
+//! in the background so clients can draw recent trends without an external
+//! metrics store.
+
+use crate::{
+    event::{Event, Metric, MetricValue},
+    metrics::{capture_metrics, get_controller},
+};
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use std::{
+    collections::{btree_map::Entry, BTreeMap, VecDeque},
+    sync::RwLock,
+};
+use tokio::time::Duration;
+
+/// Number of samples kept for each component, that is five minutes.
+pub const HISTORY_CAPACITY: usize = 300;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+lazy_static! {
+    static ref HISTORY: RwLock<History> = RwLock::new(History::new(HISTORY_CAPACITY));
+}
+
+/// Errors of a component, keyed by metric name and `error_type` tag.
+type Errors = BTreeMap<(String, Option<String>), f64>;
+
+/// Sum and count of the observations of a histogram.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Observations {
+    sum: f64,
+    count: u32,
+}
+
+impl Observations {
+    /// Mean of the observations made since `last`.
+    fn mean_since(self, last: Option<Self>) -> Option<f64> {
+        let last = last.unwrap_or_default();
+        match self.count.checked_sub(last.count) {
+            Some(count) if count > 0 => Some((self.sum - last.sum) / count as f64),
+            // The histogram restarted along with its component.
+            None if self.count > 0 => Some(self.sum / self.count as f64),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Counters {
+    events_in: f64,
+    events_out: f64,
+    processed_bytes: f64,
+    errors: Errors,
+    buffer_events: Option<f64>,
+    buffer_max_events: Option<f64>,
+    in_flight: Option<Observations>,
+    concurrency_limit: Option<Observations>,
+}
+
+#[derive(Debug, Clone, PartialEq, SimpleObject)]
+pub struct ErrorTypeCount {
+    /// Name of the metric counting the errors, such as `processing_errors_total`
+    pub metric: String,
+
+    /// Error type, as tagged by the component
+    pub error_type: Option<String>,
+
+    /// Number of errors
+    pub count: f64,
+}
+
+fn error_counts<'a>(
+    errors: impl IntoIterator<Item = (&'a (String, Option<String>), f64)>,
+) -> Vec<ErrorTypeCount> {
+    errors
+        .into_iter()
+        .map(|((metric, error_type), count)| ErrorTypeCount {
+            metric: metric.clone(),
+            error_type: error_type.clone(),
+            count,
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, SimpleObject)]
+pub struct MetricSample {
+    /// End of the sampled second
+    pub timestamp: DateTime<Utc>,
+
+    /// Incoming events during the sampled second
+    pub events_in_throughput: i64,
+
+    /// Outgoing events during the sampled second
+    pub events_out_throughput: i64,
+
+    /// Processed bytes during the sampled second
+    pub processed_bytes_throughput: i64,
+
+    /// Errors during the sampled second
+    pub errors: i64,
+
+    /// Errors during the sampled second, by type
+    pub errors_by_type: Vec<ErrorTypeCount>,
+
+    /// Events waiting in the buffer of a sink
+    pub buffer_events: Option<i64>,
+
+    /// Capacity of the buffer of a sink, in events
+    pub buffer_max_events: Option<i64>,
+
+    /// Mean number of requests in flight during the sampled second, for sinks with
+    /// adaptive concurrency
+    pub in_flight_requests: Option<f64>,
+
+    /// Mean adaptive concurrency limit during the sampled second
+    pub concurrency_limit: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, SimpleObject)]
+pub struct ComponentMetricHistory {
+    /// Component name
+    pub name: String,
+
+    /// Recent samples, oldest first
+    pub samples: Vec<MetricSample>,
+
+    /// Errors since the component started, by type
+    pub errors_by_type: Vec<ErrorTypeCount>,
+}
+
+#[derive(Debug)]
+struct ComponentHistory {
+    last: Counters,
+    samples: VecDeque<MetricSample>,
+}
+
+#[derive(Debug)]
+struct History {
+    capacity: usize,
+    components: BTreeMap<String, ComponentHistory>,
+}
+
+/// Increase of a counter, which restarts from zero when its component is rebuilt.
+fn delta(current: f64, last: f64) -> f64 {
+    if current >= last {
+        current - last
+    } else {
+        current
+    }
+}
+
+impl History {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            components: BTreeMap::new(),
+        }
+    }
+
+    /// Records a sample of every component from the current value of their counters.
+    fn record(&mut self, timestamp: DateTime<Utc>, metrics: impl IntoIterator<Item = Metric>) {
+        let mut current = BTreeMap::<String, Counters>::new();
+        for metric in metrics {
+            let name = match metric.tag_value("component_name") {
+                Some(name) => name,
+                None => continue,
+            };
+            let counters = current.entry(name).or_default();
+
+            let value = match metric.data.value {
+                MetricValue::Counter { value } => value,
+                MetricValue::Gauge { value } => {
+                    match metric.name() {
+                        "buffer_events" => counters.buffer_events = Some(value),
+                        "buffer_max_events" => counters.buffer_max_events = Some(value),
+                        _ => (),
+                    }
+                    continue;
+                }
+                MetricValue::AggregatedHistogram { count, sum, .. } => {
+                    let observations = Some(Observations { sum, count });
+                    match metric.name() {
+                        "adaptive_concurrency_in_flight" => counters.in_flight = observations,
+                        "adaptive_concurrency_limit" => counters.concurrency_limit = observations,
+                        _ => (),
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+
+            match metric.name() {
+                "events_in_total" => counters.events_in += value,
+                "events_out_total" => counters.events_out += value,
+                "processed_bytes_total" => counters.processed_bytes += value,
+                name if name.ends_with("_errors_total") => {
+                    *counters
+                        .errors
+                        .entry((name.to_owned(), metric.tag_value("error_type")))
+                        .or_default() += value
+                }
+                _ => (),
+            }
+        }
+
+        // Components that no longer report metrics are gone.
+        self.components.retain(|name, _| current.contains_key(name));
+
+        for (name, counters) in current {
+            let history = match self.components.entry(name) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    // The first values are only the baseline of the first sample.
+                    entry.insert(ComponentHistory {
+                        last: counters,
+                        samples: VecDeque::with_capacity(self.capacity),
+                    });
+                    continue;
+                }
+            };
+
+            let errors = counters
+                .errors
+                .iter()
+                .map(|(key, &count)| {
+                    let last = history.last.errors.get(key).copied().unwrap_or(0.0);
+                    (key, delta(count, last))
+                })
+                .filter(|(_, count)| *count > 0.0)
+                .collect::<Vec<_>>();
+            let sample = MetricSample {
+                timestamp,
+                events_in_throughput: delta(counters.events_in, history.last.events_in) as i64,
+                events_out_throughput: delta(counters.events_out, history.last.events_out) as i64,
+                processed_bytes_throughput: delta(
+                    counters.processed_bytes,
+                    history.last.processed_bytes,
+                ) as i64,
+                errors: errors.iter().map(|(_, count)| count).sum::<f64>() as i64,
+                errors_by_type: error_counts(errors.iter().map(|(key, count)| (*key, *count))),
+                buffer_events: counters.buffer_events.map(|events| events as i64),
+                buffer_max_events: counters.buffer_max_events.map(|events| events as i64),
+                in_flight_requests: counters
+                    .in_flight
+                    .and_then(|in_flight| in_flight.mean_since(history.last.in_flight)),
+                concurrency_limit: counters
+                    .concurrency_limit
+                    .and_then(|limit| limit.mean_since(history.last.concurrency_limit)),
+            };
+
+            if history.samples.len() == self.capacity {
+                history.samples.pop_front();
+            }
+            history.samples.push_back(sample);
+            history.last = counters;
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<ComponentMetricHistory> {
+        self.components
+            .get(name)
+            .map(|history| ComponentMetricHistory {
+                name: name.to_owned(),
+                samples: history.samples.iter().cloned().collect(),
+                errors_by_type: error_counts(
+                    history.last.errors.iter().map(|(key, count)| (key, *count)),
+                ),
+            })
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.components.keys().cloned().collect()
+    }
+}
+
+/// The history of the `component_names`, or of every component.
+pub fn get(component_names: Option<Vec<String>>) -> Vec<ComponentMetricHistory> {
+    let history = HISTORY.read().expect("Metric history lock poisoned.");
+    component_names
+        .unwrap_or_else(|| history.names())
+        .iter()
+        .filter_map(|name| history.get(name))
+        .collect()
+}
+
+/// Samples the metrics of every component each second, forever.
+pub async fn sample() {
+    let controller = match get_controller() {
+        Ok(controller) => controller,
+        Err(error) => {
+            error!(message = "Metric history is unavailable.", %error);
+            return;
+        }
+    };
+
+    let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let metrics = capture_metrics(controller).filter_map(|event| match event {
+            Event::Metric(metric) => Some(metric),
+            _ => None,
+        });
+        HISTORY
+            .write()
+            .expect("Metric history lock poisoned.")
+            .record(Utc::now(), metrics);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::MetricKind;
+
+    fn tags(component: &str) -> BTreeMap<String, String> {
+        let mut tags = BTreeMap::new();
+        tags.insert("component_name".to_owned(), component.to_owned());
+        tags
+    }
+
+    fn counter(name: &str, component: &str, error_type: Option<&str>, value: f64) -> Metric {
+        let mut tags = tags(component);
+        if let Some(error_type) = error_type {
+            tags.insert("error_type".to_owned(), error_type.to_owned());
+        }
+        Metric::new(name, MetricKind::Absolute, MetricValue::Counter { value })
+            .with_tags(Some(tags))
+    }
+
+    #[test]
+    fn samples_throughput_and_errors_by_type() {
+        let mut history = History::new(2);
+        let now = Utc::now();
+
+        for (events_in, parse_errors, timeouts) in
+            vec![(10.0, 0.0, 1.0), (15.0, 2.0, 1.0), (25.0, 3.0, 4.0)]
+        {
+            history.record(
+                now,
+                vec![
+                    counter("events_in_total", "in", None, events_in),
+                    counter(
+                        "processing_errors_total",
+                        "in",
+                        Some("parse_failed"),
+                        parse_errors,
+                    ),
+                    counter("processing_errors_total", "in", Some("timed_out"), timeouts),
+                ],
+            );
+        }
+
+        let history = history.get("in").unwrap();
+        assert_eq!(
+            history
+                .samples
+                .iter()
+                .map(|sample| (sample.events_in_throughput, sample.errors))
+                .collect::<Vec<_>>(),
+            vec![(5, 2), (10, 4)]
+        );
+        assert_eq!(
+            history.samples[1].errors_by_type,
+            vec![
+                ErrorTypeCount {
+                    metric: "processing_errors_total".to_owned(),
+                    error_type: Some("parse_failed".to_owned()),
+                    count: 1.0,
+                },
+                ErrorTypeCount {
+                    metric: "processing_errors_total".to_owned(),
+                    error_type: Some("timed_out".to_owned()),
+                    count: 3.0,
+                },
+            ]
+        );
+        assert_eq!(
+            history
+                .errors_by_type
+                .iter()
+                .map(|errors| errors.count)
+                .collect::<Vec<_>>(),
+            vec![3.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn samples_buffer_and_concurrency() {
+        let mut history = History::new(2);
+        for (buffered, count, sum) in vec![(0.0, 2, 4.0), (40.0, 6, 16.0)] {
+            history.record(
+                Utc::now(),
+                vec![
+                    Metric::new(
+                        "buffer_events",
+                        MetricKind::Absolute,
+                        MetricValue::Gauge { value: buffered },
+                    ),
+                    Metric::new(
+                        "buffer_max_events",
+                        MetricKind::Absolute,
+                        MetricValue::Gauge { value: 500.0 },
+                    ),
+                    Metric::new(
+                        "adaptive_concurrency_in_flight",
+                        MetricKind::Absolute,
+                        MetricValue::AggregatedHistogram {
+                            buckets: Vec::new(),
+                            count,
+                            sum,
+                        },
+                    ),
+                ]
+                .into_iter()
+                .map(|metric| metric.with_tags(Some(tags("out")))),
+            );
+        }
+
+        let sample = &history.get("out").unwrap().samples[0];
+        assert_eq!(sample.buffer_events, Some(40));
+        assert_eq!(sample.buffer_max_events, Some(500));
+        assert_eq!(sample.in_flight_requests, Some(3.0));
+        assert_eq!(sample.concurrency_limit, None);
+    }
+
+    #[test]
+    fn forgets_removed_components() {
+        let mut history = History::new(2);
+        history.record(
+            Utc::now(),
+            vec![counter("events_in_total", "in", None, 1.0)],
+        );
+        history.record(
+            Utc::now(),
+            vec![counter("events_in_total", "other", None, 1.0)],
+        );
+
+        assert_eq!(history.names(), vec!["other"]);
+    }
+}
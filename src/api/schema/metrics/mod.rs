@@ -2,6 +2,7 @@ mod errors;
 mod events_in;
 mod events_out;
 pub mod filter;
+pub mod history;
 mod processed_bytes;
 mod processed_events;
 mod sink;
@@ -20,6 +21,7 @@ pub use errors::{ComponentErrorsTotal, ErrorsTotal};
 pub use events_in::{ComponentEventsInThroughput, ComponentEventsInTotal, EventsInTotal};
 pub use events_out::{ComponentEventsOutThroughput, ComponentEventsOutTotal, EventsOutTotal};
 pub use filter::*;
+pub use history::{ComponentMetricHistory, ErrorTypeCount, MetricSample};
 pub use processed_bytes::{
     ComponentProcessedBytesThroughput, ComponentProcessedBytesTotal, ProcessedBytesTotal,
 };
@@ -44,6 +46,15 @@ pub struct MetricsQuery;
 
 #[Object]
 impl MetricsQuery {
+    /// Per-second samples of the throughput and errors of components, over the last five
+    /// minutes at most. Every component is included unless `componentNames` is given.
+    async fn component_metric_history(
+        &self,
+        component_names: Option<Vec<String>>,
+    ) -> Vec<ComponentMetricHistory> {
+        history::get(component_names)
+    }
+
     #[cfg(feature = "sources-host_metrics")]
     /// Vector host metrics
     async fn host_metrics(&self) -> host::HostMetrics {
@@ -1,13 +1,14 @@
 pub mod components;
+mod config;
 mod events;
 pub mod filter;
 mod health;
 mod meta;
-mod metrics;
+pub(super) mod metrics;
 mod relay;
 pub mod sort;
 
-use async_graphql::{EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use async_graphql::{MergedObject, MergedSubscription, Schema, SchemaBuilder};
 pub use vector_core::api::schema::scalar;
 
 #[derive(MergedObject, Default)]
@@ -18,6 +19,9 @@ pub struct Query(
     meta::MetaQuery,
 );
 
+#[derive(MergedObject, Default)]
+pub struct Mutation(config::ConfigMutation);
+
 #[derive(MergedSubscription, Default)]
 pub struct Subscription(
     health::HealthSubscription,
@@ -27,6 +31,10 @@ pub struct Subscription(
 );
 
 /// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types
-pub fn build_schema() -> SchemaBuilder<Query, EmptyMutation, Subscription> {
-    Schema::build(Query::default(), EmptyMutation, Subscription::default())
+pub fn build_schema() -> SchemaBuilder<Query, Mutation, Subscription> {
+    Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    )
 }
@@ -1,24 +1,35 @@
 use super::{handler, schema, ShutdownTx};
-use crate::{config, topology};
+use crate::{config, signal::SignalTx, topology};
 use async_graphql::{
     http::{playground_source, GraphQLPlaygroundConfig},
     Data, Request, Schema,
 };
 use async_graphql_warp::{graphql_subscription_with_data, Response as GQLResponse};
 use std::{convert::Infallible, net::SocketAddr};
-use tokio::sync::oneshot;
+use tokio::{sync::oneshot, task::JoinHandle};
 use warp::{filters::BoxedFilter, http::Response, Filter, Reply};
 
 pub struct Server {
     _shutdown: ShutdownTx,
     addr: SocketAddr,
+    history: JoinHandle<()>,
 }
 
 impl Server {
     /// Start the API server. This creates the routes and spawns a Warp server. The server is
     /// gracefully shut down when Self falls out of scope by way of the oneshot sender closing.
-    pub fn start(config: &config::Config, watch_rx: topology::WatchRx) -> Self {
-        let routes = make_routes(config.api.playground, watch_rx);
+    pub fn start(
+        config: &config::Config,
+        watch_rx: topology::WatchRx,
+        signal_tx: SignalTx,
+    ) -> Self {
+        // Config reloads are only exposed when explicitly enabled.
+        let signal_tx = if config.api.reload {
+            Some(signal_tx)
+        } else {
+            None
+        };
+        let routes = make_routes(config.api.playground, watch_rx, signal_tx);
 
         let (_shutdown, rx) = oneshot::channel();
         let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(
@@ -34,7 +45,14 @@ impl Server {
         // Spawn the server in the background.
         tokio::spawn(server);
 
-        Self { _shutdown, addr }
+        // Keep a history of component metrics for as long as the server runs.
+        let history = tokio::spawn(schema::metrics::history::sample());
+
+        Self {
+            _shutdown,
+            addr,
+            history,
+        }
     }
 
     /// Returns a copy of the SocketAddr that the server was started on.
@@ -50,9 +68,23 @@ impl Server {
     }
 }
 
-fn make_routes(playground: bool, watch_tx: topology::WatchRx) -> BoxedFilter<(impl Reply,)> {
-    // Build the GraphQL schema.
-    let schema = schema::build_schema().finish();
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.history.abort();
+    }
+}
+
+fn make_routes(
+    playground: bool,
+    watch_tx: topology::WatchRx,
+    signal_tx: Option<SignalTx>,
+) -> BoxedFilter<(impl Reply,)> {
+    // Build the GraphQL schema. Mutations reload the config through the signal handler.
+    let mut schema = schema::build_schema();
+    if let Some(signal_tx) = signal_tx {
+        schema = schema.data(signal_tx);
+    }
+    let schema = schema.finish();
 
     // Routes...
 
@@ -1,23 +1,28 @@
 use super::{ShutdownRx, ShutdownTx};
 use crate::topology::fanout::ControlChannel;
 use crate::{
-    event::{Event, LogEvent},
+    event::{Event, LogEvent, Metric, VrlTarget},
     topology::{fanout, WatchRx},
 };
 use futures::{future::try_join_all, FutureExt, Sink, SinkExt};
 use itertools::Itertools;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
-    iter::FromIterator,
     pin::Pin,
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc as tokio_mpsc, mpsc::error::SendError, oneshot};
 use uuid::Uuid;
+use vrl::{diagnostic::Formatter, Program, Runtime, Value};
 
 /// A tap sender is the control channel used to surface tap payloads to a client.
 type TapSender = tokio_mpsc::Sender<TapPayload>;
 
+/// Capacity of the channel between the tap sinks and the task filtering their events. Events
+/// tapped while it's full are left out, so a slow filter never holds back the pipeline.
+const UNFILTERED_CAPACITY: usize = 1000;
+
 /// Clients can supply glob patterns to find matched topology components.
 trait GlobMatcher<T> {
     fn matches_glob(&self, rhs: T) -> bool;
@@ -39,12 +44,21 @@ pub enum TapNotification {
     NotMatched,
 }
 
+/// Where the events of a component are tapped.
+#[derive(async_graphql::Enum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TapSide {
+    /// Events received by the component, from all of its inputs
+    Input,
+    /// Events emitted by the component
+    Output,
+}
+
 /// A tap payload can either contain a log/metric event or a notification that's intended
 /// to be communicated back to the client to alert them about the status of the tap request.
 #[derive(Debug)]
 pub enum TapPayload {
-    Log(String, LogEvent),
-    Metric(String, LogEvent),
+    Log(String, TapSide, LogEvent),
+    Metric(String, TapSide, Metric),
     Notification(String, TapNotification),
 }
 
@@ -58,19 +72,145 @@ impl TapPayload {
     }
 }
 
+/// Glob patterns of the components to tap.
+#[derive(Debug, Clone, Default)]
+pub struct TapPatterns {
+    /// Components whose output is tapped
+    pub outputs: HashSet<String>,
+    /// Components whose input is tapped, that is the outputs of the components they consume
+    pub inputs: HashSet<String>,
+}
+
+impl TapPatterns {
+    fn for_side(&self, side: TapSide) -> &HashSet<String> {
+        match side {
+            TapSide::Input => &self.inputs,
+            TapSide::Output => &self.outputs,
+        }
+    }
+}
+
+/// A VRL condition selecting the events a tap relays. Unlike the `remap` condition, events it
+/// fails on are left out without being counted as errors of the tapped component.
+#[derive(Clone)]
+pub struct TapCondition {
+    program: Program,
+}
+
+impl TapCondition {
+    pub fn new(source: &str) -> Result<Self, String> {
+        vrl::compile(source, &vrl_stdlib::all())
+            .map(|program| Self { program })
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).to_string())
+    }
+
+    fn check(&self, event: Event) -> bool {
+        // The program runs on a copy, so it can't alter the relayed event.
+        let mut target = VrlTarget::new(event);
+        matches!(
+            Runtime::default().resolve(&mut target, &self.program),
+            Ok(Value::Boolean(true))
+        )
+    }
+}
+
+/// Caps the events a tap relays each second from one of the components it observes.
+#[derive(Debug)]
+struct RateLimit {
+    max_events_per_sec: u32,
+    window_start: Instant,
+    count: u32,
+}
+
+impl RateLimit {
+    fn new(max_events_per_sec: u32) -> Self {
+        Self {
+            max_events_per_sec,
+            window_start: Instant::now(),
+            count: 0,
+        }
+    }
+
+    fn allow(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.window_start) >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.count = 0;
+        }
+
+        if self.count < self.max_events_per_sec {
+            self.count += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Selects the events a tap relays to its client. The filter runs on the tap's own task, after
+/// the tap sinks queued the events, so it doesn't slow down the tapped components.
+#[derive(Default)]
+pub struct TapFilter {
+    condition: Option<TapCondition>,
+    max_events_per_sec: Option<u32>,
+    /// Rate limits of the components events were relayed from.
+    rate_limits: HashMap<String, RateLimit>,
+}
+
+impl TapFilter {
+    pub fn new(condition: Option<TapCondition>, max_events_per_sec: Option<u32>) -> Self {
+        Self {
+            condition,
+            max_events_per_sec,
+            rate_limits: HashMap::new(),
+        }
+    }
+
+    /// Whether `event` of `component_name` is relayed. The rate limit only counts the
+    /// events matching the condition.
+    fn allows(&mut self, component_name: &str, event: Event) -> bool {
+        if !self
+            .condition
+            .as_ref()
+            .map_or(true, |condition| condition.check(event))
+        {
+            return false;
+        }
+
+        match self.max_events_per_sec {
+            Some(max) => self
+                .rate_limits
+                .entry(component_name.to_owned())
+                .or_insert_with(|| RateLimit::new(max))
+                .allow(Instant::now()),
+            None => true,
+        }
+    }
+
+    /// Whether `payload` is relayed. Notifications always are.
+    fn allows_payload(&mut self, payload: &TapPayload) -> bool {
+        match payload {
+            TapPayload::Log(name, _, log) => self.allows(name, Event::from(log.clone())),
+            TapPayload::Metric(name, _, metric) => self.allows(name, Event::from(metric.clone())),
+            TapPayload::Notification(..) => true,
+        }
+    }
+}
+
 /// A `TapSink` is used as an output channel for a topology component, and receives
-/// `Event`s. If these are of type `Event::LogEvent`, they are relayed to the tap client.
+/// `Event`s. They are queued for the tap's filter, which relays them to the tap client.
 pub struct TapSink {
     tap_tx: TapSender,
     component_name: String,
-    buffer: VecDeque<LogEvent>,
+    side: TapSide,
+    buffer: VecDeque<TapPayload>,
 }
 
 impl TapSink {
-    pub fn new(tap_tx: TapSender, component_name: String) -> Self {
+    pub fn new(tap_tx: TapSender, component_name: String, side: TapSide) -> Self {
         Self {
             tap_tx,
             component_name,
+            side,
             // Pre-allocate space of 100 events, which matches the default `limit` typically
             // provided to a tap subscription. If there's a higher log volume, this will block
             // until the upstream event handler has processed the event. Generally, there should
@@ -88,15 +228,18 @@ impl Sink<Event> for TapSink {
         Poll::Ready(Ok(()))
     }
 
-    /// If the sink is ready, and the event is of type `LogEvent`, add to the buffer.
+    /// If there's space for the event in the buffer, queue it.
     fn start_send(mut self: Pin<&mut Self>, item: Event) -> Result<(), Self::Error> {
-        // If we have a `LogEvent`, and space for it in the buffer, queue it.
-        if let Event::Log(ev) = item {
-            if self.buffer.len() < self.buffer.capacity() {
-                self.buffer.push_back(ev);
-            }
+        if self.buffer.len() == self.buffer.capacity() {
+            return Ok(());
         }
 
+        let payload = match item {
+            Event::Log(ev) => TapPayload::Log(self.component_name.clone(), self.side, ev),
+            Event::Metric(ev) => TapPayload::Metric(self.component_name.clone(), self.side, ev),
+        };
+        self.buffer.push_back(payload);
+
         Ok(())
     }
 
@@ -107,16 +250,13 @@ impl Sink<Event> for TapSink {
     ) -> Poll<Result<(), Self::Error>> {
         // Loop over the buffer events, pulling from the front. This will terminate when
         // the buffer is empty.
-        while let Some(ev) = self.buffer.pop_front() {
+        while let Some(payload) = self.buffer.pop_front() {
             // Attempt to send upstream. If the channel is closed, log and break. If it's
             // full, return pending to reattempt later.
-            match self
-                .tap_tx
-                .try_send(TapPayload::Log(self.component_name.clone(), ev))
-            {
+            match self.tap_tx.try_send(payload) {
                 Err(tokio_mpsc::error::TrySendError::Closed(payload)) => {
                     debug!(
-                        message = "Couldn't send log event.",
+                        message = "Couldn't send event.",
                         payload = ?payload,
                         component_name = ?self.component_name);
 
@@ -144,13 +284,21 @@ pub struct TapController {
 
 impl TapController {
     /// Creates a new tap sink, and spawns a handler for watching for topology changes
-    /// and a separate inner handler for events. Uses a oneshot channel to trigger shutdown
-    /// of handlers when the `TapSink` drops out of scope.
-    pub fn new(watch_rx: WatchRx, tap_tx: TapSender, patterns: &[String]) -> Self {
+    /// and a separate handler filtering the tapped events. Uses a oneshot channel to trigger
+    /// shutdown of handlers when the `TapSink` drops out of scope.
+    pub fn new(
+        watch_rx: WatchRx,
+        tap_tx: TapSender,
+        patterns: TapPatterns,
+        filter: TapFilter,
+    ) -> Self {
         let (_shutdown, shutdown_rx) = oneshot::channel();
+        let (unfiltered_tx, unfiltered_rx) = tokio_mpsc::channel(UNFILTERED_CAPACITY);
 
+        tokio::spawn(filter_handler(filter, unfiltered_rx, tap_tx.clone()));
         tokio::spawn(tap_handler(
-            patterns.iter().cloned().collect(),
+            patterns,
+            unfiltered_tx,
             tap_tx,
             watch_rx,
             shutdown_rx,
@@ -160,6 +308,21 @@ impl TapController {
     }
 }
 
+/// Relays the tapped events that pass `filter` to the client. This ends once all the tap
+/// sinks are gone, or the client is.
+async fn filter_handler(
+    mut filter: TapFilter,
+    mut unfiltered_rx: tokio_mpsc::Receiver<TapPayload>,
+    tx: TapSender,
+) {
+    while let Some(payload) = unfiltered_rx.recv().await {
+        if filter.allows_payload(&payload) && tx.send(payload).await.is_err() {
+            debug!("Couldn't send event; tap gone away.");
+            break;
+        }
+    }
+}
+
 /// Provides a `ShutdownTx` that disconnects a component sink when it drops out of scope.
 fn shutdown_trigger(mut control_tx: ControlChannel, sink_id: String) -> ShutdownTx {
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
@@ -193,104 +356,114 @@ async fn send_not_matched(tx: TapSender, pattern: &str) -> Result<(), SendError<
 }
 
 /// Returns a tap handler that listens for topology changes, and connects sinks to observe
-/// `LogEvent`s` when a component matches one or more of the provided patterns.
+/// events when a component matches one or more of the provided patterns. The output of a
+/// component is tapped at the component itself, its input at each of the components it
+/// consumes. The sinks queue their events to `unfiltered_tx`, notifications go to `tx`.
 async fn tap_handler(
-    patterns: HashSet<String>,
+    patterns: TapPatterns,
+    unfiltered_tx: TapSender,
     tx: TapSender,
     mut watch_rx: WatchRx,
     mut shutdown_rx: ShutdownRx,
 ) {
     debug!(message = "Started tap.", patterns = ?patterns);
 
-    // Sinks register for the current tap. Contains the name of the matched component, and
-    // a shutdown trigger for sending a remove control message when matching sinks change.
-    let mut sinks = HashMap::new();
+    // Sinks registered for the current tap, as shutdown triggers that send a remove control
+    // message when the sinks are replaced.
+    let mut sinks = Vec::new();
+
+    // Patterns that matched on the last iteration, to compare with the latest round of
+    // matches when sending notifications.
+    let mut last_matches = HashSet::new();
 
     loop {
         tokio::select! {
             _ = &mut shutdown_rx => break,
             Ok(_) = watch_rx.changed() => {
-                // Get the patterns that matched on the last iteration, to compare with the latest
-                // round of matches when sending notifications.
-                let last_matches = patterns
-                    .iter()
-                    .filter(|pattern| sinks.keys().any(|name: &String| pattern.matches_glob(name)))
-                    .collect::<HashSet<_>>();
-
                 // Cache of matched patterns. A `HashSet` is used here to ignore repetition.
                 let mut matched = HashSet::new();
 
-                // Borrow and clone the latest outputs to register sinks. Since this blocks the
+                // Borrow and clone the latest resources to register sinks. Since this blocks the
                 // watch channel and the returned ref isn't `Send`, this requires a clone.
-                let outputs = watch_rx.borrow().clone();
-
-                // Loop over all outputs, and connect sinks for the components that match one
-                // or more patterns.
-                for (name, mut control_tx) in outputs.iter() {
-                    match patterns
+                let resource = watch_rx.borrow().clone();
+
+                // Each component that can be tapped, and the outputs its events are tapped at.
+                let targets = resource
+                    .outputs
+                    .keys()
+                    .map(|name| (name, TapSide::Output, vec![name.clone()]))
+                    .chain(
+                        resource
+                            .inputs
+                            .iter()
+                            .map(|(name, inputs)| (name, TapSide::Input, inputs.clone())),
+                    );
+
+                // (Re)connect sinks for the components that match one or more patterns. This is
+                // necessary because a component may be reconfigured with the same name as a
+                // previous, and we are not getting involved in config diffing at this point.
+                let mut new_sinks = Vec::new();
+                for (name, side, tapped_outputs) in targets {
+                    let found = patterns
+                        .for_side(side)
                         .iter()
                         .filter(|pattern| pattern.matches_glob(name))
-                        .collect_vec()
-                    {
-                        found if !found.is_empty() => {
-                            debug!(
-                                message="Component matched.",
-                                component_name = ?name, patterns = ?patterns, matched = ?found
-                            );
-
-                            // (Re)connect the sink. This is necessary because a sink may be
-                            // reconfigured with the same name as a previous, and we are not
-                            // getting involved in config diffing at this point.
-                            let id = Uuid::new_v4().to_string();
-                            let sink = TapSink::new(tx.clone(), name.to_string());
-
-                            // Attempt to connect the sink.
-                            match control_tx
-                                .send(fanout::ControlMessage::Add(id.clone(), Box::new(sink)))
-                                .await
-                            {
-                                Ok(_) => {
-                                    debug!(
-                                        message = "Sink connected.",
-                                        sink_id = ?id, component_name = ?name,
-                                    );
-
-                                    // Create a sink shutdown trigger to remove the sink
-                                    // when matched components change.
-                                    sinks
-                                        .insert(name.to_string(), shutdown_trigger(control_tx.clone(), id));
-                                }
-                                Err(err) => {
-                                    error!(
-                                        message = "Couldn't connect sink.",
-                                        error = ?err,
-                                        component_name = ?name, id = ?id
-                                    );
-                                }
-                            }
+                        .collect_vec();
+                    if found.is_empty() {
+                        debug!(
+                            message = "Component not matched.",
+                            component_name = ?name, side = ?side, patterns = ?patterns
+                        );
+                        continue;
+                    }
 
-                            matched.extend(found);
-                        }
-                        _ => {
-                            debug!(
-                                message="Component not matched.",
-                                component_name = ?name, patterns = ?patterns
-                            );
+                    debug!(
+                        message = "Component matched.",
+                        component_name = ?name, side = ?side, matched = ?found
+                    );
+
+                    for output in tapped_outputs {
+                        let mut control_tx = match resource.outputs.get(&output) {
+                            Some(control_tx) => control_tx.clone(),
+                            None => continue,
+                        };
+
+                        // Attempt to connect the sink.
+                        let id = Uuid::new_v4().to_string();
+                        let sink = TapSink::new(unfiltered_tx.clone(), name.to_string(), side);
+                        match control_tx
+                            .send(fanout::ControlMessage::Add(id.clone(), Box::new(sink)))
+                            .await
+                        {
+                            Ok(_) => {
+                                debug!(
+                                    message = "Sink connected.",
+                                    sink_id = ?id, component_name = ?name, output = ?output,
+                                );
+
+                                // Create a sink shutdown trigger to remove the sink
+                                // when matched components change.
+                                new_sinks.push(shutdown_trigger(control_tx, id));
+                            }
+                            Err(err) => {
+                                error!(
+                                    message = "Couldn't connect sink.",
+                                    error = ?err,
+                                    component_name = ?name, output = ?output, id = ?id
+                                );
+                            }
                         }
                     }
+
+                    matched.extend(found.into_iter().cloned());
                 }
 
-                // Remove components that have gone away.
-                sinks.retain(|name, _| {
-                    outputs.contains_key(name) || {
-                        debug!(message = "Removing component.", component_name = ?name);
-                        false
-                    }
-                });
+                // Replacing the sinks disconnects those of the previous topology, including
+                // those of components that have gone away.
+                sinks = new_sinks;
 
-                // Send notifications to the client. The # of notifications will always be
-                // exactly equal to the number of patterns, so we can pre-allocate capacity.
-                let mut notifications = Vec::with_capacity(patterns.len());
+                // Send notifications to the client.
+                let mut notifications = Vec::new();
 
                 // Matched notifications.
                 for pattern in matched.difference(&last_matches) {
@@ -298,7 +471,11 @@ async fn tap_handler(
                 }
 
                 // Not matched notifications.
-                for pattern in HashSet::from_iter(&patterns).difference(&matched) {
+                for pattern in patterns
+                    .outputs
+                    .union(&patterns.inputs)
+                    .filter(|pattern| !matched.contains(*pattern))
+                {
                     notifications.push(send_not_matched(tx.clone(), pattern).boxed());
                 }
 
@@ -308,20 +485,26 @@ async fn tap_handler(
                     debug!("Couldn't send notification(s); tap gone away.");
                     break;
                 }
+
+                last_matches = matched;
             }
         }
     }
 
-    debug!(message = "Stopped tap.", patterns = ?patterns);
+    debug!(message = "Stopped tap.", patterns = ?patterns, sinks = sinks.len());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::event::{Metric, MetricKind, MetricValue};
+    use crate::{
+        event::{MetricKind, MetricValue},
+        topology::TapResource,
+    };
     use futures::SinkExt;
-    use tokio::sync::watch;
+    use std::collections::HashMap;
+    use tokio::{sync::watch, time};
 
     #[test]
     /// Patterns should accept globbing.
@@ -339,10 +522,34 @@ mod tests {
         }
     }
 
+    #[test]
+    /// The rate limit should allow a number of events each second.
+    fn rate_limits() {
+        let start = Instant::now();
+        let mut rate_limit = RateLimit::new(2);
+
+        assert!(rate_limit.allow(start));
+        assert!(rate_limit.allow(start));
+        assert!(!rate_limit.allow(start + Duration::from_millis(500)));
+        assert!(rate_limit.allow(start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    /// Each component should have its own rate limit, only counting the matching events.
+    fn filters_per_component() {
+        let condition = TapCondition::new(r#".message == "keep""#).unwrap();
+        let mut filter = TapFilter::new(Some(condition), Some(1));
+
+        assert!(!filter.allows("a", Event::from("drop")));
+        assert!(filter.allows("a", Event::from("keep")));
+        assert!(!filter.allows("a", Event::from("keep")));
+        assert!(filter.allows("b", Event::from("keep")));
+    }
+
     #[tokio::test]
     /// A tap sink should match a pattern, receive the correct notifications, and
-    /// discard non `LogEvent` events.
-    async fn sink_log_events() {
+    /// relay both log and metric events.
+    async fn sink_events() {
         let pattern_matched = "tes*";
         let pattern_not_matched = "xyz";
         let name = "test";
@@ -351,17 +558,28 @@ mod tests {
         let mut outputs = HashMap::new();
         outputs.insert(name.to_string(), control_tx);
 
-        let (watch_tx, watch_rx) = watch::channel(HashMap::new());
+        let (watch_tx, watch_rx) = watch::channel(TapResource::default());
         let (sink_tx, mut sink_rx) = tokio_mpsc::channel(10);
 
         let _controller = TapController::new(
             watch_rx,
             sink_tx,
-            &[pattern_matched.to_string(), pattern_not_matched.to_string()],
+            TapPatterns {
+                outputs: vec![pattern_matched.to_string(), pattern_not_matched.to_string()]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            },
+            TapFilter::default(),
         );
 
         // Add the outputs to trigger a change event.
-        watch_tx.send(outputs).unwrap();
+        watch_tx
+            .send(TapResource {
+                outputs,
+                inputs: HashMap::new(),
+            })
+            .unwrap();
 
         // First two events should contain a notification that one pattern matched, and
         // one that didn't.
@@ -396,10 +614,64 @@ mod tests {
         let _ = fanout.send(metric_event).await.unwrap();
         let _ = fanout.send(log_event).await.unwrap();
 
-        // 3rd payload should be the log event
+        // 3rd payload should be the metric event, and the 4th the log event
         assert!(matches!(
             sink_rx.recv().await,
-            Some(TapPayload::Log(returned_name, _)) if returned_name == name
+            Some(TapPayload::Metric(returned_name, TapSide::Output, _)) if returned_name == name
         ));
+        assert!(matches!(
+            sink_rx.recv().await,
+            Some(TapPayload::Log(returned_name, TapSide::Output, _)) if returned_name == name
+        ));
+    }
+
+    #[tokio::test]
+    /// Tapping the input of a component should relay the events of the components it
+    /// consumes, as long as they match the condition and fit the rate limit.
+    async fn sink_input_events_with_filter() {
+        let (mut fanout, control_tx) = fanout::Fanout::new();
+        let mut outputs = HashMap::new();
+        outputs.insert("in".to_string(), control_tx);
+        let mut inputs = HashMap::new();
+        inputs.insert("out".to_string(), vec!["in".to_string()]);
+
+        let (watch_tx, watch_rx) = watch::channel(TapResource::default());
+        let (sink_tx, mut sink_rx) = tokio_mpsc::channel(10);
+
+        let _controller = TapController::new(
+            watch_rx,
+            sink_tx,
+            TapPatterns {
+                inputs: vec!["out".to_string()].into_iter().collect(),
+                ..Default::default()
+            },
+            TapFilter::new(
+                Some(TapCondition::new(r#".message == "keep""#).unwrap()),
+                Some(1),
+            ),
+        );
+        watch_tx.send(TapResource { outputs, inputs }).unwrap();
+
+        assert!(matches!(
+            sink_rx.recv().await,
+            Some(TapPayload::Notification(pattern, TapNotification::Matched)) if pattern == "out"
+        ));
+
+        for message in vec!["drop", "keep", "keep"] {
+            fanout.send(Event::from(message)).await.unwrap();
+        }
+
+        match sink_rx.recv().await {
+            Some(TapPayload::Log(name, TapSide::Input, log)) => {
+                assert_eq!(name, "out");
+                assert_eq!(log["message"], "keep".into());
+            }
+            payload => panic!("unexpected payload: {:?}", payload),
+        }
+
+        // The second matching event exceeds the rate limit.
+        assert!(time::timeout(Duration::from_millis(100), sink_rx.recv())
+            .await
+            .is_err());
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubCommand},
-    config, generate, heartbeat, list, metrics,
+    config, generate, generate_schema, graph, heartbeat, list, metrics,
     signal::{self, SignalTo},
     topology::{self, RunningTopology},
     trace, unit_test, validate,
@@ -14,6 +14,8 @@ use tokio::{
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
+#[cfg(feature = "disk-buffer")]
+use crate::inspect_buffers;
 #[cfg(feature = "sources-host_metrics")]
 use crate::sources::host_metrics;
 #[cfg(feature = "api-client")]
@@ -118,8 +120,13 @@ impl Application {
                     let code = match s {
                         SubCommand::Validate(v) => validate::validate(&v, color).await,
                         SubCommand::List(l) => list::cmd(&l),
+                        SubCommand::Config(c) => config::cmd::cmd(&c),
+                        SubCommand::Graph(g) => graph::cmd(&g),
                         SubCommand::Test(t) => unit_test::cmd(&t).await,
                         SubCommand::Generate(g) => generate::cmd(&g),
+                        SubCommand::GenerateSchema(g) => generate_schema::cmd(&g),
+                        #[cfg(feature = "disk-buffer")]
+                        SubCommand::Buffer(b) => inspect_buffers::cmd(&b),
                         #[cfg(feature = "api-client")]
                         SubCommand::Top(t) => top::cmd(&t).await,
                         #[cfg(feature = "api-client")]
@@ -230,7 +237,7 @@ impl Application {
                             playground: api_config.playground
                         });
 
-                        Some(api::Server::start(topology.config(), topology.watch()))
+                        Some(api::Server::start(topology.config(), topology.watch(), signal_handler.clone_tx()))
                     } else {
                         info!(message="API is disabled, enable by setting `api.enabled` to `true` and use commands like `vector top`.");
                         None
@@ -312,6 +319,43 @@ impl Application {
                                     emit!(VectorConfigLoadFailed);
                                 }
                             }
+                            SignalTo::ReloadFromApi { config: mut new_config, dry_run, reply } => {
+                                if dry_run {
+                                    let _ = reply.send(topology.check_config(&new_config).await);
+                                } else {
+                                    // Roll back to the running config if the new sinks are unhealthy.
+                                    // Nothing is checked if healthchecks are disabled.
+                                    new_config.healthchecks.set_require_healthy(true);
+                                    let diff = config::ConfigDiff::new(topology.config(), &new_config);
+                                    match topology
+                                        .reload_config_with_errors(new_config)
+                                        .await
+                                    {
+                                        Ok(errors) if errors.is_empty() => {
+                                            #[cfg(feature = "api")]
+                                            // Pass the new config to the API server.
+                                            if let Some(ref api_server) = api_server {
+                                                api_server.update_config(topology.config());
+                                            }
+
+                                            emit!(VectorReloaded { config_paths: &config_paths });
+                                            let _ = reply.send(Ok(diff));
+                                        },
+                                        Ok(errors) => {
+                                            emit!(VectorReloadFailed);
+                                            let _ = reply.send(Err(errors));
+                                        },
+                                        // Trigger graceful shutdown for what remains of the topology
+                                        Err(()) => {
+                                            emit!(VectorReloadFailed);
+                                            emit!(VectorRecoveryFailed);
+                                            let _ = reply.send(Err(vec!["Failed to restore the previous configuration.".to_owned()]));
+                                            break SignalTo::Shutdown;
+                                        }
+                                    }
+                                    sources_finished = topology.sources_finished();
+                                }
+                            }
                             _ => break signal,
                         }
                     }
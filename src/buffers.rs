@@ -1,7 +1,8 @@
-use crate::config::Resource;
+use crate::config::{schema, Resource};
 use crate::event::Event;
 use futures::Stream;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::path::PathBuf;
 pub use vector_core::buffers::*;
 
@@ -12,6 +13,8 @@ pub enum BufferConfig {
     Memory {
         #[serde(default = "BufferConfig::memory_max_events")]
         max_events: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_bytes: Option<usize>,
         #[serde(default)]
         when_full: WhenFull,
     },
@@ -21,12 +24,24 @@ pub enum BufferConfig {
         #[serde(default)]
         when_full: WhenFull,
     },
+    /// A memory buffer which writes to a disk buffer only once it is full.
+    #[cfg(feature = "disk-buffer")]
+    Overflow {
+        #[serde(default = "BufferConfig::memory_max_events")]
+        max_events: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_bytes: Option<usize>,
+        max_size: usize,
+        #[serde(default)]
+        when_full: WhenFull,
+    },
 }
 
 impl Default for BufferConfig {
     fn default() -> Self {
         BufferConfig::Memory {
             max_events: BufferConfig::memory_max_events(),
+            max_bytes: None,
             when_full: Default::default(),
         }
     }
@@ -40,6 +55,55 @@ impl BufferConfig {
         500
     }
 
+    /// The JSON Schema of the `buffer` option of sinks.
+    pub fn schema() -> serde_json::Value {
+        let when_full = schema::enumeration(
+            &[WhenFull::Block, WhenFull::DropNewest, WhenFull::DropOldest],
+            Some(WhenFull::default()),
+        );
+        let memory = json!({
+            "type": "object",
+            "properties": {
+                "type": { "const": "memory" },
+                "max_events": { "type": "integer", "minimum": 1, "default": BufferConfig::memory_max_events() },
+                "max_bytes": { "type": "integer", "minimum": 1 },
+                "when_full": when_full,
+            },
+            "required": ["type"],
+            "additionalProperties": false,
+        });
+        #[cfg(feature = "disk-buffer")]
+        let variants = vec![
+            memory,
+            json!({
+                "type": "object",
+                "properties": {
+                    "type": { "const": "disk" },
+                    "max_size": { "type": "integer", "minimum": 1 },
+                    "when_full": when_full,
+                },
+                "required": ["type", "max_size"],
+                "additionalProperties": false,
+            }),
+            json!({
+                "type": "object",
+                "properties": {
+                    "type": { "const": "overflow" },
+                    "max_events": { "type": "integer", "minimum": 1, "default": BufferConfig::memory_max_events() },
+                    "max_bytes": { "type": "integer", "minimum": 1 },
+                    "max_size": { "type": "integer", "minimum": 1 },
+                    "when_full": when_full,
+                },
+                "required": ["type", "max_size"],
+                "additionalProperties": false,
+            }),
+        ];
+        #[cfg(not(feature = "disk-buffer"))]
+        let variants = vec![memory];
+
+        json!({ "oneOf": variants, "default": BufferConfig::default() })
+    }
+
     #[cfg_attr(not(feature = "disk-buffer"), allow(unused))]
     pub fn build(
         &self,
@@ -49,9 +113,11 @@ impl BufferConfig {
         let variant = match &self {
             BufferConfig::Memory {
                 max_events,
+                max_bytes,
                 when_full,
             } => Variant::Memory {
                 max_events: *max_events,
+                max_bytes: *max_bytes,
                 when_full: *when_full,
             },
             #[cfg(feature = "disk-buffer")]
@@ -67,6 +133,23 @@ impl BufferConfig {
                     .to_path_buf(),
                 name: sink_name.to_string(),
             },
+            #[cfg(feature = "disk-buffer")]
+            BufferConfig::Overflow {
+                max_events,
+                max_bytes,
+                max_size,
+                when_full,
+            } => Variant::Overflow {
+                max_events: *max_events,
+                max_bytes: *max_bytes,
+                max_size: *max_size,
+                when_full: *when_full,
+                data_dir: data_dir
+                    .as_ref()
+                    .ok_or_else(|| "Must set data_dir to use on-disk buffering.".to_string())?
+                    .to_path_buf(),
+                name: sink_name.to_string(),
+            },
         };
         build(variant)
     }
@@ -77,7 +160,9 @@ impl BufferConfig {
         match self {
             BufferConfig::Memory { .. } => Vec::new(),
             #[cfg(feature = "disk-buffer")]
-            BufferConfig::Disk { .. } => vec![Resource::DiskBuffer(sink_name.to_string())],
+            BufferConfig::Disk { .. } | BufferConfig::Overflow { .. } => {
+                vec![Resource::DiskBuffer(sink_name.to_string())]
+            }
         }
     }
 }
@@ -99,6 +184,7 @@ mod test {
           "#,
             BufferConfig::Memory {
                 max_events: 500,
+                max_bytes: None,
                 when_full: WhenFull::Block,
             },
         );
@@ -110,6 +196,7 @@ mod test {
           "#,
             BufferConfig::Memory {
                 max_events: 100,
+                max_bytes: None,
                 when_full: WhenFull::Block,
             },
         );
@@ -121,10 +208,24 @@ mod test {
           "#,
             BufferConfig::Memory {
                 max_events: 500,
+                max_bytes: None,
                 when_full: WhenFull::DropNewest,
             },
         );
 
+        check(
+            r#"
+          type = "memory"
+          max_bytes = 1048576
+          when_full = "drop_oldest"
+          "#,
+            BufferConfig::Memory {
+                max_events: 500,
+                max_bytes: Some(1048576),
+                when_full: WhenFull::DropOldest,
+            },
+        );
+
         #[cfg(feature = "disk-buffer")]
         check(
             r#"
@@ -136,5 +237,19 @@ mod test {
                 when_full: WhenFull::Block,
             },
         );
+
+        #[cfg(feature = "disk-buffer")]
+        check(
+            r#"
+          type = "overflow"
+          max_size = 1024
+          "#,
+            BufferConfig::Overflow {
+                max_events: 500,
+                max_bytes: None,
+                max_size: 1024,
+                when_full: WhenFull::Block,
+            },
+        );
     }
 }
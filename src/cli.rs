@@ -1,7 +1,9 @@
-use crate::{config, generate, get_version, list, unit_test, validate};
+use crate::{config, generate, generate_schema, get_version, graph, list, unit_test, validate};
 use std::path::PathBuf;
 use structopt::{clap::AppSettings, StructOpt};
 
+#[cfg(feature = "disk-buffer")]
+use crate::inspect_buffers;
 #[cfg(feature = "api-client")]
 use crate::tap;
 #[cfg(feature = "api-client")]
@@ -34,8 +36,11 @@ impl Opts {
     pub fn log_level(&self) -> &'static str {
         let (quiet_level, verbose_level) = match self.sub_command {
             Some(SubCommand::Validate(_))
+            | Some(SubCommand::Config(_))
             | Some(SubCommand::Generate(_))
-            | Some(SubCommand::List(_)) => {
+            | Some(SubCommand::GenerateSchema(_))
+            | Some(SubCommand::List(_))
+            | Some(SubCommand::Graph(_)) => {
                 if self.root.verbose == 0 {
                     (self.root.quiet + 1, self.root.verbose)
                 } else {
@@ -156,16 +161,29 @@ pub enum SubCommand {
     /// Validate the target config, then exit.
     Validate(validate::Opts),
 
+    /// Print the configuration resulting from merging the target config files, then exit.
+    Config(config::cmd::Opts),
+
     /// Generate a Vector configuration containing a list of components.
     Generate(generate::Opts),
 
+    /// Print the JSON Schema of Vector configuration files, then exit.
+    GenerateSchema(generate_schema::Opts),
+
     /// List available components, then exit.
     List(list::Opts),
 
+    /// Render the topology of the target config as a DOT or Mermaid graph, then exit.
+    Graph(graph::Opts),
+
     /// Run Vector config unit tests, then exit. This command is experimental and therefore subject to change.
     /// For guidance on how to write unit tests check out: https://vector.dev/guides/level-up/unit-testing/
     Test(unit_test::Opts),
 
+    /// Inspect and repair the disk buffers of a stopped Vector instance.
+    #[cfg(feature = "disk-buffer")]
+    Buffer(inspect_buffers::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
@@ -229,6 +247,7 @@ impl std::str::FromStr for LogFormat {
 
 pub fn handle_config_errors(errors: Vec<String>) -> exitcode::ExitCode {
     for error in errors {
+        let error = crate::config::secret::redact(&error);
         error!(message = "Configuration error.", %error);
     }
 
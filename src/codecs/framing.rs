@@ -0,0 +1,307 @@
+use bytes::{Bytes, BytesMut};
+use codec::BytesDelimitedCodec;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::io;
+use tokio_util::codec::{Decoder, LengthDelimitedCodec};
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum FramingError {
+    #[snafu(display("Delimiter must be a single ASCII character, got {:?}", delimiter))]
+    NonAsciiDelimiter { delimiter: char },
+}
+
+/// Determines how a stream of bytes is split into frames before parsing.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(tag = "method", rename_all = "snake_case", deny_unknown_fields)]
+pub enum FramingConfig {
+    /// Each received message (datagram, request body, ...) is a single frame.
+    /// On a stream, the message is everything received until it's closed.
+    Bytes {
+        #[serde(default)]
+        max_length: Option<usize>,
+    },
+    /// Frames are separated by `\n`.
+    NewlineDelimited {
+        #[serde(default)]
+        max_length: Option<usize>,
+    },
+    /// Frames are separated by an arbitrary ASCII character.
+    CharacterDelimited {
+        delimiter: char,
+        #[serde(default)]
+        max_length: Option<usize>,
+    },
+    /// Frames are prefixed with their length as a 32-bit big-endian integer.
+    LengthDelimited,
+}
+
+impl FramingConfig {
+    pub const fn bytes() -> Self {
+        Self::Bytes { max_length: None }
+    }
+
+    pub fn newline_delimited(max_length: usize) -> Self {
+        Self::NewlineDelimited {
+            max_length: Some(max_length),
+        }
+    }
+
+    /// Caps `bytes` framing which has no `max_length` at `max_length`. Sources
+    /// reading streams use it, since a message only ends with its stream.
+    pub fn with_stream_max_length(self, max_length: usize) -> Self {
+        match self {
+            Self::Bytes { max_length: None } => Self::Bytes {
+                max_length: Some(max_length),
+            },
+            framing => framing,
+        }
+    }
+
+    pub fn build(&self) -> Result<Framer, FramingError> {
+        Ok(match self {
+            Self::Bytes { max_length } => {
+                Framer::Bytes(BytesCodec::new(max_length.unwrap_or(usize::MAX)))
+            }
+            Self::NewlineDelimited { max_length } => {
+                Framer::Delimited(delimited_codec(b'\n', *max_length))
+            }
+            Self::CharacterDelimited {
+                delimiter,
+                max_length,
+            } => {
+                if !delimiter.is_ascii() {
+                    return Err(FramingError::NonAsciiDelimiter {
+                        delimiter: *delimiter,
+                    });
+                }
+                Framer::Delimited(delimited_codec(*delimiter as u8, *max_length))
+            }
+            Self::LengthDelimited => Framer::LengthDelimited(LengthDelimitedCodec::new()),
+        })
+    }
+}
+
+fn delimited_codec(delimiter: u8, max_length: Option<usize>) -> BytesDelimitedCodec {
+    match max_length {
+        Some(max_length) => BytesDelimitedCodec::new_with_max_length(delimiter, max_length),
+        None => BytesDelimitedCodec::new(delimiter),
+    }
+}
+
+/// Frames a whole message. Messages longer than `max_length` are discarded as
+/// soon as they exceed it, rather than buffered until their end.
+#[derive(Debug, Clone, Copy)]
+pub struct BytesCodec {
+    max_length: usize,
+    is_discarding: bool,
+}
+
+impl BytesCodec {
+    pub const fn new(max_length: usize) -> Self {
+        Self {
+            max_length,
+            is_discarding: false,
+        }
+    }
+
+    fn discard_if_too_long(&mut self, buf: &mut BytesMut) {
+        if buf.len() > self.max_length {
+            if !self.is_discarding {
+                warn!(
+                    message = "Discarding frame larger than max_length.",
+                    buf_len = buf.len(),
+                    max_length = self.max_length,
+                    internal_log_rate_secs = 30
+                );
+            }
+            self.is_discarding = true;
+            buf.clear();
+        }
+    }
+}
+
+impl Decoder for BytesCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        // The frame is only emitted once the whole message has been received.
+        self.discard_if_too_long(buf);
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        self.discard_if_too_long(buf);
+        if self.is_discarding {
+            self.is_discarding = false;
+            buf.clear();
+            Ok(None)
+        } else if buf.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(buf.split().freeze()))
+        }
+    }
+}
+
+/// Splits a byte stream into frames according to a `FramingConfig`.
+#[derive(Debug)]
+pub enum Framer {
+    Bytes(BytesCodec),
+    Delimited(BytesDelimitedCodec),
+    LengthDelimited(LengthDelimitedCodec),
+}
+
+impl Clone for Framer {
+    /// `LengthDelimitedCodec` is not `Clone`, and since framers are only
+    /// cloned to hand a fresh instance to each new connection, the clone of a
+    /// length delimited framer starts with an empty decoding state.
+    fn clone(&self) -> Self {
+        match self {
+            Self::Bytes(codec) => Self::Bytes(BytesCodec::new(codec.max_length)),
+            Self::Delimited(codec) => Self::Delimited(*codec),
+            Self::LengthDelimited(_) => Self::LengthDelimited(LengthDelimitedCodec::new()),
+        }
+    }
+}
+
+impl Decoder for Framer {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        match self {
+            Self::Bytes(codec) => codec.decode(buf),
+            Self::Delimited(codec) => codec.decode(buf),
+            Self::LengthDelimited(codec) => Ok(codec.decode(buf)?.map(BytesMut::freeze)),
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        match self {
+            Self::Bytes(codec) => codec.decode_eof(buf),
+            Self::Delimited(codec) => codec.decode_eof(buf),
+            Self::LengthDelimited(codec) => Ok(codec.decode_eof(buf)?.map(BytesMut::freeze)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BufMut;
+
+    fn frames(config: FramingConfig, input: &[u8]) -> Vec<Bytes> {
+        let mut framer = config.build().unwrap();
+        let mut buf = BytesMut::from(input);
+        let mut frames = Vec::new();
+        while let Some(frame) = framer.decode_eof(&mut buf).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn bytes_framing_emits_whole_message() {
+        assert_eq!(
+            frames(FramingConfig::bytes(), b"foo\nbar"),
+            vec![Bytes::from("foo\nbar")]
+        );
+        assert!(frames(FramingConfig::bytes(), b"").is_empty());
+    }
+
+    #[test]
+    fn bytes_framing_discards_long_streams() {
+        let mut framer = FramingConfig::bytes()
+            .with_stream_max_length(4)
+            .build()
+            .unwrap();
+        let mut buf = BytesMut::from("foo");
+        assert_eq!(framer.decode(&mut buf).unwrap(), None);
+        assert_eq!(buf.len(), 3);
+
+        // The message is dropped as soon as it's too long, and what's left of
+        // it isn't framed once the stream ends.
+        buf.extend_from_slice(b"bar");
+        assert_eq!(framer.decode(&mut buf).unwrap(), None);
+        assert!(buf.is_empty());
+        buf.extend_from_slice(b"baz");
+        assert_eq!(framer.decode_eof(&mut buf).unwrap(), None);
+
+        let mut buf = BytesMut::from("foo");
+        assert_eq!(
+            framer.decode_eof(&mut buf).unwrap(),
+            Some(Bytes::from("foo"))
+        );
+    }
+
+    #[test]
+    fn newline_delimited_framing() {
+        assert_eq!(
+            frames(FramingConfig::newline_delimited(1024), b"foo\nbar\nbaz"),
+            vec![Bytes::from("foo"), Bytes::from("bar"), Bytes::from("baz")]
+        );
+    }
+
+    #[test]
+    fn character_delimited_framing() {
+        let config = FramingConfig::CharacterDelimited {
+            delimiter: ',',
+            max_length: None,
+        };
+        assert_eq!(
+            frames(config, b"foo,bar"),
+            vec![Bytes::from("foo"), Bytes::from("bar")]
+        );
+    }
+
+    #[test]
+    fn character_delimited_rejects_non_ascii() {
+        let config = FramingConfig::CharacterDelimited {
+            delimiter: 'é',
+            max_length: None,
+        };
+        assert_eq!(
+            config.build().unwrap_err(),
+            FramingError::NonAsciiDelimiter { delimiter: 'é' }
+        );
+    }
+
+    #[test]
+    fn length_delimited_framing() {
+        let mut input = BytesMut::new();
+        input.put_u32(3);
+        input.put_slice(b"foo");
+        input.put_u32(3);
+        input.put_slice(b"bar");
+        assert_eq!(
+            frames(FramingConfig::LengthDelimited, &input),
+            vec![Bytes::from("foo"), Bytes::from("bar")]
+        );
+    }
+
+    #[test]
+    fn deserialize_framing_config() {
+        #[derive(Deserialize)]
+        struct Config {
+            framing: FramingConfig,
+        }
+
+        let config: Config = toml::from_str(
+            r#"
+            framing.method = "character_delimited"
+            framing.delimiter = "\t"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.framing,
+            FramingConfig::CharacterDelimited {
+                delimiter: '\t',
+                max_length: None
+            }
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Shared decoding of raw bytes into events.
+//!
+//! A `Decoder` runs in two stages: a `Framer` splits the incoming bytes into
+//! frames, and a `Parser` turns every frame into one or more events. Sources
+//! expose both stages to users through the `decoding` option.
+
+mod framing;
+mod parsing;
+
+pub use framing::{BytesCodec, Framer, FramingConfig, FramingError};
+#[cfg(feature = "codecs-syslog")]
+pub(crate) use parsing::{insert_fields_from_syslog, resolve_year};
+pub use parsing::{ParseError, Parser, ParserConfig};
+
+use crate::{config::DataType, event::Event, internal_events::DecoderParseFailed};
+use bytes::{Bytes, BytesMut};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, io};
+
+/// The `decoding` option of a source.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DecodingConfig {
+    /// How to split incoming data into frames. Each source has its own
+    /// default, matching how it splits data without a `decoding` option.
+    #[serde(default)]
+    pub framing: Option<FramingConfig>,
+    /// How to turn a frame into events.
+    #[serde(default)]
+    pub codec: ParserConfig,
+}
+
+impl DecodingConfig {
+    pub fn new(framing: Option<FramingConfig>, codec: ParserConfig) -> Self {
+        Self { framing, codec }
+    }
+
+    /// Builds the framer, falling back to the framing given by the source
+    /// when none is configured.
+    pub fn build_framer(&self, default_framing: FramingConfig) -> crate::Result<Framer> {
+        Ok(self.framing.as_ref().unwrap_or(&default_framing).build()?)
+    }
+
+    pub fn build_parser(&self) -> Parser {
+        self.codec.build()
+    }
+
+    pub fn output_type(&self) -> DataType {
+        self.codec.output_type()
+    }
+
+    pub fn build(&self, default_framing: FramingConfig) -> crate::Result<Decoder> {
+        Ok(Decoder::new(
+            self.build_framer(default_framing)?,
+            self.build_parser(),
+        ))
+    }
+
+    /// Decodes all events in a self contained payload, as sources do for
+    /// `SourceConfig::decode_payload`.
+    pub fn decode_payload(
+        &self,
+        default_framing: FramingConfig,
+        payload: Bytes,
+    ) -> crate::Result<Vec<Event>> {
+        let mut decoder = self.build(default_framing)?;
+        Ok(decoder.decode_all(BytesMut::from(&payload[..]))?)
+    }
+}
+
+/// Decodes events out of a byte stream by framing and then parsing it.
+///
+/// Frames which fail to parse are reported through an internal event and
+/// skipped, so a single malformed message does not end a connection. Each
+/// decoded event is paired with the byte size of the frame it came from; when
+/// a frame produces several events, its size is only reported with the first.
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    framer: Framer,
+    parser: Parser,
+    pending: VecDeque<(Event, usize)>,
+}
+
+impl Decoder {
+    pub fn new(framer: Framer, parser: Parser) -> Self {
+        Self {
+            framer,
+            parser,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Decodes all events in a self contained chunk of bytes, such as a
+    /// datagram or a request body.
+    pub fn decode_all(&mut self, mut buf: BytesMut) -> Result<Vec<Event>, io::Error> {
+        let mut events = Vec::new();
+        while let Some((event, _)) = tokio_util::codec::Decoder::decode_eof(self, &mut buf)? {
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    fn decode_next(
+        &mut self,
+        buf: &mut BytesMut,
+        eof: bool,
+    ) -> Result<Option<(Event, usize)>, io::Error> {
+        use tokio_util::codec::Decoder as _;
+
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Ok(Some(item));
+            }
+
+            let frame = if eof {
+                self.framer.decode_eof(buf)?
+            } else {
+                self.framer.decode(buf)?
+            };
+            let frame = match frame {
+                Some(frame) => frame,
+                None => return Ok(None),
+            };
+
+            let byte_size = frame.len();
+            match self.parser.parse(frame) {
+                Ok(events) => {
+                    let sizes = std::iter::once(byte_size).chain(std::iter::repeat(0));
+                    self.pending.extend(events.into_iter().zip(sizes));
+                }
+                Err(error) => emit!(DecoderParseFailed { error }),
+            }
+        }
+    }
+}
+
+impl tokio_util::codec::Decoder for Decoder {
+    type Item = (Event, usize);
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_next(buf, false)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_next(buf, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::log_schema;
+    use tokio_util::codec::Decoder as _;
+
+    #[test]
+    fn decodes_framed_json() {
+        let config = DecodingConfig::new(None, ParserConfig::Json);
+        let mut decoder = config
+            .build(FramingConfig::newline_delimited(1024))
+            .unwrap();
+        let mut buf = BytesMut::from("{\"a\":1}\nnot json\n[{\"a\":2},{\"a\":3}]\n{\"a\"");
+
+        let (event, size) = decoder.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(event.as_log()["a"], 1.into());
+        assert_eq!(size, 7);
+
+        // The invalid frame is skipped, and the array expands to two events.
+        let (event, size) = decoder.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(event.as_log()["a"], 2.into());
+        assert_eq!(size, 17);
+        let (event, size) = decoder.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(event.as_log()["a"], 3.into());
+        assert_eq!(size, 0);
+
+        // The last frame is incomplete.
+        assert!(decoder.decode(&mut buf).unwrap().is_none());
+        assert_eq!(&buf[..], b"{\"a\"");
+    }
+
+    #[test]
+    fn decode_all_with_default_framing() {
+        let config = DecodingConfig::default();
+        let mut decoder = config.build(FramingConfig::bytes()).unwrap();
+        let events = decoder.decode_all(BytesMut::from("foo\nbar")).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "foo\nbar".into()
+        );
+    }
+
+    #[cfg(feature = "codecs-syslog")]
+    #[test]
+    fn deserialize_decoding_config() {
+        let config: DecodingConfig = toml::from_str(
+            r#"
+            framing.method = "newline_delimited"
+            framing.max_length = 10
+            codec = "syslog"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            DecodingConfig::new(
+                Some(FramingConfig::newline_delimited(10)),
+                ParserConfig::Syslog
+            )
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{
+    config::{log_schema, DataType},
+    event::{proto, Event, LogEvent, Value},
+};
+use bytes::Bytes;
+#[cfg(feature = "codecs-syslog")]
+use chrono::Datelike;
+use chrono::Utc;
+use prost::Message;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use snafu::{ResultExt, Snafu};
+#[cfg(feature = "codecs-syslog")]
+use syslog_loose::{IncompleteDate, ProcId, Protocol};
+
+#[derive(Debug, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Invalid JSON: {}", source))]
+    InvalidJson { source: serde_json::Error },
+    #[snafu(display("Expected JSON object or array of objects, got {}", kind))]
+    UnexpectedJsonType { kind: &'static str },
+    #[snafu(display("Invalid UTF-8: {}", source))]
+    InvalidUtf8 { source: std::str::Utf8Error },
+    #[snafu(display("Invalid native event: {}", source))]
+    InvalidNative { source: prost::DecodeError },
+}
+
+impl ParseError {
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Self::InvalidJson { .. } | Self::UnexpectedJsonType { .. } => "json",
+            Self::InvalidUtf8 { .. } => "utf8",
+            Self::InvalidNative { .. } => "native",
+        }
+    }
+}
+
+/// Determines how a single frame is turned into events.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq, Derivative, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum ParserConfig {
+    /// The frame is used as the `message` of a log event.
+    #[derivative(Default)]
+    Bytes,
+    /// The frame is a JSON object, or an array of JSON objects.
+    Json,
+    /// The frame is a RFC 3164 or RFC 5424 syslog message.
+    #[cfg(feature = "codecs-syslog")]
+    Syslog,
+    /// The frame is an event encoded with Vector's protobuf event format.
+    Native,
+}
+
+impl ParserConfig {
+    pub fn build(&self) -> Parser {
+        Parser(*self)
+    }
+
+    /// Only the native codec can produce metric events.
+    pub fn output_type(&self) -> DataType {
+        match self {
+            Self::Native => DataType::Any,
+            Self::Bytes | Self::Json => DataType::Log,
+            #[cfg(feature = "codecs-syslog")]
+            Self::Syslog => DataType::Log,
+        }
+    }
+}
+
+/// Turns frames into events according to a `ParserConfig`.
+#[derive(Debug, Clone, Copy)]
+pub struct Parser(ParserConfig);
+
+impl Parser {
+    pub fn parse(&self, frame: Bytes) -> Result<Vec<Event>, ParseError> {
+        match self.0 {
+            ParserConfig::Bytes => Ok(vec![Event::from(frame)]),
+            ParserConfig::Json => parse_json(&frame),
+            #[cfg(feature = "codecs-syslog")]
+            ParserConfig::Syslog => {
+                let line = std::str::from_utf8(&frame).context(InvalidUtf8)?;
+                Ok(vec![parse_syslog(line)])
+            }
+            ParserConfig::Native => proto::EventWrapper::decode(frame)
+                .map(|event| vec![Event::from(event)])
+                .context(InvalidNative),
+        }
+    }
+}
+
+fn parse_json(frame: &[u8]) -> Result<Vec<Event>, ParseError> {
+    match serde_json::from_slice(frame).context(InvalidJson)? {
+        JsonValue::Array(values) => values
+            .into_iter()
+            .map(json_object_to_event)
+            .collect::<Result<_, _>>(),
+        value => json_object_to_event(value).map(|event| vec![event]),
+    }
+}
+
+fn json_object_to_event(value: JsonValue) -> Result<Event, ParseError> {
+    match value {
+        JsonValue::Object(map) => {
+            let mut log = LogEvent::default();
+            log.insert(log_schema().timestamp_key(), Utc::now());
+            for (key, value) in map {
+                log.insert_flat(key, value);
+            }
+            Ok(log.into())
+        }
+        value => Err(ParseError::UnexpectedJsonType {
+            kind: json_value_kind(&value),
+        }),
+    }
+}
+
+fn json_value_kind(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Object(_) => "object",
+        JsonValue::Array(_) => "array",
+        JsonValue::String(_) => "string",
+        JsonValue::Number(_) => "number",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Null => "null",
+    }
+}
+
+#[cfg(feature = "codecs-syslog")]
+fn parse_syslog(line: &str) -> Event {
+    let parsed = syslog_loose::parse_message_with_year(line.trim(), resolve_year);
+    let mut event = Event::from(parsed.msg);
+
+    let timestamp = parsed
+        .timestamp
+        .map(|ts| ts.into())
+        .unwrap_or_else(Utc::now);
+    event
+        .as_mut_log()
+        .insert(log_schema().timestamp_key(), timestamp);
+
+    insert_fields_from_syslog(event.as_mut_log(), parsed);
+
+    event
+}
+
+/// Function used to resolve the year for syslog messages that don't include the year.
+/// If the current month is January, and the syslog message is for December, it will take the previous year.
+/// Otherwise, take the current year.
+#[cfg(feature = "codecs-syslog")]
+pub(crate) fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
+    let now = Utc::now();
+    if now.month() == 1 && month == 12 {
+        now.year() - 1
+    } else {
+        now.year()
+    }
+}
+
+#[cfg(feature = "codecs-syslog")]
+pub(crate) fn insert_fields_from_syslog(log: &mut LogEvent, parsed: syslog_loose::Message<&str>) {
+    if let Some(host) = parsed.hostname {
+        log.insert("hostname", host.to_string());
+    }
+    if let Some(severity) = parsed.severity {
+        log.insert("severity", severity.as_str().to_owned());
+    }
+    if let Some(facility) = parsed.facility {
+        log.insert("facility", facility.as_str().to_owned());
+    }
+    if let Protocol::RFC5424(version) = parsed.protocol {
+        log.insert("version", version as i64);
+    }
+    if let Some(app_name) = parsed.appname {
+        log.insert("appname", app_name.to_owned());
+    }
+    if let Some(msg_id) = parsed.msgid {
+        log.insert("msgid", msg_id.to_owned());
+    }
+    if let Some(procid) = parsed.procid {
+        let value: Value = match procid {
+            ProcId::PID(pid) => pid.into(),
+            ProcId::Name(name) => name.to_string().into(),
+        };
+        log.insert("procid", value);
+    }
+
+    for element in parsed.structured_data.into_iter() {
+        for (name, value) in element.params.into_iter() {
+            let key = format!("{}.{}", element.id, name);
+            log.insert(key, value.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(config: ParserConfig, frame: &str) -> Result<Vec<Event>, ParseError> {
+        config.build().parse(Bytes::from(frame.to_owned()))
+    }
+
+    #[test]
+    fn bytes_parser_sets_message() {
+        let events = parse(ParserConfig::Bytes, "foo bar").unwrap();
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "foo bar".into());
+        assert!(log.contains(log_schema().timestamp_key()));
+    }
+
+    #[test]
+    fn json_parser_object() {
+        let events = parse(ParserConfig::Json, r#"{"foo": "bar", "baz.qux": 1}"#).unwrap();
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["foo"], "bar".into());
+        assert_eq!(log.get_flat("baz.qux"), Some(&Value::from(1)));
+        assert!(log.contains(log_schema().timestamp_key()));
+    }
+
+    #[test]
+    fn json_parser_array_of_objects() {
+        let events = parse(ParserConfig::Json, r#"[{"a": 1}, {"a": 2}]"#).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].as_log()["a"], 2.into());
+    }
+
+    #[test]
+    fn json_parser_rejects_scalars() {
+        assert!(matches!(
+            parse(ParserConfig::Json, "42"),
+            Err(ParseError::UnexpectedJsonType { kind: "number" })
+        ));
+        assert!(matches!(
+            parse(ParserConfig::Json, "{"),
+            Err(ParseError::InvalidJson { .. })
+        ));
+    }
+
+    #[cfg(feature = "codecs-syslog")]
+    #[test]
+    fn syslog_parser() {
+        let events = parse(
+            ParserConfig::Syslog,
+            r#"<13>1 2019-02-13T19:48:34+00:00 74794bfb6795 root 8449 - [meta sequenceId="1"] i am foobar"#,
+        )
+        .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "i am foobar".into());
+        assert_eq!(log["hostname"], "74794bfb6795".into());
+        assert_eq!(log["appname"], "root".into());
+        assert_eq!(log["procid"], 8449.into());
+        assert_eq!(log["meta.sequenceId"], "1".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            chrono::DateTime::parse_from_rfc3339("2019-02-13T19:48:34+00:00")
+                .unwrap()
+                .with_timezone(&Utc)
+                .into()
+        );
+    }
+
+    #[test]
+    fn native_parser_roundtrip() {
+        let mut event = Event::from("foo");
+        event.as_mut_log().insert("bar", "baz");
+        let mut frame = Vec::new();
+        proto::EventWrapper::from(event.clone())
+            .encode(&mut frame)
+            .unwrap();
+
+        let events = ParserConfig::Native
+            .build()
+            .parse(Bytes::from(frame))
+            .unwrap();
+        assert_eq!(events, vec![event]);
+    }
+}
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::net::{Ipv4Addr, SocketAddr};
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Copy, Clone)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Copy, Clone, JsonSchema)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     #[serde(default = "default_enabled")]
@@ -12,6 +13,9 @@ pub struct Options {
 
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    #[serde(default = "default_reload")]
+    pub reload: bool,
 }
 
 impl Default for Options {
@@ -20,6 +24,7 @@ impl Default for Options {
             enabled: default_enabled(),
             playground: default_playground(),
             address: default_address(),
+            reload: default_reload(),
         }
     }
 }
@@ -39,6 +44,10 @@ fn default_playground() -> bool {
     true
 }
 
+fn default_reload() -> bool {
+    false
+}
+
 impl Options {
     pub fn merge(&mut self, other: Self) -> Result<(), String> {
         // Merge options
@@ -64,6 +73,7 @@ impl Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            reload: self.reload | other.reload,
         };
 
         *self = options;
@@ -77,6 +87,7 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        reload: false,
     };
 
     a.merge(Options::default()).unwrap();
@@ -87,6 +98,7 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            reload: false,
         }
     );
 }
@@ -98,6 +110,7 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        reload: false,
     };
 
     a.merge(Options::default()).unwrap();
@@ -108,6 +121,7 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            reload: false,
         }
     );
 }
@@ -1,13 +1,17 @@
 #[cfg(feature = "api")]
 use super::api;
 use super::{
-    compiler, default_data_dir, provider, Config, GlobalOptions, HealthcheckOptions, SinkConfig,
-    SinkOuter, SourceConfig, SourceOuter, TestDefinition, TransformConfig, TransformOuter,
+    compiler, default_data_dir, provider,
+    secret::{Redacted, SecretBackend},
+    templating::{ComponentTables, Instance},
+    Config, GlobalOptions, HealthcheckOptions, SinkConfig, SinkOuter, SourceConfig, SourceOuter,
+    TestDefinition, TransformConfig, TransformOuter,
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Deserialize, Serialize, Debug, Default)]
+#[derive(Deserialize, Serialize, Default)]
 #[serde(deny_unknown_fields)]
 pub struct ConfigBuilder {
     #[serde(flatten)]
@@ -18,11 +22,19 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub healthchecks: HealthcheckOptions,
     #[serde(default)]
+    pub secret: IndexMap<String, SecretBackend>,
+    #[serde(default)]
     pub sources: IndexMap<String, SourceOuter>,
     #[serde(default)]
     pub sinks: IndexMap<String, SinkOuter>,
     #[serde(default)]
     pub transforms: IndexMap<String, TransformOuter>,
+    #[serde(default, skip_serializing_if = "ComponentTables::is_empty")]
+    pub templates: ComponentTables,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub instances: IndexMap<String, Instance>,
+    #[serde(default, skip_serializing_if = "ComponentTables::is_empty")]
+    pub overlays: ComponentTables,
     #[serde(default)]
     pub tests: Vec<TestDefinition>,
     pub provider: Option<Box<dyn provider::ProviderConfig>>,
@@ -40,6 +52,29 @@ impl Clone for ConfigBuilder {
     }
 }
 
+// Components are configured with the values of their secrets, so those
+// must be redacted.
+impl fmt::Debug for ConfigBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("ConfigBuilder");
+        debug.field("global", &self.global);
+        #[cfg(feature = "api")]
+        debug.field("api", &self.api);
+        debug
+            .field("healthchecks", &self.healthchecks)
+            .field("secret", &self.secret)
+            .field("sources", &Redacted(&self.sources))
+            .field("sinks", &Redacted(&self.sinks))
+            .field("transforms", &Redacted(&self.transforms))
+            .field("templates", &Redacted(&self.templates))
+            .field("instances", &Redacted(&self.instances))
+            .field("overlays", &Redacted(&self.overlays))
+            .field("tests", &Redacted(&self.tests))
+            .field("provider", &Redacted(&self.provider))
+            .finish()
+    }
+}
+
 impl From<Config> for ConfigBuilder {
     fn from(c: Config) -> Self {
         ConfigBuilder {
@@ -47,9 +82,13 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "api")]
             api: c.api,
             healthchecks: c.healthchecks,
+            secret: IndexMap::new(),
             sources: c.sources,
             sinks: c.sinks,
             transforms: c.transforms,
+            templates: ComponentTables::default(),
+            instances: IndexMap::new(),
+            overlays: ComponentTables::default(),
             provider: None,
             tests: c.tests,
         }
@@ -145,6 +184,19 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate transform name found: {}", k));
             }
         });
+        with.secret.keys().for_each(|k| {
+            if self.secret.contains_key(k) {
+                errors.push(format!("duplicate secret backend name found: {}", k));
+            }
+        });
+        with.instances.keys().for_each(|k| {
+            if self.instances.contains_key(k) {
+                errors.push(format!("duplicate instance name found: {}", k));
+            }
+        });
+        if let Err(template_errors) = self.templates.append(with.templates) {
+            errors.extend(template_errors);
+        }
         with.tests.iter().for_each(|wt| {
             if self.tests.iter().any(|t| t.name == wt.name) {
                 errors.push(format!("duplicate test name found: {}", wt.name));
@@ -154,9 +206,12 @@ impl ConfigBuilder {
             return Err(errors);
         }
 
+        self.secret.extend(with.secret);
         self.sources.extend(with.sources);
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
+        self.instances.extend(with.instances);
+        self.overlays.merge(with.overlays);
         self.tests.extend(with.tests);
 
         Ok(())
@@ -0,0 +1,277 @@
+//! The `vector config` command, for printing the configuration that results
+//! from merging config files, interpolating them and expanding their templates
+//! and overlays.
+
+use super::{
+    builder::ConfigBuilder, secret, templating, Format, SinkOuter, SourceOuter, TransformOuter,
+};
+use crate::config;
+use colored::*;
+use exitcode::ExitCode;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Vector config files to merge.
+    /// Format is detected from the file name.
+    /// If none are specified the default config path `/etc/vector/vector.toml`
+    /// will be targeted.
+    #[structopt(
+        name = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        use_delimiter(true)
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Format of the printed config.
+    #[structopt(long, default_value = "toml", possible_values = &["toml", "json", "yaml"])]
+    format: Format,
+
+    /// Include the options left to their default value.
+    #[structopt(long)]
+    include_defaults: bool,
+
+    /// Print the merged config. This is what the command always does, the
+    /// flag is only accepted for compatibility.
+    #[structopt(long)]
+    print: bool,
+}
+
+/// Merges the config files, expanding templates and overlays, and checks the
+/// result the same way as the topology checks of `vector validate`.
+fn merge(paths: &[(PathBuf, config::FormatHint)]) -> Result<ConfigBuilder, Vec<String>> {
+    let paths =
+        config::process_paths(paths).ok_or_else(|| vec!["No config file paths".to_owned()])?;
+    config::init_log_schema(&paths, true)?;
+
+    let (mut builder, warnings) = config::load_builder_from_paths(&paths)?;
+    templating::expand(&mut builder)?;
+
+    let (_, build_warnings) = builder.clone().build_with_warnings()?;
+    for warning in warnings.into_iter().chain(build_warnings) {
+        eprintln!("{}", secret::redact(&warning).yellow());
+    }
+
+    Ok(builder)
+}
+
+/// Every path to a table entry in `value`, parents first.
+fn paths(value: &Value, prefix: &[String], paths: &mut Vec<Vec<String>>) {
+    if let Value::Object(table) = value {
+        for (key, value) in table {
+            let mut path = prefix.to_vec();
+            path.push(key.clone());
+            paths.push(path.clone());
+            self::paths(value, &path, paths);
+        }
+    }
+}
+
+fn remove(value: &mut Value, path: &[String]) -> bool {
+    let (key, parents) = match path.split_last() {
+        Some(split) => split,
+        None => return false,
+    };
+
+    let table = parents
+        .iter()
+        .try_fold(value, |value, parent| value.get_mut(parent));
+    match table {
+        Some(Value::Object(table)) => table.remove(key).is_some(),
+        _ => false,
+    }
+}
+
+/// Removes the options of `value` that are at their default, that is those
+/// that `T` deserializes the same without.
+fn without_defaults<T: Serialize + DeserializeOwned>(value: Value) -> Value {
+    let normalize = |value: &Value| {
+        serde_json::from_value::<T>(value.clone())
+            .ok()
+            .and_then(|value| serde_json::to_value(value).ok())
+    };
+    let full = match normalize(&value) {
+        Some(full) => full,
+        None => return value,
+    };
+
+    let mut all = Vec::new();
+    paths(&full, &[], &mut all);
+
+    let mut stripped = full.clone();
+    for path in all {
+        let mut candidate = stripped.clone();
+        if remove(&mut candidate, &path) && normalize(&candidate).as_ref() == Some(&full) {
+            stripped = candidate;
+        }
+    }
+    stripped
+}
+
+/// Unset options are left out, as not every format has a null value.
+fn without_nulls(value: Value) -> Value {
+    match value {
+        Value::Object(table) => Value::Object(
+            table
+                .into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, without_nulls(value)))
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(without_nulls).collect()),
+        value => value,
+    }
+}
+
+fn strip_components<T: Serialize + DeserializeOwned>(value: &mut Value) {
+    if let Some(components) = value.as_object_mut() {
+        for component in components.values_mut() {
+            *component = without_defaults::<T>(component.take());
+        }
+    }
+}
+
+/// The merged config, with the options at their default unless `include_defaults`.
+fn resolve(builder: &ConfigBuilder, include_defaults: bool) -> Result<Value, String> {
+    let mut value = serde_json::to_value(builder).map_err(|error| error.to_string())?;
+
+    if !include_defaults {
+        let table = value
+            .as_object_mut()
+            .ok_or_else(|| "Config is not a table".to_owned())?;
+        let mut sources = table.remove("sources").unwrap_or(Value::Null);
+        let mut transforms = table.remove("transforms").unwrap_or(Value::Null);
+        let mut sinks = table.remove("sinks").unwrap_or(Value::Null);
+        strip_components::<SourceOuter>(&mut sources);
+        strip_components::<TransformOuter>(&mut transforms);
+        strip_components::<SinkOuter>(&mut sinks);
+
+        value = without_defaults::<ConfigBuilder>(value);
+        if let Some(table) = value.as_object_mut() {
+            for (key, components) in vec![
+                ("sources", sources),
+                ("transforms", transforms),
+                ("sinks", sinks),
+            ] {
+                if components.as_object().map_or(false, |c| !c.is_empty()) {
+                    table.insert(key.to_owned(), components);
+                }
+            }
+        }
+    }
+
+    Ok(without_nulls(value))
+}
+
+fn serialize(value: &Value, format: Format) -> Result<String, String> {
+    match format {
+        Format::Toml => toml::Value::try_from(value)
+            .and_then(|value| toml::to_string(&value))
+            .map_err(|error| error.to_string()),
+        Format::Json => serde_json::to_string_pretty(value)
+            .map(|json| json + "\n")
+            .map_err(|error| error.to_string()),
+        Format::Yaml => serde_yaml::to_string(value).map_err(|error| error.to_string()),
+    }
+}
+
+pub fn cmd(opts: &Opts) -> ExitCode {
+    let paths = config::merge_path_lists(vec![(&opts.paths, None)]);
+    let builder = match merge(&paths) {
+        Ok(builder) => builder,
+        Err(errors) => {
+            errors
+                .iter()
+                .for_each(|error| eprintln!("{}", secret::redact(error).red()));
+            return exitcode::CONFIG;
+        }
+    };
+
+    let resolved = resolve(&builder, opts.include_defaults).map(|mut value| {
+        secret::hide_sensitive(&mut value);
+        value
+    });
+    match resolved.and_then(|value| serialize(&value, opts.format)) {
+        // Components are configured with the values of their secrets, which
+        // may also be quoted by options that aren't sensitive.
+        Ok(merged) => {
+            print!("{}", secret::redact(&merged));
+            exitcode::OK
+        }
+        Err(error) => {
+            eprintln!("{}", format!("Failed to serialize config: {}", error).red());
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sources-stdin", feature = "sinks-blackhole"))]
+mod tests {
+    use super::*;
+    use crate::config::format;
+    use serde_json::json;
+
+    fn builder(config: &str) -> ConfigBuilder {
+        format::deserialize(config, Some(Format::Toml)).unwrap()
+    }
+
+    #[test]
+    fn leaves_out_defaults() {
+        let builder = builder(
+            r#"
+            timezone = "UTC"
+
+            [sources.in]
+            type = "stdin"
+
+            [sinks.out]
+            type = "blackhole"
+            inputs = ["in"]
+            print_amount = 10
+            "#,
+        );
+
+        assert_eq!(
+            resolve(&builder, false).unwrap(),
+            json!({
+                "timezone": "UTC",
+                "sources": { "in": { "type": "stdin" } },
+                "sinks": { "out": { "type": "blackhole", "inputs": ["in"], "print_amount": 10 } },
+            })
+        );
+
+        let with_defaults = resolve(&builder, true).unwrap();
+        assert_eq!(with_defaults["data_dir"], json!("/var/lib/vector/"));
+        assert_eq!(with_defaults["sources"]["in"]["max_length"], json!(102400));
+    }
+
+    #[test]
+    fn serializes_every_format() {
+        let value = resolve(
+            &builder(
+                r#"
+                [sources.in]
+                type = "stdin"
+
+                [sinks.out]
+                type = "blackhole"
+                inputs = ["in"]
+                "#,
+            ),
+            false,
+        )
+        .unwrap();
+
+        for format in vec![Format::Toml, Format::Json, Format::Yaml] {
+            let serialized = serialize(&value, format).unwrap();
+            let parsed: ConfigBuilder = format::deserialize(&serialized, Some(format)).unwrap();
+            assert!(parsed.sinks.contains_key("out"));
+        }
+    }
+}
@@ -1,13 +1,11 @@
-use super::{builder::ConfigBuilder, validation, Config, TransformOuter};
-use indexmap::IndexMap;
+use super::{builder::ConfigBuilder, templating, validation, Config};
 
 pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<String>> {
     let mut errors = Vec::new();
 
+    templating::expand(&mut builder)?;
     expand_wildcards(&mut builder);
 
-    let expansions = expand_macros(&mut builder)?;
-
     let warnings = validation::warnings(&builder);
 
     if let Err(type_errors) = validation::check_shape(&builder) {
@@ -33,7 +31,6 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
                 sinks: builder.sinks,
                 transforms: builder.transforms,
                 tests: builder.tests,
-                expansions,
             },
             warnings,
         ))
@@ -42,49 +39,6 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
     }
 }
 
-/// Some component configs can act like macros and expand themselves into multiple replacement
-/// configs. Performs those expansions and records the relevant metadata.
-pub(super) fn expand_macros(
-    config: &mut ConfigBuilder,
-) -> Result<IndexMap<String, Vec<String>>, Vec<String>> {
-    let mut expanded_transforms = IndexMap::new();
-    let mut expansions = IndexMap::new();
-    let mut errors = Vec::new();
-
-    while let Some((k, mut t)) = config.transforms.pop() {
-        if let Some(expanded) = match t.inner.expand() {
-            Ok(e) => e,
-            Err(err) => {
-                errors.push(format!("failed to expand transform '{}': {}", k, err));
-                continue;
-            }
-        } {
-            let mut children = Vec::new();
-            for (name, child) in expanded {
-                let full_name = format!("{}.{}", k, name);
-                expanded_transforms.insert(
-                    full_name.clone(),
-                    TransformOuter {
-                        inputs: t.inputs.clone(),
-                        inner: child,
-                    },
-                );
-                children.push(full_name);
-            }
-            expansions.insert(k.clone(), children);
-        } else {
-            expanded_transforms.insert(k, t);
-        }
-    }
-    config.transforms = expanded_transforms;
-
-    if !errors.is_empty() {
-        Err(errors)
-    } else {
-        Ok(expansions)
-    }
-}
-
 /// Expand trailing `*` wildcards in input lists
 fn expand_wildcards(config: &mut ConfigBuilder) {
     let candidates = config
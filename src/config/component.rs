@@ -2,7 +2,8 @@ use snafu::Snafu;
 use std::marker::PhantomData;
 use toml::Value;
 
-use super::GenerateConfig;
+use super::{schema, GenerateConfig};
+use serde::de::DeserializeOwned;
 
 #[derive(Debug, Snafu, Clone, PartialEq)]
 pub enum ExampleError {
@@ -17,6 +18,7 @@ pub enum ExampleError {
 pub struct ComponentDescription<T: Sized> {
     pub type_str: &'static str,
     example_value: fn() -> Option<Value>,
+    schema: fn() -> serde_json::Value,
     component_type: PhantomData<T>,
 }
 
@@ -28,10 +30,11 @@ where
 {
     /// Creates a new component plugin description.
     /// Configuration example is generated by the `GenerateConfig` trait.
-    pub fn new<B: GenerateConfig>(type_str: &'static str) -> Self {
+    pub fn new<B: GenerateConfig + DeserializeOwned>(type_str: &'static str) -> Self {
         ComponentDescription {
             type_str,
             example_value: || Some(B::generate_config()),
+            schema: schema::component_config::<B>,
             component_type: PhantomData,
         }
     }
@@ -47,6 +50,17 @@ where
             .and_then(|t| (t.example_value)().ok_or(ExampleError::MissingExample))
     }
 
+    /// Returns the JSON Schema of every plugin registered of a type, sorted by
+    /// type.
+    pub fn schemas() -> Vec<(&'static str, serde_json::Value)> {
+        let mut schemas = Vec::new();
+        for definition in inventory::iter::<ComponentDescription<T>> {
+            schemas.push((definition.type_str, (definition.schema)()));
+        }
+        schemas.sort_unstable_by_key(|(type_str, _)| *type_str);
+        schemas
+    }
+
     /// Returns a sorted Vec of all plugins registered of a type.
     pub fn types() -> Vec<&'static str> {
         let mut types = Vec::new();
@@ -1,7 +1,9 @@
 use super::Config;
 use indexmap::IndexMap;
+use serde_json::Value;
 use std::collections::HashSet;
 
+#[derive(Debug)]
 pub struct ConfigDiff {
     pub sources: Difference,
     pub transforms: Difference,
@@ -14,10 +16,33 @@ impl ConfigDiff {
     }
 
     pub fn new(old: &Config, new: &Config) -> Self {
+        let mut sinks = Difference::new(&old.sinks, &new.sinks);
+        // Sinks only changed in the fields they can update while running are
+        // reconfigured rather than rebuilt.
+        let to_reconfigure = sinks
+            .to_change
+            .iter()
+            .filter(|&name| {
+                let reconfigurable = new.sinks[name].inner.reconfigurable_fields();
+                !reconfigurable.is_empty()
+                    && changed_fields(&old.sinks[name], &new.sinks[name])
+                        .iter()
+                        .all(|field| {
+                            reconfigurable.iter().any(|&reconfigurable| {
+                                field == reconfigurable
+                                    || field.starts_with(&format!("{}.", reconfigurable))
+                            })
+                        })
+            })
+            .cloned()
+            .collect::<HashSet<_>>();
+        sinks.to_change = &sinks.to_change - &to_reconfigure;
+        sinks.to_reconfigure = to_reconfigure;
+
         ConfigDiff {
             sources: Difference::new(&old.sources, &new.sources),
             transforms: Difference::new(&old.transforms, &new.transforms),
-            sinks: Difference::new(&old.sinks, &new.sinks),
+            sinks,
         }
     }
 
@@ -30,9 +55,13 @@ impl ConfigDiff {
     }
 }
 
+#[derive(Debug)]
 pub struct Difference {
     pub to_remove: HashSet<String>,
     pub to_change: HashSet<String>,
+    /// Changed, but only in fields that can be updated without rebuilding the
+    /// component.
+    pub to_reconfigure: HashSet<String>,
     pub to_add: HashSet<String>,
 }
 
@@ -63,6 +92,7 @@ impl Difference {
         Self {
             to_remove,
             to_change,
+            to_reconfigure: HashSet::new(),
             to_add,
         }
     }
@@ -84,3 +114,113 @@ impl Difference {
         self.to_change.iter().chain(self.to_remove.iter())
     }
 }
+
+/// The dotted paths of the fields that differ between the serialized
+/// configurations of a component.
+fn changed_fields<C: serde::Serialize>(old: &C, new: &C) -> Vec<String> {
+    fn walk(path: &str, old: &Value, new: &Value, changed: &mut Vec<String>) {
+        match (old, new) {
+            (Value::Object(old), Value::Object(new)) => {
+                let keys = old
+                    .keys()
+                    .chain(new.keys().filter(|key| !old.contains_key(*key)));
+                for key in keys {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", path, key)
+                    };
+                    walk(
+                        &path,
+                        old.get(key).unwrap_or(&Value::Null),
+                        new.get(key).unwrap_or(&Value::Null),
+                        changed,
+                    );
+                }
+            }
+            (old, new) if old != new => changed.push(path.to_owned()),
+            _ => (),
+        }
+    }
+
+    let mut changed = Vec::new();
+    walk(
+        "",
+        &serde_json::to_value(old).unwrap(),
+        &serde_json::to_value(new).unwrap(),
+        &mut changed,
+    );
+    changed
+}
+
+#[cfg(all(test, feature = "sources-stdin", feature = "sinks-file"))]
+mod tests {
+    use super::*;
+    use crate::config::{self, Format};
+
+    fn load(sink: &str) -> Config {
+        config::load_from_str(
+            &format!(
+                r#"
+                [sources.in]
+                type = "stdin"
+
+                [sinks.out]
+                type = "file"
+                inputs = ["in"]
+                {}
+                "#,
+                sink
+            ),
+            Some(Format::Toml),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn lists_changed_fields() {
+        let old = serde_json::json!({ "a": 1, "b": { "c": 2, "d": 3 } });
+        let new = serde_json::json!({ "a": 1, "b": { "c": 4 }, "e": 5 });
+
+        assert_eq!(changed_fields(&old, &new), vec!["b.c", "b.d", "e"]);
+    }
+
+    #[test]
+    fn reconfigures_sinks_in_place() {
+        let old = load(
+            r#"path = "/tmp/a.log"
+                encoding = "text""#,
+        );
+        let new = load(
+            r#"path = "/tmp/b.log"
+                encoding = "json""#,
+        );
+
+        let diff = ConfigDiff::new(&old, &new);
+        assert!(diff.sinks.to_change.is_empty());
+        assert_eq!(
+            diff.sinks.to_reconfigure,
+            vec!["out".to_owned()].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn rebuilds_sinks_with_other_changes() {
+        let old = load(
+            r#"path = "/tmp/a.log"
+                encoding = "text""#,
+        );
+        let new = load(
+            r#"path = "/tmp/b.log"
+                encoding = "text"
+                compression = "gzip""#,
+        );
+
+        let diff = ConfigDiff::new(&old, &new);
+        assert!(diff.sinks.to_reconfigure.is_empty());
+        assert_eq!(
+            diff.sinks.to_change,
+            vec!["out".to_owned()].into_iter().collect()
+        );
+    }
+}
@@ -37,6 +37,22 @@ impl Format {
     }
 }
 
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "toml" => Ok(Format::Toml),
+            "json" => Ok(Format::Json),
+            "yaml" => Ok(Format::Yaml),
+            s => Err(format!(
+                "{} is not a valid option, expected `toml`, `json` or `yaml`",
+                s
+            )),
+        }
+    }
+}
+
 /// Parse the string represented in the specified format.
 /// If the format is unknown - fallback to the default format and attempt
 /// parsing using that.
@@ -51,6 +67,91 @@ where
     }
 }
 
+/// Like `deserialize`, but every string value of the parsed document is
+/// replaced by `map` before it is deserialized, so the replacements never
+/// need to be escaped for the format. Keys are left as they are.
+pub fn deserialize_mapping_strings<T, F>(
+    content: &str,
+    format: FormatHint,
+    mut map: F,
+) -> Result<T, Vec<String>>
+where
+    T: de::DeserializeOwned,
+    F: FnMut(String) -> String,
+{
+    match format.unwrap_or_default() {
+        Format::Toml => toml::from_str(content)
+            .map(|value| map_toml(value, &mut map))
+            .and_then(toml::Value::try_into)
+            .map_err(|e| vec![e.to_string()]),
+        Format::Yaml => serde_yaml::from_str(content)
+            .map(|value| map_yaml(value, &mut map))
+            .and_then(serde_yaml::from_value)
+            .map_err(|e| vec![e.to_string()]),
+        Format::Json => serde_json::from_str(content)
+            .map(|value| map_json(value, &mut map))
+            .and_then(serde_json::from_value)
+            .map_err(|e| vec![e.to_string()]),
+    }
+}
+
+fn map_toml(value: toml::Value, map: &mut impl FnMut(String) -> String) -> toml::Value {
+    match value {
+        toml::Value::String(string) => toml::Value::String(map(string)),
+        toml::Value::Array(values) => toml::Value::Array(
+            values
+                .into_iter()
+                .map(|value| map_toml(value, map))
+                .collect(),
+        ),
+        toml::Value::Table(table) => toml::Value::Table(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, map_toml(value, map)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn map_yaml(value: serde_yaml::Value, map: &mut impl FnMut(String) -> String) -> serde_yaml::Value {
+    match value {
+        serde_yaml::Value::String(string) => serde_yaml::Value::String(map(string)),
+        serde_yaml::Value::Sequence(values) => serde_yaml::Value::Sequence(
+            values
+                .into_iter()
+                .map(|value| map_yaml(value, map))
+                .collect(),
+        ),
+        serde_yaml::Value::Mapping(mapping) => serde_yaml::Value::Mapping(
+            mapping
+                .into_iter()
+                .map(|(key, value)| (key, map_yaml(value, map)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn map_json(value: serde_json::Value, map: &mut impl FnMut(String) -> String) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(string) => serde_json::Value::String(map(string)),
+        serde_json::Value::Array(values) => serde_json::Value::Array(
+            values
+                .into_iter()
+                .map(|value| map_json(value, map))
+                .collect(),
+        ),
+        serde_json::Value::Object(object) => serde_json::Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| (key, map_json(value, map)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,10 @@
-use super::{builder::ConfigBuilder, format, validation, vars, Config, Format, FormatHint};
+use super::{builder::ConfigBuilder, format, secret, validation, vars, Config, Format, FormatHint};
 use crate::signal;
 use glob::glob;
+use indexmap::IndexMap;
 use lazy_static::lazy_static;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs::File,
     path::{Path, PathBuf},
     sync::Mutex,
@@ -152,17 +153,35 @@ fn load_from_inputs(
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
 
+    // Secret backends may be declared in any of the inputs, so all of them are
+    // read before secrets are interpolated.
+    let mut contents = Vec::new();
+    let mut backends = IndexMap::new();
     for (input, format) in inputs {
-        if let Err(errs) = load(input, format).and_then(|(n, mut warn)| {
+        match read(input).and_then(|(content, mut warn)| {
             warnings.append(&mut warn);
-            config.append(n)
+            secret::backends(&content, format).map(|found| (content, found))
         }) {
+            Ok((content, found)) => {
+                backends.extend(found);
+                contents.push((content, format));
+            }
+            Err(errs) => errors.extend(errs),
+        }
+    }
+
+    let mut resolved = HashSet::new();
+    for (content, format) in contents {
+        if let Err(errs) = secret::deserialize(&content, format, &backends, &mut resolved)
+            .and_then(|n| config.append(n))
+        {
             // TODO: add back paths
             errors.extend(errs.iter().map(|e| e.to_string()));
         }
     }
 
     if errors.is_empty() {
+        secret::set_resolved(resolved);
         Ok((config, warnings))
     } else {
         Err(errors)
@@ -185,9 +204,29 @@ fn open_config(path: &Path) -> Option<File> {
 }
 
 pub fn load(
-    mut input: impl std::io::Read,
+    input: impl std::io::Read,
+    format: FormatHint,
+) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
+    let (content, warnings) = read(input)?;
+    let backends = secret::backends(&content, format)?;
+    let mut resolved = HashSet::new();
+    let builder = secret::deserialize(&content, format, &backends, &mut resolved)?;
+    secret::set_resolved(resolved);
+    Ok((builder, warnings))
+}
+
+/// Like `load`, but leaves `SECRET[...]` placeholders as they are, so no
+/// secret backend is queried.
+pub fn load_without_secrets(
+    input: impl std::io::Read,
     format: FormatHint,
 ) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
+    let (content, warnings) = read(input)?;
+    format::deserialize(&content, format).map(|builder| (builder, warnings))
+}
+
+/// Reads `input`, interpolating environment variables.
+fn read(mut input: impl std::io::Read) -> Result<(String, Vec<String>), Vec<String>> {
     let mut source_string = String::new();
     input
         .read_to_string(&mut source_string)
@@ -199,7 +238,5 @@ pub fn load(
             vars.insert("HOSTNAME".into(), hostname);
         }
     }
-    let (with_vars, warnings) = vars::interpolate(&source_string, &vars);
-
-    format::deserialize(&with_vars, format).map(|builder| (builder, warnings))
+    Ok(vars::interpolate(&source_string, &vars))
 }
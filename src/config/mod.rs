@@ -1,14 +1,20 @@
 use crate::{
     buffers::Acker,
-    conditions,
-    event::Metric,
+    conditions, dead_letter,
+    event::{Event, Metric},
     shutdown::ShutdownSignal,
     sinks::{self, util::UriSerde},
     sources, transforms, Pipeline,
 };
 use async_trait::async_trait;
+use bytes::Bytes;
 use component::ComponentDescription;
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
 use indexmap::IndexMap; // IndexMap preserves insertion order, allowing us to output errors in the same order they are present in the file
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use shared::TimeZone;
 use snafu::{ResultExt, Snafu};
@@ -18,28 +24,33 @@ use std::fs::DirBuilder;
 use std::hash::Hash;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use tokio::sync::watch;
 
 pub mod api;
 mod builder;
+pub mod cmd;
 mod compiler;
 pub mod component;
 mod diff;
 pub mod format;
 mod loading;
 pub mod provider;
+pub mod schema;
+pub mod secret;
+pub mod templating;
 mod unit_test;
 mod validation;
 mod vars;
 pub mod watcher;
 
 pub use builder::ConfigBuilder;
-pub use diff::ConfigDiff;
+pub use diff::{ConfigDiff, Difference};
 pub use format::{Format, FormatHint};
 pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider, load_from_str,
-    merge_path_lists, process_paths, CONFIG_PATHS,
+    load_without_secrets, merge_path_lists, process_paths, CONFIG_PATHS,
 };
-pub use unit_test::build_unit_tests_main as build_unit_tests;
+pub use unit_test::{build_unit_tests_main as build_unit_tests, TestSnapshot};
 pub use validation::warnings;
 pub use vector_core::config::{log_schema, LogSchema};
 
@@ -60,7 +71,7 @@ pub fn init_log_schema(
     )
 }
 
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct Config {
     pub global: GlobalOptions,
     #[cfg(feature = "api")]
@@ -70,7 +81,24 @@ pub struct Config {
     pub sinks: IndexMap<String, SinkOuter>,
     pub transforms: IndexMap<String, TransformOuter>,
     tests: Vec<TestDefinition>,
-    expansions: IndexMap<String, Vec<String>>,
+}
+
+// Components are configured with the values of their secrets, so those
+// must be redacted.
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Config");
+        debug.field("global", &self.global);
+        #[cfg(feature = "api")]
+        debug.field("api", &self.api);
+        debug
+            .field("healthchecks", &self.healthchecks)
+            .field("sources", &secret::Redacted(&self.sources))
+            .field("sinks", &secret::Redacted(&self.sinks))
+            .field("transforms", &secret::Redacted(&self.transforms))
+            .field("tests", &secret::Redacted(&self.tests))
+            .finish()
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
@@ -154,7 +182,7 @@ impl GlobalOptions {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema)]
 #[serde(default)]
 pub struct HealthcheckOptions {
     pub enabled: bool,
@@ -192,6 +220,13 @@ pub enum DataType {
 
 pub trait GenerateConfig {
     fn generate_config() -> toml::Value;
+
+    /// The JSON Schema of the config. Configs deriving `JsonSchema` should
+    /// return `schema::derive::<Self>()`, otherwise the schema is inferred
+    /// from the example.
+    fn generate_schema() -> serde_json::Value {
+        schema::infer(&Self::generate_config())
+    }
 }
 
 #[macro_export]
@@ -201,15 +236,23 @@ macro_rules! impl_generate_config_from_default {
             fn generate_config() -> toml::Value {
                 toml::Value::try_from(&Self::default()).unwrap()
             }
+
+            fn generate_schema() -> serde_json::Value {
+                $crate::config::schema::infer_defaults(&Self::generate_config())
+            }
         }
     };
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+// Components are configured with the values of their secrets, so those
+// must be redacted.
+#[derive(Derivative, Deserialize, Serialize)]
+#[derivative(Debug)]
 pub struct SourceOuter {
     #[serde(default = "default_acknowledgements")]
     pub acknowledgements: bool,
     #[serde(flatten)]
+    #[derivative(Debug(format_with = "secret::fmt_redacted"))]
     pub(super) inner: Box<dyn SourceConfig>,
 }
 
@@ -239,6 +282,14 @@ pub trait SourceConfig: core::fmt::Debug + Send + Sync {
     fn resources(&self) -> Vec<Resource> {
         Vec::new()
     }
+
+    /// Decodes a raw payload, such as a line or a request body, into the
+    /// events the source would emit for it. This is used by unit tests to
+    /// check the parsing of the source; `None` if the source doesn't support
+    /// it.
+    fn decode_payload(&self, _payload: Bytes) -> Option<crate::Result<Vec<Event>>> {
+        None
+    }
 }
 
 pub struct SourceContext {
@@ -285,7 +336,8 @@ pub type SourceDescription = ComponentDescription<Box<dyn SourceConfig>>;
 
 inventory::collect!(SourceDescription);
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Derivative, Deserialize, Serialize)]
+#[derivative(Debug)]
 pub struct SinkOuter {
     pub inputs: Vec<String>,
 
@@ -300,7 +352,16 @@ pub struct SinkOuter {
     #[serde(default)]
     pub buffer: crate::buffers::BufferConfig,
 
+    /// Whether copies of the events the sink rejects are sent to its
+    /// `dead_letter` output.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub dead_letter: bool,
+
     #[serde(flatten)]
+    #[derivative(Debug(format_with = "secret::fmt_redacted"))]
     pub inner: Box<dyn SinkConfig>,
 }
 
@@ -308,6 +369,7 @@ impl SinkOuter {
     pub fn new(inputs: Vec<String>, inner: Box<dyn SinkConfig>) -> Self {
         SinkOuter {
             buffer: Default::default(),
+            dead_letter: false,
             healthcheck: SinkHealthcheckOptions::default(),
             healthcheck_uri: None,
             inner,
@@ -315,6 +377,12 @@ impl SinkOuter {
         }
     }
 
+    /// The name of the output of the sink with the events it rejected, if enabled.
+    pub fn dead_letter_output(&self, name: &str) -> Option<String> {
+        self.dead_letter
+            .then(|| output_name(name, dead_letter::DEAD_LETTER))
+    }
+
     pub fn resources(&self, name: &str) -> Vec<Resource> {
         let mut resources = self.inner.resources();
         resources.append(&mut self.buffer.resources(name));
@@ -338,10 +406,11 @@ impl SinkOuter {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(default)]
 pub struct SinkHealthcheckOptions {
     pub enabled: bool,
+    #[schemars(with = "Option<String>")]
     pub uri: Option<UriSerde>,
 }
 
@@ -385,6 +454,26 @@ pub trait SinkConfig: core::fmt::Debug + Send + Sync {
     fn resources(&self) -> Vec<Resource> {
         Vec::new()
     }
+
+    /// Options, as dotted paths, that the running sink can update from
+    /// `SinkContext::reconfigurations`. A sink changed only in these options
+    /// is reconfigured in place on reload, keeping its buffer and connections,
+    /// rather than rebuilt.
+    ///
+    /// Batch settings can be passed on to `BatchSink::with_reconfigurations`.
+    /// Request settings are baked into the tower service stack when the sink
+    /// is built, so changing them always rebuilds the sink.
+    fn reconfigurable_fields(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Encodes events into the bytes the sink would write for them, or the
+    /// body of the request it would send, without connecting anywhere. This is
+    /// used by unit tests to check the output of the sink; `None` if the sink
+    /// doesn't support it.
+    async fn encode_payload(&self, _events: Vec<Event>) -> Option<crate::Result<Vec<u8>>> {
+        None
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -392,6 +481,7 @@ pub struct SinkContext {
     pub(super) acker: Acker,
     pub(super) healthcheck: SinkHealthcheckOptions,
     pub(super) globals: GlobalOptions,
+    pub(super) reconfigure: watch::Receiver<serde_json::Value>,
 }
 
 impl SinkContext {
@@ -401,6 +491,7 @@ impl SinkContext {
             acker: Acker::Null,
             healthcheck: SinkHealthcheckOptions::default(),
             globals: GlobalOptions::default(),
+            reconfigure: watch::channel(serde_json::Value::Null).1,
         }
     }
 
@@ -411,19 +502,82 @@ impl SinkContext {
     pub fn globals(&self) -> &GlobalOptions {
         &self.globals
     }
+
+    /// The configurations the sink is reconfigured with, as they are reloaded.
+    ///
+    /// The stream ends once the topology drops the sink's reconfiguration
+    /// channel, such as when the sink is removed or rebuilt, and is fused so
+    /// it can be polled again after that.
+    pub fn reconfigurations<C>(&self) -> BoxStream<'static, C>
+    where
+        C: serde::de::DeserializeOwned + Send + 'static,
+    {
+        stream::unfold(self.reconfigure.clone(), |mut reconfigure| async move {
+            reconfigure.changed().await.ok()?;
+            let config = reconfigure.borrow().clone();
+            Some((config, reconfigure))
+        })
+        .filter_map(|mut config| async move {
+            // Sink configurations are tagged with their type.
+            if let Some(config) = config.as_object_mut() {
+                config.remove("type");
+            }
+            serde_json::from_value(config)
+                .map_err(|error| error!(message = "Invalid sink reconfiguration.", %error))
+                .ok()
+        })
+        .fuse()
+        .boxed()
+    }
 }
 
 pub type SinkDescription = ComponentDescription<Box<dyn SinkConfig>>;
 
 inventory::collect!(SinkDescription);
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Derivative, Deserialize, Serialize)]
+#[derivative(Debug)]
 pub struct TransformOuter {
     pub inputs: Vec<String>,
     #[serde(flatten)]
+    #[derivative(Debug(format_with = "secret::fmt_redacted"))]
     pub inner: Box<dyn TransformConfig>,
 }
 
+impl TransformOuter {
+    /// The full names of the named outputs of the transform, with the type of
+    /// their events.
+    pub fn named_outputs(&self, name: &str) -> Vec<(String, DataType)> {
+        self.inner
+            .named_outputs()
+            .into_iter()
+            .map(|output| (output_name(name, &output.name), output.data_type))
+            .collect()
+    }
+}
+
+/// An output of a transform besides its default one, which other components
+/// consume as `<transform>.<output>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Output {
+    pub name: String,
+    pub data_type: DataType,
+}
+
+impl Output {
+    pub fn new(name: impl Into<String>, data_type: DataType) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+        }
+    }
+}
+
+/// Full name of the named `output` of component `name`, as used in `inputs`.
+pub fn output_name(name: &str, output: &str) -> String {
+    format!("{}.{}", name, output)
+}
+
 #[async_trait]
 #[typetag::serde(tag = "type")]
 pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
@@ -435,11 +589,10 @@ pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone
 
     fn transform_type(&self) -> &'static str;
 
-    /// Allows a transform configuration to expand itself into multiple "child"
-    /// transformations to replace it. This allows a transform to act as a macro
-    /// for various patterns.
-    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
-        Ok(None)
+    /// The outputs of the transform besides its default one, to which it
+    /// sends events through `FunctionTransform::transform_with_outputs`.
+    fn named_outputs(&self) -> Vec<Output> {
+        Vec::new()
     }
 }
 
@@ -546,6 +699,10 @@ pub struct TestDefinition {
     pub outputs: Vec<TestOutput>,
     #[serde(default)]
     pub no_outputs_from: Vec<String>,
+    /// Log fields left out of the snapshot of the test, for values that
+    /// change between runs such as the hostname added by a source.
+    #[serde(default)]
+    pub snapshot_ignore: Vec<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -566,6 +723,9 @@ pub struct TestInput {
     pub value: Option<String>,
     pub log_fields: Option<IndexMap<String, TestInputValue>>,
     pub metric: Option<Metric>,
+    /// Milliseconds the simulated clock of the test advances by, after the
+    /// previous input, before this input is inserted.
+    pub delay_ms: Option<u64>,
 }
 
 fn default_test_input_type() -> String {
@@ -577,6 +737,8 @@ fn default_test_input_type() -> String {
 pub struct TestOutput {
     pub extract_from: String,
     pub conditions: Option<Vec<conditions::AnyCondition>>,
+    /// The exact bytes a sink is expected to write for the events it receives.
+    pub payload: Option<String>,
 }
 
 impl Config {
@@ -584,14 +746,38 @@ impl Config {
         Default::default()
     }
 
-    /// Expand a logical component name (i.e. from the config file) into the names of the
-    /// components it was expanded to as part of the macro process. Does not check that the
-    /// identifier is otherwise valid.
-    pub fn get_inputs(&self, identifier: &str) -> Vec<String> {
-        self.expansions
-            .get(identifier)
-            .cloned()
-            .unwrap_or_else(|| vec![String::from(identifier)])
+    /// The full names of the named outputs of component `name`, including the
+    /// dead-letter output of a sink.
+    pub fn named_outputs(&self, name: &str) -> Vec<String> {
+        let transform_outputs = self.transforms.get(name).map(|transform| {
+            transform
+                .named_outputs(name)
+                .into_iter()
+                .map(|(output, _)| output)
+                .collect()
+        });
+        let sink_outputs = self
+            .sinks
+            .get(name)
+            .map(|sink| sink.dead_letter_output(name).into_iter().collect());
+        transform_outputs.or(sink_outputs).unwrap_or_default()
+    }
+
+    /// The name of the component an input refers to, which is the input itself
+    /// unless it's a named output.
+    pub fn input_component<'a>(&'a self, input: &'a str) -> Option<&'a str> {
+        if self.sources.contains_key(input) || self.transforms.contains_key(input) {
+            return Some(input);
+        }
+        self.transforms
+            .keys()
+            .chain(self.sinks.keys())
+            .find(|name| {
+                self.named_outputs(name)
+                    .iter()
+                    .any(|output| output == input)
+            })
+            .map(String::as_str)
     }
 }
 
@@ -0,0 +1,403 @@
+//! JSON Schema for configuration files, covering every registered component.
+//!
+//! Components deriving `JsonSchema` describe every option of their config, with
+//! the variants and defaults serde accepts. The schemas of the other components
+//! are inferred from the examples of `GenerateConfig`. Examples don't show
+//! every option, so inferred objects allow other properties: the schema helps
+//! editors complete and document configs, but a config matching it may still
+//! be invalid.
+
+use super::{
+    default_data_dir, GenerateConfig, HealthcheckOptions, LogSchema, SinkDescription,
+    SinkHealthcheckOptions, SourceDescription, TransformDescription,
+};
+use crate::{buffers::BufferConfig, sinks::util::Compression};
+use schemars::{gen::SchemaSettings, JsonSchema};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
+
+/// The schema of `T`, derived from its type and serde attributes.
+pub fn derive<T: JsonSchema>() -> Value {
+    let generator = SchemaSettings::draft07()
+        .with(|settings| {
+            settings.inline_subschemas = true;
+            settings.meta_schema = None;
+        })
+        .into_generator();
+    let mut schema = json!(generator.into_root_schema_for::<T>());
+    // The title is the name of the Rust type, which means nothing in a config.
+    if let Some(schema) = schema.as_object_mut() {
+        schema.remove("title");
+    }
+    schema
+}
+
+/// The schema of a value shaped like `example`, giving its values as examples.
+pub fn infer(example: &toml::Value) -> Value {
+    infer_with(example, "examples")
+}
+
+/// The schema of a value shaped like `default`, giving its values as defaults.
+pub fn infer_defaults(default: &toml::Value) -> Value {
+    infer_with(default, "default")
+}
+
+fn infer_with(value: &toml::Value, annotation: &str) -> Value {
+    let annotate = |mut schema: Value| {
+        let value = json!(value);
+        schema[annotation] = if annotation == "examples" {
+            json!([value])
+        } else {
+            value
+        };
+        schema
+    };
+
+    match value {
+        toml::Value::String(_) => annotate(json!({ "type": "string" })),
+        toml::Value::Integer(_) => annotate(json!({ "type": "integer" })),
+        toml::Value::Float(_) => annotate(json!({ "type": "number" })),
+        toml::Value::Boolean(_) => annotate(json!({ "type": "boolean" })),
+        toml::Value::Datetime(_) => annotate(json!({ "type": "string", "format": "date-time" })),
+        toml::Value::Array(values) => {
+            let mut schema = annotate(json!({ "type": "array" }));
+            if let Some(first) = values.first() {
+                schema["items"] = infer_with(first, "examples");
+            }
+            schema
+        }
+        toml::Value::Table(table) => {
+            let properties = table
+                .iter()
+                .map(|(key, value)| (key.clone(), infer_with(value, annotation)))
+                .collect::<Map<_, _>>();
+            json!({
+                "type": "object",
+                "properties": properties,
+                "additionalProperties": true,
+            })
+        }
+    }
+}
+
+/// The schema of the component configured by `T`.
+///
+/// The shape of `compression` can't be inferred from an example, and most
+/// examples leave it out, so it's added to the schema of every component
+/// which accepts a valid algorithm but rejects an invalid one.
+pub(super) fn component_config<T: GenerateConfig + DeserializeOwned>() -> Value {
+    let example = T::generate_config();
+    let accepts_compression = |algorithm: &str| match example.clone() {
+        toml::Value::Table(mut table) => {
+            table.insert("compression".to_owned(), algorithm.into());
+            toml::Value::Table(table).try_into::<T>().is_ok()
+        }
+        _ => false,
+    };
+
+    let schema = T::generate_schema();
+    if accepts_compression("gzip") && !accepts_compression("not_an_algorithm") {
+        with_property(schema, "compression", Compression::schema())
+    } else {
+        schema
+    }
+}
+
+/// The schema of one of `variants`, using their serialized representation.
+pub fn enumeration<T: Serialize>(variants: &[T], default: Option<T>) -> Value {
+    let mut schema = json!({ "enum": variants });
+    if let Some(default) = default {
+        schema["default"] = json!(default);
+    }
+    schema
+}
+
+/// Adds or replaces the schema of the `name` property of an object schema.
+pub fn with_property(mut schema: Value, name: &str, property: Value) -> Value {
+    schema["properties"][name] = property;
+    schema
+}
+
+fn object(properties: Value) -> Value {
+    json!({ "type": "object", "properties": properties })
+}
+
+/// Adds the `type` of a component, which sets its schema apart from those of
+/// the other components of its kind.
+fn component(type_str: &str, schema: Value, common: &Value) -> Value {
+    let mut schema = with_property(schema, "type", json!({ "const": type_str }));
+    if let (Some(properties), Some(common)) =
+        (schema["properties"].as_object_mut(), common.as_object())
+    {
+        for (name, property) in common {
+            properties.insert(name.clone(), property.clone());
+        }
+    }
+    schema["required"] = json!(["type"]);
+    schema
+}
+
+fn components(schemas: Vec<(&'static str, Value)>, common: Value) -> Value {
+    let schemas = schemas
+        .into_iter()
+        .map(|(type_str, schema)| component(type_str, schema, &common))
+        .collect::<Vec<_>>();
+    json!({
+        "type": "object",
+        "additionalProperties": { "oneOf": schemas },
+    })
+}
+
+fn inputs() -> Value {
+    json!({
+        "type": "array",
+        "items": { "type": "string" },
+        "minItems": 1,
+    })
+}
+
+fn secret_backends() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": {
+            "oneOf": [
+                {
+                    "type": "object",
+                    "properties": {
+                        "type": { "const": "directory" },
+                        "path": { "type": "string" },
+                    },
+                    "required": ["type", "path"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "type": { "const": "exec" },
+                        "command": { "type": "array", "items": { "type": "string" }, "minItems": 1 },
+                        "timeout_secs": { "type": "integer", "minimum": 0, "default": 5 },
+                    },
+                    "required": ["type", "command"],
+                    "additionalProperties": false,
+                },
+            ],
+        },
+    })
+}
+
+fn component_tables() -> Value {
+    let tables = json!({ "type": "object", "additionalProperties": { "type": "object" } });
+    object(json!({
+        "sources": tables,
+        "transforms": tables,
+        "sinks": tables,
+    }))
+}
+
+fn instances() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": {
+            "type": "object",
+            "properties": {
+                "template": { "type": "string" },
+                "params": { "type": "object" },
+            },
+            "required": ["template"],
+            "additionalProperties": false,
+        },
+    })
+}
+
+/// The JSON Schema of configuration files.
+pub fn generate() -> Value {
+    let sources = components(
+        SourceDescription::schemas(),
+        json!({ "acknowledgements": { "type": "boolean", "default": false } }),
+    );
+    let transforms = components(
+        TransformDescription::schemas(),
+        json!({ "inputs": inputs() }),
+    );
+    let sinks = components(
+        SinkDescription::schemas(),
+        json!({
+            "inputs": inputs(),
+            "buffer": BufferConfig::schema(),
+            "healthcheck": {
+                "oneOf": [
+                    { "type": "boolean" },
+                    derive::<SinkHealthcheckOptions>(),
+                ],
+            },
+            "dead_letter": { "type": "boolean", "default": false },
+        }),
+    );
+    #[cfg_attr(not(feature = "api"), allow(unused_mut))]
+    let mut properties = json!({
+        "data_dir": {
+            "type": "string",
+            "default": default_data_dir(),
+        },
+        "log_schema": derive::<LogSchema>(),
+        "timezone": { "type": "string", "default": "local" },
+        "healthchecks": derive::<HealthcheckOptions>(),
+        "secret": secret_backends(),
+        "sources": sources,
+        "transforms": transforms,
+        "sinks": sinks,
+        "templates": component_tables(),
+        "instances": instances(),
+        "overlays": component_tables(),
+        "tests": { "type": "array", "items": { "type": "object" } },
+    });
+    #[cfg(feature = "api")]
+    {
+        properties["api"] = derive::<super::api::Options>();
+    }
+
+    let mut schema = object(properties);
+    schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+    schema["title"] = json!("Vector configuration");
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infers_schema_from_example() {
+        let example = toml::from_str(
+            r#"
+            address = "0.0.0.0:9000"
+            max_length = 102400
+            tags = ["a", "b"]
+
+            [keepalive]
+            time_secs = 60
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            infer(&example),
+            json!({
+                "type": "object",
+                "properties": {
+                    "address": { "type": "string", "examples": ["0.0.0.0:9000"] },
+                    "max_length": { "type": "integer", "examples": [102400] },
+                    "tags": {
+                        "type": "array",
+                        "examples": [["a", "b"]],
+                        "items": { "type": "string", "examples": ["a"] },
+                    },
+                    "keepalive": {
+                        "type": "object",
+                        "properties": {
+                            "time_secs": { "type": "integer", "examples": [60] },
+                        },
+                        "additionalProperties": true,
+                    },
+                },
+                "additionalProperties": true,
+            })
+        );
+    }
+
+    #[test]
+    fn describes_enumerations_with_serde_names() {
+        use vector_core::buffers::WhenFull;
+
+        assert_eq!(
+            enumeration(
+                &[WhenFull::Block, WhenFull::DropNewest],
+                Some(WhenFull::Block)
+            ),
+            json!({ "enum": ["block", "drop_newest"], "default": "block" })
+        );
+    }
+
+    #[test]
+    fn derives_variants_and_defaults() {
+        #[derive(serde::Deserialize, JsonSchema)]
+        #[serde(tag = "mode", rename_all = "snake_case")]
+        #[allow(dead_code)]
+        enum Mode {
+            Tcp {
+                #[serde(default = "default_port")]
+                port: u16,
+            },
+            UnixStream {
+                path: String,
+            },
+        }
+
+        fn default_port() -> u16 {
+            9000
+        }
+
+        let schema = derive::<Mode>();
+        let variants = schema["oneOf"].as_array().unwrap();
+        let modes = variants
+            .iter()
+            .map(|variant| variant["properties"]["mode"]["enum"][0].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(modes, vec![json!("tcp"), json!("unix_stream")]);
+        assert_eq!(variants[0]["properties"]["port"]["default"], json!(9000));
+        assert!(schema.get("title").is_none());
+    }
+
+    #[test]
+    fn derives_defaults_of_options() {
+        let schema = derive::<HealthcheckOptions>();
+
+        assert_eq!(schema["properties"]["enabled"]["default"], json!(true));
+        assert_eq!(
+            schema["properties"]["require_healthy"]["default"],
+            json!(false)
+        );
+    }
+
+    #[cfg(all(feature = "sinks-http", feature = "sinks-console"))]
+    #[test]
+    fn describes_compression_of_sinks_accepting_it() {
+        let schema = generate();
+        let sinks = schema["properties"]["sinks"]["additionalProperties"]["oneOf"]
+            .as_array()
+            .unwrap();
+        let sink = |type_str: &str| {
+            sinks
+                .iter()
+                .find(|schema| schema["properties"]["type"]["const"] == type_str)
+                .unwrap()
+        };
+
+        assert_eq!(
+            sink("http")["properties"]["compression"],
+            Compression::schema()
+        );
+        assert!(sink("console")["properties"].get("compression").is_none());
+    }
+
+    #[test]
+    fn covers_every_component() {
+        let schema = generate();
+
+        for (kind, types) in vec![
+            ("sources", SourceDescription::types()),
+            ("transforms", TransformDescription::types()),
+            ("sinks", SinkDescription::types()),
+        ] {
+            let schemas = schema["properties"][kind]["additionalProperties"]["oneOf"]
+                .as_array()
+                .unwrap();
+            assert_eq!(schemas.len(), types.len());
+            for type_str in types {
+                assert!(schemas
+                    .iter()
+                    .any(|schema| schema["properties"]["type"]["const"] == type_str));
+            }
+        }
+    }
+}
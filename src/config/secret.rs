@@ -0,0 +1,521 @@
+//! Resolution of `SECRET[backend.key]` placeholders in configuration files.
+//!
+//! Secret backends are declared in the `secret` table of any of the loaded
+//! config files. Unlike environment variables, the placeholders are replaced
+//! in the string values of the parsed config files, so secrets don't need to be
+//! escaped for the format. The values resolved by the last loaded config are
+//! remembered so they can be redacted from anything we print about it.
+
+use super::{format, FormatHint};
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use snafu::{OptionExt, ResultExt, Snafu};
+use std::{
+    collections::HashSet,
+    fmt,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    sync::RwLock,
+    thread,
+    time::{Duration, Instant},
+};
+
+const REDACTED: &str = "**REDACTED**";
+
+/// The options which hold credentials in the configs of components, whatever
+/// the component. Their values may come from environment variables rather than
+/// secret backends, so they're hidden by name rather than by value.
+const SENSITIVE_FIELDS: &[&str] = &[
+    "api_key",
+    "connection_string",
+    "default_api_key",
+    "default_ingest_token",
+    "insert_key",
+    "key_pass",
+    "license_key",
+    "password",
+    "secret_access_key",
+    "shared_key",
+    "token",
+];
+
+lazy_static! {
+    static ref PLACEHOLDER: Regex = Regex::new(r"SECRET\[(\w+)\.([\w.\-]+)\]").unwrap();
+    static ref RESOLVED: RwLock<HashSet<String>> = RwLock::default();
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SecretBackend {
+    /// Every secret is a file, named after its key, in a directory.
+    Directory(DirectoryBackend),
+    /// Secrets are printed by a command, which is given the key as its last
+    /// argument.
+    Exec(ExecBackend),
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DirectoryBackend {
+    pub path: PathBuf,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExecBackend {
+    pub command: Vec<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+// None of these messages may contain a secret value.
+#[derive(Debug, Snafu)]
+pub enum SecretError {
+    #[snafu(display("invalid key {:?}", key))]
+    InvalidKey { key: String },
+    #[snafu(display("could not read {:?}: {}", path, source))]
+    ReadFile { path: PathBuf, source: io::Error },
+    #[snafu(display("command must not be empty"))]
+    EmptyCommand,
+    #[snafu(display("could not run {:?}: {}", command, source))]
+    Spawn { command: String, source: io::Error },
+    #[snafu(display("command timed out after {} seconds", timeout_secs))]
+    Timeout { timeout_secs: u64 },
+    #[snafu(display("command failed: {}", status))]
+    Failed { status: ExitStatus },
+    #[snafu(display("could not read command output: {}", source))]
+    ReadOutput { source: io::Error },
+    #[snafu(display("secret is not valid UTF-8"))]
+    NotUtf8,
+}
+
+impl SecretBackend {
+    pub fn retrieve(&self, key: &str) -> Result<String, SecretError> {
+        let value = match self {
+            SecretBackend::Directory(backend) => backend.retrieve(key)?,
+            SecretBackend::Exec(backend) => backend.retrieve(key)?,
+        };
+        Ok(value
+            .trim_end_matches(|c| c == '\n' || c == '\r')
+            .to_owned())
+    }
+}
+
+impl DirectoryBackend {
+    fn retrieve(&self, key: &str) -> Result<String, SecretError> {
+        // Keys must not escape the directory.
+        let mut components = Path::new(key).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(_)), None) => (),
+            _ => return InvalidKey { key }.fail(),
+        }
+
+        let path = self.path.join(key);
+        let bytes = std::fs::read(&path).context(ReadFile { path })?;
+        String::from_utf8(bytes).ok().context(NotUtf8)
+    }
+}
+
+impl ExecBackend {
+    fn retrieve(&self, key: &str) -> Result<String, SecretError> {
+        let (program, args) = self.command.split_first().context(EmptyCommand)?;
+        let mut child = Command::new(program)
+            .args(args)
+            .arg(key)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()
+            .context(Spawn { command: program })?;
+
+        // Read the output concurrently so a large secret can't fill the pipe
+        // and stall the command until it times out.
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let reader = thread::spawn(move || {
+            let mut output = Vec::new();
+            stdout.read_to_end(&mut output).map(|_| output)
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(self.timeout_secs);
+        let status = loop {
+            if let Some(status) = child.try_wait().context(ReadOutput)? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Timeout {
+                    timeout_secs: self.timeout_secs,
+                }
+                .fail();
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        if !status.success() {
+            return Failed { status }.fail();
+        }
+
+        let output = reader
+            .join()
+            .expect("Secret reader thread panicked.")
+            .context(ReadOutput)?;
+        String::from_utf8(output).ok().context(NotUtf8)
+    }
+}
+
+/// The secret backends declared in `content`, which is otherwise left to be
+/// deserialized as a `ConfigBuilder`.
+pub fn backends(
+    content: &str,
+    format: FormatHint,
+) -> Result<IndexMap<String, SecretBackend>, Vec<String>> {
+    #[derive(Deserialize)]
+    struct Backends {
+        #[serde(default)]
+        secret: IndexMap<String, SecretBackend>,
+    }
+
+    format::deserialize::<Backends>(content, format).map(|backends| backends.secret)
+}
+
+/// Deserializes `content`, replacing every `SECRET[backend.key]` in its string
+/// values by the secret retrieved from `backend`, which is added to `resolved`.
+pub fn deserialize<T: DeserializeOwned>(
+    content: &str,
+    format: FormatHint,
+    backends: &IndexMap<String, SecretBackend>,
+    resolved: &mut HashSet<String>,
+) -> Result<T, Vec<String>> {
+    if !PLACEHOLDER.is_match(content) {
+        return format::deserialize(content, format);
+    }
+
+    let mut retrieved = IndexMap::new();
+    let result = format::deserialize_mapping_strings(content, format, |string| {
+        interpolate(&string, backends, &mut retrieved)
+    });
+
+    let (values, errors): (Vec<_>, Vec<_>) = retrieved
+        .into_iter()
+        .map(|(_, result)| result)
+        .partition(Result::is_ok);
+    if !errors.is_empty() {
+        return Err(errors.into_iter().filter_map(Result::err).collect());
+    }
+
+    resolved.extend(
+        values
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|value| !value.is_empty()),
+    );
+
+    // Deserialization errors may quote the secrets.
+    result.map_err(|errors| {
+        errors
+            .iter()
+            .map(|error| redact_with(error, resolved))
+            .collect()
+    })
+}
+
+/// Replaces the secrets redacted by `redact` with those `resolved` by a newly
+/// loaded config, so secrets it no longer uses aren't remembered forever.
+pub fn set_resolved(resolved: HashSet<String>) {
+    *RESOLVED.write().expect("Secrets lock poisoned.") = resolved;
+}
+
+/// Replaces every placeholder in `input`, remembering what was retrieved so
+/// each secret is only retrieved once.
+fn interpolate(
+    input: &str,
+    backends: &IndexMap<String, SecretBackend>,
+    retrieved: &mut IndexMap<(String, String), Result<String, String>>,
+) -> String {
+    PLACEHOLDER
+        .replace_all(input, |caps: &Captures<'_>| {
+            let (name, key) = (&caps[1], &caps[2]);
+            retrieved
+                .entry((name.to_owned(), key.to_owned()))
+                .or_insert_with(|| match backends.get(name) {
+                    Some(backend) => backend.retrieve(key).map_err(|error| {
+                        format!(
+                            "Failed to retrieve secret {:?} from backend {:?}: {}",
+                            key, name, error
+                        )
+                    }),
+                    None => Err(format!(
+                        "Unknown secret backend in config. name = {:?}",
+                        name
+                    )),
+                })
+                .clone()
+                .unwrap_or_default()
+        })
+        .into_owned()
+}
+
+/// Replaces every secret value resolved by the last loaded config in `text`.
+pub fn redact(text: &str) -> String {
+    redact_with(text, &RESOLVED.read().expect("Secrets lock poisoned."))
+}
+
+/// Replaces every one of `secrets` in `text`.
+///
+/// Only whole occurrences are replaced: a secret starting or ending with a
+/// word character isn't redacted where it's part of a longer word, so short
+/// secrets don't mangle unrelated text.
+pub fn redact_with(text: &str, secrets: &HashSet<String>) -> String {
+    // Longest first, so a secret containing another is redacted whole.
+    let mut secrets = secrets.iter().collect::<Vec<_>>();
+    secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+    secrets
+        .into_iter()
+        .fold(text.to_owned(), |text, secret| redact_secret(&text, secret))
+}
+
+fn redact_secret(text: &str, secret: &str) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let starts_word = secret.chars().next().map_or(false, is_word);
+    let ends_word = secret.chars().next_back().map_or(false, is_word);
+
+    let mut redacted = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (start, _) in text.match_indices(secret) {
+        let end = start + secret.len();
+        if (starts_word && text[..start].chars().next_back().map_or(false, is_word))
+            || (ends_word && text[end..].chars().next().map_or(false, is_word))
+        {
+            continue;
+        }
+        redacted.push_str(&text[copied..start]);
+        redacted.push_str(REDACTED);
+        copied = end;
+    }
+    redacted.push_str(&text[copied..]);
+    redacted
+}
+
+/// Formats the `Debug` representation of a field with secrets redacted, for
+/// use with `#[derivative(Debug(format_with = "..."))]`.
+pub fn fmt_redacted<T: fmt::Debug>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(&Redacted(value), f)
+}
+
+/// Hides a field which is always sensitive from its `Debug` representation,
+/// for use with `#[derivative(Debug(format_with = "..."))]`.
+pub fn fmt_hidden<T>(_value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(REDACTED)
+}
+
+/// Hides the values of the sensitive options anywhere in `value`, the way
+/// `fmt_hidden` hides them from `Debug` representations.
+pub fn hide_sensitive(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(table) => {
+            for (key, value) in table {
+                if SENSITIVE_FIELDS.contains(&key.as_str()) && !value.is_object() {
+                    *value = REDACTED.into();
+                } else {
+                    hide_sensitive(value);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(hide_sensitive),
+        _ => (),
+    }
+}
+
+/// Formats the `Debug` representation of the value with secrets redacted.
+pub struct Redacted<'a, T>(pub &'a T);
+
+impl<T: fmt::Debug> fmt::Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let debug = if f.alternate() {
+            format!("{:#?}", self.0)
+        } else {
+            format!("{:?}", self.0)
+        };
+        f.write_str(&redact(&debug))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Format;
+    use std::collections::HashMap;
+
+    fn interpolate(
+        input: &str,
+        backends: &IndexMap<String, SecretBackend>,
+    ) -> Result<String, Vec<String>> {
+        interpolate_resolving(input, backends, &mut HashSet::new())
+    }
+
+    fn interpolate_resolving(
+        input: &str,
+        backends: &IndexMap<String, SecretBackend>,
+        resolved: &mut HashSet<String>,
+    ) -> Result<String, Vec<String>> {
+        let document = format!("value = {:?}", input);
+        deserialize::<HashMap<String, String>>(&document, Some(Format::Toml), backends, resolved)
+            .map(|mut values| values.remove("value").unwrap())
+    }
+
+    fn directory() -> (tempfile::TempDir, IndexMap<String, SecretBackend>) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("api_key"), "s3cr3t-api-key\n").unwrap();
+        let backends = vec![(
+            "files".to_owned(),
+            SecretBackend::Directory(DirectoryBackend {
+                path: dir.path().to_owned(),
+            }),
+        )]
+        .into_iter()
+        .collect();
+        (dir, backends)
+    }
+
+    #[test]
+    fn interpolates_directory_secrets() {
+        let (_dir, backends) = directory();
+        let mut resolved = HashSet::new();
+
+        assert_eq!(
+            interpolate_resolving("key SECRET[files.api_key]", &backends, &mut resolved).unwrap(),
+            "key s3cr3t-api-key"
+        );
+        assert_eq!(
+            redact_with("the key is s3cr3t-api-key", &resolved),
+            "the key is **REDACTED**"
+        );
+    }
+
+    #[test]
+    fn secrets_need_no_escaping() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("password"), "p\"a\\s's: #1\n").unwrap();
+        let backends = vec![(
+            "files".to_owned(),
+            SecretBackend::Directory(DirectoryBackend {
+                path: dir.path().to_owned(),
+            }),
+        )]
+        .into_iter()
+        .collect();
+
+        for (document, format) in &[
+            ("password = \"SECRET[files.password]\"", Format::Toml),
+            ("password: SECRET[files.password]", Format::Yaml),
+            (r#"{"password": "SECRET[files.password]"}"#, Format::Json),
+        ] {
+            let values: HashMap<String, String> =
+                deserialize(document, Some(*format), &backends, &mut HashSet::new()).unwrap();
+            assert_eq!(values["password"], "p\"a\\s's: #1", "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn redacts_whole_secrets() {
+        let secrets = vec!["ab1".to_owned()].into_iter().collect();
+
+        assert_eq!(
+            redact_with("ab1, cab1 and ab12 but ab1", &secrets),
+            "**REDACTED**, cab1 and ab12 but **REDACTED**"
+        );
+    }
+
+    #[test]
+    fn hides_sensitive_fields() {
+        let mut value = serde_json::json!({
+            "sinks": {
+                "out": {
+                    "auth": { "strategy": "basic", "user": "vector", "password": "hunter2" },
+                    "token": "abc",
+                    "store_api_key": true,
+                    "headers": [{ "api_key": "def" }],
+                },
+            },
+        });
+
+        hide_sensitive(&mut value);
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "sinks": {
+                    "out": {
+                        "auth": { "strategy": "basic", "user": "vector", "password": REDACTED },
+                        "token": REDACTED,
+                        "store_api_key": true,
+                        "headers": [{ "api_key": REDACTED }],
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_keys_outside_directory() {
+        let (_dir, backends) = directory();
+
+        let errors = interpolate("SECRET[files...]", &backends).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![r#"Failed to retrieve secret ".." from backend "files": invalid key "..""#]
+        );
+    }
+
+    #[test]
+    fn reports_unknown_backends() {
+        let errors =
+            interpolate("SECRET[vault.key] SECRET[vault.key]", &IndexMap::new()).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![r#"Unknown secret backend in config. name = "vault""#]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn interpolates_exec_secrets() {
+        let backends = vec![(
+            "helper".to_owned(),
+            SecretBackend::Exec(ExecBackend {
+                command: vec!["echo".into(), "secret-for".into()],
+                timeout_secs: 5,
+            }),
+        )]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            interpolate("SECRET[helper.db.password]", &backends).unwrap(),
+            "secret-for db.password"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exec_errors_hide_output() {
+        let backends = vec![(
+            "helper".to_owned(),
+            SecretBackend::Exec(ExecBackend {
+                command: vec!["sh".into(), "-c".into(), "echo leaked; exit 3".into()],
+                timeout_secs: 5,
+            }),
+        )]
+        .into_iter()
+        .collect();
+
+        let errors = interpolate("SECRET[helper.key]", &backends).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(!errors[0].contains("leaked"));
+    }
+}
@@ -0,0 +1,396 @@
+//! Reusable component templates and overlays.
+//!
+//! Templates are components declared once, in the `templates` table, with
+//! `%{param}` placeholders. Each of the `instances` adds a component made from
+//! a template and its own parameters. Overlays, in the `overlays` table, patch
+//! fields of components declared in any of the config files. Both are expanded
+//! once all the config files are merged.
+
+use super::ConfigBuilder;
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+lazy_static! {
+    static ref PARAM: Regex = Regex::new(r"%\{(\w+)\}").unwrap();
+}
+
+/// Raw component tables, by kind of component.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ComponentTables {
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub sources: IndexMap<String, Value>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub transforms: IndexMap<String, Value>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub sinks: IndexMap<String, Value>,
+}
+
+impl ComponentTables {
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty() && self.transforms.is_empty() && self.sinks.is_empty()
+    }
+
+    /// Adds templates, rejecting names that are already taken.
+    pub fn append(&mut self, with: Self) -> Result<(), Vec<String>> {
+        let errors = with
+            .sources
+            .keys()
+            .chain(with.transforms.keys())
+            .chain(with.sinks.keys())
+            .filter(|name| self.get(name).next().is_some())
+            .map(|name| format!("duplicate template name found: {}", name))
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        self.sources.extend(with.sources);
+        self.transforms.extend(with.transforms);
+        self.sinks.extend(with.sinks);
+        Ok(())
+    }
+
+    /// Adds overlays, merging those of the same component so the later ones
+    /// take precedence.
+    pub fn merge(&mut self, with: Self) {
+        for (tables, with) in vec![
+            (&mut self.sources, with.sources),
+            (&mut self.transforms, with.transforms),
+            (&mut self.sinks, with.sinks),
+        ] {
+            for (name, patch) in with {
+                match tables.get_mut(&name) {
+                    Some(existing) => merge(existing, patch),
+                    None => {
+                        tables.insert(name, patch);
+                    }
+                }
+            }
+        }
+    }
+
+    fn get<'a>(&'a self, name: &'a str) -> impl Iterator<Item = (Kind, &'a Value)> + 'a {
+        vec![
+            (Kind::Source, &self.sources),
+            (Kind::Transform, &self.transforms),
+            (Kind::Sink, &self.sinks),
+        ]
+        .into_iter()
+        .filter_map(move |(kind, tables)| tables.get(name).map(|table| (kind, table)))
+    }
+}
+
+/// A component made from a template.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Instance {
+    pub template: String,
+    #[serde(default)]
+    pub params: IndexMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Source,
+    Transform,
+    Sink,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Kind::Source => "Source",
+            Kind::Transform => "Transform",
+            Kind::Sink => "Sink",
+        }
+    }
+}
+
+/// Replaces the `%{param}` placeholders in the strings of `value`. A string
+/// made of a single placeholder takes the value of the parameter as is, so
+/// parameters aren't limited to strings.
+fn substitute(value: &Value, params: &IndexMap<String, Value>, missing: &mut Vec<String>) -> Value {
+    match value {
+        Value::String(string) => {
+            if let Some(caps) = PARAM.captures(string) {
+                if caps[0].len() == string.len() {
+                    if let Some(param) = params.get(&caps[1]) {
+                        return param.clone();
+                    }
+                }
+            }
+
+            let substituted =
+                PARAM.replace_all(string, |caps: &Captures<'_>| match params.get(&caps[1]) {
+                    Some(Value::String(param)) => param.clone(),
+                    Some(param) => param.to_string(),
+                    None => {
+                        missing.push(caps[1].to_owned());
+                        caps[0].to_owned()
+                    }
+                });
+            Value::String(substituted.into_owned())
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| substitute(value, params, missing))
+                .collect(),
+        ),
+        Value::Object(table) => Value::Object(
+            table
+                .iter()
+                .map(|(key, value)| (key.clone(), substitute(value, params, missing)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+/// Merges `patch` into `value`. Tables are merged key by key, anything else is
+/// replaced.
+fn merge(value: &mut Value, patch: Value) {
+    match (value, patch) {
+        (Value::Object(table), Value::Object(patch)) => {
+            for (key, patch) in patch {
+                match table.get_mut(&key) {
+                    Some(value) => merge(value, patch),
+                    None => {
+                        table.insert(key, patch);
+                    }
+                }
+            }
+        }
+        (value, patch) => *value = patch,
+    }
+}
+
+fn insert<C: DeserializeOwned>(
+    components: &mut IndexMap<String, C>,
+    kind: Kind,
+    name: String,
+    value: Value,
+) -> Result<(), String> {
+    if components.contains_key(&name) {
+        return Err(format!(
+            "duplicate {} name found: {}",
+            kind.as_str().to_lowercase(),
+            name
+        ));
+    }
+
+    let component = serde_json::from_value(value)
+        .map_err(|error| format!("{} \"{}\": {}", kind.as_str(), name, error))?;
+    components.insert(name, component);
+    Ok(())
+}
+
+fn patch<C: Serialize + DeserializeOwned>(
+    components: &mut IndexMap<String, C>,
+    kind: Kind,
+    name: &str,
+    patch: Value,
+) -> Result<(), String> {
+    let component = components.get_mut(name).ok_or_else(|| {
+        format!(
+            "Overlay of unknown {} \"{}\".",
+            kind.as_str().to_lowercase(),
+            name
+        )
+    })?;
+
+    let mut value = serde_json::to_value(&*component)
+        .map_err(|error| format!("{} \"{}\": {}", kind.as_str(), name, error))?;
+    merge(&mut value, patch);
+    *component = serde_json::from_value(value)
+        .map_err(|error| format!("{} \"{}\": {}", kind.as_str(), name, error))?;
+    Ok(())
+}
+
+/// Adds the instances of templates to the components of `builder`, then
+/// applies the overlays.
+pub fn expand(builder: &mut ConfigBuilder) -> Result<(), Vec<String>> {
+    let templates = std::mem::take(&mut builder.templates);
+    let instances = std::mem::take(&mut builder.instances);
+    let overlays = std::mem::take(&mut builder.overlays);
+    let mut errors = Vec::new();
+
+    for (name, instance) in instances {
+        let found = templates.get(&instance.template).collect::<Vec<_>>();
+        let (kind, template) = match found.as_slice() {
+            [found] => *found,
+            [] => {
+                errors.push(format!(
+                    "Instance \"{}\" of unknown template \"{}\".",
+                    name, instance.template
+                ));
+                continue;
+            }
+            _ => {
+                errors.push(format!(
+                    "Template \"{}\" is declared for more than one kind of component.",
+                    instance.template
+                ));
+                continue;
+            }
+        };
+
+        let mut missing = Vec::new();
+        let value = substitute(template, &instance.params, &mut missing);
+        if !missing.is_empty() {
+            missing.dedup();
+            errors.push(format!(
+                "{} \"{}\": missing template parameters {:?}.",
+                kind.as_str(),
+                name,
+                missing
+            ));
+            continue;
+        }
+
+        let inserted = match kind {
+            Kind::Source => insert(&mut builder.sources, kind, name, value),
+            Kind::Transform => insert(&mut builder.transforms, kind, name, value),
+            Kind::Sink => insert(&mut builder.sinks, kind, name, value),
+        };
+        errors.extend(inserted.err());
+    }
+
+    for (name, value) in overlays.sources {
+        errors.extend(patch(&mut builder.sources, Kind::Source, &name, value).err());
+    }
+    for (name, value) in overlays.transforms {
+        errors.extend(patch(&mut builder.transforms, Kind::Transform, &name, value).err());
+    }
+    for (name, value) in overlays.sinks {
+        errors.extend(patch(&mut builder.sinks, Kind::Sink, &name, value).err());
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn substitutes_params() {
+        let params = vec![
+            ("region".to_owned(), json!("us-east-1")),
+            ("port".to_owned(), json!(9000)),
+        ]
+        .into_iter()
+        .collect();
+        let mut missing = Vec::new();
+
+        assert_eq!(
+            substitute(
+                &json!({
+                    "endpoint": "https://%{region}.example.com:%{port}",
+                    "port": "%{port}",
+                    "tags": ["%{region}", "%{zone}"],
+                }),
+                &params,
+                &mut missing
+            ),
+            json!({
+                "endpoint": "https://us-east-1.example.com:9000",
+                "port": 9000,
+                "tags": ["us-east-1", "%{zone}"],
+            })
+        );
+        assert_eq!(missing, vec!["zone"]);
+    }
+
+    #[test]
+    fn merges_patches() {
+        let mut value = json!({ "batch": { "max_events": 10, "timeout_secs": 1 }, "tags": ["a"] });
+        merge(
+            &mut value,
+            json!({ "batch": { "max_events": 500 }, "tags": ["b"] }),
+        );
+        assert_eq!(
+            value,
+            json!({ "batch": { "max_events": 500, "timeout_secs": 1 }, "tags": ["b"] })
+        );
+    }
+
+    #[cfg(all(feature = "sources-stdin", feature = "sinks-blackhole"))]
+    #[test]
+    fn expands_instances_and_overlays() {
+        use crate::config::{format, Format};
+
+        let mut builder = ConfigBuilder::default();
+        for config in &[
+            r#"
+            [sources.logs_us]
+            type = "stdin"
+
+            [sources.logs_eu]
+            type = "stdin"
+
+            [templates.sinks.regional]
+            type = "blackhole"
+            inputs = ["logs_%{region}"]
+            print_amount = "%{amount}"
+
+            [instances.out_us]
+            template = "regional"
+            params = { region = "us", amount = 10 }
+
+            [instances.out_eu]
+            template = "regional"
+            params = { region = "eu", amount = 10 }
+            "#,
+            r#"
+            [overlays.sinks.out_eu]
+            print_amount = 100
+            "#,
+        ] {
+            builder
+                .append(format::deserialize(config, Some(Format::Toml)).unwrap())
+                .unwrap();
+        }
+
+        expand(&mut builder).unwrap();
+        assert!(builder.templates.is_empty());
+        assert_eq!(builder.sinks["out_us"].inputs, vec!["logs_us"]);
+        assert_eq!(builder.sinks["out_eu"].inputs, vec!["logs_eu"]);
+
+        let print_amount = |name: &str| {
+            serde_json::to_value(&builder.sinks[name]).unwrap()["print_amount"].clone()
+        };
+        assert_eq!(print_amount("out_us"), json!(10));
+        assert_eq!(print_amount("out_eu"), json!(100));
+    }
+
+    #[test]
+    fn reports_unknown_templates_and_components() {
+        let mut builder = ConfigBuilder::default();
+        builder.instances.insert(
+            "out".to_owned(),
+            Instance {
+                template: "missing".to_owned(),
+                params: IndexMap::new(),
+            },
+        );
+        builder.overlays.sinks.insert("other".to_owned(), json!({}));
+
+        assert_eq!(
+            expand(&mut builder).unwrap_err(),
+            vec![
+                r#"Instance "out" of unknown template "missing"."#,
+                r#"Overlay of unknown sink "other"."#,
+            ]
+        );
+    }
+}
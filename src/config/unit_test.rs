@@ -1,12 +1,22 @@
 use super::{Config, ConfigBuilder, TestDefinition, TestInput, TestInputValue};
-use crate::config::{self, GlobalOptions, TransformConfig};
+use crate::config::{self, log_schema, output_name, templating, GlobalOptions, TransformConfig};
 use crate::{
     conditions::Condition,
     event::{Event, Value},
-    transforms::Transform,
+    transforms::{NamedOutputs, TaskTransform, Transform},
 };
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::StreamExt;
 use indexmap::IndexMap;
-use std::{collections::HashMap, path::PathBuf};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::time::Instant;
 
 pub async fn build_unit_tests_main(
     paths: &[(PathBuf, config::FormatHint)],
@@ -22,10 +32,10 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
     let mut tests = vec![];
     let mut errors = vec![];
 
-    let expansions = super::compiler::expand_macros(&mut builder)?;
+    templating::expand(&mut builder)?;
 
     // Don't let this escape since it's not validated
-    let config = Config {
+    let config = Arc::new(Config {
         global: builder.global,
         #[cfg(feature = "api")]
         api: builder.api,
@@ -34,8 +44,7 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
         sinks: builder.sinks,
         transforms: builder.transforms,
         tests: builder.tests,
-        expansions,
-    };
+    });
 
     for test in &config.tests {
         match build_unit_test(test, &config).await {
@@ -59,17 +68,28 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
 
 pub struct UnitTest {
     pub name: String,
-    inputs: Vec<(Vec<String>, Event)>,
+    /// Input events with the transforms they are inserted into, and the time
+    /// they are inserted at on the simulated clock.
+    inputs: Vec<(Vec<String>, Duration, Event)>,
+    /// Raw inputs of every source inserted into, and the events it decoded.
+    decoded: HashMap<String, (Vec<Event>, Vec<Event>)>,
     transforms: IndexMap<String, UnitTestTransform>,
     checks: Vec<UnitTestCheck>,
+    sink_checks: Vec<UnitTestSinkCheck>,
     no_outputs_from: Vec<String>,
+    /// Log fields removed from events before they are snapshotted.
+    snapshot_ignore: Vec<String>,
     globals: GlobalOptions,
+    config: Arc<Config>,
 }
 
 struct UnitTestTransform {
     transform: Transform,
     config: Box<dyn TransformConfig>,
-    next: Vec<String>,
+    /// Full names of the named outputs of the transform.
+    outputs: Vec<String>,
+    /// Transforms consuming the outputs of this one, with the output they consume.
+    next: Vec<(String, String)>,
 }
 
 struct UnitTestCheck {
@@ -77,6 +97,12 @@ struct UnitTestCheck {
     conditions: Vec<Box<dyn Condition>>,
 }
 
+struct UnitTestSinkCheck {
+    sink: String,
+    /// Expected payload; without it, the payload is printed for inspection.
+    payload: Option<String>,
+}
+
 fn event_to_string(event: &Event) -> String {
     match event {
         Event::Log(log) => serde_json::to_string(&log).unwrap_or_else(|_| "{}".into()),
@@ -103,33 +129,84 @@ fn events_to_string(name: &str, events: &[Event]) -> String {
     }
 }
 
+/// Runs a task transform over `inputs` on a simulated clock, each input arriving
+/// at its time since the start of the test. Outputs are stamped with the time
+/// they were emitted at.
+fn run_task_transform(
+    transform: Box<dyn TaskTransform>,
+    inputs: Vec<(Duration, Event)>,
+) -> Vec<(Duration, Event)> {
+    // The clock of a runtime can only be paused on a current thread runtime,
+    // which can't be started from within the runtime the test may run on.
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to build the runtime of a unit test.");
+        runtime.block_on(async move {
+            // Without a paused clock, inputs have no delays to wait for.
+            #[cfg(any(test, feature = "unit-test-clock"))]
+            tokio::time::pause();
+            let start = Instant::now();
+            let in_stream = futures::stream::iter(inputs).then(move |(at, event)| async move {
+                tokio::time::sleep_until(start + at).await;
+                event
+            });
+            transform
+                .transform(Box::pin(in_stream))
+                .map(|event| (start.elapsed(), event))
+                .collect()
+                .await
+        })
+    })
+    .join()
+    .expect("Transform panicked in a unit test.")
+}
+
 fn walk(
     node: &str,
-    mut inputs: Vec<Event>,
+    inputs: Vec<(Duration, Event)>,
     transforms: &mut IndexMap<String, UnitTestTransform>,
     aggregated_results: &mut HashMap<String, (Vec<Event>, Vec<Event>)>,
     globals: &GlobalOptions,
 ) {
-    let mut results = Vec::new();
+    // Results by output, starting with the default one.
+    let mut results_by_output = HashMap::new();
+    results_by_output.insert(node.to_owned(), Vec::new());
     let mut targets = Vec::new();
 
     // Use `remove` to take ownership.
     if let Some((key, mut target)) = transforms.remove_entry(node) {
+        for output in &target.outputs {
+            results_by_output.insert(output.clone(), Vec::new());
+        }
         match target.transform {
             Transform::Function(ref mut t) => {
-                for input in inputs.clone() {
-                    t.transform(&mut results, input)
+                // Function transforms output as soon as they receive an event.
+                for (at, input) in inputs.clone() {
+                    let mut results = Vec::new();
+                    let mut named = NamedOutputs::default();
+                    if target.outputs.is_empty() {
+                        t.transform(&mut results, input)
+                    } else {
+                        t.transform_with_outputs(&mut results, &mut named, input)
+                    }
+                    if let Some(default) = results_by_output.get_mut(node) {
+                        default.extend(results.into_iter().map(|event| (at, event)));
+                    }
+                    for (output, events) in named.drain() {
+                        let output = output_name(node, &output);
+                        if let Some(results) = results_by_output.get_mut(&output) {
+                            results.extend(events.into_iter().map(|event| (at, event)));
+                        }
+                    }
                 }
                 targets = target.next.clone();
                 transforms.insert(key, target);
             }
             Transform::Task(t) => {
                 error!("Using a recently refactored `TaskTransform` in a unit test. You may experience limited support for multiple inputs.");
-                let in_stream = futures::stream::iter(inputs.clone());
-                let out_stream = t.transform(Box::pin(in_stream));
-                // TODO(new-transform-enum): Handle Many
-                let out_iter = futures::executor::block_on_stream(out_stream);
-                results.extend(out_iter);
+                results_by_output.insert(node.to_owned(), run_task_transform(t, inputs.clone()));
                 targets = target.next.clone();
                 // TODO: This is a hack.
                 // Our tasktransforms must consume the transform to attach it to an input stream, so we rebuild it between input streams.
@@ -137,43 +214,154 @@ fn walk(
                     transform:  futures::executor::block_on(target.config.clone().build(globals))
                         .expect("Failed to build a known valid transform config. Things may have changed during runtime."),
                     config: target.config,
+                    outputs: target.outputs,
                     next: target.next
                 });
             }
         }
     }
 
-    for child in targets {
+    for (output, child) in targets {
         walk(
             &child,
-            results.clone(),
+            results_by_output.get(&output).cloned().unwrap_or_default(),
             transforms,
             aggregated_results,
             globals,
         );
     }
 
-    if let Some((mut e_inputs, mut e_results)) = aggregated_results.remove(node) {
-        inputs.append(&mut e_inputs);
-        results.append(&mut e_results);
+    for (output, results) in results_by_output {
+        let mut inputs = inputs
+            .iter()
+            .map(|(_, event)| event.clone())
+            .collect::<Vec<_>>();
+        let mut results = results
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect::<Vec<_>>();
+        if let Some((mut e_inputs, mut e_results)) = aggregated_results.remove(&output) {
+            inputs.append(&mut e_inputs);
+            results.append(&mut e_results);
+        }
+        aggregated_results.insert(output, (inputs, results));
+    }
+}
+
+/// The outcome of running a unit test.
+#[derive(Debug, Default)]
+pub struct UnitTestResult {
+    pub inspections: Vec<String>,
+    pub errors: Vec<String>,
+    /// What the checked components output, to compare with a recorded snapshot.
+    pub snapshot: TestSnapshot,
+}
+
+/// The outputs of the checked components of a test: the events of sources and
+/// transforms, and the payloads of sinks.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TestSnapshot {
+    /// Events, encoded as JSON, by the name of the component that output them.
+    #[serde(default)]
+    pub outputs: BTreeMap<String, Vec<serde_json::Value>>,
+    /// Payloads by the name of the sink that encoded them.
+    #[serde(default)]
+    pub payloads: BTreeMap<String, String>,
+}
+
+impl TestSnapshot {
+    /// Describes how `actual` differs from the recorded snapshot, one error per
+    /// component. Events are compared in order: lines starting with `-` are
+    /// recorded events and those starting with `+` are the ones received
+    /// instead.
+    pub fn diff(&self, actual: &TestSnapshot) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        let names = self.outputs.keys().chain(actual.outputs.keys());
+        for name in names.collect::<BTreeSet<_>>() {
+            let expected = self
+                .outputs
+                .get(name)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let received = actual
+                .outputs
+                .get(name)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let mut lines = Vec::new();
+            for i in 0..expected.len().max(received.len()) {
+                let (expected, received) = (expected.get(i), received.get(i));
+                if expected != received {
+                    lines.extend(expected.map(|event| format!("- {}", event)));
+                    lines.extend(received.map(|event| format!("+ {}", event)));
+                }
+            }
+            if !lines.is_empty() {
+                errors.push(format!(
+                    "check '{}' failed, outputs don't match the snapshot:\n  {}",
+                    name,
+                    lines.join("\n  ")
+                ));
+            }
+        }
+
+        let names = self.payloads.keys().chain(actual.payloads.keys());
+        for name in names.collect::<BTreeSet<_>>() {
+            let (expected, received) = (self.payloads.get(name), actual.payloads.get(name));
+            if expected != received {
+                errors.push(format!(
+                    "check sink '{}' failed, payload doesn't match the snapshot:\n  expected: {:?}\n    actual: {:?}",
+                    name, expected, received,
+                ));
+            }
+        }
+
+        errors
+    }
+}
+
+/// Removes the fields ignored by the snapshot of a test from log events.
+fn without_fields(event: &Event, ignore: &[String]) -> Event {
+    let mut event = event.clone();
+    if let Event::Log(log) = &mut event {
+        for field in ignore {
+            log.remove(field);
+        }
+    }
+    event
+}
+
+fn event_to_json(event: &Event) -> serde_json::Value {
+    match event {
+        Event::Log(log) => serde_json::to_value(log),
+        Event::Metric(metric) => serde_json::to_value(metric),
     }
-    aggregated_results.insert(node.into(), (inputs, results));
+    .unwrap_or(serde_json::Value::Null)
 }
 
 impl UnitTest {
     // Executes each test and provides a tuple of inspections and error lists.
     pub fn run(&mut self) -> (Vec<String>, Vec<String>) {
+        let result = self.run_with_snapshot();
+        (result.inspections, result.errors)
+    }
+
+    /// Executes the test, also recording the outputs of the checked components.
+    pub fn run_with_snapshot(&mut self) -> UnitTestResult {
+        let mut snapshot = TestSnapshot::default();
         let mut errors = Vec::new();
         let mut inspections = Vec::new();
-        let mut results = HashMap::new();
+        let mut results = self.decoded.clone();
 
-        let mut inputs_by_target = HashMap::new();
-        for (targets, event) in &self.inputs {
+        // Targets are walked in the order of their first input.
+        let mut inputs_by_target = IndexMap::new();
+        for (targets, at, event) in &self.inputs {
             for target in targets {
                 let entry = inputs_by_target
                     .entry(target.clone())
                     .or_insert_with(Vec::new);
-                entry.push(event.clone());
+                entry.push((*at, event.clone()));
             }
         }
 
@@ -189,6 +377,13 @@ impl UnitTest {
 
         for check in &self.checks {
             if let Some((inputs, outputs)) = results.get(&check.extract_from) {
+                snapshot.outputs.insert(
+                    check.extract_from.clone(),
+                    outputs
+                        .iter()
+                        .map(|event| event_to_json(&without_fields(event, &self.snapshot_ignore)))
+                        .collect(),
+                );
                 if check.conditions.is_empty() {
                     inspections.push(format!(
                         "check transform '{}' payloads (events encoded as JSON):\n{}\n{}",
@@ -247,6 +442,72 @@ impl UnitTest {
             }
         }
 
+        for check in &self.sink_checks {
+            let sink = &self.config.sinks[&check.sink];
+            let inputs = sink
+                .inputs
+                .iter()
+                .filter_map(|input| results.get(input))
+                .flat_map(|(_, outputs)| outputs.iter().cloned())
+                .collect::<Vec<_>>();
+            let payload = match futures::executor::block_on(
+                sink.inner.encode_payload(inputs.clone()),
+            ) {
+                Some(Ok(payload)) => payload,
+                Some(Err(error)) => {
+                    errors.push(format!(
+                        "check sink '{}' failed to encode events: {}\npayloads (events encoded as JSON):\n{}",
+                        check.sink,
+                        error,
+                        events_to_string(" input", &inputs),
+                    ));
+                    continue;
+                }
+                None => {
+                    errors.push(format!(
+                        "check sink '{}' failed: sinks of type '{}' can't be tested.",
+                        check.sink,
+                        sink.inner.sink_type(),
+                    ));
+                    continue;
+                }
+            };
+
+            // The snapshot payload is encoded again without the ignored fields.
+            let snapshot_payload = if self.snapshot_ignore.is_empty() {
+                Some(Ok(payload.clone()))
+            } else {
+                let inputs = inputs
+                    .iter()
+                    .map(|event| without_fields(event, &self.snapshot_ignore))
+                    .collect();
+                futures::executor::block_on(sink.inner.encode_payload(inputs))
+            };
+            if let Some(Ok(snapshot_payload)) = snapshot_payload {
+                snapshot.payloads.insert(
+                    check.sink.clone(),
+                    String::from_utf8_lossy(&snapshot_payload).into_owned(),
+                );
+            }
+
+            match &check.payload {
+                None => inspections.push(format!(
+                    "check sink '{}' payload:\n{}\n  payload: {:?}",
+                    check.sink,
+                    events_to_string(" input", &inputs),
+                    String::from_utf8_lossy(&payload),
+                )),
+                Some(expected) if expected.as_bytes() != payload.as_slice() => errors.push(format!(
+                    "check sink '{}' failed, payload doesn't match:\n  expected: {:?}\n    actual: {:?}\npayloads (events encoded as JSON):\n{}",
+                    check.sink,
+                    expected,
+                    String::from_utf8_lossy(&payload),
+                    events_to_string(" input", &inputs),
+                )),
+                Some(_) => {}
+            }
+        }
+
         for tform in &self.no_outputs_from {
             if let Some((inputs, outputs)) = results.get(tform) {
                 if !outputs.is_empty() {
@@ -260,7 +521,11 @@ impl UnitTest {
             }
         }
 
-        (inspections, errors)
+        UnitTestResult {
+            inspections,
+            errors,
+            snapshot,
+        }
     }
 }
 
@@ -320,17 +585,93 @@ fn reduce_transforms(
     });
 }
 
-fn build_input(config: &Config, input: &TestInput) -> Result<(Vec<String>, Event), String> {
-    let target = config.get_inputs(&input.insert_at);
+/// Builds the events of a test input, along with the transforms they are
+/// inserted into. An input inserted into a source is decoded by the source
+/// first, and recorded in `decoded` so that the source can be checked too.
+fn build_input(
+    config: &Config,
+    input: &TestInput,
+    timestamp: DateTime<Utc>,
+    decoded: &mut HashMap<String, (Vec<Event>, Vec<Event>)>,
+) -> Result<(Vec<String>, Vec<Event>), String> {
+    if let Some(source) = config.sources.get(&input.insert_at) {
+        let value = match (input.type_str.as_ref(), &input.value) {
+            ("raw", Some(value)) => value,
+            _ => {
+                return Err(format!(
+                    "input inserted into source '{}' requires type 'raw' and the field 'value'",
+                    input.insert_at
+                ))
+            }
+        };
+        let decoded_since = Utc::now();
+        let mut events = match source.inner.decode_payload(Bytes::from(value.clone())) {
+            Some(Ok(events)) => events,
+            Some(Err(error)) => {
+                return Err(format!(
+                    "source '{}' failed to decode input: {}",
+                    input.insert_at, error
+                ))
+            }
+            None => {
+                return Err(format!(
+                    "sources of type '{}' can't decode test inputs",
+                    source.inner.source_type()
+                ))
+            }
+        };
+
+        // Sources stamp events with the time they receive them, unless they
+        // parse one from the input, so only timestamps taken while decoding
+        // are replaced by the simulated time.
+        for event in &mut events {
+            if let Event::Log(log) = event {
+                let received = match log.get(log_schema().timestamp_key()) {
+                    Some(Value::Timestamp(received)) => *received >= decoded_since,
+                    _ => false,
+                };
+                if received {
+                    log.insert(log_schema().timestamp_key(), timestamp);
+                }
+            }
+        }
+
+        let mut raw_event = Event::from(value.clone());
+        raw_event
+            .as_mut_log()
+            .insert(log_schema().timestamp_key(), timestamp);
+        let (raw, results) = decoded.entry(input.insert_at.clone()).or_default();
+        raw.push(raw_event);
+        results.extend(events.iter().cloned());
+
+        let targets = config
+            .transforms
+            .iter()
+            .filter(|(_, transform)| transform.inputs.contains(&input.insert_at))
+            .map(|(name, _)| name.clone())
+            .collect();
+        return Ok((targets, events));
+    }
+
+    let target = vec![input.insert_at.clone()];
 
     match input.type_str.as_ref() {
         "raw" => match input.value.as_ref() {
-            Some(v) => Ok((target, Event::from(v.clone()))),
+            Some(v) => {
+                let mut event = Event::from(v.clone());
+                event
+                    .as_mut_log()
+                    .insert(log_schema().timestamp_key(), timestamp);
+                Ok((target, vec![event]))
+            }
             None => Err("input type 'raw' requires the field 'value'".to_string()),
         },
         "log" => {
             if let Some(log_fields) = &input.log_fields {
                 let mut event = Event::from("");
+                event
+                    .as_mut_log()
+                    .insert(log_schema().timestamp_key(), timestamp);
                 for (path, value) in log_fields {
                     let value: Value = match value {
                         TestInputValue::String(s) => Value::from(s.to_owned()),
@@ -340,14 +681,14 @@ fn build_input(config: &Config, input: &TestInput) -> Result<(Vec<String>, Event
                     };
                     event.as_mut_log().insert(path.to_owned(), value);
                 }
-                Ok((target, event))
+                Ok((target, vec![event]))
             } else {
                 Err("input type 'log' requires the field 'log_fields'".to_string())
             }
         }
         "metric" => {
             if let Some(metric) = &input.metric {
-                Ok((target, Event::Metric(metric.clone())))
+                Ok((target, vec![Event::Metric(metric.clone())]))
             } else {
                 Err("input type 'metric' requires the field 'metric'".to_string())
             }
@@ -359,24 +700,61 @@ fn build_input(config: &Config, input: &TestInput) -> Result<(Vec<String>, Event
     }
 }
 
+/// The wall clock time at `at` on the simulated clock of a test, which starts
+/// at the Unix epoch so that the outputs of a test don't depend on when it runs.
+///
+/// Transforms follow the simulated clock when they rely on tokio timers, like
+/// the flushes of `reduce`, but not when they read the wall clock, like the
+/// `now` function of VRL.
+fn simulated_time(at: Duration) -> Option<DateTime<Utc>> {
+    Utc.timestamp(0, 0)
+        .checked_add_signed(chrono::Duration::from_std(at).ok()?)
+}
+
 fn build_inputs(
     config: &Config,
     definition: &TestDefinition,
-) -> Result<Vec<(Vec<String>, Event)>, Vec<String>> {
+    decoded: &mut HashMap<String, (Vec<Event>, Vec<Event>)>,
+) -> Result<Vec<(Vec<String>, Duration, Vec<Event>)>, Vec<String>> {
     let mut inputs = Vec::new();
     let mut errors = vec![];
 
-    if let Some(input_def) = &definition.input {
-        match build_input(config, input_def) {
-            Ok(input_event) => inputs.push(input_event),
-            Err(err) => errors.push(err),
-        }
-    } else if definition.inputs.is_empty() {
+    if definition.input.is_none() && definition.inputs.is_empty() {
         errors.push("must specify at least one input.".to_owned());
     }
-    for input_def in &definition.inputs {
-        match build_input(config, input_def) {
-            Ok(input_event) => inputs.push(input_event),
+    // Inputs are inserted in order, each after the delay it specifies.
+    let mut clock = Duration::default();
+    for input_def in definition.input.iter().chain(&definition.inputs) {
+        let delay_ms = input_def.delay_ms.unwrap_or(0);
+        if delay_ms > 0 && !cfg!(any(test, feature = "unit-test-clock")) {
+            errors.push(format!(
+                "input to '{}' sets `delay_ms`, which requires Vector to be built with the `unit-test-clock` feature.",
+                input_def.insert_at
+            ));
+            continue;
+        }
+
+        // Task transforms are run with timers set for each input.
+        let timestamp = clock
+            .checked_add(Duration::from_millis(delay_ms))
+            .filter(|&at| Instant::now().checked_add(at).is_some())
+            .and_then(|at| {
+                clock = at;
+                simulated_time(at)
+            });
+        let timestamp = match timestamp {
+            Some(timestamp) => timestamp,
+            None => {
+                errors.push(format!(
+                    "input to '{}' is delayed past the end of the simulated clock, `delay_ms` is too large.",
+                    input_def.insert_at
+                ));
+                break;
+            }
+        };
+
+        match build_input(config, input_def, timestamp, decoded) {
+            Ok((targets, events)) => inputs.push((targets, clock, events)),
             Err(err) => errors.push(err),
         }
     }
@@ -390,11 +768,12 @@ fn build_inputs(
 
 async fn build_unit_test(
     definition: &TestDefinition,
-    config: &Config,
+    config: &Arc<Config>,
 ) -> Result<UnitTest, Vec<String>> {
     let mut errors = vec![];
 
-    let inputs = match build_inputs(config, definition) {
+    let mut decoded = HashMap::new();
+    let inputs = match build_inputs(config, definition, &mut decoded) {
         Ok(inputs) => inputs,
         Err(mut errs) => {
             errors.append(&mut errs);
@@ -412,13 +791,14 @@ async fn build_unit_test(
 
     config.transforms.iter().for_each(|(k, t)| {
         t.inputs.iter().for_each(|i| {
-            if let Some(outputs) = transform_outputs.get_mut(i) {
+            let component = config.input_component(i).unwrap_or(i);
+            if let Some(outputs) = transform_outputs.get_mut(component) {
                 outputs.insert(k.to_string(), ());
             }
         })
     });
 
-    for (i, (input_target, _)) in inputs.iter().enumerate() {
+    for (i, (input_target, _, _)) in inputs.iter().enumerate() {
         for target in input_target {
             if !transform_outputs.contains_key(target) {
                 errors.push(format!(
@@ -434,10 +814,18 @@ async fn build_unit_test(
 
     let mut leaves: IndexMap<String, ()> = IndexMap::new();
     definition.outputs.iter().for_each(|o| {
-        leaves.insert(o.extract_from.clone(), ());
+        // The payload of a sink is encoded from the events of its inputs.
+        let extract_from = match config.sinks.get(&o.extract_from) {
+            Some(sink) => sink.inputs.iter().collect(),
+            None => vec![&o.extract_from],
+        };
+        for name in extract_from {
+            let component = config.input_component(name);
+            leaves.insert(component.unwrap_or(name).to_owned(), ());
+        }
     });
     definition.no_outputs_from.iter().for_each(|o| {
-        leaves.insert(o.clone(), ());
+        leaves.insert(config.input_component(o).unwrap_or(o).to_owned(), ());
     });
 
     // Reduce the configured transforms into just the ones connecting our test
@@ -445,7 +833,7 @@ async fn build_unit_test(
     reduce_transforms(
         inputs
             .iter()
-            .map(|(names, _)| names)
+            .map(|(names, _, _)| names)
             .flatten()
             .cloned()
             .collect::<Vec<_>>(),
@@ -459,12 +847,29 @@ async fn build_unit_test(
         if let Some(outputs) = transform_outputs.remove(name) {
             match transform_config.inner.build(&config.global).await {
                 Ok(transform) => {
+                    let named_outputs = transform_config
+                        .named_outputs(name)
+                        .into_iter()
+                        .map(|(output, _)| output)
+                        .collect::<Vec<_>>();
+                    let next = outputs
+                        .into_iter()
+                        .flat_map(|(child, _)| {
+                            config.transforms[&child]
+                                .inputs
+                                .iter()
+                                .filter(|input| *input == name || named_outputs.contains(input))
+                                .map(|input| (input.clone(), child.clone()))
+                                .collect::<Vec<_>>()
+                        })
+                        .collect();
                     transforms.insert(
                         name.clone(),
                         UnitTestTransform {
                             transform,
                             config: transform_config.inner.clone(),
-                            next: outputs.into_iter().map(|(k, _)| k).collect(),
+                            outputs: named_outputs,
+                            next,
                         },
                     );
                 }
@@ -483,9 +888,21 @@ async fn build_unit_test(
         return Err(errors);
     }
 
+    let reached = |name: &str| {
+        let component = config.input_component(name).unwrap_or(name);
+        transforms.contains_key(component) || decoded.contains_key(component)
+    };
     definition.outputs.iter().for_each(|o| {
-        if !transforms.contains_key(&o.extract_from) {
-            let targets = inputs.iter().map(|(i, _)| i).flatten().collect::<Vec<_>>();
+        let output_reached = match config.sinks.get(&o.extract_from) {
+            Some(sink) => sink.inputs.iter().any(|input| reached(input)),
+            None => reached(&o.extract_from),
+        };
+        if !output_reached {
+            let targets = inputs
+                .iter()
+                .map(|(i, _, _)| i)
+                .flatten()
+                .collect::<Vec<_>>();
             if targets.len() == 1 {
                 errors.push(format!(
                     "unable to complete topology between target transform '{}' and output target '{}'",
@@ -500,10 +917,41 @@ async fn build_unit_test(
         }
     });
 
+    // Sinks are checked by the payload they encode, rather than conditions.
+    let mut sink_checks = Vec::new();
+    for o in &definition.outputs {
+        match config.sinks.get(&o.extract_from) {
+            Some(sink) => {
+                if o.conditions.is_some() {
+                    errors.push(format!(
+                        "output target '{}' is a sink and can only be checked with `payload`",
+                        o.extract_from
+                    ));
+                } else if sink.inner.encode_payload(Vec::new()).await.is_none() {
+                    errors.push(format!(
+                        "sinks of type '{}' can't encode test outputs",
+                        sink.inner.sink_type()
+                    ));
+                } else {
+                    sink_checks.push(UnitTestSinkCheck {
+                        sink: o.extract_from.clone(),
+                        payload: o.payload.clone(),
+                    });
+                }
+            }
+            None if o.payload.is_some() => errors.push(format!(
+                "output target '{}' isn't a sink, `payload` can only be checked for sinks",
+                o.extract_from
+            )),
+            None => {}
+        }
+    }
+
     // Build all output conditions.
     let checks = definition
         .outputs
         .iter()
+        .filter(|o| !config.sinks.contains_key(&o.extract_from))
         .map(|o| {
             let mut conditions: Vec<Box<dyn Condition>> = Vec::new();
             for (index, cond_conf) in o
@@ -540,11 +988,22 @@ async fn build_unit_test(
     } else {
         Ok(UnitTest {
             name: definition.name.clone(),
-            inputs,
+            inputs: inputs
+                .into_iter()
+                .flat_map(|(targets, at, events)| {
+                    events
+                        .into_iter()
+                        .map(move |event| (targets.clone(), at, event))
+                })
+                .collect(),
+            decoded,
             transforms,
             checks,
+            sink_checks,
             no_outputs_from: definition.no_outputs_from.clone(),
+            snapshot_ignore: definition.snapshot_ignore.clone(),
             globals: config.global.clone(),
+            config: Arc::clone(config),
         })
     }
 }
@@ -960,6 +1419,43 @@ mod tests {
         assert_eq!(tests[0].run().1, Vec::<String>::new());
     }
 
+    #[tokio::test]
+    async fn test_template_instance() {
+        let config: ConfigBuilder = toml::from_str(indoc! {r#"
+            [templates.transforms.tagged]
+              inputs = ["%{input}"]
+              type = "add_fields"
+              [templates.transforms.tagged.fields]
+                tag = "%{tag}"
+
+            [instances.foo]
+              template = "tagged"
+              params = { input = "ignored", tag = "first" }
+
+            [instances.bar]
+              template = "tagged"
+              params = { input = "foo", tag = "second" }
+
+            [[tests]]
+              name = "instantiated transforms"
+
+              [tests.input]
+                insert_at = "foo"
+                value = "nah this doesnt matter"
+
+              [[tests.outputs]]
+                extract_from = "bar"
+                [[tests.outputs.conditions]]
+                  type = "check_fields"
+                  "tag.equals" = "second"
+                  "message.equals" = "nah this doesnt matter"
+        "#})
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        assert_eq!(tests[0].run().1, Vec::<String>::new());
+    }
+
     #[tokio::test]
     async fn test_route() {
         let config: ConfigBuilder = toml::from_str(indoc! {r#"
@@ -1260,6 +1756,194 @@ mod tests {
         assert_eq!(tests[0].run().1, Vec::<String>::new());
     }
 
+    #[tokio::test]
+    async fn test_metric_input_sequence() {
+        let config: ConfigBuilder = toml::from_str(indoc! { r#"
+            [transforms.foo]
+              inputs = ["ignored"]
+              type = "add_tags"
+              [transforms.foo.tags]
+                new_tag = "new value added"
+
+            [[tests]]
+              name = "metric events of every type"
+
+              [[tests.inputs]]
+                insert_at = "foo"
+                type = "metric"
+                [tests.inputs.metric]
+                  kind = "absolute"
+                  name = "gauge"
+                  gauge = { value = 1.5 }
+
+              [[tests.inputs]]
+                insert_at = "foo"
+                type = "metric"
+                [tests.inputs.metric]
+                  kind = "incremental"
+                  name = "set"
+                  set = { values = ["a", "b"] }
+
+              [[tests.inputs]]
+                insert_at = "foo"
+                type = "metric"
+                [tests.inputs.metric]
+                  kind = "incremental"
+                  name = "distribution"
+                  distribution = { samples = [{ value = 1.0, rate = 2 }], statistic = "histogram" }
+
+              [[tests.inputs]]
+                insert_at = "foo"
+                type = "metric"
+                [tests.inputs.metric]
+                  kind = "incremental"
+                  name = "counter"
+                  counter = { value = 3.0 }
+
+              [[tests.outputs]]
+                extract_from = "foo"
+                [[tests.outputs.conditions]]
+                  type = "check_fields"
+                  "new_tag.eq" = "new value added"
+        "#})
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        let result = tests[0].run_with_snapshot();
+        assert_eq!(result.errors, Vec::<String>::new());
+        let names = result.snapshot.outputs["foo"]
+            .iter()
+            .map(|metric| metric["name"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["gauge", "set", "distribution", "counter"]);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_ignore() {
+        let config: ConfigBuilder = toml::from_str(indoc! { r#"
+            [transforms.foo]
+              inputs = ["ignored"]
+              type = "add_fields"
+              [transforms.foo.fields]
+                new_field = "string value"
+
+            [[tests]]
+              name = "ignored fields aren't snapshotted"
+              snapshot_ignore = ["host"]
+
+              [tests.input]
+                insert_at = "foo"
+                type = "log"
+                [tests.input.log_fields]
+                  message = "nah"
+                  host = "my-laptop"
+
+              [[tests.outputs]]
+                extract_from = "foo"
+                [[tests.outputs.conditions]]
+                  type = "check_fields"
+                  "host.eq" = "my-laptop"
+        "#})
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        let result = tests[0].run_with_snapshot();
+        assert_eq!(result.errors, Vec::<String>::new());
+        let output = &result.snapshot.outputs["foo"][0];
+        assert_eq!(output["message"], "nah");
+        assert_eq!(output["new_field"], "string value");
+        assert!(output.get("host").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_simulated_clock() {
+        let config: ConfigBuilder = toml::from_str(indoc! { r#"
+            [transforms.foo]
+              inputs = ["ignored"]
+              type = "reduce"
+              expire_after_ms = 1000
+              flush_period_ms = 100
+
+            [[tests]]
+              name = "events expire between inputs"
+
+              [[tests.inputs]]
+                insert_at = "foo"
+                type = "log"
+                [tests.inputs.log_fields]
+                  count = 1
+
+              [[tests.inputs]]
+                insert_at = "foo"
+                type = "log"
+                delay_ms = 500
+                [tests.inputs.log_fields]
+                  count = 2
+
+              [[tests.inputs]]
+                insert_at = "foo"
+                type = "log"
+                delay_ms = 60000
+                [tests.inputs.log_fields]
+                  count = 4
+
+              [[tests.outputs]]
+                extract_from = "foo"
+        "#})
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        let result = tests[0].run_with_snapshot();
+        assert_eq!(result.errors, Vec::<String>::new());
+        let outputs = &result.snapshot.outputs["foo"];
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0]["count"], 3);
+        assert_eq!(
+            outputs[0]["timestamp_end"],
+            serde_json::to_value(Value::from(Utc.timestamp(0, 500_000_000))).unwrap()
+        );
+        assert_eq!(outputs[1]["count"], 4);
+        assert_eq!(
+            outputs[1]["timestamp"],
+            serde_json::to_value(Value::from(Utc.timestamp(60, 500_000_000))).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn parse_delay_out_of_range() {
+        let config: ConfigBuilder = toml::from_str(indoc! { r#"
+            [transforms.foo]
+              inputs = ["ignored"]
+              type = "add_fields"
+              [transforms.foo.fields]
+                new_field = "string value"
+
+            [[tests]]
+              name = "delayed too long"
+
+              [[tests.inputs]]
+                insert_at = "foo"
+                value = "first"
+                delay_ms = 9223372036854775807
+
+              [[tests.outputs]]
+                extract_from = "foo"
+                [[tests.outputs.conditions]]
+                  type = "check_fields"
+                  "new_field.equals" = "string value"
+        "#})
+        .unwrap();
+
+        let errs = build_unit_tests(config).await.err().unwrap();
+        assert_eq!(
+            errs,
+            vec![indoc! {r#"
+                Failed to build test 'delayed too long':
+                  input to 'foo' is delayed past the end of the simulated clock, `delay_ms` is too large."#}
+            .to_owned(),]
+        );
+    }
+
     #[tokio::test]
     async fn test_success_over_gap() {
         let config: ConfigBuilder = toml::from_str(indoc! { r#"
@@ -1449,4 +2133,153 @@ mod tests {
                     ]);
                 */
     }
+
+    #[cfg(all(feature = "sources-syslog", feature = "sinks-console"))]
+    #[tokio::test]
+    async fn test_source_and_sink() {
+        let config: ConfigBuilder = toml::from_str(indoc! {r#"
+            [sources.in]
+              type = "syslog"
+              mode = "tcp"
+              address = "127.0.0.1:1514"
+
+            [transforms.foo]
+              inputs = [ "in" ]
+              type = "add_fields"
+              [transforms.foo.fields]
+                env = "test"
+
+            [sinks.out]
+              inputs = [ "foo" ]
+              type = "console"
+              encoding.codec = "text"
+
+            [[tests]]
+              name = "parse and encode"
+
+              [tests.input]
+                insert_at = "in"
+                value = "<13>1 2021-06-01T00:00:00Z host app - - - hello"
+
+              [[tests.outputs]]
+                extract_from = "in"
+                [[tests.outputs.conditions]]
+                  type = "check_fields"
+                  "appname.equals" = "app"
+
+              [[tests.outputs]]
+                extract_from = "foo"
+                [[tests.outputs.conditions]]
+                  type = "check_fields"
+                  "env.equals" = "test"
+
+              [[tests.outputs]]
+                extract_from = "out"
+                payload = "hello\n"
+
+            [[tests]]
+              name = "wrong payload"
+
+              [tests.input]
+                insert_at = "in"
+                value = "<13>1 2021-06-01T00:00:00Z host app - - - hello"
+
+              [[tests.outputs]]
+                extract_from = "out"
+                payload = "goodbye\n"
+        "#})
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        assert_eq!(tests[0].run().1, Vec::<String>::new());
+
+        let errors = tests[1].run().1;
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with(indoc! {r#"
+            check sink 'out' failed, payload doesn't match:
+              expected: "goodbye\n"
+                actual: "hello\n""#}));
+    }
+
+    #[cfg(all(feature = "sources-stdin", feature = "sources-syslog"))]
+    #[tokio::test]
+    async fn test_source_timestamps() {
+        let config: ConfigBuilder = toml::from_str(indoc! {r#"
+            [sources.lines]
+              type = "stdin"
+
+            [sources.syslog]
+              type = "syslog"
+              mode = "tcp"
+              address = "127.0.0.1:1514"
+
+            [[tests]]
+              name = "received events are stamped with the simulated clock"
+
+              [[tests.inputs]]
+                insert_at = "lines"
+                value = "hello"
+                delay_ms = 1500
+
+              [[tests.inputs]]
+                insert_at = "syslog"
+                value = "<13>1 2021-06-01T00:00:00Z host app - - - hello"
+
+              [[tests.outputs]]
+                extract_from = "lines"
+
+              [[tests.outputs]]
+                extract_from = "syslog"
+        "#})
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        let result = tests[0].run_with_snapshot();
+        assert_eq!(
+            result.snapshot.outputs["lines"][0]["timestamp"],
+            serde_json::to_value(Value::from(Utc.timestamp(1, 500_000_000))).unwrap()
+        );
+        assert!(result.snapshot.outputs["lines"][0].get("host").is_none());
+        assert_eq!(
+            result.snapshot.outputs["syslog"][0]["timestamp"],
+            serde_json::to_value(Value::from(Utc.ymd(2021, 6, 1).and_hms(0, 0, 0))).unwrap()
+        );
+    }
+
+    #[cfg(feature = "sinks-blackhole")]
+    #[tokio::test]
+    async fn test_unsupported_sink() {
+        let config: ConfigBuilder = toml::from_str(indoc! {r#"
+            [transforms.foo]
+              inputs = [ "ignored" ]
+              type = "add_fields"
+              [transforms.foo.fields]
+                env = "test"
+
+            [sinks.out]
+              inputs = [ "foo" ]
+              type = "blackhole"
+
+            [[tests]]
+              name = "unsupported sink"
+
+              [tests.input]
+                insert_at = "foo"
+                value = "hello"
+
+              [[tests.outputs]]
+                extract_from = "out"
+                payload = "hello"
+        "#})
+        .unwrap();
+
+        let errs = build_unit_tests(config).await.err().unwrap();
+        assert_eq!(
+            errs,
+            vec![indoc! {r#"
+                Failed to build test 'unsupported sink':
+                  sinks of type 'blackhole' can't encode test outputs"#}
+            .to_owned(),]
+        );
+    }
 }
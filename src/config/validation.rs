@@ -43,7 +43,7 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         uses.push(ctype);
     }
 
-    for (name, uses) in name_uses.into_iter().filter(|(_name, uses)| uses.len() > 1) {
+    for (name, uses) in name_uses.iter().filter(|(_name, uses)| uses.len() > 1) {
         errors.push(format!(
             "More than one component with name \"{}\" ({}).",
             name,
@@ -51,6 +51,17 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         ));
     }
 
+    // Named outputs share the namespace of the components
+    let named_outputs = named_outputs(config);
+    for (output, (ctype, name, _)) in &named_outputs {
+        if name_uses.contains_key(output.as_str()) {
+            errors.push(format!(
+                "Output \"{}\" of {} \"{}\" has the same name as a component.",
+                output, ctype, name
+            ));
+        }
+    }
+
     // Warnings and errors
     let sink_inputs = config
         .sinks
@@ -70,7 +81,10 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         }
 
         for input in inputs {
-            if !config.sources.contains_key(&input) && !config.transforms.contains_key(&input) {
+            if !config.sources.contains_key(&input)
+                && !config.transforms.contains_key(&input)
+                && !named_outputs.contains_key(&input)
+            {
                 errors.push(format!(
                     "Input {:?} for {} {:?} doesn't exist.",
                     input, output_type, name
@@ -86,6 +100,22 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// The named outputs of the components, along with the kind and name of their
+/// component and the type of their events.
+fn named_outputs(config: &ConfigBuilder) -> HashMap<String, (&'static str, &String, DataType)> {
+    let transform_outputs = config.transforms.iter().flat_map(|(name, transform)| {
+        transform
+            .named_outputs(name)
+            .into_iter()
+            .map(move |(output, ty)| (output, ("transform", name, ty)))
+    });
+    let sink_outputs = config.sinks.iter().filter_map(|(name, sink)| {
+        sink.dead_letter_output(name)
+            .map(|output| (output, ("sink", name, sink.inner.input_type())))
+    });
+    transform_outputs.chain(sink_outputs).collect()
+}
+
 pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     let source_resources = config
         .sources
@@ -122,14 +152,22 @@ pub fn warnings(config: &ConfigBuilder) -> Vec<String> {
         .keys()
         .map(|name| ("transform", name.clone()));
     for (input_type, name) in transform_names.chain(source_names) {
+        // Transforms are consumed through any of their outputs
+        let mut outputs = vec![name.clone()];
+        if let Some(transform) = config.transforms.get(&name) {
+            outputs.extend(
+                transform
+                    .named_outputs(&name)
+                    .into_iter()
+                    .map(|(output, _)| output),
+            );
+        }
+        let consumed = |inputs: &Vec<String>| outputs.iter().any(|output| inputs.contains(output));
         if !config
             .transforms
             .iter()
-            .any(|(_, transform)| transform.inputs.contains(&name))
-            && !config
-                .sinks
-                .iter()
-                .any(|(_, sink)| sink.inputs.contains(&name))
+            .any(|(_, transform)| consumed(&transform.inputs))
+            && !config.sinks.iter().any(|(_, sink)| consumed(&sink.inputs))
         {
             warnings.push(format!(
                 "{} {:?} has no consumers",
@@ -285,6 +323,25 @@ impl From<&ConfigBuilder> for Graph {
             graph.add_sink(name, config.inner.input_type(), config.inputs.clone());
         }
 
+        // Named outputs are nodes fed by the inputs of their component
+        for (name, config) in config.transforms.iter() {
+            for (output, ty) in config.named_outputs(name) {
+                graph.add_transform(
+                    &output,
+                    config.inner.input_type(),
+                    ty,
+                    config.inputs.clone(),
+                );
+            }
+        }
+
+        for (name, config) in config.sinks.iter() {
+            if let Some(output) = config.dead_letter_output(name) {
+                let ty = config.inner.input_type();
+                graph.add_transform(&output, ty, ty, config.inputs.clone());
+            }
+        }
+
         graph
     }
 }
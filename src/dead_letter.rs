@@ -0,0 +1,155 @@
+//! Dead-letter outputs
+//!
+//! Transforms configured to reroute the events they drop send them to their
+//! `<name>.dropped` output, and sinks with `dead_letter` enabled send copies
+//! of the events rejected downstream to their `<name>.dead_letter` output.
+//! These events carry a `dead_letter` field (or `dead_letter_*` tags for
+//! metrics) explaining why they were rejected, so they can be routed to
+//! storage and replayed later.
+
+use crate::event::{BatchNotifier, BatchStatus, Event};
+use futures::{future, Stream, StreamExt};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// Name of the named output of a transform carrying the events it dropped.
+pub const DROPPED: &str = "dropped";
+
+/// Name of the output of a sink carrying the events it had rejected.
+pub const DEAD_LETTER: &str = "dead_letter";
+
+/// Log field, and prefix of metric tags, holding why an event was rejected.
+const FIELD: &str = "dead_letter";
+
+/// Number of copies of events that wait for the sink to finalize their
+/// originals, and as many again queued behind them. Once both are full, the
+/// sink is held back until some of its events are finalized.
+const IN_FLIGHT: usize = 1024;
+
+/// Records why `event` was rejected: `reason` is a short description of the
+/// kind of failure, such as `error` or `rejected`, and `message` the details.
+pub fn set_reason(event: &mut Event, reason: &str, message: &str) {
+    insert(event, "reason", reason);
+    insert(event, "message", message);
+}
+
+/// Records which component rejected `event`.
+pub fn set_component(event: &mut Event, kind: &str, name: &str, component_type: &str) {
+    insert(event, "component_kind", kind);
+    insert(event, "component_name", name);
+    insert(event, "component_type", component_type);
+}
+
+fn insert(event: &mut Event, key: &str, value: &str) {
+    match event {
+        Event::Log(log) => {
+            log.insert(format!("{}.{}", FIELD, key), value);
+        }
+        Event::Metric(metric) => {
+            metric.set_tag_value(format!("{}_{}", FIELD, key), value.to_owned());
+        }
+    }
+}
+
+/// Tracks the delivery of the events of `input` to sink `name`, returning
+/// the events to pass on to the sink along with a stream of copies of the
+/// ones the sink rejected. The latter ends once the former was dropped and
+/// all of its events were finalized.
+///
+/// The copies are dropped as soon as their originals are delivered, and the
+/// statuses are awaited concurrently, so a slow request doesn't hold back the
+/// copies of the events of other requests.
+pub fn track_rejected<S>(
+    input: S,
+    name: String,
+    component_type: &'static str,
+) -> (
+    impl Stream<Item = Event> + Send,
+    impl Stream<Item = Event> + Send,
+)
+where
+    S: Stream<Item = Event> + Send,
+{
+    let (tx, rx) = mpsc::channel(IN_FLIGHT);
+
+    let input = input.then(move |mut event| {
+        // The copy is only sent on once the sink is done with the event, so
+        // it must not hold back the acknowledgement of the original.
+        let mut copy = event.clone();
+        drop(copy.metadata_mut().take_finalizers());
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        event.add_batch_notifier(batch);
+        let tx = tx.clone();
+        async move {
+            // The receiving end lives as long as the returned stream.
+            let _ = tx.send((receiver, copy)).await;
+            event
+        }
+    });
+
+    let rejected = ReceiverStream::new(rx)
+        .map(|(receiver, copy)| async move { (receiver.recv_with_reason().await, copy) })
+        .buffer_unordered(IN_FLIGHT)
+        .filter_map(move |((status, reason), mut event)| {
+            future::ready((status == BatchStatus::Failed).then(|| {
+                let message = reason.as_deref().unwrap_or("Rejected by the sink.");
+                set_reason(&mut event, "rejected", message);
+                set_component(&mut event, "sink", &name, component_type);
+                event
+            }))
+        });
+
+    (input, rejected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{EventStatus, LogEvent};
+    use futures::stream;
+
+    #[tokio::test]
+    async fn tracks_rejected_events() {
+        let events = vec![Event::from("delivered"), Event::from("rejected")];
+        let (input, rejected) = track_rejected(stream::iter(events), "out".into(), "http");
+
+        for event in input.collect::<Vec<_>>().await {
+            match event.as_log()["message"].to_string_lossy().as_str() {
+                "rejected" => event.metadata().reject("400 Bad Request"),
+                _ => event.metadata().update_status(EventStatus::Delivered),
+            }
+        }
+
+        let rejected = rejected.collect::<Vec<_>>().await;
+        assert_eq!(rejected.len(), 1);
+        let log: &LogEvent = rejected[0].as_log();
+        assert_eq!(log["message"], "rejected".into());
+        assert_eq!(log["dead_letter.reason"], "rejected".into());
+        assert_eq!(log["dead_letter.message"], "400 Bad Request".into());
+        assert_eq!(log["dead_letter.component_name"], "out".into());
+        assert_eq!(log["dead_letter.component_type"], "http".into());
+    }
+
+    #[tokio::test]
+    async fn copies_rejected_events_out_of_order() {
+        let events = vec![Event::from("first"), Event::from("second")];
+        let (input, rejected) = track_rejected(stream::iter(events), "out".into(), "http");
+        let mut events = input.collect::<Vec<_>>().await;
+
+        // The first event is still in flight when the second is rejected.
+        let first = events.remove(0);
+        events[0].metadata().reject("400 Bad Request");
+        drop(events);
+
+        let mut rejected = Box::pin(rejected);
+        let second = rejected.next().await.unwrap();
+        assert_eq!(second.as_log()["message"], "second".into());
+
+        first.metadata().reject("400 Bad Request");
+        drop(first);
+        let first = rejected.next().await.unwrap();
+        assert_eq!(first.as_log()["message"], "first".into());
+        assert!(rejected.next().await.is_none());
+    }
+}
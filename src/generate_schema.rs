@@ -0,0 +1,37 @@
+//! The `vector generate-schema` command, for printing the JSON Schema of
+//! configuration files.
+
+use crate::config::schema;
+use colored::*;
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Write the schema to a file instead of printing it.
+    #[structopt(long, parse(from_os_str))]
+    file: Option<PathBuf>,
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let schema =
+        serde_json::to_string_pretty(&schema::generate()).expect("Serializing the schema failed.");
+
+    match &opts.file {
+        Some(path) => match fs::write(path, schema + "\n") {
+            Ok(()) => {
+                println!("Schema written to {:?}.", path);
+                exitcode::OK
+            }
+            Err(error) => {
+                eprintln!("{}", format!("Failed to write {:?}: {}", path, error).red());
+                exitcode::IOERR
+            }
+        },
+        None => {
+            println!("{}", schema);
+            exitcode::OK
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! The `vector graph` command, for rendering the topology of a configuration
+//! as a DOT or Mermaid graph.
+
+use crate::config::{self, Config, DataType};
+use colored::*;
+use exitcode::ExitCode;
+use std::{fmt::Write, path::PathBuf};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Vector config files to render.
+    /// Format is detected from the file name.
+    /// If none are specified the default config path `/etc/vector/vector.toml`
+    /// will be targeted.
+    #[structopt(
+        name = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        use_delimiter(true)
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Format of the graph.
+    #[structopt(long, default_value = "dot", possible_values = &["dot", "mermaid"])]
+    format: Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Dot,
+    Mermaid,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(Format::Dot),
+            "mermaid" => Ok(Format::Mermaid),
+            s => Err(format!(
+                "{} is not a valid option, expected `dot` or `mermaid`",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Source,
+    Transform,
+    Sink,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Kind::Source => "source",
+            Kind::Transform => "transform",
+            Kind::Sink => "sink",
+        }
+    }
+
+    fn color(self) -> &'static str {
+        match self {
+            Kind::Source => "#c8e6c9",
+            Kind::Transform => "#bbdefb",
+            Kind::Sink => "#ffe0b2",
+        }
+    }
+}
+
+struct Node<'a> {
+    name: &'a str,
+    kind: Kind,
+    component_type: &'static str,
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    label: String,
+}
+
+pub fn cmd(opts: &Opts) -> ExitCode {
+    let paths = config::merge_path_lists(vec![(&opts.paths, None)]);
+    let config = config::process_paths(&paths)
+        .ok_or_else(|| vec!["No config file paths".to_owned()])
+        .and_then(|paths| {
+            config::init_log_schema(&paths, true)?;
+            config::load_from_paths(&paths)
+        });
+
+    match config {
+        Ok(config) => {
+            print!("{}", render(&config, opts.format));
+            exitcode::OK
+        }
+        Err(errors) => {
+            errors.iter().for_each(|error| eprintln!("{}", error.red()));
+            exitcode::CONFIG
+        }
+    }
+}
+
+fn render(config: &Config, format: Format) -> String {
+    let (nodes, edges) = topology(config);
+    match format {
+        Format::Dot => render_dot(&nodes, &edges),
+        Format::Mermaid => render_mermaid(&nodes, &edges),
+    }
+}
+
+/// The components of `config` and the connections between them, labelled
+/// with the output they come from and the type of their events.
+fn topology(config: &Config) -> (Vec<Node<'_>>, Vec<Edge>) {
+    let sources = config.sources.iter().map(|(name, source)| Node {
+        name,
+        kind: Kind::Source,
+        component_type: source.inner.source_type(),
+    });
+    let transforms = config.transforms.iter().map(|(name, transform)| Node {
+        name,
+        kind: Kind::Transform,
+        component_type: transform.inner.transform_type(),
+    });
+    let sinks = config.sinks.iter().map(|(name, sink)| Node {
+        name,
+        kind: Kind::Sink,
+        component_type: sink.inner.sink_type(),
+    });
+    let nodes = sources.chain(transforms).chain(sinks).collect::<Vec<_>>();
+    let index = |name: &str| nodes.iter().position(|node| node.name == name);
+
+    let transform_inputs = config
+        .transforms
+        .iter()
+        .map(|(name, transform)| (name, &transform.inputs));
+    let sink_inputs = config.sinks.iter().map(|(name, sink)| (name, &sink.inputs));
+    let mut edges = Vec::new();
+    for (name, inputs) in transform_inputs.chain(sink_inputs) {
+        for input in inputs {
+            let (component, data_type) = match output_type(config, input) {
+                Some(output) => output,
+                None => continue,
+            };
+            let data_type = data_type_name(data_type);
+            let label = match input
+                .strip_prefix(component)
+                .and_then(|s| s.strip_prefix('.'))
+            {
+                Some(output) => format!("{}: {}", output, data_type),
+                None => data_type.to_owned(),
+            };
+            if let (Some(from), Some(to)) = (index(component), index(name)) {
+                edges.push(Edge { from, to, label });
+            }
+        }
+    }
+
+    (nodes, edges)
+}
+
+/// The component `input` refers to, along with the type of the events of the
+/// output it designates.
+fn output_type<'a>(config: &'a Config, input: &'a str) -> Option<(&'a str, DataType)> {
+    let component = config.input_component(input)?;
+    let data_type = if let Some(source) = config.sources.get(component) {
+        source.inner.output_type()
+    } else if let Some(sink) = config.sinks.get(component) {
+        // Dead-letter outputs carry the events of the sink
+        sink.inner.input_type()
+    } else {
+        let transform = &config.transforms[component];
+        if input == component {
+            transform.inner.output_type()
+        } else {
+            transform
+                .named_outputs(component)
+                .into_iter()
+                .find(|(output, _)| output == input)
+                .map(|(_, data_type)| data_type)?
+        }
+    };
+    Some((component, data_type))
+}
+
+fn data_type_name(data_type: DataType) -> &'static str {
+    match data_type {
+        DataType::Any => "any",
+        DataType::Log => "log",
+        DataType::Metric => "metric",
+    }
+}
+
+fn render_dot(nodes: &[Node<'_>], edges: &[Edge]) -> String {
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+
+    let mut out = String::from("digraph {\n");
+    for node in nodes {
+        let label = format!(
+            "{}\n({} {})",
+            node.name,
+            node.component_type,
+            node.kind.as_str()
+        );
+        writeln!(
+            out,
+            "  {} [shape=box, style=filled, fillcolor={}, label={}]",
+            quote(node.name),
+            quote(node.kind.color()),
+            quote(&label).replace('\n', "\\n"),
+        )
+        .expect("Writing to a String failed.");
+    }
+    for edge in edges {
+        writeln!(
+            out,
+            "  {} -> {} [label={}]",
+            quote(nodes[edge.from].name),
+            quote(nodes[edge.to].name),
+            quote(&edge.label),
+        )
+        .expect("Writing to a String failed.");
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_mermaid(nodes: &[Node<'_>], edges: &[Edge]) -> String {
+    // Component names aren't valid Mermaid ids, so nodes are numbered.
+    let escape = |s: &str| s.replace('"', "#quot;");
+
+    let mut out = String::from("flowchart LR\n");
+    for (i, node) in nodes.iter().enumerate() {
+        writeln!(
+            out,
+            "  n{}[\"{}<br>({} {})\"]:::{}",
+            i,
+            escape(node.name),
+            node.component_type,
+            node.kind.as_str(),
+            node.kind.as_str(),
+        )
+        .expect("Writing to a String failed.");
+    }
+    for edge in edges {
+        writeln!(
+            out,
+            "  n{} -->|\"{}\"| n{}",
+            edge.from,
+            escape(&edge.label),
+            edge.to
+        )
+        .expect("Writing to a String failed.");
+    }
+    for kind in &[Kind::Source, Kind::Transform, Kind::Sink] {
+        writeln!(out, "  classDef {} fill:{}", kind.as_str(), kind.color())
+            .expect("Writing to a String failed.");
+    }
+    out
+}
+
+#[cfg(all(
+    test,
+    feature = "sources-stdin",
+    feature = "transforms-log_to_metric",
+    feature = "sinks-console"
+))]
+mod tests {
+    use super::*;
+    use crate::config::Format as ConfigFormat;
+    use indoc::indoc;
+
+    fn load() -> Config {
+        config::load_from_str(
+            indoc! {r#"
+                [sources.in]
+                type = "stdin"
+
+                [transforms.metrics]
+                type = "log_to_metric"
+                inputs = ["in"]
+                metrics = [{ type = "counter", field = "code" }]
+
+                [sinks.out]
+                type = "console"
+                inputs = ["metrics", "metrics.unmatched"]
+                encoding = "json"
+            "#},
+            Some(ConfigFormat::Toml),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn renders_dot() {
+        assert_eq!(
+            render(&load(), Format::Dot),
+            indoc! {r##"
+                digraph {
+                  "in" [shape=box, style=filled, fillcolor="#c8e6c9", label="in\n(stdin source)"]
+                  "metrics" [shape=box, style=filled, fillcolor="#bbdefb", label="metrics\n(log_to_metric transform)"]
+                  "out" [shape=box, style=filled, fillcolor="#ffe0b2", label="out\n(console sink)"]
+                  "in" -> "metrics" [label="log"]
+                  "metrics" -> "out" [label="metric"]
+                  "metrics" -> "out" [label="unmatched: log"]
+                }
+            "##}
+        );
+    }
+
+    #[test]
+    fn renders_mermaid() {
+        assert_eq!(
+            render(&load(), Format::Mermaid),
+            indoc! {r#"
+                flowchart LR
+                  n0["in<br>(stdin source)"]:::source
+                  n1["metrics<br>(log_to_metric transform)"]:::transform
+                  n2["out<br>(console sink)"]:::sink
+                  n0 -->|"log"| n1
+                  n1 -->|"metric"| n2
+                  n1 -->|"unmatched: log"| n2
+                  classDef source fill:#c8e6c9
+                  classDef transform fill:#bbdefb
+                  classDef sink fill:#ffe0b2
+            "#}
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! The `vector buffer` command, for inspecting and repairing the disk buffers
+//! of a stopped Vector instance.
+
+use crate::{
+    buffers::disk::leveldb_buffer::inspect::{self, Inspector},
+    config,
+    event::Event,
+};
+use colored::*;
+use exitcode::ExitCode;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Vector config files to read `data_dir` from.
+    /// Format is detected from the file name.
+    /// If none are specified the default config path `/etc/vector/vector.toml`
+    /// will be targeted.
+    #[structopt(
+        name = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        use_delimiter(true)
+    )]
+    paths: Vec<PathBuf>,
+
+    /// The data directory to use instead of the `data_dir` of the configuration.
+    #[structopt(long)]
+    data_dir: Option<PathBuf>,
+
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(rename_all = "kebab-case")]
+enum Command {
+    /// List the disk buffers of all sinks, with the number of events and bytes in them.
+    List,
+
+    /// Print events of the buffer of a sink as JSON, one per line, along with their keys.
+    Dump {
+        /// The sink whose buffer to dump.
+        sink: String,
+
+        /// Number of events to print.
+        #[structopt(short = "n", long, default_value = "10")]
+        count: usize,
+
+        /// Number of events to skip before printing.
+        #[structopt(long, default_value = "0")]
+        skip: usize,
+    },
+
+    /// Delete the events with keys in the given range from the buffer of a sink.
+    Truncate {
+        /// The sink whose buffer to truncate.
+        sink: String,
+
+        /// First key to delete.
+        #[structopt(long)]
+        from: Option<usize>,
+
+        /// First key to keep after the deleted ones.
+        #[structopt(long)]
+        to: Option<usize>,
+    },
+
+    /// Delete the records which can't be decoded from the buffer of a sink.
+    SkipCorrupted {
+        /// The sink whose buffer to repair.
+        sink: String,
+    },
+}
+
+pub fn cmd(opts: &Opts) -> ExitCode {
+    let data_dir = match data_dir(opts) {
+        Ok(data_dir) => data_dir,
+        Err(errors) => {
+            errors.iter().for_each(|error| eprintln!("{}", error.red()));
+            return exitcode::CONFIG;
+        }
+    };
+
+    match &opts.command {
+        Command::List => {
+            let names = match inspect::list(&data_dir) {
+                Ok(names) => names,
+                Err(error) => {
+                    eprintln!(
+                        "{}",
+                        format!("Unable to read {:?}: {}", data_dir, error).red()
+                    );
+                    return exitcode::IOERR;
+                }
+            };
+            for name in names {
+                match Inspector::open(&data_dir, &name) {
+                    Ok(buffer) => {
+                        let stats = buffer.stats::<Event>();
+                        println!(
+                            "{}: {} events, {} bytes, {} corrupted",
+                            name, stats.events, stats.bytes, stats.corrupted
+                        );
+                    }
+                    Err(error) => println!("{}: {}", name, error.to_string().red()),
+                }
+            }
+            exitcode::OK
+        }
+        Command::Dump { sink, count, skip } => with_buffer(&data_dir, sink, |buffer| {
+            for record in buffer.records::<Event>().skip(*skip).take(*count) {
+                let line = match record.event {
+                    Ok(event) => json!({
+                        "key": record.key,
+                        "size": record.size,
+                        "event": match event {
+                            Event::Log(log) => serde_json::to_value(log),
+                            Event::Metric(metric) => serde_json::to_value(metric),
+                        }
+                        .expect("JSON serialization of event failed. Please report."),
+                    }),
+                    Err(error) => json!({
+                        "key": record.key,
+                        "size": record.size,
+                        "error": error,
+                    }),
+                };
+                println!("{}", line);
+            }
+        }),
+        Command::Truncate { sink, from, to } => {
+            if from.is_none() && to.is_none() {
+                eprintln!("{}", "Either --from or --to is required.".red());
+                return exitcode::USAGE;
+            }
+            with_buffer(&data_dir, sink, |buffer| {
+                println!("Deleted {} events.", buffer.truncate(*from, *to));
+            })
+        }
+        Command::SkipCorrupted { sink } => with_buffer(&data_dir, sink, |buffer| {
+            println!(
+                "Deleted {} corrupted records.",
+                buffer.skip_corrupted::<Event>()
+            );
+        }),
+    }
+}
+
+fn data_dir(opts: &Opts) -> Result<PathBuf, Vec<String>> {
+    if let Some(data_dir) = &opts.data_dir {
+        return Ok(data_dir.clone());
+    }
+
+    let paths = config::merge_path_lists(vec![(&opts.paths, None)]);
+    let paths = config::process_paths(&paths).ok_or_else(|| vec!["No config file paths".into()])?;
+    config::init_log_schema(&paths, true)?;
+    let config = config::load_from_paths(&paths)?;
+    config
+        .global
+        .data_dir
+        .ok_or_else(|| vec!["The configuration has no data_dir, set one with --data-dir".into()])
+}
+
+fn with_buffer(data_dir: &Path, sink: &str, f: impl FnOnce(&Inspector)) -> ExitCode {
+    match Inspector::open(data_dir, sink) {
+        Ok(buffer) => {
+            f(&buffer);
+            exitcode::OK
+        }
+        Err(error) => {
+            eprintln!(
+                "{}",
+                format!(
+                    "Unable to open the buffer of sink {:?}, is Vector still running? {}",
+                    sink, error
+                )
+                .red()
+            );
+            exitcode::IOERR
+        }
+    }
+}
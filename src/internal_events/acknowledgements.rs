@@ -0,0 +1,29 @@
+use super::InternalEvent;
+use metrics::counter;
+use vector_core::event::BatchStatus;
+
+/// A source didn't acknowledge a batch of events upstream because not all
+/// sinks delivered them.
+#[derive(Debug)]
+pub struct BatchNotDelivered {
+    pub status: BatchStatus,
+}
+
+impl InternalEvent for BatchNotDelivered {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Events were not delivered by all sinks; not acknowledging them upstream.",
+            status = ?self.status,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        let status = match self.status {
+            BatchStatus::Delivered => "delivered",
+            BatchStatus::Errored => "errored",
+            BatchStatus::Failed => "failed",
+        };
+        counter!("batches_not_delivered_total", 1, "status" => status);
+    }
+}
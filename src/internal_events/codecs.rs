@@ -0,0 +1,46 @@
+use super::InternalEvent;
+use crate::codecs::ParseError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub(crate) struct DecoderParseFailed {
+    pub error: ParseError,
+}
+
+impl InternalEvent for DecoderParseFailed {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to parse frame.",
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1,
+            "error_type" => "failed_parse",
+            "codec" => self.error.kind(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DecoderFramingFailed {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for DecoderFramingFailed {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed framing bytes.",
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1,
+            "error_type" => "failed_framing",
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 
+mod acknowledgements;
 mod adaptive_concurrency;
 mod add_fields;
 mod add_tags;
@@ -23,6 +24,7 @@ pub(crate) mod aws_s3;
 #[cfg(feature = "sinks-aws_sqs")]
 mod aws_sqs;
 mod blackhole;
+mod codecs;
 #[cfg(feature = "transforms-coercer")]
 mod coercer;
 #[cfg(feature = "transforms-concat")]
@@ -123,6 +125,7 @@ mod wasm;
 
 pub mod kubernetes;
 
+pub use self::acknowledgements::*;
 pub use self::adaptive_concurrency::*;
 pub use self::add_fields::*;
 pub use self::add_tags::*;
@@ -144,6 +147,7 @@ pub use self::aws_kinesis_streams::*;
 #[cfg(feature = "sinks-aws_sqs")]
 pub use self::aws_sqs::*;
 pub use self::blackhole::*;
+pub(crate) use self::codecs::*;
 #[cfg(feature = "transforms-coercer")]
 pub(crate) use self::coercer::*;
 #[cfg(feature = "transforms-concat")]
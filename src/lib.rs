@@ -25,6 +25,8 @@ pub mod conditions;
 pub mod dns;
 pub mod expiring_hash_map;
 pub mod generate;
+pub mod generate_schema;
+pub mod graph;
 #[cfg(feature = "wasm")]
 pub mod wasm;
 #[macro_use]
@@ -34,9 +36,13 @@ pub mod api;
 pub mod app;
 pub mod async_read;
 pub mod buffers;
+pub mod codecs;
+pub mod dead_letter;
 pub mod encoding_transcode;
 pub mod heartbeat;
 pub mod http;
+#[cfg(feature = "disk-buffer")]
+pub mod inspect_buffers;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 pub mod kafka;
 pub mod kubernetes;
@@ -0,0 +1,224 @@
+use super::Result;
+use crate::{
+    config::{
+        self,
+        provider::{ProviderConfig, ProviderDescription},
+        Format,
+    },
+    signal,
+};
+use async_stream::stream;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::time;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct DirectoryConfig {
+    path: Option<PathBuf>,
+    poll_interval_secs: u64,
+}
+
+impl Default for DirectoryConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            poll_interval_secs: 5,
+        }
+    }
+}
+
+/// The contents of the config fragments in a directory, used to tell whether
+/// any of them changed.
+type Fingerprint = Vec<(PathBuf, Vec<u8>)>;
+
+/// Lists the config fragments in `dir`, that is the files with an extension of
+/// a known config format, sorted by name.
+fn fragments(dir: &Path) -> std::result::Result<Vec<PathBuf>, Vec<String>> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|error| vec![format!("Could not read directory {:?}: {}.", dir, error)])?;
+
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && Format::from_path(path).is_ok())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    if paths.is_empty() {
+        Err(vec![format!("No config fragments found in {:?}.", dir)])
+    } else {
+        Ok(paths)
+    }
+}
+
+fn fingerprint(dir: &Path) -> std::result::Result<Fingerprint, Vec<String>> {
+    fragments(dir)?
+        .into_iter()
+        .map(|path| {
+            std::fs::read(&path)
+                .map(|content| (path.clone(), content))
+                .map_err(|error| vec![format!("Could not read {:?}: {}.", path, error)])
+        })
+        .collect()
+}
+
+/// Merges the config fragments in `dir` into a `ConfigBuilder`.
+pub(super) fn load_directory(dir: &Path) -> Result {
+    let paths = fragments(dir)?
+        .into_iter()
+        .map(|path| (path, None))
+        .collect::<Vec<_>>();
+
+    let (config_builder, warnings) = config::load_builder_from_paths(&paths)?;
+    if config_builder.provider.is_some() {
+        return Err(vec![format!(
+            "Config fragments in {:?} must not declare a provider.",
+            dir
+        )]);
+    }
+
+    for warning in warnings.into_iter() {
+        warn!("{}", warning);
+    }
+
+    Ok(config_builder)
+}
+
+/// Checks the directory every `poll_interval_secs`, returning a stream of `ConfigBuilder`
+/// whenever a fragment was added, changed or removed.
+fn poll_directory(
+    poll_interval_secs: u64,
+    path: PathBuf,
+    mut last: Option<Fingerprint>,
+) -> impl Stream<Item = signal::SignalTo> {
+    let duration = time::Duration::from_secs(poll_interval_secs);
+    let mut interval = time::interval_at(time::Instant::now() + duration, duration);
+
+    stream! {
+        loop {
+            interval.tick().await;
+
+            let current = fingerprint(&path).ok();
+            if current.is_none() || current == last {
+                continue;
+            }
+            last = current;
+
+            info!(message = "Configuration fragments changed.", path = ?path);
+            match load_directory(&path) {
+                Ok(config_builder) => yield signal::SignalTo::ReloadFromConfigBuilder(config_builder),
+                Err(errors) => {
+                    for error in errors {
+                        error!(message = "Could not load configuration fragments.", %error, path = ?path);
+                    }
+                }
+            };
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "directory")]
+impl ProviderConfig for DirectoryConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> Result {
+        let path = self
+            .path
+            .take()
+            .ok_or_else(|| vec!["Path is required for the `directory` provider.".to_owned()])?;
+
+        let last = fingerprint(&path).ok();
+        let config_builder = load_directory(&path)?;
+
+        // Reload whenever the fragments change.
+        signal_handler.add(poll_directory(self.poll_interval_secs, path, last));
+
+        Ok(config_builder)
+    }
+
+    fn provider_type(&self) -> &'static str {
+        "directory"
+    }
+}
+
+inventory::submit! {
+    ProviderDescription::new::<DirectoryConfig>("directory")
+}
+
+impl_generate_config_from_default!(DirectoryConfig);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use std::fs;
+
+    #[cfg(all(feature = "sources-stdin", feature = "sinks-blackhole"))]
+    #[tokio::test]
+    async fn merges_fragments_and_reloads_on_change() {
+        let dir = temp_dir();
+        fs::create_dir(&dir).unwrap();
+        fs::write(
+            dir.join("source.toml"),
+            r#"
+            [sources.in]
+            type = "stdin"
+            "#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("sink.yaml"),
+            r#"
+            sinks:
+              out:
+                type: blackhole
+                inputs: ["in"]
+            "#,
+        )
+        .unwrap();
+        fs::write(dir.join("README.md"), "Not a fragment.").unwrap();
+
+        let (mut signal_handler, mut signal_rx) = signal::SignalHandler::new();
+        let mut provider = DirectoryConfig {
+            path: Some(dir.clone()),
+            poll_interval_secs: 1,
+        };
+        let config_builder = provider.build(&mut signal_handler).await.unwrap();
+        assert!(config_builder.sources.contains_key("in"));
+        assert!(config_builder.sinks.contains_key("out"));
+
+        fs::write(
+            dir.join("other_sink.toml"),
+            r#"
+            [sinks.other]
+            type = "blackhole"
+            inputs = ["in"]
+            "#,
+        )
+        .unwrap();
+
+        match time::timeout(time::Duration::from_secs(5), signal_rx.recv()).await {
+            Ok(Some(signal::SignalTo::ReloadFromConfigBuilder(config_builder))) => {
+                assert!(config_builder.sinks.contains_key("other"));
+                assert!(config_builder.sinks.contains_key("out"));
+            }
+            _ => panic!("Expected a reload."),
+        }
+
+        signal_handler.clear();
+    }
+
+    #[tokio::test]
+    async fn rejects_empty_directories() {
+        let dir = temp_dir();
+        fs::create_dir(&dir).unwrap();
+
+        let (mut signal_handler, _signal_rx) = signal::SignalHandler::new();
+        let mut provider = DirectoryConfig {
+            path: Some(dir),
+            poll_interval_secs: 1,
+        };
+        let errors = provider.build(&mut signal_handler).await.unwrap_err();
+        assert!(errors[0].starts_with("No config fragments found"));
+    }
+}
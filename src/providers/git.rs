@@ -0,0 +1,297 @@
+use super::{directory::load_directory, Result};
+use crate::{
+    config::{
+        self,
+        provider::{ProviderConfig, ProviderDescription},
+    },
+    signal,
+};
+use async_stream::stream;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::{process::Command, time};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct GitConfig {
+    repository: Option<String>,
+    #[serde(rename = "ref")]
+    reference: String,
+    path: PathBuf,
+    checkout_dir: Option<PathBuf>,
+    poll_interval_secs: u64,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            repository: None,
+            reference: "HEAD".to_owned(),
+            path: PathBuf::new(),
+            checkout_dir: None,
+            poll_interval_secs: 30,
+        }
+    }
+}
+
+/// A working tree following a ref of a repository.
+#[derive(Debug, Clone)]
+struct Checkout {
+    repository: String,
+    reference: String,
+    dir: PathBuf,
+}
+
+impl Checkout {
+    async fn git(&self, args: &[&str]) -> std::result::Result<String, String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.dir)
+            .args(args)
+            .output()
+            .await
+            .map_err(|error| format!("Could not run git: {}.", error))?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        } else {
+            Err(format!(
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    async fn init(&self) -> std::result::Result<(), String> {
+        std::fs::create_dir_all(&self.dir).map_err(|error| {
+            format!(
+                "Could not create checkout directory {:?}: {}.",
+                self.dir, error
+            )
+        })?;
+        self.git(&["init", "--quiet"]).await.map(|_| ())
+    }
+
+    /// Fetches the ref, returning the commit it points to.
+    async fn fetch(&self) -> std::result::Result<String, String> {
+        self.git(&[
+            "fetch",
+            "--quiet",
+            "--force",
+            &self.repository,
+            &self.reference,
+        ])
+        .await?;
+        self.git(&["rev-parse", "FETCH_HEAD"]).await
+    }
+
+    async fn checkout(&self, commit: &str) -> std::result::Result<(), String> {
+        self.git(&["checkout", "--quiet", "--force", "--detach", commit])
+            .await
+            .map(|_| ())
+    }
+}
+
+/// Loads the configuration at `path` in the working tree, which is either a file or a
+/// directory of config fragments.
+fn load_path(dir: &Path, path: &Path) -> Result {
+    let path = dir.join(path);
+    if path.is_dir() {
+        return load_directory(&path);
+    }
+
+    let (config_builder, warnings) = config::load_builder_from_paths(&[(path.clone(), None)])?;
+    if config_builder.provider.is_some() {
+        return Err(vec![format!("{:?} must not declare a provider.", path)]);
+    }
+
+    for warning in warnings.into_iter() {
+        warn!("{}", warning);
+    }
+
+    Ok(config_builder)
+}
+
+/// Fetches the ref every `poll_interval_secs`, returning a stream of `ConfigBuilder` whenever
+/// it points to another commit.
+fn poll_git(
+    poll_interval_secs: u64,
+    checkout: Checkout,
+    path: PathBuf,
+    mut last: String,
+) -> impl Stream<Item = signal::SignalTo> {
+    let duration = time::Duration::from_secs(poll_interval_secs);
+    let mut interval = time::interval_at(time::Instant::now() + duration, duration);
+
+    stream! {
+        loop {
+            interval.tick().await;
+
+            let commit = match checkout.fetch().await {
+                Ok(commit) if commit != last => commit,
+                Ok(_) => continue,
+                Err(error) => {
+                    error!(message = "Could not fetch configuration.", %error, repository = ?checkout.repository);
+                    continue;
+                }
+            };
+
+            info!(message = "Configuration changed.", %commit, repository = ?checkout.repository);
+            if let Err(error) = checkout.checkout(&commit).await {
+                error!(message = "Could not check out configuration.", %error, repository = ?checkout.repository);
+                continue;
+            }
+            last = commit;
+
+            match load_path(&checkout.dir, &path) {
+                Ok(config_builder) => yield signal::SignalTo::ReloadFromConfigBuilder(config_builder),
+                Err(errors) => {
+                    for error in errors {
+                        error!(message = "Could not load configuration.", %error, repository = ?checkout.repository);
+                    }
+                }
+            };
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "git")]
+impl ProviderConfig for GitConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> Result {
+        let mut repository = self
+            .repository
+            .take()
+            .ok_or_else(|| vec!["Repository is required for the `git` provider.".to_owned()])?;
+
+        // Git runs in the checkout directory, so local repositories need an absolute path.
+        if let Ok(path) = Path::new(&repository).canonicalize() {
+            repository = path.to_string_lossy().into_owned();
+        }
+
+        let checkout = Checkout {
+            repository,
+            reference: self.reference.clone(),
+            dir: self.checkout_dir.take().unwrap_or_else(|| {
+                std::env::temp_dir().join(format!("vector-git-provider-{}", std::process::id()))
+            }),
+        };
+
+        info!(
+            message = "Attempting to retrieve configuration.",
+            repository = ?checkout.repository,
+            reference = ?checkout.reference
+        );
+        checkout.init().await.map_err(|error| vec![error])?;
+        let commit = checkout.fetch().await.map_err(|error| vec![error])?;
+        checkout
+            .checkout(&commit)
+            .await
+            .map_err(|error| vec![error])?;
+        let config_builder = load_path(&checkout.dir, &self.path)?;
+
+        // Poll for new commits.
+        signal_handler.add(poll_git(
+            self.poll_interval_secs,
+            checkout,
+            self.path.clone(),
+            commit,
+        ));
+
+        Ok(config_builder)
+    }
+
+    fn provider_type(&self) -> &'static str {
+        "git"
+    }
+}
+
+inventory::submit! {
+    ProviderDescription::new::<GitConfig>("git")
+}
+
+impl_generate_config_from_default!(GitConfig);
+
+#[cfg(all(test, feature = "sources-stdin", feature = "sinks-blackhole"))]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use std::fs;
+
+    /// Commits `config` as `vector/vector.toml` to the repository in `dir`.
+    fn commit(dir: &Path, config: &str) {
+        fs::create_dir_all(dir.join("vector")).unwrap();
+        fs::write(dir.join("vector/vector.toml"), config).unwrap();
+
+        for args in &[
+            vec!["add", "--all"],
+            vec![
+                "-c",
+                "user.name=Vector",
+                "-c",
+                "user.email=vector@example.com",
+                "commit",
+                "--quiet",
+                "--message",
+                "Update configuration",
+            ],
+        ] {
+            let status = std::process::Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+    }
+
+    const CONFIG: &str = r#"
+        [sources.in]
+        type = "stdin"
+
+        [sinks.out]
+        type = "blackhole"
+        inputs = ["in"]
+    "#;
+
+    #[tokio::test]
+    async fn checks_out_ref_and_reloads_on_commit() {
+        let repository = temp_dir();
+        fs::create_dir(&repository).unwrap();
+        assert!(std::process::Command::new("git")
+            .args(&["init", "--quiet"])
+            .arg(&repository)
+            .status()
+            .unwrap()
+            .success());
+        commit(&repository, CONFIG);
+
+        let (mut signal_handler, mut signal_rx) = signal::SignalHandler::new();
+        let mut provider = GitConfig {
+            repository: Some(repository.to_string_lossy().into_owned()),
+            path: "vector/vector.toml".into(),
+            checkout_dir: Some(temp_dir()),
+            poll_interval_secs: 1,
+            ..Default::default()
+        };
+        let config_builder = provider.build(&mut signal_handler).await.unwrap();
+        assert!(config_builder.sinks.contains_key("out"));
+
+        commit(
+            &repository,
+            &CONFIG.replace("[sinks.out]", "[sinks.renamed]"),
+        );
+
+        match time::timeout(time::Duration::from_secs(5), signal_rx.recv()).await {
+            Ok(Some(signal::SignalTo::ReloadFromConfigBuilder(config_builder))) => {
+                assert!(config_builder.sinks.contains_key("renamed"));
+            }
+            _ => panic!("Expected a reload."),
+        }
+
+        signal_handler.clear();
+    }
+}
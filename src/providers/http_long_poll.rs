@@ -0,0 +1,364 @@
+use super::{http::RequestConfig, Result};
+use crate::{
+    config::{
+        self,
+        provider::{ProviderConfig, ProviderDescription},
+    },
+    http::HttpClient,
+    signal,
+    tls::{TlsOptions, TlsSettings},
+};
+use async_stream::stream;
+use bytes::{Buf, Bytes};
+use futures::Stream;
+use http::{header, StatusCode};
+use hyper::Body;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use tokio::time;
+use url::Url;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct HttpLongPollConfig {
+    url: Option<Url>,
+    request: RequestConfig,
+    retry_backoff_secs: u64,
+    #[serde(flatten)]
+    tls_options: Option<TlsOptions>,
+}
+
+impl Default for HttpLongPollConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            request: RequestConfig::default(),
+            retry_backoff_secs: 5,
+            tls_options: None,
+        }
+    }
+}
+
+/// The outcome of a long-poll request.
+enum Poll {
+    /// The configuration, along with its `ETag` if the server supports them.
+    Changed { config: Bytes, etag: Option<String> },
+    /// The configuration still matches the `ETag` of the request. Servers answer this way once
+    /// they stop waiting for a change.
+    NotModified,
+}
+
+/// Requests the configuration, which the server holds back until it no longer matches `etag`.
+async fn long_poll(
+    http_client: &HttpClient,
+    url: &Url,
+    headers: &IndexMap<String, String>,
+    etag: Option<&str>,
+) -> std::result::Result<Poll, String> {
+    let mut builder = http::request::Builder::new().uri(url.to_string());
+    for (name, value) in headers.iter() {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    if let Some(etag) = etag {
+        builder = builder.header(header::IF_NONE_MATCH, etag);
+    }
+
+    let request = builder
+        .body(Body::empty())
+        .map_err(|_| "Couldn't create HTTP request".to_owned())?;
+
+    let response = http_client
+        .send(request)
+        .await
+        .map_err(|error| format!("HTTP error: {}", error))?;
+
+    match response.status() {
+        StatusCode::NOT_MODIFIED => Ok(Poll::NotModified),
+        status if status.is_success() => {
+            let etag = response
+                .headers()
+                .get(header::ETAG)
+                .and_then(|etag| etag.to_str().ok())
+                .map(ToOwned::to_owned);
+            let config = hyper::body::to_bytes(response.into_body())
+                .await
+                .map_err(|error| format!("Error interpreting response: {}", error))?;
+            Ok(Poll::Changed { config, etag })
+        }
+        status => Err(format!("Unexpected HTTP status: {}", status)),
+    }
+}
+
+fn load(config: &Bytes) -> Result {
+    let (config_builder, warnings) = config::load(config.chunk(), None)?;
+
+    for warning in warnings.into_iter() {
+        warn!("{}", warning);
+    }
+
+    Ok(config_builder)
+}
+
+/// Long-polls the HTTP endpoint, returning a stream of `ConfigBuilder` whenever the
+/// configuration changes. Requests start at least `retry_backoff_secs` apart, so endpoints which
+/// answer right away, without `ETag`s or without holding requests back, aren't polled in a loop.
+fn watch_http(
+    http_client: HttpClient,
+    url: Url,
+    headers: IndexMap<String, String>,
+    retry_backoff_secs: u64,
+    mut last: Bytes,
+    mut etag: Option<String>,
+) -> impl Stream<Item = signal::SignalTo> {
+    let retry_backoff = time::Duration::from_secs(retry_backoff_secs);
+
+    stream! {
+        let mut requested_at = None;
+        loop {
+            if let Some(requested_at) = requested_at {
+                time::sleep_until(requested_at + retry_backoff).await;
+            }
+            requested_at = Some(time::Instant::now());
+
+            match long_poll(&http_client, &url, &headers, etag.as_deref()).await {
+                Ok(Poll::NotModified) => continue,
+                Ok(Poll::Changed { config, etag: new_etag }) => {
+                    etag = new_etag;
+                    if config == last {
+                        continue;
+                    }
+                    last = config;
+
+                    info!(message = "Configuration changed.", url = ?url.as_str());
+                    match load(&last) {
+                        Ok(config_builder) => yield signal::SignalTo::ReloadFromConfigBuilder(config_builder),
+                        Err(errors) => {
+                            for error in errors {
+                                error!(message = "Could not load configuration.", %error, url = ?url.as_str());
+                            }
+                        }
+                    }
+                }
+                Err(error) => {
+                    error!(message = "Long-poll request failed.", %error, url = ?url.as_str());
+                    time::sleep(retry_backoff).await;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "http_long_poll")]
+impl ProviderConfig for HttpLongPollConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> Result {
+        let url = self
+            .url
+            .take()
+            .ok_or_else(|| vec!["URL is required for the `http_long_poll` provider.".to_owned()])?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls_options)
+            .map_err(|_| vec!["Invalid TLS options".to_owned()])?;
+        let http_client = HttpClient::<Body>::new(tls_settings)
+            .map_err(|_| vec!["Invalid TLS settings".to_owned()])?;
+        let headers = self.request.headers.clone();
+
+        info!(
+            message = "Attempting to retrieve configuration.",
+            url = ?url.as_str()
+        );
+        let (config, etag) = match long_poll(&http_client, &url, &headers, None).await {
+            Ok(Poll::Changed { config, etag }) => (config, etag),
+            Ok(Poll::NotModified) => {
+                return Err(vec!["Unexpected HTTP status: 304 Not Modified".to_owned()])
+            }
+            Err(error) => return Err(vec![error]),
+        };
+        let config_builder = load(&config)?;
+
+        // Wait for changes to remote configuration.
+        signal_handler.add(watch_http(
+            http_client,
+            url,
+            headers,
+            self.retry_backoff_secs,
+            config,
+            etag,
+        ));
+
+        Ok(config_builder)
+    }
+
+    fn provider_type(&self) -> &'static str {
+        "http_long_poll"
+    }
+}
+
+inventory::submit! {
+    ProviderDescription::new::<HttpLongPollConfig>("http_long_poll")
+}
+
+impl_generate_config_from_default!(HttpLongPollConfig);
+
+#[cfg(all(test, feature = "sources-stdin", feature = "sinks-blackhole"))]
+mod tests {
+    use super::*;
+    use crate::test_util::next_addr;
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Request, Response, Server,
+    };
+    use std::{
+        convert::Infallible,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+    };
+    use tokio::sync::Notify;
+
+    const CONFIG: &str = r#"
+        [sources.in]
+        type = "stdin"
+
+        [sinks.out]
+        type = "blackhole"
+        inputs = ["in"]
+    "#;
+
+    /// A stand-in for a long-polling server, which holds requests back until the version in
+    /// their `If-None-Match` header is outdated.
+    #[derive(Clone)]
+    struct Stub {
+        version: Arc<Mutex<(u64, String)>>,
+        changed: Arc<Notify>,
+    }
+
+    impl Stub {
+        fn update(&self, config: &str) {
+            let mut version = self.version.lock().unwrap();
+            *version = (version.0 + 1, config.to_owned());
+            self.changed.notify_waiters();
+        }
+
+        async fn respond(self, request: Request<Body>) -> Response<Body> {
+            let wanted = request
+                .headers()
+                .get(header::IF_NONE_MATCH)
+                .map(|etag| etag.to_str().unwrap().to_owned());
+
+            loop {
+                let changed = self.changed.notified();
+                let (etag, config) = {
+                    let version = self.version.lock().unwrap();
+                    (format!("\"{}\"", version.0), version.1.clone())
+                };
+                if wanted.as_ref() != Some(&etag) {
+                    return Response::builder()
+                        .header(header::ETAG, etag)
+                        .body(Body::from(config))
+                        .unwrap();
+                }
+                if time::timeout(time::Duration::from_secs(1), changed)
+                    .await
+                    .is_err()
+                {
+                    return Response::builder()
+                        .status(StatusCode::NOT_MODIFIED)
+                        .body(Body::empty())
+                        .unwrap();
+                }
+            }
+        }
+    }
+
+    fn serve(stub: Stub) -> Url {
+        let addr = next_addr();
+        let service = make_service_fn(move |_| {
+            let stub = stub.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let stub = stub.clone();
+                    async move { Ok::<_, Infallible>(stub.respond(request).await) }
+                }))
+            }
+        });
+        tokio::spawn(Server::bind(&addr).serve(service));
+
+        format!("http://{}/config", addr).parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn waits_between_immediate_answers() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let addr = next_addr();
+        let counted = Arc::clone(&requests);
+        let service = make_service_fn(move |_| {
+            let counted = Arc::clone(&counted);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    counted.fetch_add(1, Ordering::SeqCst);
+                    // Answers right away instead of holding requests back.
+                    let response = if request.headers().contains_key(header::IF_NONE_MATCH) {
+                        Response::builder()
+                            .status(StatusCode::NOT_MODIFIED)
+                            .body(Body::empty())
+                    } else {
+                        Response::builder()
+                            .header(header::ETAG, "\"1\"")
+                            .body(Body::from(CONFIG))
+                    };
+                    async move { Ok::<_, Infallible>(response.unwrap()) }
+                }))
+            }
+        });
+        tokio::spawn(Server::bind(&addr).serve(service));
+        time::sleep(time::Duration::from_millis(50)).await;
+
+        let (mut signal_handler, _signal_rx) = signal::SignalHandler::new();
+        let mut provider = HttpLongPollConfig {
+            url: Some(format!("http://{}/config", addr).parse().unwrap()),
+            retry_backoff_secs: 1,
+            ..Default::default()
+        };
+        provider.build(&mut signal_handler).await.unwrap();
+
+        time::sleep(time::Duration::from_millis(1500)).await;
+        // The initial request, then one watching request per second.
+        assert!(requests.load(Ordering::SeqCst) <= 3);
+
+        signal_handler.clear();
+    }
+
+    #[tokio::test]
+    async fn reloads_when_etag_changes() {
+        let stub = Stub {
+            version: Arc::new(Mutex::new((1, CONFIG.to_owned()))),
+            changed: Arc::new(Notify::new()),
+        };
+        let url = serve(stub.clone());
+        time::sleep(time::Duration::from_millis(50)).await;
+
+        let (mut signal_handler, mut signal_rx) = signal::SignalHandler::new();
+        let mut provider = HttpLongPollConfig {
+            url: Some(url),
+            retry_backoff_secs: 1,
+            ..Default::default()
+        };
+        let config_builder = provider.build(&mut signal_handler).await.unwrap();
+        assert!(config_builder.sinks.contains_key("out"));
+
+        // Outlive a few `304 Not Modified` answers before changing the config.
+        time::sleep(time::Duration::from_millis(2500)).await;
+        stub.update(&CONFIG.replace("[sinks.out]", "[sinks.renamed]"));
+
+        match time::timeout(time::Duration::from_secs(5), signal_rx.recv()).await {
+            Ok(Some(signal::SignalTo::ReloadFromConfigBuilder(config_builder))) => {
+                assert!(config_builder.sinks.contains_key("renamed"));
+            }
+            _ => panic!("Expected a reload."),
+        }
+
+        signal_handler.clear();
+    }
+}
@@ -1,4 +1,7 @@
+pub mod directory;
+pub mod git;
 pub mod http;
+pub mod http_long_poll;
 
 use super::config::ConfigBuilder;
 
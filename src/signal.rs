@@ -1,5 +1,5 @@
-use super::config::ConfigBuilder;
-use tokio::sync::{broadcast, mpsc};
+use super::config::{Config, ConfigBuilder, ConfigDiff};
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio_stream::{Stream, StreamExt};
 
 pub type ShutdownTx = broadcast::Sender<()>;
@@ -13,6 +13,13 @@ pub enum SignalTo {
     ReloadFromConfigBuilder(ConfigBuilder),
     /// Signal to reload config from the filesystem.
     ReloadFromDisk,
+    /// Signal to reload a config received by the API. The difference with the
+    /// running config is replied once it's applied, or right away for a dry run.
+    ReloadFromApi {
+        config: Config,
+        dry_run: bool,
+        reply: oneshot::Sender<Result<ConfigDiff, Vec<String>>>,
+    },
     /// Signal to shutdown process.
     Shutdown,
     /// Shutdown process immediately.
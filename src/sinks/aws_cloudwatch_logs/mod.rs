@@ -166,7 +166,8 @@ impl CloudwatchLogsSinkConfig {
         let client = rusoto::client()?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client =
+            rusoto_core::Client::new_with_encoding(creds, client, self.compression.try_into()?);
         Ok(CloudWatchLogsClient::new_with_client(client, region))
     }
 }
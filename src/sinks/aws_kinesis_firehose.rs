@@ -3,7 +3,7 @@ use crate::{
     event::Event,
     rusoto::{self, AwsAuthentication, RegionOrEndpoint},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, StandardEncodings},
         retries::RetryLogic,
         sink::Response,
         BatchConfig, BatchSettings, Compression, EncodedEvent, EncodedLength, TowerRequestConfig,
@@ -40,7 +40,7 @@ pub struct KinesisFirehoseSinkConfig {
     pub stream_name: String,
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(default)]
     pub compression: Compression,
     #[serde(default)]
@@ -60,13 +60,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 inventory::submit! {
     SinkDescription::new::<KinesisFirehoseSinkConfig>("aws_kinesis_firehose")
 }
@@ -133,7 +126,8 @@ impl KinesisFirehoseSinkConfig {
         let client = rusoto::client()?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client =
+            rusoto_core::Client::new_with_encoding(creds, client, self.compression.try_into()?);
         Ok(KinesisFirehoseClient::new_with_client(client, region))
     }
 }
@@ -163,7 +157,7 @@ impl KinesisFirehoseService {
                 cx.acker(),
             )
             .sink_map_err(|error| error!(message = "Fatal kinesis firehose sink error.", %error))
-            .with_flat_map(move |e| stream::iter(Some(encode_event(e, &encoding))).map(Ok));
+            .with_flat_map(move |e| stream::iter(encode_event(e, &encoding)).map(Ok));
 
         Ok(sink)
     }
@@ -241,21 +235,18 @@ enum HealthcheckError {
     StreamNamesMismatch { name: String, stream_name: String },
 }
 
-fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> EncodedEvent<Record> {
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-    let data = match encoding.codec() {
-        Encoding::Json => serde_json::to_vec(&log).expect("Error encoding event as json."),
-
-        Encoding::Text => log
-            .get(crate::config::log_schema().message_key())
-            .map(|v| v.as_bytes().to_vec())
-            .unwrap_or_default(),
-    };
+fn encode_event(
+    event: Event,
+    encoding: &EncodingConfig<StandardEncodings>,
+) -> Option<EncodedEvent<Record>> {
+    let mut data = Vec::new();
+    encoding::encode_event(encoding, event, &mut data)
+        .map_err(|error| error!(message = "Error encoding event.", %error))
+        .ok()?;
 
     let data = Bytes::from(data);
 
-    EncodedEvent::new(Record { data })
+    Some(EncodedEvent::new(Record { data }))
 }
 
 #[cfg(test)]
@@ -271,7 +262,7 @@ mod tests {
     #[test]
     fn firehose_encode_event_text() {
         let message = "hello world".to_string();
-        let event = encode_event(message.clone().into(), &Encoding::Text.into());
+        let event = encode_event(message.clone().into(), &StandardEncodings::Text.into()).unwrap();
 
         assert_eq!(&event.item.data[..], message.as_bytes());
     }
@@ -281,7 +272,7 @@ mod tests {
         let message = "hello world".to_string();
         let mut event = Event::from(message.clone());
         event.as_mut_log().insert("key", "value");
-        let event = encode_event(event, &Encoding::Json.into());
+        let event = encode_event(event, &StandardEncodings::Json.into()).unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&event.item.data[..]).unwrap();
 
@@ -325,7 +316,7 @@ mod integration_tests {
         let config = KinesisFirehoseSinkConfig {
             stream_name: stream.clone(),
             region: RegionOrEndpoint::with_endpoint("http://localhost:4566".into()),
-            encoding: EncodingConfig::from(Encoding::Json), // required for ES destination w/ localstack
+            encoding: EncodingConfig::from(StandardEncodings::Json), // required for ES destination w/ localstack
             compression: Compression::None,
             batch: BatchConfig {
                 max_events: Some(2),
@@ -1,10 +1,10 @@
 use crate::{
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::AwsKinesisStreamsEventSent,
     rusoto::{self, AwsAuthentication, RegionOrEndpoint},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, StandardEncodings},
         retries::RetryLogic,
         sink::Response,
         BatchConfig, BatchSettings, Compression, EncodedEvent, EncodedLength, TowerRequestConfig,
@@ -43,7 +43,7 @@ pub struct KinesisSinkConfig {
     pub partition_key_field: Option<String>,
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(default)]
     pub compression: Compression,
     #[serde(default)]
@@ -63,13 +63,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 inventory::submit! {
     SinkDescription::new::<KinesisSinkConfig>("aws_kinesis_streams")
 }
@@ -136,7 +129,8 @@ impl KinesisSinkConfig {
         let client = rusoto::client()?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client =
+            rusoto_core::Client::new_with_encoding(creds, client, self.compression.try_into()?);
         Ok(KinesisClient::new_with_client(client, region))
     }
 }
@@ -267,9 +261,9 @@ enum HealthcheckError {
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     partition_key_field: &Option<String>,
-    encoding: &EncodingConfig<Encoding>,
+    encoding: &EncodingConfig<StandardEncodings>,
 ) -> Option<EncodedEvent<PutRecordsRequestEntry>> {
     let partition_key = if let Some(partition_key_field) = partition_key_field {
         if let Some(v) = event.as_log().get(&partition_key_field) {
@@ -292,16 +286,10 @@ fn encode_event(
         partition_key
     };
 
-    encoding.apply_rules(&mut event);
-
-    let log = event.into_log();
-    let data = match encoding.codec() {
-        Encoding::Json => serde_json::to_vec(&log).expect("Error encoding event as json."),
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.as_bytes().to_vec())
-            .unwrap_or_default(),
-    };
+    let mut data = Vec::new();
+    encoding::encode_event(encoding, event, &mut data)
+        .map_err(|error| error!(message = "Error encoding event.", %error))
+        .ok()?;
 
     Some(EncodedEvent::new(PutRecordsRequestEntry {
         data: Bytes::from(data),
@@ -322,6 +310,7 @@ fn gen_partition_key() -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::log_schema;
     use crate::{event::Event, test_util::random_string};
     use std::collections::BTreeMap;
 
@@ -333,7 +322,12 @@ mod tests {
     #[test]
     fn kinesis_encode_event_text() {
         let message = "hello world".to_string();
-        let event = encode_event(message.clone().into(), &None, &Encoding::Text.into()).unwrap();
+        let event = encode_event(
+            message.clone().into(),
+            &None,
+            &StandardEncodings::Text.into(),
+        )
+        .unwrap();
 
         assert_eq!(&event.item.data[..], message.as_bytes());
     }
@@ -343,7 +337,7 @@ mod tests {
         let message = "hello world".to_string();
         let mut event = Event::from(message.clone());
         event.as_mut_log().insert("key", "value");
-        let event = encode_event(event, &None, &Encoding::Json.into()).unwrap();
+        let event = encode_event(event, &None, &StandardEncodings::Json.into()).unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&event.item.data[..]).unwrap();
 
@@ -355,7 +349,8 @@ mod tests {
     fn kinesis_encode_event_custom_partition_key() {
         let mut event = Event::from("hello world");
         event.as_mut_log().insert("key", "some_key");
-        let event = encode_event(event, &Some("key".into()), &Encoding::Text.into()).unwrap();
+        let event =
+            encode_event(event, &Some("key".into()), &StandardEncodings::Text.into()).unwrap();
 
         assert_eq!(&event.item.data[..], b"hello world");
         assert_eq!(&event.item.partition_key, &"some_key".to_string());
@@ -365,7 +360,8 @@ mod tests {
     fn kinesis_encode_event_custom_partition_key_limit() {
         let mut event = Event::from("hello world");
         event.as_mut_log().insert("key", random_string(300));
-        let event = encode_event(event, &Some("key".into()), &Encoding::Text.into()).unwrap();
+        let event =
+            encode_event(event, &Some("key".into()), &StandardEncodings::Text.into()).unwrap();
 
         assert_eq!(&event.item.data[..], b"hello world");
         assert_eq!(event.item.partition_key.len(), 256);
@@ -376,7 +372,7 @@ mod tests {
         let mut event = Event::from("hello world");
         event.as_mut_log().insert("key", "some_key");
 
-        let mut encoding: EncodingConfig<_> = Encoding::Json.into();
+        let mut encoding: EncodingConfig<_> = StandardEncodings::Json.into();
         encoding.except_fields = Some(vec!["key".into()]);
 
         let event = encode_event(event, &Some("key".into()), &encoding).unwrap();
@@ -416,7 +412,7 @@ mod integration_tests {
             stream_name: stream.clone(),
             partition_key_field: None,
             region: RegionOrEndpoint::with_endpoint("http://localhost:4566".into()),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::None,
             batch: BatchConfig {
                 max_events: Some(2),
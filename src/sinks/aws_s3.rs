@@ -1,12 +1,12 @@
 use crate::{
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::TemplateRenderingFailed,
     rusoto::{self, AwsAuthentication, RegionOrEndpoint},
     serde::to_string,
     sinks::util::{
         batch::{BatchConfig, BatchSettings},
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, StandardEncodings},
         retries::RetryLogic,
         sink::Response,
         Buffer, Compression, Concurrency, EncodedEvent, PartitionBatchSink, PartitionBuffer,
@@ -51,7 +51,7 @@ pub struct S3SinkConfig {
     options: S3Options,
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
     #[serde(default)]
@@ -124,13 +124,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-}
-
 inventory::submit! {
     SinkDescription::new::<S3SinkConfig>("aws_s3")
 }
@@ -145,7 +138,7 @@ impl GenerateConfig for S3SinkConfig {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -394,7 +387,7 @@ impl RetryLogic for S3RetryLogic {
 fn encode_event(
     mut event: Event,
     key_prefix: &Template,
-    encoding: &EncodingConfig<Encoding>,
+    encoding: &EncodingConfig<StandardEncodings>,
 ) -> Option<EncodedEvent<PartitionInnerBuffer<Vec<u8>, Bytes>>> {
     let key = key_prefix
         .render_string(&event)
@@ -407,27 +400,12 @@ fn encode_event(
         })
         .ok()?;
 
-    encoding.apply_rules(&mut event);
-
-    let log = event.into_log();
-    let bytes = match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log)
-            .map(|mut b| {
-                b.push(b'\n');
-                b
-            })
-            .expect("Failed to encode event as json, this is a bug!"),
-        Encoding::Text => {
-            let mut bytes = log
-                .get(log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default();
-            bytes.push(b'\n');
-            bytes
-        }
-    };
+    let metadata = std::mem::take(event.metadata_mut());
+    let mut bytes = Vec::new();
+    encoding::encode_framed_event(encoding, event, &mut bytes)
+        .map_err(|error| error!(message = "Error encoding event.", %error))
+        .ok()?;
 
-    let (_fields, metadata) = log.into_parts();
     Some(EncodedEvent {
         item: PartitionInnerBuffer::new(bytes, key.into()),
         metadata: Some(metadata),
@@ -437,6 +415,7 @@ fn encode_event(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::log_schema;
 
     #[test]
     fn generate_config() {
@@ -450,7 +429,7 @@ mod tests {
         let encoded = encode_event(
             message.clone().into(),
             &batch_time_format,
-            &Encoding::Text.into(),
+            &StandardEncodings::Text.into(),
         )
         .unwrap();
 
@@ -466,7 +445,8 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let batch_time_format = Template::try_from("date=%F").unwrap();
-        let encoded = encode_event(event, &batch_time_format, &Encoding::Ndjson.into()).unwrap();
+        let encoded =
+            encode_event(event, &batch_time_format, &StandardEncodings::Ndjson.into()).unwrap();
 
         let (bytes, _) = encoded.item.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -484,7 +464,7 @@ mod tests {
         let key_prefix = Template::try_from("{{ key }}").unwrap();
 
         let encoding_config = EncodingConfig {
-            codec: Encoding::Ndjson,
+            codec: StandardEncodings::Ndjson,
             schema: None,
             only_fields: None,
             except_fields: Some(vec!["key".into()]),
@@ -760,7 +740,7 @@ mod integration_tests {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::with_endpoint("http://localhost:4566".to_owned()),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::None,
             batch: BatchConfig {
                 max_bytes: Some(batch_size),
@@ -1,10 +1,10 @@
 use crate::{
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::{AwsSqsEventSent, TemplateRenderingFailed},
     rusoto::{self, AwsAuthentication, RegionOrEndpoint},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, StandardEncodings},
         retries::RetryLogic,
         sink::Response,
         BatchSettings, EncodedEvent, EncodedLength, TowerRequestConfig, VecBuffer,
@@ -57,7 +57,7 @@ pub struct SqsSinkConfig {
     pub queue_url: String,
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     pub message_group_id: Option<String>,
     #[serde(default)]
     pub request: TowerRequestConfig,
@@ -74,13 +74,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 inventory::submit! {
     SinkDescription::new::<SqsSinkConfig>("aws_sqs")
 }
@@ -249,12 +242,10 @@ impl RetryLogic for SqsRetryLogic {
 }
 
 fn encode_event(
-    mut event: Event,
-    encoding: &EncodingConfig<Encoding>,
+    event: Event,
+    encoding: &EncodingConfig<StandardEncodings>,
     message_group_id: Option<&Template>,
 ) -> Option<EncodedEvent<SendMessageEntry>> {
-    encoding.apply_rules(&mut event);
-
     let message_group_id = match message_group_id {
         Some(tpl) => match tpl.render_string(&event) {
             Ok(value) => Some(value),
@@ -270,14 +261,14 @@ fn encode_event(
         None => None,
     };
 
-    let log = event.into_log();
-    let message_body = match encoding.codec() {
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.to_string_lossy())
-            .unwrap_or_else(|| "".into()),
-        Encoding::Json => serde_json::to_string(&log).expect("Error encoding event as json."),
-    };
+    let mut bytes = Vec::new();
+    encoding::encode_event(encoding, event, &mut bytes)
+        .map_err(|error| error!(message = "Error encoding event.", %error))
+        .ok()?;
+    // SQS message bodies must be valid unicode.
+    let message_body = String::from_utf8(bytes)
+        .map_err(|error| error!(message = "Encoded event is not valid UTF-8.", %error))
+        .ok()?;
 
     Some(EncodedEvent::new(SendMessageEntry {
         message_body,
@@ -288,12 +279,18 @@ fn encode_event(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::log_schema;
     use std::collections::BTreeMap;
 
     #[test]
     fn sqs_encode_event_text() {
         let message = "hello world".to_string();
-        let event = encode_event(message.clone().into(), &Encoding::Text.into(), None).unwrap();
+        let event = encode_event(
+            message.clone().into(),
+            &StandardEncodings::Text.into(),
+            None,
+        )
+        .unwrap();
 
         assert_eq!(&event.item.message_body, &message);
     }
@@ -303,7 +300,7 @@ mod tests {
         let message = "hello world".to_string();
         let mut event = Event::from(message.clone());
         event.as_mut_log().insert("key", "value");
-        let event = encode_event(event, &Encoding::Json.into(), None).unwrap();
+        let event = encode_event(event, &StandardEncodings::Json.into(), None).unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_str(&event.item.message_body).unwrap();
 
@@ -340,7 +337,7 @@ mod integration_tests {
         let config = SqsSinkConfig {
             queue_url: queue_url.clone(),
             region: RegionOrEndpoint::with_endpoint("http://localhost:4566".into()),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             message_group_id: None,
             request: Default::default(),
             assume_role: None,
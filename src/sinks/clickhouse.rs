@@ -69,6 +69,7 @@ impl SinkConfig for ClickhouseConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        self.compression.gzip_or_none("ClickHouse")?;
         let batch = BatchSettings::default()
             .bytes(bytesize::mib(10u64))
             .timeout(1)
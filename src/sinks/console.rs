@@ -1,10 +1,10 @@
 use crate::{
     buffers::Acker,
-    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::{ConsoleEventProcessed, ConsoleFieldNotFound},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, EncodingConfiguration, StandardEncodings},
         StreamSink,
     },
 };
@@ -32,14 +32,7 @@ pub enum Target {
 pub struct ConsoleSinkConfig {
     #[serde(default)]
     pub target: Target,
-    pub encoding: EncodingConfig<Encoding>,
-}
-
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
+    pub encoding: EncodingConfig<StandardEncodings>,
 }
 
 inventory::submit! {
@@ -50,7 +43,7 @@ impl GenerateConfig for ConsoleSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             target: Target::Stdout,
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
         })
         .unwrap()
     }
@@ -89,45 +82,38 @@ impl SinkConfig for ConsoleSinkConfig {
     fn sink_type(&self) -> &'static str {
         "console"
     }
+
+    async fn encode_payload(&self, events: Vec<Event>) -> Option<crate::Result<Vec<u8>>> {
+        Some(Ok(events
+            .into_iter()
+            .filter_map(|event| encode_event(event, &self.encoding))
+            .flatten()
+            .collect()))
+    }
 }
 
-fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Option<String> {
-    encoding.apply_rules(&mut event);
-    match event {
-        Event::Log(log) => match encoding.codec() {
-            Encoding::Json => serde_json::to_string(&log)
-                .map_err(|error| {
-                    error!(message = "Error encoding json.", %error);
-                })
-                .ok(),
-            Encoding::Text => {
-                let field = crate::config::log_schema().message_key();
-                match log.get(field) {
-                    Some(v) => Some(v.to_string_lossy()),
-                    None => {
-                        emit!(ConsoleFieldNotFound {
-                            missing_field: field,
-                        });
-                        None
-                    }
-                }
-            }
-        },
-        Event::Metric(metric) => match encoding.codec() {
-            Encoding::Json => serde_json::to_string(&metric)
-                .map_err(|error| {
-                    error!(message = "Error encoding json.", %error);
-                })
-                .ok(),
-            Encoding::Text => Some(format!("{}", metric)),
-        },
+fn encode_event(event: Event, encoding: &EncodingConfig<StandardEncodings>) -> Option<Vec<u8>> {
+    if let (StandardEncodings::Text, Event::Log(log)) = (encoding.codec(), &event) {
+        let field = log_schema().message_key();
+        if !log.contains(field) {
+            emit!(ConsoleFieldNotFound {
+                missing_field: field,
+            });
+            return None;
+        }
     }
+
+    let mut buf = Vec::new();
+    encoding::encode_framed_event(encoding, event, &mut buf)
+        .map_err(|error| error!(message = "Error encoding event.", %error))
+        .ok()?;
+    Some(buf)
 }
 
 struct WriterSink {
     acker: Acker,
     output: Box<dyn io::AsyncWrite + Send + Sync + Unpin>,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
 }
 
 #[async_trait]
@@ -135,9 +121,8 @@ impl StreamSink for WriterSink {
     async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
         while let Some(event) = input.next().await {
             self.acker.ack(1);
-            if let Some(mut buf) = encode_event(event, &self.encoding) {
-                buf.push('\n');
-                if let Err(error) = self.output.write_all(buf.as_bytes()).await {
+            if let Some(buf) = encode_event(event, &self.encoding) {
+                if let Err(error) = self.output.write_all(&buf).await {
                     // Error when writing to stdout/stderr is likely irrecoverable,
                     // so stop the sink.
                     error!(message = "Error writing to output. Stopping sink.", %error);
@@ -155,7 +140,7 @@ impl StreamSink for WriterSink {
 
 #[cfg(test)]
 mod test {
-    use super::{encode_event, ConsoleSinkConfig, Encoding, EncodingConfig};
+    use super::{encode_event, ConsoleSinkConfig, EncodingConfig, StandardEncodings};
     use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
     use crate::event::{Event, Value};
     use chrono::{offset::TimeZone, Utc};
@@ -166,13 +151,19 @@ mod test {
         crate::test_util::test_generate_config::<ConsoleSinkConfig>();
     }
 
+    fn encode(event: Event, codec: StandardEncodings) -> String {
+        let buf = encode_event(event, &EncodingConfig::from(codec)).unwrap();
+        String::from_utf8(buf)
+            .unwrap()
+            .strip_suffix('\n')
+            .unwrap()
+            .to_owned()
+    }
+
     #[test]
     fn encodes_raw_logs() {
         let event = Event::from("foo");
-        assert_eq!(
-            "foo",
-            encode_event(event, &EncodingConfig::from(Encoding::Text)).unwrap()
-        );
+        assert_eq!("foo", encode(event, StandardEncodings::Text));
     }
 
     #[test]
@@ -183,9 +174,9 @@ mod test {
         log.insert("z", Value::from(25));
         log.insert("a", Value::from("0"));
 
-        let encoded = encode_event(event, &EncodingConfig::from(Encoding::Json));
+        let encoded = encode(event, StandardEncodings::Json);
         let expected = r#"{"a":"0","x":"23","z":25}"#;
-        assert_eq!(encoded.unwrap(), expected);
+        assert_eq!(encoded, expected);
     }
 
     #[test]
@@ -210,7 +201,7 @@ mod test {
         );
         assert_eq!(
             r#"{"name":"foos","namespace":"vector","tags":{"Key3":"Value3","key1":"value1","key2":"value2"},"timestamp":"2018-11-14T08:09:10.000000011Z","kind":"incremental","counter":{"value":100.0}}"#,
-            encode_event(event, &EncodingConfig::from(Encoding::Json)).unwrap()
+            encode(event, StandardEncodings::Json)
         );
     }
 
@@ -225,7 +216,7 @@ mod test {
         ));
         assert_eq!(
             r#"{"name":"users","kind":"incremental","set":{"values":["bob"]}}"#,
-            encode_event(event, &EncodingConfig::from(Encoding::Json)).unwrap()
+            encode(event, StandardEncodings::Json)
         );
     }
 
@@ -241,7 +232,7 @@ mod test {
         ));
         assert_eq!(
             r#"{"name":"glork","kind":"incremental","distribution":{"samples":[{"value":10.0,"rate":1}],"statistic":"histogram"}}"#,
-            encode_event(event, &EncodingConfig::from(Encoding::Json)).unwrap()
+            encode(event, StandardEncodings::Json)
         );
     }
 
@@ -254,9 +245,6 @@ mod test {
                 values: vec!["bob".into()].into_iter().collect(),
             },
         ));
-        assert_eq!(
-            "users{} + bob",
-            encode_event(event, &EncodingConfig::from(Encoding::Text)).unwrap()
-        );
+        assert_eq!("users{} + bob", encode(event, StandardEncodings::Text));
     }
 }
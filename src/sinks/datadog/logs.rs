@@ -7,24 +7,23 @@ use crate::{
         util::{
             batch::{Batch, BatchError},
             encode_event,
-            encoding::{EncodingConfig, EncodingConfiguration},
+            encoding::{EncodingConfig, EncodingConfiguration, StandardEncodings},
             http::{HttpSink, PartitionHttpSink},
-            BatchConfig, BatchSettings, BoxedRawValue, Compression, EncodedEvent, Encoding,
-            JsonArrayBuffer, PartitionBuffer, PartitionInnerBuffer, TowerRequestConfig, VecBuffer,
+            BatchConfig, BatchSettings, BoxedRawValue, Compression, EncodedEvent, JsonArrayBuffer,
+            PartitionBuffer, PartitionInnerBuffer, TowerRequestConfig, VecBuffer,
         },
         Healthcheck, VectorSink,
     },
     tls::{MaybeTlsSettings, TlsConfig},
 };
 use bytes::Bytes;
-use flate2::write::GzEncoder;
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode};
 use hyper::body::Body;
 use indoc::indoc;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::{io::Write, sync::Arc, time::Duration};
+use std::{sync::Arc, time::Duration};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
@@ -36,7 +35,7 @@ pub struct DatadogLogsConfig {
     // Deprecated name
     #[serde(alias = "api_key")]
     default_api_key: String,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     tls: Option<TlsConfig>,
 
     #[serde(default)]
@@ -169,21 +168,14 @@ impl DatadogLogsConfig {
 
         let compression = self.compression.unwrap_or(Compression::Gzip(None));
 
-        let (request, body) = match compression {
-            Compression::None => (request, body),
-            Compression::Gzip(level) => {
-                // Default the compression level to 6, which is similar to datadog agent.
-                // https://docs.datadoghq.com/agent/logs/log_transport/?tab=https#log-compression
-                let level = level.unwrap_or(6);
-                let mut encoder =
-                    GzEncoder::new(Vec::new(), flate2::Compression::new(level as u32));
-
-                encoder.write_all(&body)?;
-                (
-                    request.header("Content-Encoding", "gzip"),
-                    encoder.finish()?,
-                )
-            }
+        // Default the compression level to 6, which is similar to datadog agent.
+        // https://docs.datadoghq.com/agent/logs/log_transport/?tab=https#log-compression
+        let (request, body) = match compression.content_encoding() {
+            Some(content_encoding) => (
+                request.header("Content-Encoding", content_encoding),
+                compression.compress(&body, 6)?,
+            ),
+            None => (request, body),
         };
 
         request
@@ -197,11 +189,15 @@ impl DatadogLogsConfig {
 #[typetag::serde(name = "datadog_logs")]
 impl SinkConfig for DatadogLogsConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if let Some(compression) = self.compression {
+            compression.gzip_or_none("Datadog")?;
+        }
+
         // Create a different sink depending on which encoding we have chosen.
-        // Json and Text have different batching strategies and so each needs to be
-        // handled differently.
+        // Json events are batched into an array, while every other encoding is
+        // sent as lines of text, so each needs to be handled differently.
         match self.encoding.codec {
-            Encoding::Json => {
+            StandardEncodings::Json => {
                 let batch_settings = self.batch_settings()?;
                 self.build_sink(
                     cx,
@@ -214,7 +210,7 @@ impl SinkConfig for DatadogLogsConfig {
                     batch_settings.timeout,
                 )
             }
-            Encoding::Text => {
+            _ => {
                 let batch_settings = self.batch_settings()?;
                 self.build_sink(
                     cx,
@@ -6,7 +6,7 @@ use crate::{
     internal_events::{ElasticSearchEventEncoded, TemplateRenderingFailed},
     rusoto::{self, region_from_endpoint, AwsAuthentication, RegionOrEndpoint},
     sinks::util::{
-        encoding::{EncodingConfigWithDefault, EncodingConfiguration},
+        encoding::{EncodingConfigWithDefault, EncodingConfiguration, StandardEncodings},
         http::{BatchedHttpSink, HttpSink, RequestConfig},
         retries::{RetryAction, RetryLogic},
         BatchConfig, BatchSettings, Buffer, Compression, EncodedEvent, TowerRequestConfig,
@@ -51,7 +51,7 @@ pub struct ElasticSearchConfig {
         skip_serializing_if = "crate::serde::skip_serializing_if_default",
         default
     )]
-    pub encoding: EncodingConfigWithDefault<Encoding>,
+    pub encoding: EncodingConfigWithDefault<StandardEncodings>,
     #[serde(default)]
     pub batch: BatchConfig,
     #[serde(default)]
@@ -74,14 +74,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
-#[serde(rename_all = "snake_case")]
-#[derivative(Default)]
-pub enum Encoding {
-    #[derivative(Default)]
-    Default,
-}
-
 #[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
 pub enum ElasticSearchAuth {
@@ -189,6 +181,11 @@ enum ParseError {
     AwsCredentialsGenerateFailed { source: CredentialsError },
     #[snafu(display("Index template parse error: {}", source))]
     IndexTemplate { source: TemplateParseError },
+    #[snafu(display(
+        "The {:?} codec is not supported, documents are encoded as JSON",
+        codec
+    ))]
+    UnsupportedCodec { codec: StandardEncodings },
 }
 
 #[async_trait::async_trait]
@@ -389,6 +386,13 @@ impl ElasticSearchCommon {
             .into());
         }
 
+        // The bulk API only accepts JSON documents, so the other codecs are
+        // rejected. `text` is the default codec, and so means no codec was set.
+        match config.encoding.codec() {
+            StandardEncodings::Text | StandardEncodings::Json | StandardEncodings::Ndjson => {}
+            codec => return Err(ParseError::UnsupportedCodec { codec: *codec }.into()),
+        }
+
         let authorization = match &config.auth {
             Some(ElasticSearchAuth::Basic { user, password }) => Some(Auth::Basic {
                 user: user.clone(),
@@ -410,7 +414,7 @@ impl ElasticSearchCommon {
             Some(ElasticSearchAuth::Aws(aws)) => Some(aws.build(&region, None)?),
         };
 
-        let compression = config.compression;
+        let compression = config.compression.gzip_or_none("Elasticsearch")?;
         let index = config.index.as_deref().unwrap_or("vector-%Y.%m.%d");
         let index = Template::try_from(index).context(IndexTemplate)?;
 
@@ -620,6 +624,23 @@ mod tests {
         assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
     }
 
+    #[test]
+    fn rejects_codecs_other_than_json() {
+        let config = ElasticSearchConfig {
+            endpoint: String::from("https://example.com"),
+            encoding: StandardEncodings::Csv.into(),
+            ..Default::default()
+        };
+        assert!(ElasticSearchCommon::parse_config(&config).is_err());
+
+        let config = ElasticSearchConfig {
+            endpoint: String::from("https://example.com"),
+            encoding: StandardEncodings::Json.into(),
+            ..Default::default()
+        };
+        assert!(ElasticSearchCommon::parse_config(&config).is_ok());
+    }
+
     #[test]
     fn handles_error_response() {
         let json = "{\"took\":185,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
@@ -1,26 +1,30 @@
 use crate::expiring_hash_map::ExpiringHashMap;
 use crate::{
     buffers::Acker,
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::FileOpen,
     internal_events::TemplateRenderingFailed,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
-        StreamSink,
+        buffer::compression::GZIP_DEFAULT,
+        encoding::{encode_framed_event, EncodingConfig, StandardEncodings},
+        Compression, Compressor, StreamSink,
     },
     template::Template,
 };
-use async_compression::tokio::write::GzipEncoder;
 use async_trait::async_trait;
 use bytes::Bytes;
+use derivative::Derivative;
 use futures::{
     future,
-    stream::{BoxStream, StreamExt},
+    stream::{self, BoxStream, FusedStream, StreamExt},
     FutureExt,
 };
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, Instant};
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
 
 use tokio::{
     fs::{self, File},
@@ -35,7 +39,7 @@ use std::convert::TryFrom;
 pub struct FileSinkConfig {
     pub path: Template,
     pub idle_timeout_secs: Option<u64>,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(
         default,
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
@@ -52,64 +56,76 @@ impl GenerateConfig for FileSinkConfig {
         toml::Value::try_from(Self {
             path: Template::try_from("/tmp/vector-%Y-%m-%d.log").unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Default::default(),
         })
         .unwrap()
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-}
-
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
-#[serde(rename_all = "snake_case")]
-pub enum Compression {
-    Gzip,
-    None,
-}
-
-impl Default for Compression {
-    fn default() -> Self {
-        Compression::None
-    }
-}
-
 enum OutFile {
     Regular(File),
-    Gzip(GzipEncoder<File>),
+    /// The compressor is taken when the file is closed, to write out the
+    /// remaining data and any trailer.
+    Compressed {
+        file: File,
+        compressor: Option<Compressor>,
+    },
 }
 
 impl OutFile {
     fn new(file: File, compression: Compression) -> Self {
         match compression {
             Compression::None => OutFile::Regular(file),
-            Compression::Gzip => OutFile::Gzip(GzipEncoder::new(file)),
+            compression => OutFile::Compressed {
+                file,
+                compressor: Some(
+                    Compressor::new(compression, Vec::new(), GZIP_DEFAULT)
+                        .expect("Creating an encoder over a Vec can't fail"),
+                ),
+            },
         }
     }
 
-    async fn sync_all(&mut self) -> Result<(), std::io::Error> {
+    fn file(&mut self) -> &mut File {
         match self {
-            OutFile::Regular(file) => file.sync_all().await,
-            OutFile::Gzip(gzip) => gzip.get_mut().sync_all().await,
+            OutFile::Regular(file) => file,
+            OutFile::Compressed { file, .. } => file,
         }
     }
 
+    async fn sync_all(&mut self) -> Result<(), std::io::Error> {
+        self.file().sync_all().await
+    }
+
     async fn shutdown(&mut self) -> Result<(), std::io::Error> {
-        match self {
-            OutFile::Regular(file) => file.shutdown().await,
-            OutFile::Gzip(gzip) => gzip.shutdown().await,
+        if let OutFile::Compressed { file, compressor } = self {
+            if let Some(compressor) = compressor.take() {
+                file.write_all(&compressor.finish()?).await?;
+            }
         }
+        self.file().shutdown().await
     }
 
+    /// Compressed data is written out as soon as the compressor produces it,
+    /// which for most algorithms happens once an internal block is full.
     async fn write_all(&mut self, src: &[u8]) -> Result<(), std::io::Error> {
         match self {
             OutFile::Regular(file) => file.write_all(src).await,
-            OutFile::Gzip(gzip) => gzip.write_all(src).await,
+            OutFile::Compressed {
+                file,
+                compressor: Some(compressor),
+            } => {
+                compressor.write_all(src)?;
+                let compressed = std::mem::take(compressor.get_mut());
+                file.write_all(&compressed).await
+            }
+            OutFile::Compressed {
+                compressor: None, ..
+            } => Err(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "Write to a closed file.",
+            )),
         }
     }
 
@@ -128,7 +144,8 @@ impl SinkConfig for FileSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let sink = FileSink::new(&self, cx.acker());
+        let mut sink = FileSink::new(&self, cx.acker());
+        sink.reconfigurations = cx.reconfigurations();
         Ok((
             super::VectorSink::Stream(Box::new(sink)),
             future::ok(()).boxed(),
@@ -142,16 +159,36 @@ impl SinkConfig for FileSinkConfig {
     fn sink_type(&self) -> &'static str {
         "file"
     }
+
+    fn reconfigurable_fields(&self) -> &'static [&'static str] {
+        &["path", "idle_timeout_secs", "encoding"]
+    }
+
+    /// Encodes the events as they are written to a single file, compressing
+    /// them one at a time as `OutFile` does.
+    async fn encode_payload(&self, events: Vec<Event>) -> Option<crate::Result<Vec<u8>>> {
+        let encode = || -> crate::Result<Vec<u8>> {
+            let mut compressor = Compressor::new(self.compression, Vec::new(), GZIP_DEFAULT)?;
+            for event in events {
+                compressor.write_all(&encode_file_event(&self.encoding, event)?)?;
+            }
+            Ok(compressor.finish()?)
+        };
+        Some(encode())
+    }
 }
 
-#[derive(Debug)]
+#[derive(Derivative)]
+#[derivative(Debug)]
 pub struct FileSink {
     acker: Acker,
     path: Template,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
     compression: Compression,
+    #[derivative(Debug = "ignore")]
+    reconfigurations: BoxStream<'static, FileSinkConfig>,
 }
 
 impl FileSink {
@@ -163,9 +200,19 @@ impl FileSink {
             idle_timeout: Duration::from_secs(config.idle_timeout_secs.unwrap_or(30)),
             files: ExpiringHashMap::default(),
             compression: config.compression,
+            reconfigurations: stream::empty().boxed(),
         }
     }
 
+    /// Open files are kept, so they are written with their new encoding but
+    /// keep their idle timeout until they are next written to.
+    fn reconfigure(&mut self, config: FileSinkConfig) {
+        debug!(message = "Reconfiguring sink.", path = ?config.path);
+        self.path = config.path;
+        self.encoding = config.encoding;
+        self.idle_timeout = Duration::from_secs(config.idle_timeout_secs.unwrap_or(30));
+    }
+
     /// Uses pass the `event` to `self.path` template to obtain the file path
     /// to store the event as.
     fn partition_event(&mut self, event: &Event) -> Option<bytes::Bytes> {
@@ -191,8 +238,17 @@ impl FileSink {
     }
 
     async fn run(&mut self, mut input: BoxStream<'_, Event>) -> crate::Result<()> {
+        // Reconfigurations end when the topology drops the sink's channel,
+        // after which the stream must not be polled again.
+        let mut reconfigurations =
+            std::mem::replace(&mut self.reconfigurations, stream::empty().boxed()).fuse();
         loop {
             tokio::select! {
+                config = reconfigurations.next(), if !reconfigurations.is_terminated() => {
+                    if let Some(config) = config {
+                        self.reconfigure(config);
+                    }
+                }
                 event = input.next() => {
                     match event {
                         Some(event) => {
@@ -312,26 +368,21 @@ async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
         .await
 }
 
-pub fn encode_event(encoding: &EncodingConfig<Encoding>, mut event: Event) -> Vec<u8> {
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-    match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log).expect("Unable to encode event as JSON."),
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.to_string_lossy().into_bytes())
-            .unwrap_or_default(),
-    }
+fn encode_file_event(
+    encoding: &EncodingConfig<StandardEncodings>,
+    event: Event,
+) -> Result<Vec<u8>, std::io::Error> {
+    let mut buf = Vec::new();
+    encode_framed_event(encoding, event, &mut buf)?;
+    Ok(buf)
 }
 
 async fn write_event_to_file(
     file: &mut OutFile,
     event: Event,
-    encoding: &EncodingConfig<Encoding>,
+    encoding: &EncodingConfig<StandardEncodings>,
 ) -> Result<(), std::io::Error> {
-    let mut buf = encode_event(encoding, event);
-    buf.push(b'\n');
-    file.write_all(&buf[..]).await
+    file.write_all(&encode_file_event(encoding, event)?).await
 }
 
 #[async_trait]
@@ -345,9 +396,12 @@ impl StreamSink for FileSink {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util::{
-        lines_from_file, lines_from_gzip_file, random_events_with_stream, random_lines_with_stream,
-        temp_dir, temp_file, trace_init,
+    use crate::{
+        config::log_schema,
+        test_util::{
+            lines_from_file, lines_from_gzip_file, lines_from_zst_file, random_events_with_stream,
+            random_lines_with_stream, temp_dir, temp_file, trace_init,
+        },
     };
     use futures::{stream, SinkExt};
     use std::convert::TryInto;
@@ -366,7 +420,7 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::None,
         };
 
@@ -391,8 +445,8 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
-            compression: Compression::Gzip,
+            encoding: StandardEncodings::Text.into(),
+            compression: Compression::gzip_default(),
         };
 
         let mut sink = FileSink::new(&config, Acker::Null);
@@ -407,6 +461,31 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn single_partition_zstd() {
+        trace_init();
+
+        let template = temp_file();
+
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: StandardEncodings::Text.into(),
+            compression: Compression::zstd_default(),
+        };
+
+        let mut sink = FileSink::new(&config, Acker::Null);
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
+        sink.run(events).await.unwrap();
+
+        let output = lines_from_zst_file(template);
+        for (input, output) in input.into_iter().zip(output) {
+            assert_eq!(input, output);
+        }
+    }
+
     #[tokio::test]
     async fn many_partitions() {
         trace_init();
@@ -421,7 +500,7 @@ mod tests {
         let config = FileSinkConfig {
             path: template.try_into().unwrap(),
             idle_timeout_secs: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::None,
         };
 
@@ -502,7 +581,7 @@ mod tests {
         let config = FileSinkConfig {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: Some(1),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::None,
         };
 
@@ -533,4 +612,65 @@ mod tests {
         let output = lines_from_file(template);
         assert_eq!(input, output);
     }
+
+    #[tokio::test]
+    async fn reconfigures_in_place() {
+        trace_init();
+
+        let directory = temp_dir();
+        let config = |name: &str| FileSinkConfig {
+            path: directory.join(name).to_str().unwrap().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: StandardEncodings::Text.into(),
+            compression: Compression::None,
+        };
+
+        let mut sink = FileSink::new(&config("before.log"), Acker::Null);
+        let (mut reconfigure_tx, reconfigure_rx) = futures::channel::mpsc::channel(0);
+        sink.reconfigurations = reconfigure_rx.boxed();
+        let (mut tx, rx) = futures::channel::mpsc::channel(0);
+        let sink = tokio::spawn(async move { sink.run(Box::pin(rx)).await });
+
+        tx.send(Event::from("before")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        reconfigure_tx.send(config("after.log")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        tx.send(Event::from("after")).await.unwrap();
+        drop(tx);
+        sink.await.unwrap().unwrap();
+
+        assert_eq!(
+            lines_from_file(directory.join("before.log")),
+            vec!["before"]
+        );
+        assert_eq!(lines_from_file(directory.join("after.log")), vec!["after"]);
+    }
+
+    #[tokio::test]
+    async fn runs_after_reconfigurations_end() {
+        trace_init();
+
+        let template = temp_file();
+        let config = FileSinkConfig {
+            path: template.clone().try_into().unwrap(),
+            idle_timeout_secs: None,
+            encoding: StandardEncodings::Text.into(),
+            compression: Compression::None,
+        };
+
+        // The test context drops its reconfiguration channel right away.
+        let mut sink = FileSink::new(&config, Acker::Null);
+        sink.reconfigurations = SinkContext::new_test().reconfigurations();
+        let (mut tx, rx) = futures::channel::mpsc::channel(0);
+        let sink = tokio::spawn(async move { sink.run(Box::pin(rx)).await });
+
+        for line in &["first", "second"] {
+            tx.send(Event::from(*line)).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        drop(tx);
+        sink.await.unwrap().unwrap();
+
+        assert_eq!(lines_from_file(template), vec!["first", "second"]);
+    }
 }
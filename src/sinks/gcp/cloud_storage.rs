@@ -8,7 +8,7 @@ use crate::{
     sinks::{
         util::{
             batch::{BatchConfig, BatchSettings},
-            encoding::{EncodingConfig, EncodingConfiguration},
+            encoding::{self, EncodingConfig, EncodingConfiguration, StandardEncodings},
             retries::{RetryAction, RetryLogic},
             Buffer, Compression, Concurrency, EncodedEvent, PartitionBatchSink, PartitionBuffer,
             PartitionInnerBuffer, ServiceBuilderExt, TowerRequestConfig,
@@ -63,7 +63,7 @@ pub struct GcsSinkConfig {
     filename_time_format: Option<String>,
     filename_append_uuid: Option<bool>,
     filename_extension: Option<String>,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     #[serde(default)]
     compression: Compression,
     #[serde(default)]
@@ -76,7 +76,7 @@ pub struct GcsSinkConfig {
 }
 
 #[cfg(test)]
-fn default_config(e: Encoding) -> GcsSinkConfig {
+fn default_config(e: StandardEncodings) -> GcsSinkConfig {
     GcsSinkConfig {
         bucket: Default::default(),
         acl: Default::default(),
@@ -127,22 +127,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
-#[serde(rename_all = "snake_case")]
-enum Encoding {
-    Text,
-    Ndjson,
-}
-
-impl Encoding {
-    fn content_type(self) -> &'static str {
-        match self {
-            Self::Text => "text/plain",
-            Self::Ndjson => "application/x-ndjson",
-        }
-    }
-}
-
 inventory::submit! {
     SinkDescription::new::<GcsSinkConfig>(NAME)
 }
@@ -354,7 +338,12 @@ impl RequestSettings {
         let acl = config
             .acl
             .map(|acl| HeaderValue::from_str(&to_string(acl)).unwrap());
-        let content_type = HeaderValue::from_str(config.encoding.codec().content_type()).unwrap();
+        let content_type = match config.encoding.codec() {
+            // Events are written one per line, so JSON objects are newline delimited.
+            StandardEncodings::Json => StandardEncodings::Ndjson.content_type(),
+            codec => codec.content_type(),
+        };
+        let content_type = HeaderValue::from_str(content_type).unwrap();
         let content_encoding = config
             .compression
             .content_encoding()
@@ -402,9 +391,9 @@ fn make_header((name, value): (&String, &String)) -> crate::Result<(HeaderName,
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key_prefix: &Template,
-    encoding: &EncodingConfig<Encoding>,
+    encoding: &EncodingConfig<StandardEncodings>,
 ) -> Option<EncodedEvent<PartitionInnerBuffer<Vec<u8>, Bytes>>> {
     let key = key_prefix
         .render_string(&event)
@@ -416,24 +405,10 @@ fn encode_event(
             });
         })
         .ok()?;
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-    let bytes = match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log)
-            .map(|mut b| {
-                b.push(b'\n');
-                b
-            })
-            .expect("Failed to encode event as json, this is a bug!"),
-        Encoding::Text => {
-            let mut bytes = log
-                .get(crate::config::log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default();
-            bytes.push(b'\n');
-            bytes
-        }
-    };
+    let mut bytes = Vec::new();
+    encoding::encode_framed_event(encoding, event, &mut bytes)
+        .map_err(|error| error!(message = "Error encoding event.", %error))
+        .ok()?;
 
     Some(EncodedEvent::new(PartitionInnerBuffer::new(
         bytes,
@@ -484,7 +459,7 @@ mod tests {
         let encoded = encode_event(
             message.clone().into(),
             &batch_time_format,
-            &Encoding::Text.into(),
+            &StandardEncodings::Text.into(),
         )
         .unwrap();
 
@@ -500,7 +475,8 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let batch_time_format = Template::try_from("date=%F").unwrap();
-        let encoded = encode_event(event, &batch_time_format, &Encoding::Ndjson.into()).unwrap();
+        let encoded =
+            encode_event(event, &batch_time_format, &StandardEncodings::Ndjson.into()).unwrap();
 
         let (bytes, _) = encoded.item.into_parts();
         let map: HashMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -521,7 +497,7 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let key_format = Template::try_from("key: {{ key }}").unwrap();
-        let encoded = encode_event(event, &key_format, &Encoding::Text.into()).unwrap();
+        let encoded = encode_event(event, &key_format, &StandardEncodings::Text.into()).unwrap();
 
         let (_, key) = encoded.item.into_parts();
         assert_eq!(key, "key: value");
@@ -538,7 +514,7 @@ mod tests {
             filename_extension: extension.map(Into::into),
             filename_append_uuid: Some(uuid),
             compression,
-            ..default_config(Encoding::Ndjson)
+            ..default_config(StandardEncodings::Ndjson)
         })
         .expect("Could not create request settings")
     }
@@ -1,19 +1,18 @@
 use crate::{
-    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     http::{Auth, HttpClient, MaybeAuth},
     internal_events::{HttpEventEncoded, HttpEventMissingMessage},
     sinks::util::{
         buffer::compression::GZIP_DEFAULT,
-        encoding::{EncodingConfig, EncodingConfiguration},
-        http::{BatchedHttpSink, HttpSink, RequestConfig},
-        BatchConfig, BatchSettings, Buffer, Compression, Concurrency, EncodedEvent,
+        encoding::{self, EncodingConfig, EncodingConfiguration, StandardEncodings},
+        http::{build_request_for_events, BatchedHttpSink, HttpSink, RequestConfig},
+        BatchConfig, BatchSettings, BatchSize, Buffer, Compression, Concurrency, EncodedEvent,
         TowerRequestConfig, UriSerde,
     },
     tls::{TlsOptions, TlsSettings},
 };
-use flate2::write::GzEncoder;
-use futures::{future, FutureExt, SinkExt};
+use futures::{future, FutureExt, SinkExt, StreamExt};
 use http::{
     header::{self, HeaderName, HeaderValue},
     Method, Request, StatusCode, Uri,
@@ -23,7 +22,6 @@ use indexmap::IndexMap;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
-use std::io::Write;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -49,7 +47,7 @@ pub struct HttpSinkConfig {
     pub headers: Option<IndexMap<String, String>>,
     #[serde(default)]
     pub compression: Compression,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(default)]
     pub batch: BatchConfig,
     #[serde(default)]
@@ -58,7 +56,7 @@ pub struct HttpSinkConfig {
 }
 
 #[cfg(test)]
-fn default_config(e: Encoding) -> HttpSinkConfig {
+fn default_config(e: StandardEncodings) -> HttpSinkConfig {
     HttpSinkConfig {
         uri: Default::default(),
         method: Default::default(),
@@ -96,14 +94,6 @@ pub enum HttpMethod {
     Patch,
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-    Json,
-}
-
 inventory::submit! {
     SinkDescription::new::<HttpSinkConfig>("http")
 }
@@ -144,20 +134,33 @@ impl SinkConfig for HttpSinkConfig {
         config.request.add_old_option(config.headers.take());
         validate_headers(&config.request.headers, &config.auth)?;
 
-        let batch = BatchSettings::default()
+        let batch_defaults = BatchSettings::default()
             .bytes(bytesize::mib(10u64))
-            .timeout(1)
-            .parse_config(config.batch)?;
+            .timeout(1);
+        let batch = batch_defaults.parse_config(config.batch)?;
         let request = config.request.tower.unwrap_with(&REQUEST_DEFAULTS);
+        let reconfigurations = cx
+            .reconfigurations::<HttpSinkConfig>()
+            .filter_map(move |config| {
+                future::ready(
+                    batch_defaults
+                        .parse_config(config.batch)
+                        .map_err(|error| error!(message = "Invalid batch reconfiguration.", %error))
+                        .ok(),
+                )
+            })
+            .map(|batch| (batch_buffer(batch.size), batch.timeout))
+            .boxed();
 
         let sink = BatchedHttpSink::new(
             config,
-            Buffer::new(batch.size, Compression::None),
+            batch_buffer(batch.size),
             request,
             batch.timeout,
             client,
             cx.acker(),
         )
+        .with_reconfigurations(reconfigurations)
         .sink_map_err(|error| error!(message = "Fatal HTTP sink error.", %error));
 
         let sink = super::VectorSink::Sink(Box::new(sink));
@@ -172,6 +175,19 @@ impl SinkConfig for HttpSinkConfig {
     fn sink_type(&self) -> &'static str {
         "http"
     }
+
+    fn reconfigurable_fields(&self) -> &'static [&'static str] {
+        &["batch"]
+    }
+
+    async fn encode_payload(&self, events: Vec<Event>) -> Option<crate::Result<Vec<u8>>> {
+        let batch = batch_buffer(BatchSize::default());
+        Some(
+            build_request_for_events(self, batch, events)
+                .await
+                .map(Request::into_body),
+        )
+    }
 }
 
 #[async_trait::async_trait]
@@ -180,43 +196,35 @@ impl HttpSink for HttpSinkConfig {
     type Output = Vec<u8>;
 
     fn encode_event(&self, mut event: Event) -> Option<EncodedEvent<Self::Input>> {
-        self.encoding.apply_rules(&mut event);
-        let event = event.into_log();
-
-        let body = match &self.encoding.codec() {
-            Encoding::Text => {
-                if let Some(v) = event.get(crate::config::log_schema().message_key()) {
-                    let mut b = v.to_string_lossy().into_bytes();
-                    b.push(b'\n');
-                    b
-                } else {
-                    emit!(HttpEventMissingMessage);
-                    return None;
-                }
-            }
-
-            Encoding::Ndjson => {
-                let mut b = serde_json::to_vec(&event)
-                    .map_err(|error| panic!("Unable to encode into JSON: {}", error))
-                    .ok()?;
-                b.push(b'\n');
-                b
-            }
+        if self.encoding.codec() == &StandardEncodings::Text
+            && !event.as_log().contains(log_schema().message_key())
+        {
+            emit!(HttpEventMissingMessage);
+            return None;
+        }
 
-            Encoding::Json => {
-                let mut b = serde_json::to_vec(&event)
-                    .map_err(|error| panic!("Unable to encode into JSON: {}", error))
-                    .ok()?;
-                b.push(b',');
-                b
+        let metadata = std::mem::take(event.metadata_mut());
+        let mut body = Vec::new();
+        let result = match self.encoding.codec() {
+            // The events of a batch are joined into a JSON array when building the request.
+            StandardEncodings::Json => {
+                encoding::encode_event(&self.encoding, event, &mut body).map(|_| body.push(b','))
             }
+            _ => encoding::encode_framed_event(&self.encoding, event, &mut body).map(|_| ()),
         };
+        if let Err(error) = result {
+            error!(message = "Error encoding event.", %error);
+            return None;
+        }
 
         emit!(HttpEventEncoded {
             byte_size: body.len(),
         });
 
-        Some(EncodedEvent::new(body).with_metadata(event))
+        Some(EncodedEvent {
+            item: body,
+            metadata: Some(metadata),
+        })
     }
 
     async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
@@ -232,32 +240,22 @@ impl HttpSink for HttpSinkConfig {
         };
         let uri: Uri = self.uri.uri.clone();
 
-        let ct = match self.encoding.codec() {
-            Encoding::Text => "text/plain",
-            Encoding::Ndjson => "application/x-ndjson",
-            Encoding::Json => {
-                body.insert(0, b'[');
-                body.pop(); // remove trailing comma from last record
-                body.push(b']');
-                "application/json"
-            }
-        };
+        let codec = self.encoding.codec();
+        if codec == &StandardEncodings::Json {
+            body.insert(0, b'[');
+            body.pop(); // remove trailing comma from last record
+            body.push(b']');
+        }
+        let ct = codec.content_type();
 
         let mut builder = Request::builder()
             .method(method)
             .uri(uri)
             .header("Content-Type", ct);
 
-        match self.compression {
-            Compression::Gzip(level) => {
-                builder = builder.header("Content-Encoding", "gzip");
-
-                let level = level.unwrap_or(GZIP_DEFAULT) as u32;
-                let mut w = GzEncoder::new(Vec::new(), flate2::Compression::new(level));
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail");
-            }
-            Compression::None => {}
+        if let Some(content_encoding) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", content_encoding);
+            body = self.compression.compress(&body, GZIP_DEFAULT)?;
         }
 
         for (header, value) in self.request.headers.iter() {
@@ -274,6 +272,12 @@ impl HttpSink for HttpSinkConfig {
     }
 }
 
+/// Batches aren't compressed with `compression`: JSON batches are only closed
+/// into an array by `build_request`, which then compresses the whole body.
+fn batch_buffer(size: BatchSize<Buffer>) -> Buffer {
+    Buffer::new(size, Compression::None)
+}
+
 async fn healthcheck(uri: UriSerde, auth: Option<Auth>, client: HttpClient) -> crate::Result<()> {
     let auth = auth.choose_one(&uri.auth)?;
     let uri = uri.with_default_parts();
@@ -326,7 +330,7 @@ mod tests {
     use http::request::Parts;
     use hyper::{Method, Response, StatusCode};
     use serde::Deserialize;
-    use std::io::{BufRead, BufReader};
+    use std::io::{BufRead, BufReader, Read};
     use std::sync::{atomic, Arc};
     use vector_core::event::{BatchNotifier, BatchStatus};
 
@@ -337,10 +341,10 @@ mod tests {
 
     #[test]
     fn http_encode_event_text() {
-        let encoding = EncodingConfig::from(Encoding::Text);
+        let encoding = EncodingConfig::from(StandardEncodings::Text);
         let event = Event::from("hello world");
 
-        let mut config = default_config(Encoding::Text);
+        let mut config = default_config(StandardEncodings::Text);
         config.encoding = encoding;
         let bytes = config.encode_event(event).unwrap().item;
 
@@ -349,10 +353,10 @@ mod tests {
 
     #[test]
     fn http_encode_event_json() {
-        let encoding = EncodingConfig::from(Encoding::Ndjson);
+        let encoding = EncodingConfig::from(StandardEncodings::Ndjson);
         let event = Event::from("hello world");
 
-        let mut config = default_config(Encoding::Json);
+        let mut config = default_config(StandardEncodings::Json);
         config.encoding = encoding;
         let bytes = config.encode_event(event).unwrap().item;
 
@@ -368,6 +372,25 @@ mod tests {
         assert_eq!(output.message, "hello world".to_string());
     }
 
+    #[tokio::test]
+    async fn http_encode_payload_compressed() {
+        let mut config = default_config(StandardEncodings::Json);
+        config.compression = Compression::gzip_default();
+        config.encoding.except_fields = Some(vec!["timestamp".into()]);
+
+        let payload = config
+            .encode_payload(vec![Event::from("hello"), Event::from("world")])
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut decompressed = String::new();
+        MultiGzDecoder::new(payload.as_slice())
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, r#"[{"message":"hello"},{"message":"world"}]"#);
+    }
+
     #[test]
     fn http_validates_normal_headers() {
         let config = r#"
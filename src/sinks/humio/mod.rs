@@ -1,7 +1,7 @@
 pub mod logs;
 pub mod metrics;
 
-use crate::sinks::splunk_hec;
+use crate::sinks::util::encoding::StandardEncodings;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
@@ -11,11 +11,11 @@ pub enum Encoding {
     Text,
 }
 
-impl From<Encoding> for splunk_hec::Encoding {
-    fn from(v: Encoding) -> Self {
+impl From<Encoding> for StandardEncodings {
+    fn from(v: Encoding) -> StandardEncodings {
         match v {
-            Encoding::Json => splunk_hec::Encoding::Json,
-            Encoding::Text => splunk_hec::Encoding::Text,
+            Encoding::Json => StandardEncodings::Json,
+            Encoding::Text => StandardEncodings::Text,
         }
     }
 }
@@ -6,7 +6,7 @@ use crate::{
     kafka::{KafkaAuthConfig, KafkaCompression, KafkaStatisticsContext},
     serde::to_string,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, StandardEncodings},
         BatchConfig,
     },
     template::{Template, TemplateParseError},
@@ -48,7 +48,7 @@ pub struct KafkaSinkConfig {
     bootstrap_servers: String,
     topic: String,
     key_field: Option<String>,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     /// These batching options will **not** override librdkafka_options values.
     #[serde(default)]
     batch: BatchConfig,
@@ -72,18 +72,11 @@ fn default_message_timeout_ms() -> u64 {
     300000 // default in librdkafka
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 pub struct KafkaSink {
     producer: Arc<FutureProducer<KafkaStatisticsContext>>,
     topic: Template,
     key_field: Option<String>,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     delivery_fut: FuturesUnordered<BoxFuture<'static, (usize, Result<DeliveryFuture, KafkaError>)>>,
     in_flight: FuturesUnordered<
         BoxFuture<'static, (usize, Result<Result<(i32, i64), KafkaError>, Canceled>)>,
@@ -296,7 +289,9 @@ impl Sink<Event> for KafkaSink {
             Event::Metric(metric) => metric.data.timestamp.as_ref(),
         }
         .map(|ts| ts.timestamp_millis());
-        let (key, body) = encode_event(item, &self.key_field, &self.encoding);
+        let (key, body) = encode_event(item, &self.key_field, &self.encoding).map_err(|error| {
+            error!(message = "Error encoding event.", %error);
+        })?;
 
         let seqno = self.seq_head;
         self.seq_head += 1;
@@ -402,10 +397,10 @@ async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key_field: &Option<String>,
-    encoding: &EncodingConfig<Encoding>,
-) -> (Vec<u8>, Vec<u8>) {
+    encoding: &EncodingConfig<StandardEncodings>,
+) -> std::io::Result<(Vec<u8>, Vec<u8>)> {
     let key = key_field
         .as_ref()
         .and_then(|f| match &event {
@@ -417,23 +412,10 @@ fn encode_event(
         })
         .unwrap_or_default();
 
-    encoding.apply_rules(&mut event);
-
-    let body = match event {
-        Event::Log(log) => match encoding.codec() {
-            Encoding::Json => serde_json::to_vec(&log).unwrap(),
-            Encoding::Text => log
-                .get(log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default(),
-        },
-        Event::Metric(metric) => match encoding.codec() {
-            Encoding::Json => serde_json::to_vec(&metric).unwrap(),
-            Encoding::Text => metric.to_string().into_bytes(),
-        },
-    };
+    let mut body = Vec::new();
+    encoding::encode_event(encoding, event, &mut body)?;
 
-    (key, body)
+    Ok((key, body))
 }
 
 #[cfg(test)]
@@ -455,8 +437,9 @@ mod tests {
         let (key_bytes, bytes) = encode_event(
             message.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Text),
-        );
+            &EncodingConfig::from(StandardEncodings::Text),
+        )
+        .unwrap();
 
         assert_eq!(&key_bytes[..], key.as_bytes());
         assert_eq!(&bytes[..], message.as_bytes());
@@ -473,8 +456,9 @@ mod tests {
         let (key, bytes) = encode_event(
             event,
             &Some("key".into()),
-            &EncodingConfig::from(Encoding::Json),
-        );
+            &EncodingConfig::from(StandardEncodings::Json),
+        )
+        .unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
 
@@ -494,8 +478,9 @@ mod tests {
         let (key_bytes, bytes) = encode_event(
             metric.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Text),
-        );
+            &EncodingConfig::from(StandardEncodings::Text),
+        )
+        .unwrap();
 
         assert_eq!("", String::from_utf8_lossy(&key_bytes));
         assert_eq!(metric.to_string(), String::from_utf8_lossy(&bytes));
@@ -511,8 +496,9 @@ mod tests {
         let (key_bytes, bytes) = encode_event(
             metric.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Json),
-        );
+            &EncodingConfig::from(StandardEncodings::Json),
+        )
+        .unwrap();
 
         assert_eq!("", String::from_utf8_lossy(&key_bytes));
         assert_eq!(
@@ -531,13 +517,14 @@ mod tests {
             event,
             &Some("key".into()),
             &EncodingConfig {
-                codec: Encoding::Json,
+                codec: StandardEncodings::Json,
                 schema: None,
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
             },
-        );
+        )
+        .unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
 
@@ -572,7 +559,7 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".into(),
             topic: topic.clone(),
             key_field: None,
-            encoding: EncodingConfig::from(Encoding::Text),
+            encoding: EncodingConfig::from(StandardEncodings::Text),
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
             auth: KafkaAuthConfig::default(),
@@ -623,7 +610,7 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".to_string(),
             topic: format!("{}-%Y%m%d", topic),
             compression: KafkaCompression::None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             key_field: None,
             auth: KafkaAuthConfig {
                 sasl: None,
@@ -777,7 +764,7 @@ mod integration_test {
             bootstrap_servers: server.to_string(),
             topic: format!("{}-%Y%m%d", topic),
             key_field: None,
-            encoding: EncodingConfig::from(Encoding::Text),
+            encoding: EncodingConfig::from(StandardEncodings::Text),
             batch: BatchConfig::default(),
             compression,
             auth: kafka_auth.clone(),
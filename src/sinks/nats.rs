@@ -5,7 +5,7 @@ use crate::{
     event::Event,
     internal_events::{NatsEventSendFail, NatsEventSendSuccess, TemplateRenderingFailed},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, StandardEncodings},
         StreamSink,
     },
     template::{Template, TemplateParseError},
@@ -28,7 +28,7 @@ enum BuildError {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NatsSinkConfig {
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     #[serde(default = "default_name")]
     name: String,
     subject: String,
@@ -39,13 +39,6 @@ fn default_name() -> String {
     String::from("vector")
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 inventory::submit! {
     SinkDescription::new::<NatsSinkConfig>("nats")
 }
@@ -115,7 +108,7 @@ struct NatsOptions {
 }
 
 pub struct NatsSink {
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     options: NatsOptions,
     subject: Template,
     url: String,
@@ -171,10 +164,17 @@ impl StreamSink for NatsSink {
                 }
             };
 
-            let log = encode_event(event, &self.encoding);
-            let message_len = log.len();
+            let message = match encode_event(event, &self.encoding) {
+                Ok(message) => message,
+                Err(error) => {
+                    error!(message = "Error encoding event.", %error);
+                    self.acker.ack(1);
+                    continue;
+                }
+            };
+            let message_len = message.len();
 
-            match nc.publish(&subject, log).await {
+            match nc.publish(&subject, message).await {
                 Ok(_) => {
                     emit!(NatsEventSendSuccess {
                         byte_size: message_len,
@@ -192,23 +192,19 @@ impl StreamSink for NatsSink {
     }
 }
 
-fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> String {
-    encoding.apply_rules(&mut event);
-
-    match encoding.codec() {
-        Encoding::Json => serde_json::to_string(event.as_log()).unwrap(),
-        Encoding::Text => event
-            .as_log()
-            .get(crate::config::log_schema().message_key())
-            .map(|v| v.to_string_lossy())
-            .unwrap_or_default(),
-    }
+fn encode_event(
+    event: Event,
+    encoding: &EncodingConfig<StandardEncodings>,
+) -> std::io::Result<Vec<u8>> {
+    let mut message = Vec::new();
+    encoding::encode_event(encoding, event, &mut message)?;
+    Ok(message)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use super::{encode_event, Encoding, EncodingConfig};
+    use super::{encode_event, EncodingConfig, StandardEncodings};
     use crate::event::{Event, Value};
 
     #[test]
@@ -220,8 +216,8 @@ mod tests {
     fn encodes_raw_logs() {
         let event = Event::from("foo");
         assert_eq!(
-            "foo",
-            encode_event(event, &EncodingConfig::from(Encoding::Text))
+            b"foo",
+            &encode_event(event, &EncodingConfig::from(StandardEncodings::Text)).unwrap()[..]
         );
     }
 
@@ -233,9 +229,9 @@ mod tests {
         log.insert("z", Value::from(25));
         log.insert("a", Value::from("0"));
 
-        let encoded = encode_event(event, &EncodingConfig::from(Encoding::Json));
+        let encoded = encode_event(event, &EncodingConfig::from(StandardEncodings::Json)).unwrap();
         let expected = r#"{"a":"0","x":"23","z":25}"#;
-        assert_eq!(encoded, expected);
+        assert_eq!(encoded, expected.as_bytes());
     }
 }
 
@@ -258,7 +254,7 @@ mod integration_tests {
         let subject = format!("test-{}", random_string(10));
 
         let cnf = NatsSinkConfig {
-            encoding: EncodingConfig::from(Encoding::Text),
+            encoding: EncodingConfig::from(StandardEncodings::Text),
             name: "".to_owned(),
             subject: subject.clone(),
             url: "nats://127.0.0.1:4222".to_owned(),
@@ -3,8 +3,9 @@ use crate::{
     sinks::{
         http::{HttpMethod, HttpSinkConfig},
         util::{
-            encoding::EncodingConfig, http::RequestConfig, BatchConfig, Compression, Concurrency,
-            TowerRequestConfig,
+            encoding::{EncodingConfig, StandardEncodings},
+            http::RequestConfig,
+            BatchConfig, Compression, Concurrency, TowerRequestConfig,
         },
     },
 };
@@ -69,10 +70,10 @@ pub enum Encoding {
     Json,
 }
 
-impl From<Encoding> for crate::sinks::http::Encoding {
-    fn from(v: Encoding) -> crate::sinks::http::Encoding {
+impl From<Encoding> for StandardEncodings {
+    fn from(v: Encoding) -> StandardEncodings {
         match v {
-            Encoding::Json => crate::sinks::http::Encoding::Json,
+            Encoding::Json => StandardEncodings::Json,
         }
     }
 }
@@ -152,7 +153,7 @@ impl NewRelicLogsConfig {
             method: Some(HttpMethod::Post),
             auth: None,
             headers: None,
-            compression: self.compression,
+            compression: self.compression.gzip_or_none("New Relic")?,
             encoding: self.encoding.clone().into_encoding(),
 
             batch,
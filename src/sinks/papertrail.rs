@@ -2,9 +2,9 @@ use crate::{
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
+        encoding::{self, EncodingConfig, StandardEncodings},
         tcp::TcpSinkConfig,
-        EncodedEvent, Encoding, UriSerde,
+        EncodedEvent, UriSerde,
     },
     tcp::TcpKeepaliveConfig,
     tls::TlsConfig,
@@ -18,7 +18,7 @@ use syslog::{Facility, Formatter3164, LogFormat, Severity};
 #[serde(deny_unknown_fields)]
 pub struct PapertrailConfig {
     endpoint: UriSerde,
-    encoding: EncodingConfig<Encoding>,
+    encoding: EncodingConfig<StandardEncodings>,
     keepalive: Option<TcpKeepaliveConfig>,
     tls: Option<TlsConfig>,
     send_buffer_bytes: Option<usize>,
@@ -65,7 +65,7 @@ impl SinkConfig for PapertrailConfig {
 
         let sink_config = TcpSinkConfig::new(address, self.keepalive, tls, self.send_buffer_bytes);
 
-        sink_config.build(cx, move |event| Some(encode_event(event, pid, &encoding)))
+        sink_config.build(cx, move |event| encode_event(event, pid, &encoding))
     }
 
     fn input_type(&self) -> DataType {
@@ -80,8 +80,8 @@ impl SinkConfig for PapertrailConfig {
 fn encode_event(
     mut event: Event,
     pid: u32,
-    encoding: &EncodingConfig<Encoding>,
-) -> EncodedEvent<Bytes> {
+    encoding: &EncodingConfig<StandardEncodings>,
+) -> Option<EncodedEvent<Bytes>> {
     let host = event
         .as_mut_log()
         .remove(log_schema().host_key())
@@ -96,24 +96,22 @@ fn encode_event(
 
     let mut s: Vec<u8> = Vec::new();
 
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-
-    let message = match encoding.codec() {
-        Encoding::Json => serde_json::to_string(&log).unwrap(),
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.to_string_lossy())
-            .unwrap_or_default(),
-    };
+    let mut message = Vec::new();
+    encoding::encode_event(encoding, event, &mut message)
+        .map_err(|error| error!(message = "Unable to encode.", %error))
+        .ok()?;
 
     formatter
-        .format(&mut s, Severity::LOG_INFO, message)
+        .format(
+            &mut s,
+            Severity::LOG_INFO,
+            String::from_utf8_lossy(&message).into_owned(),
+        )
         .unwrap();
 
     s.push(b'\n');
 
-    EncodedEvent::new(Bytes::from(s))
+    Some(EncodedEvent::new(Bytes::from(s)))
 }
 
 #[cfg(test)]
@@ -134,13 +132,14 @@ mod tests {
             evt,
             0,
             &EncodingConfig {
-                codec: Encoding::Json,
+                codec: StandardEncodings::Json,
                 schema: None,
                 only_fields: None,
                 except_fields: Some(vec!["magic".into()]),
                 timestamp_format: None,
             },
         )
+        .unwrap()
         .item;
 
         let msg =
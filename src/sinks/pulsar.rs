@@ -1,9 +1,9 @@
 use crate::{
     buffers::Acker,
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::PulsarEncodeEventFailed,
-    sinks::util::encoding::{EncodingConfig, EncodingConfiguration},
+    sinks::util::encoding::{self, EncodingConfig, EncodingConfiguration, StandardEncodings},
 };
 use futures::{future::BoxFuture, ready, stream::FuturesUnordered, FutureExt, Sink, Stream};
 use pulsar::{
@@ -40,14 +40,52 @@ pub struct AuthConfig {
     token: String, // <jwt token>
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
+/// The standard codecs, or Avro with the schema given in `encoding.schema`.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(untagged)]
 pub enum Encoding {
-    Text,
-    Json,
+    Standard(StandardEncodings),
+    Avro(AvroEncoding),
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AvroEncoding {
     Avro,
 }
 
+impl From<StandardEncodings> for Encoding {
+    fn from(codec: StandardEncodings) -> Self {
+        Self::Standard(codec)
+    }
+}
+
+enum PulsarEncoder {
+    Standard(EncodingConfig<StandardEncodings>),
+    Avro(EncodingConfig<Encoding>, avro_rs::Schema),
+}
+
+impl PulsarEncoder {
+    fn new(encoding: EncodingConfig<Encoding>) -> crate::Result<Self> {
+        Ok(match encoding.codec {
+            Encoding::Standard(codec) => Self::Standard(EncodingConfig {
+                codec,
+                schema: encoding.schema,
+                only_fields: encoding.only_fields,
+                except_fields: encoding.except_fields,
+                timestamp_format: encoding.timestamp_format,
+            }),
+            Encoding::Avro(_) => {
+                let schema = encoding.schema().as_deref().ok_or(
+                    "Avro requires a schema, specify a schema file with `encoding.schema`.",
+                )?;
+                let schema = avro_rs::Schema::parse_str(schema)?;
+                Self::Avro(encoding, schema)
+            }
+        })
+    }
+}
+
 type PulsarProducer = Producer<TokioExecutor>;
 type BoxedPulsarProducer = Box<PulsarProducer>;
 
@@ -58,8 +96,7 @@ enum PulsarSinkState {
 }
 
 struct PulsarSink {
-    encoding: EncodingConfig<Encoding>,
-    avro_schema: Option<avro_rs::Schema>,
+    encoder: PulsarEncoder,
     state: PulsarSinkState,
     in_flight:
         FuturesUnordered<BoxFuture<'static, (usize, Result<CommandSendReceipt, PulsarError>)>>,
@@ -79,7 +116,7 @@ impl GenerateConfig for PulsarSinkConfig {
         toml::Value::try_from(Self {
             endpoint: "pulsar://127.0.0.1:6650".to_string(),
             topic: "topic-1234".to_string(),
-            encoding: Encoding::Text.into(),
+            encoding: Encoding::from(StandardEncodings::Text).into(),
             auth: None,
         })
         .unwrap()
@@ -159,23 +196,8 @@ impl PulsarSink {
         encoding: EncodingConfig<Encoding>,
         acker: Acker,
     ) -> crate::Result<Self> {
-        let schema = match &encoding.codec() {
-            Encoding::Avro => {
-                if let Some(schema) = &encoding.schema() {
-                    avro_rs::Schema::parse_str(schema).ok()
-                } else {
-                    return Err(
-                        "Avro requires a schema, specify a schema file with `encoding.schema`."
-                            .into(),
-                    );
-                }
-            }
-            _ => None,
-        };
-
         Ok(Self {
-            encoding,
-            avro_schema: schema,
+            encoder: PulsarEncoder::new(encoding)?,
             state: PulsarSinkState::Ready(Box::new(producer)),
             in_flight: FuturesUnordered::new(),
             acker,
@@ -220,7 +242,7 @@ impl Sink<Event> for PulsarSink {
             "Expected `poll_ready` to be called first."
         );
 
-        let message = encode_event(item, &self.encoding, &self.avro_schema).map_err(|e| {
+        let message = encode_event(item, &self.encoder).map_err(|e| {
             emit!(PulsarEncodeEventFailed {
                 error: &*e.to_string()
             })
@@ -281,30 +303,18 @@ impl Sink<Event> for PulsarSink {
     }
 }
 
-fn encode_event(
-    mut item: Event,
-    encoding: &EncodingConfig<Encoding>,
-    avro_schema: &Option<avro_rs::Schema>,
-) -> crate::Result<Vec<u8>> {
-    encoding.apply_rules(&mut item);
-    let log = item.into_log();
-
-    Ok(match encoding.codec() {
-        Encoding::Json => serde_json::to_vec(&log)?,
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.as_bytes().to_vec())
-            .unwrap_or_default(),
-        Encoding::Avro => {
-            let value = avro_rs::to_value(log)?;
-            let resolved_value =
-                avro_rs::types::Value::resolve(value, avro_schema.as_ref().unwrap())?;
-            avro_rs::to_avro_datum(
-                &avro_schema
-                    .as_ref()
-                    .expect("Avro encoding selected but no schema found. Please report this."),
-                resolved_value,
-            )?
+fn encode_event(mut item: Event, encoder: &PulsarEncoder) -> crate::Result<Vec<u8>> {
+    Ok(match encoder {
+        PulsarEncoder::Standard(encoding) => {
+            let mut bytes = Vec::new();
+            encoding::encode_event(encoding, item, &mut bytes)?;
+            bytes
+        }
+        PulsarEncoder::Avro(encoding, schema) => {
+            encoding.apply_rules(&mut item);
+            let value = avro_rs::to_value(item.into_log())?;
+            let resolved_value = avro_rs::types::Value::resolve(value, schema)?;
+            avro_rs::to_avro_datum(schema, resolved_value)?
         }
     })
 }
@@ -312,8 +322,13 @@ fn encode_event(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::log_schema;
     use std::collections::HashMap;
 
+    fn encoder(codec: StandardEncodings) -> PulsarEncoder {
+        PulsarEncoder::new(Encoding::from(codec).into()).unwrap()
+    }
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<PulsarSinkConfig>();
@@ -324,7 +339,7 @@ mod tests {
         let msg = "hello_world".to_owned();
         let mut evt = Event::from(msg.clone());
         evt.as_mut_log().insert("key", "value");
-        let result = encode_event(evt, &EncodingConfig::from(Encoding::Json), &None).unwrap();
+        let result = encode_event(evt, &encoder(StandardEncodings::Json)).unwrap();
         let map: HashMap<String, String> = serde_json::from_slice(&result[..]).unwrap();
         assert_eq!(msg, map[&log_schema().message_key().to_string()]);
     }
@@ -333,7 +348,7 @@ mod tests {
     fn pulsar_event_text() {
         let msg = "hello_world".to_owned();
         let evt = Event::from(msg.clone());
-        let event = encode_event(evt, &EncodingConfig::from(Encoding::Text), &None).unwrap();
+        let event = encode_event(evt, &encoder(StandardEncodings::Text)).unwrap();
 
         assert_eq!(&event[..], msg.as_bytes());
     }
@@ -353,10 +368,10 @@ mod tests {
         let msg = "hello_world".to_owned();
         let mut evt = Event::from(msg);
         evt.as_mut_log().insert("key", "value");
-        let mut encoding = EncodingConfig::from(Encoding::Avro);
+        let mut encoding = EncodingConfig::from(Encoding::Avro(AvroEncoding::Avro));
         encoding.schema = Some(raw_schema.to_string());
         let schema = avro_rs::Schema::parse_str(&raw_schema).unwrap();
-        let result = encode_event(evt.clone(), &encoding, &Some(schema.clone())).unwrap();
+        let result = encode_event(evt.clone(), &PulsarEncoder::new(encoding).unwrap()).unwrap();
 
         let value = avro_rs::to_value(evt.into_log()).unwrap();
         let resolved_value = avro_rs::types::Value::resolve(value, &schema).unwrap();
@@ -372,22 +387,52 @@ mod tests {
         let mut evt = Event::from(msg);
         evt.as_mut_log().insert("key", "value");
 
-        let event = encode_event(
-            evt,
-            &EncodingConfig {
-                codec: Encoding::Json,
-                schema: None,
-                only_fields: None,
-                except_fields: Some(vec!["key".into()]),
-                timestamp_format: None,
-            },
-            &None,
-        )
+        let encoder = PulsarEncoder::new(EncodingConfig {
+            codec: StandardEncodings::Json.into(),
+            schema: None,
+            only_fields: None,
+            except_fields: Some(vec!["key".into()]),
+            timestamp_format: None,
+        })
         .unwrap();
+        let event = encode_event(evt, &encoder).unwrap();
 
         let map: HashMap<String, String> = serde_json::from_slice(&event[..]).unwrap();
         assert!(!map.contains_key("key"));
     }
+
+    #[test]
+    fn pulsar_parses_encodings() {
+        let config: PulsarSinkConfig = toml::from_str(
+            r#"
+            endpoint = "pulsar://127.0.0.1:6650"
+            topic = "topic-1234"
+            encoding = "ndjson"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.encoding.codec,
+            Encoding::from(StandardEncodings::Ndjson)
+        );
+
+        let config: PulsarSinkConfig = toml::from_str(
+            r#"
+            endpoint = "pulsar://127.0.0.1:6650"
+            topic = "topic-1234"
+            encoding.codec = "avro"
+            encoding.schema = "{}"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.encoding.codec, Encoding::Avro(AvroEncoding::Avro));
+    }
+
+    #[test]
+    fn pulsar_avro_requires_schema() {
+        let encoding = EncodingConfig::from(Encoding::Avro(AvroEncoding::Avro));
+        assert!(PulsarEncoder::new(encoding).is_err());
+    }
 }
 
 #[cfg(feature = "pulsar-integration-tests")]
@@ -409,7 +454,7 @@ mod integration_tests {
         let cnf = PulsarSinkConfig {
             endpoint: "pulsar://127.0.0.1:6650".to_owned(),
             topic: topic.clone(),
-            encoding: Encoding::Text.into(),
+            encoding: Encoding::from(StandardEncodings::Text).into(),
             auth: None,
         };
 
@@ -1,23 +1,28 @@
 #[cfg(unix)]
 use crate::sinks::util::unix::UnixSinkConfig;
 use crate::{
-    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    config::{schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::Event,
     sinks::util::{
-        encode_event, encoding::EncodingConfig, tcp::TcpSinkConfig, udp::UdpSinkConfig, Encoding,
+        encode_event,
+        encoding::{EncodingConfig, StandardEncodings},
+        tcp::TcpSinkConfig,
+        udp::UdpSinkConfig,
     },
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, JsonSchema)]
 // TODO: add back when serde-rs/serde#1358 is addressed
 // #[serde(deny_unknown_fields)]
 pub struct SocketSinkConfig {
     #[serde(flatten)]
     pub mode: Mode,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(tag = "mode", rename_all = "snake_case")]
 pub enum Mode {
     Tcp(TcpSinkConfig),
@@ -39,17 +44,21 @@ impl GenerateConfig for SocketSinkConfig {
         )
         .unwrap()
     }
+
+    fn generate_schema() -> serde_json::Value {
+        schema::derive::<Self>()
+    }
 }
 
 impl SocketSinkConfig {
-    pub fn new(mode: Mode, encoding: EncodingConfig<Encoding>) -> Self {
+    pub fn new(mode: Mode, encoding: EncodingConfig<StandardEncodings>) -> Self {
         SocketSinkConfig { mode, encoding }
     }
 
     pub fn make_basic_tcp_config(address: String) -> Self {
         Self::new(
             Mode::Tcp(TcpSinkConfig::from_address(address)),
-            EncodingConfig::from(Encoding::Text),
+            EncodingConfig::from(StandardEncodings::Text),
         )
     }
 }
@@ -78,6 +87,14 @@ impl SinkConfig for SocketSinkConfig {
     fn sink_type(&self) -> &'static str {
         "socket"
     }
+
+    async fn encode_payload(&self, events: Vec<Event>) -> Option<crate::Result<Vec<u8>>> {
+        Some(Ok(events
+            .into_iter()
+            .filter_map(|event| encode_event(event, &self.encoding))
+            .flat_map(|encoded| encoded.item)
+            .collect()))
+    }
 }
 
 #[cfg(test)]
@@ -111,7 +128,7 @@ mod test {
 
         let config = SocketSinkConfig {
             mode: Mode::Udp(UdpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -153,7 +170,7 @@ mod test {
         let addr = next_addr();
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Json.into(),
+            encoding: StandardEncodings::Json.into(),
         };
 
         let context = SinkContext::new_test();
@@ -224,7 +241,7 @@ mod test {
                 }),
                 None,
             )),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -340,7 +357,7 @@ mod test {
         let addr = next_addr();
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
         };
 
         let context = SinkContext::new_test();
@@ -4,9 +4,9 @@ use crate::{
     http::HttpClient,
     internal_events::{SplunkEventEncodeError, SplunkEventSent, TemplateRenderingFailed},
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
-        http::{BatchedHttpSink, HttpSink},
-        BatchConfig, BatchSettings, Buffer, Compression, Concurrency, EncodedEvent,
+        encoding::{EncodingConfig, EncodingConfiguration, StandardEncodings},
+        http::{build_request_for_events, BatchedHttpSink, HttpSink},
+        BatchConfig, BatchSettings, BatchSize, Buffer, Compression, Concurrency, EncodedEvent,
         TowerRequestConfig,
     },
     template::Template,
@@ -27,9 +27,11 @@ pub enum BuildError {
     UriMissingScheme,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Derivative, Clone)]
+#[derivative(Debug)]
 #[serde(deny_unknown_fields)]
 pub struct HecSinkConfig {
+    #[derivative(Debug(format_with = "crate::config::secret::fmt_hidden"))]
     pub token: String,
     // Deprecated name
     #[serde(alias = "host")]
@@ -41,7 +43,7 @@ pub struct HecSinkConfig {
     pub index: Option<Template>,
     pub sourcetype: Option<Template>,
     pub source: Option<Template>,
-    pub encoding: EncodingConfig<Encoding>,
+    pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(default)]
     pub compression: Compression,
     #[serde(default)]
@@ -59,13 +61,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Derivative)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 fn host_key() -> String {
     crate::config::log_schema().host_key().to_string()
 }
@@ -84,7 +79,7 @@ impl GenerateConfig for HecSinkConfig {
             index: None,
             sourcetype: None,
             source: None,
-            encoding: Encoding::Text.into(),
+            encoding: StandardEncodings::Text.into(),
             compression: Compression::default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -102,6 +97,14 @@ impl SinkConfig for HecSinkConfig {
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         validate_host(&self.endpoint)?;
+        self.compression.gzip_or_none("Splunk HEC")?;
+        if self.encoding.codec() == &StandardEncodings::Native {
+            return Err(format!(
+                "The {:?} codec is not supported by the `splunk_hec` sink.",
+                self.encoding.codec()
+            )
+            .into());
+        }
 
         let batch = BatchSettings::default()
             .bytes(bytesize::mib(1u64))
@@ -133,6 +136,15 @@ impl SinkConfig for HecSinkConfig {
     fn sink_type(&self) -> &'static str {
         "splunk_hec"
     }
+
+    async fn encode_payload(&self, events: Vec<Event>) -> Option<crate::Result<Vec<u8>>> {
+        let batch = Buffer::new(BatchSize::default(), self.compression);
+        Some(
+            build_request_for_events(self, batch, events)
+                .await
+                .map(Request::into_body),
+        )
+    }
 }
 
 #[async_trait::async_trait]
@@ -198,18 +210,29 @@ impl HttpSink for HecSinkConfig {
 
         let mut event = Event::Log(event);
         self.encoding.apply_rules(&mut event);
-        let log = event.into_log();
 
-        let event = match self.encoding.codec() {
-            Encoding::Json => json!(&log),
-            Encoding::Text => json!(log
-                .get(log_schema().message_key())
-                .map(|v| v.to_string_lossy())
-                .unwrap_or_else(|| "".into())),
+        // JSON events are sent as objects, other encodings as strings.
+        let encoded = match self.encoding.codec() {
+            StandardEncodings::Json | StandardEncodings::Ndjson => Ok(json!(event.as_log())),
+            codec => {
+                let mut bytes = Vec::new();
+                codec
+                    .encode_event(&event, self.encoding.only_fields().as_deref(), &mut bytes)
+                    .map(|_| json!(String::from_utf8_lossy(&bytes)))
+                    .map_err(serde_json::Error::io)
+            }
         };
+        let encoded = match encoded {
+            Ok(encoded) => encoded,
+            Err(error) => {
+                emit!(SplunkEventEncodeError { error });
+                return None;
+            }
+        };
+        let log = event.into_log();
 
         let mut body = json!({
-            "event": event,
+            "event": encoded,
             "fields": fields,
             "time": timestamp
         });
@@ -423,6 +446,47 @@ mod tests {
         assert_eq!((hec_event.time * 1000f64).fract(), 0f64);
     }
 
+    #[test]
+    fn splunk_encode_event_logfmt() {
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("key", "value");
+
+        let (config, _cx) = load_sink::<HecSinkConfig>(
+            r#"
+            host = "test.com"
+            token = "alksjdfo"
+            host_key = "host"
+
+            [encoding]
+            codec = "logfmt"
+        "#,
+        )
+        .unwrap();
+
+        let bytes = config.encode_event(event).unwrap().item;
+
+        let hec_event = serde_json::from_slice::<HecEventText>(&bytes[..]).unwrap();
+
+        assert_eq!(
+            hec_event.event.as_str(),
+            r#"key=value message="hello world""#
+        );
+    }
+
+    #[tokio::test]
+    async fn splunk_rejects_native_encoding() {
+        let (config, cx) = load_sink::<HecSinkConfig>(
+            r#"
+            endpoint = "http://test.com"
+            token = "alksjdfo"
+            encoding = "native"
+        "#,
+        )
+        .unwrap();
+
+        assert!(config.build(cx).await.is_err());
+    }
+
     #[test]
     fn splunk_validate_host() {
         let valid = "http://localhost:8888".to_string();
@@ -484,7 +548,7 @@ mod integration_tests {
     async fn splunk_insert_message() {
         let cx = SinkContext::new_test();
 
-        let config = config(Encoding::Text, vec![]).await;
+        let config = config(StandardEncodings::Text, vec![]).await;
         let (sink, _) = config.build(cx).await.unwrap();
 
         let message = random_string(100);
@@ -506,7 +570,7 @@ mod integration_tests {
     async fn splunk_insert_broken_token() {
         let cx = SinkContext::new_test();
 
-        let mut config = config(Encoding::Text, vec![]).await;
+        let mut config = config(StandardEncodings::Text, vec![]).await;
         config.token = "BROKEN_TOKEN".into();
         let (sink, _) = config.build(cx).await.unwrap();
 
@@ -524,7 +588,7 @@ mod integration_tests {
     async fn splunk_insert_source() {
         let cx = SinkContext::new_test();
 
-        let mut config = config(Encoding::Text, vec![]).await;
+        let mut config = config(StandardEncodings::Text, vec![]).await;
         config.source = Template::try_from("/var/log/syslog".to_string()).ok();
 
         let (sink, _) = config.build(cx).await.unwrap();
@@ -542,7 +606,7 @@ mod integration_tests {
     async fn splunk_insert_index() {
         let cx = SinkContext::new_test();
 
-        let mut config = config(Encoding::Text, vec![]).await;
+        let mut config = config(StandardEncodings::Text, vec![]).await;
         config.index = Template::try_from("custom_index".to_string()).ok();
         let (sink, _) = config.build(cx).await.unwrap();
 
@@ -560,7 +624,7 @@ mod integration_tests {
         let cx = SinkContext::new_test();
 
         let indexed_fields = vec!["asdf".to_string()];
-        let mut config = config(Encoding::Json, indexed_fields).await;
+        let mut config = config(StandardEncodings::Json, indexed_fields).await;
         config.index = Template::try_from("{{ index_name }}".to_string()).ok();
 
         let (sink, _) = config.build(cx).await.unwrap();
@@ -580,7 +644,7 @@ mod integration_tests {
     async fn splunk_insert_many() {
         let cx = SinkContext::new_test();
 
-        let config = config(Encoding::Text, vec![]).await;
+        let config = config(StandardEncodings::Text, vec![]).await;
         let (sink, _) = config.build(cx).await.unwrap();
 
         let (messages, events) = random_lines_with_stream(100, 10, None);
@@ -611,7 +675,7 @@ mod integration_tests {
         let cx = SinkContext::new_test();
 
         let indexed_fields = vec!["asdf".into()];
-        let config = config(Encoding::Json, indexed_fields).await;
+        let config = config(StandardEncodings::Json, indexed_fields).await;
         let (sink, _) = config.build(cx).await.unwrap();
 
         let message = random_string(100);
@@ -631,7 +695,7 @@ mod integration_tests {
         let cx = SinkContext::new_test();
 
         let indexed_fields = vec!["asdf".into()];
-        let config = config(Encoding::Json, indexed_fields).await;
+        let config = config(StandardEncodings::Json, indexed_fields).await;
         let (sink, _) = config.build(cx).await.unwrap();
 
         let message = random_string(100);
@@ -654,7 +718,7 @@ mod integration_tests {
         let cx = SinkContext::new_test();
 
         let indexed_fields = vec!["asdf".to_string()];
-        let mut config = config(Encoding::Json, indexed_fields).await;
+        let mut config = config(StandardEncodings::Json, indexed_fields).await;
         config.sourcetype = Template::try_from("_json".to_string()).ok();
 
         let (sink, _) = config.build(cx).await.unwrap();
@@ -679,7 +743,7 @@ mod integration_tests {
 
         let config = HecSinkConfig {
             host_key: "roast".into(),
-            ..config(Encoding::Json, vec!["asdf".to_string()]).await
+            ..config(StandardEncodings::Json, vec!["asdf".to_string()]).await
         };
 
         let (sink, _) = config.build(cx).await.unwrap();
@@ -710,7 +774,7 @@ mod integration_tests {
 
         // OK
         {
-            let config = config(Encoding::Text, vec![]).await;
+            let config = config(StandardEncodings::Text, vec![]).await;
             let healthcheck = config_to_healthcheck(config);
             healthcheck.await.unwrap();
         }
@@ -719,7 +783,7 @@ mod integration_tests {
         {
             let config = HecSinkConfig {
                 endpoint: "http://localhost:1111".to_string(),
-                ..config(Encoding::Text, vec![]).await
+                ..config(StandardEncodings::Text, vec![]).await
             };
             let healthcheck = config_to_healthcheck(config);
             healthcheck.await.unwrap_err();
@@ -742,7 +806,7 @@ mod integration_tests {
         {
             let config = HecSinkConfig {
                 endpoint: "http://localhost:5503".to_string(),
-                ..config(Encoding::Text, vec![]).await
+                ..config(StandardEncodings::Text, vec![]).await
             };
 
             let unhealthy = warp::any()
@@ -787,7 +851,7 @@ mod integration_tests {
     }
 
     async fn config(
-        encoding: impl Into<EncodingConfig<Encoding>>,
+        encoding: impl Into<EncodingConfig<StandardEncodings>>,
         indexed_fields: Vec<String>,
     ) -> HecSinkConfig {
         HecSinkConfig {
@@ -1,18 +1,34 @@
+use flate2::write::GzEncoder;
 use serde::{de, ser};
-use serde_json::Value;
-use std::fmt;
+use serde_json::{json, Value};
+use std::{
+    fmt,
+    io::{self, Write},
+    ops::RangeInclusive,
+};
 
 pub const GZIP_NONE: usize = 0;
 pub const GZIP_FAST: usize = 1;
 pub const GZIP_DEFAULT: usize = 6;
 pub const GZIP_BEST: usize = 9;
 
+pub const ZSTD_FAST: usize = 1;
+pub const ZSTD_DEFAULT: usize = 3;
+pub const ZSTD_BEST: usize = 19;
+pub const ZSTD_MAX: usize = 21;
+
 #[derive(Debug, Derivative, Copy, Clone, Eq, PartialEq)]
 #[derivative(Default)]
 pub enum Compression {
     #[derivative(Default)]
     None,
     Gzip(Option<usize>),
+    Zstd(Option<usize>),
+    /// The snappy framing format, as opposed to the raw block format used by
+    /// protocols such as Prometheus remote write.
+    Snappy,
+    /// The LZ4 frame format.
+    Lz4,
 }
 
 impl Compression {
@@ -20,10 +36,17 @@ impl Compression {
         Compression::Gzip(None)
     }
 
+    pub const fn zstd_default() -> Compression {
+        Compression::Zstd(None)
+    }
+
     pub fn content_encoding(&self) -> Option<&'static str> {
         match self {
             Self::None => None,
             Self::Gzip(_) => Some("gzip"),
+            Self::Zstd(_) => Some("zstd"),
+            Self::Snappy => Some("x-snappy-framed"),
+            Self::Lz4 => Some("lz4"),
         }
     }
 
@@ -31,27 +54,231 @@ impl Compression {
         match self {
             Self::None => "log",
             Self::Gzip(_) => "log.gz",
+            Self::Zstd(_) => "log.zst",
+            Self::Snappy => "log.sz",
+            Self::Lz4 => "log.lz4",
+        }
+    }
+
+    /// Compresses a whole payload at once, using `default_level` when no
+    /// gzip level is configured.
+    pub fn compress(&self, input: &[u8], default_level: usize) -> io::Result<Vec<u8>> {
+        let mut compressor = Compressor::new(*self, Vec::new(), default_level)?;
+        compressor.write_all(input)?;
+        compressor.finish()
+    }
+
+    /// Fails unless the compression is gzip or none, the only encodings
+    /// accepted by `service`.
+    pub fn gzip_or_none(self, service: &str) -> crate::Result<Self> {
+        match self {
+            Compression::None | Compression::Gzip(_) => Ok(self),
+            compression => Err(format!(
+                "Compression {} is not supported by {}, use \"gzip\" or \"none\".",
+                compression, service
+            )
+            .into()),
         }
     }
+
+    /// The JSON Schema of the `compression` option, matching what we
+    /// deserialize.
+    pub fn schema() -> Value {
+        let level = |range: RangeInclusive<usize>, names: &[&str]| {
+            json!({
+                "oneOf": [
+                    { "type": "integer", "minimum": range.start(), "maximum": range.end() },
+                    { "enum": names },
+                ],
+            })
+        };
+        let algorithm = |name: &str, level: Option<Value>| {
+            let mut schema = json!({
+                "type": "object",
+                "properties": { "algorithm": { "const": name } },
+                "required": ["algorithm"],
+                "additionalProperties": false,
+            });
+            if let Some(level) = level {
+                schema["properties"]["level"] = level;
+            }
+            schema
+        };
+
+        json!({
+            "oneOf": [
+                { "enum": ["none", "gzip", "zstd", "snappy", "lz4"] },
+                algorithm("none", None),
+                algorithm(
+                    "gzip",
+                    Some(level(GZIP_NONE..=GZIP_BEST, &["none", "fast", "default", "best"])),
+                ),
+                algorithm(
+                    "zstd",
+                    Some(level(ZSTD_FAST..=ZSTD_MAX, &["fast", "default", "best"])),
+                ),
+                algorithm("snappy", None),
+                algorithm("lz4", None),
+            ],
+        })
+    }
 }
+
 impl fmt::Display for Compression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Compression::None => write!(f, "none"),
             Compression::Gzip(ref level) => write!(f, "gzip({})", level.unwrap_or(GZIP_DEFAULT)),
+            Compression::Zstd(ref level) => write!(f, "zstd({})", level.unwrap_or(ZSTD_DEFAULT)),
+            Compression::Snappy => write!(f, "snappy"),
+            Compression::Lz4 => write!(f, "lz4"),
         }
     }
 }
 
 #[cfg(feature = "rusoto_core")]
-impl From<Compression> for rusoto_core::encoding::ContentEncoding {
-    fn from(compression: Compression) -> Self {
+impl std::convert::TryFrom<Compression> for rusoto_core::encoding::ContentEncoding {
+    type Error = crate::Error;
+
+    fn try_from(compression: Compression) -> Result<Self, Self::Error> {
         match compression {
-            Compression::None => rusoto_core::encoding::ContentEncoding::Identity,
+            Compression::None => Ok(rusoto_core::encoding::ContentEncoding::Identity),
             Compression::Gzip(level) => {
                 let level = level.unwrap_or(GZIP_DEFAULT);
-                rusoto_core::encoding::ContentEncoding::Gzip(None, level as u32)
+                Ok(rusoto_core::encoding::ContentEncoding::Gzip(
+                    None,
+                    level as u32,
+                ))
+            }
+            compression => Err(format!(
+                "Compression {} is not supported by AWS APIs, use \"gzip\" or \"none\".",
+                compression
+            )
+            .into()),
+        }
+    }
+}
+
+/// A synchronous writer which compresses everything written to it into a
+/// `Vec<u8>`.
+///
+/// Most algorithms buffer data internally, so the compressed bytes written so
+/// far may lag behind the input until `finish` is called.
+pub enum Compressor {
+    Plain(Vec<u8>),
+    Gzip(GzEncoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+    #[cfg(feature = "snap")]
+    Snappy(snap::write::FrameEncoder<Vec<u8>>),
+    #[cfg(feature = "lz4_flex")]
+    Lz4(lz4_flex::frame::FrameEncoder<Vec<u8>>),
+}
+
+impl Compressor {
+    /// `default_level` is the gzip level used when none is configured, as
+    /// sinks differ in their trade off between CPU and bandwidth.
+    pub fn new(
+        compression: Compression,
+        buffer: Vec<u8>,
+        default_level: usize,
+    ) -> io::Result<Self> {
+        Ok(match compression {
+            Compression::None => Self::Plain(buffer),
+            Compression::Gzip(level) => {
+                let level = level.unwrap_or(default_level);
+                Self::Gzip(GzEncoder::new(
+                    buffer,
+                    flate2::Compression::new(level as u32),
+                ))
             }
+            Compression::Zstd(level) => {
+                let level = level.unwrap_or(ZSTD_DEFAULT);
+                Self::Zstd(zstd::stream::write::Encoder::new(buffer, level as i32)?)
+            }
+            #[cfg(feature = "snap")]
+            Compression::Snappy => Self::Snappy(snap::write::FrameEncoder::new(buffer)),
+            #[cfg(feature = "lz4_flex")]
+            Compression::Lz4 => Self::Lz4(lz4_flex::frame::FrameEncoder::new(buffer)),
+            // Only the sinks offering these algorithms pull in their dependencies.
+            #[allow(unreachable_patterns)]
+            compression => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("Compression {} isn't available in this build.", compression),
+                ))
+            }
+        })
+    }
+
+    /// The compressed bytes written so far.
+    pub fn get_mut(&mut self) -> &mut Vec<u8> {
+        match self {
+            Self::Plain(inner) => inner,
+            Self::Gzip(inner) => inner.get_mut(),
+            Self::Zstd(inner) => inner.get_mut(),
+            #[cfg(feature = "snap")]
+            Self::Snappy(inner) => inner.get_mut(),
+            #[cfg(feature = "lz4_flex")]
+            Self::Lz4(inner) => inner.get_mut(),
+        }
+    }
+
+    /// Flushes all remaining data and writes any trailer, returning the
+    /// compressed output.
+    pub fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Plain(inner) => Ok(inner),
+            Self::Gzip(inner) => inner.finish(),
+            Self::Zstd(inner) => inner.finish(),
+            #[cfg(feature = "snap")]
+            Self::Snappy(inner) => inner
+                .into_inner()
+                .map_err(|error| io::Error::new(error.error().kind(), error.error().to_string())),
+            #[cfg(feature = "lz4_flex")]
+            Self::Lz4(inner) => inner
+                .finish()
+                .map_err(|error| io::Error::new(io::ErrorKind::Other, error)),
+        }
+    }
+}
+
+impl fmt::Debug for Compressor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Plain(_) => "Plain",
+            Self::Gzip(_) => "Gzip",
+            Self::Zstd(_) => "Zstd",
+            #[cfg(feature = "snap")]
+            Self::Snappy(_) => "Snappy",
+            #[cfg(feature = "lz4_flex")]
+            Self::Lz4(_) => "Lz4",
+        };
+        f.debug_tuple("Compressor").field(&name).finish()
+    }
+}
+
+impl Write for Compressor {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(inner) => inner.write(buf),
+            Self::Gzip(inner) => inner.write(buf),
+            Self::Zstd(inner) => inner.write(buf),
+            #[cfg(feature = "snap")]
+            Self::Snappy(inner) => inner.write(buf),
+            #[cfg(feature = "lz4_flex")]
+            Self::Lz4(inner) => inner.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(inner) => inner.flush(),
+            Self::Gzip(inner) => inner.flush(),
+            Self::Zstd(inner) => inner.flush(),
+            #[cfg(feature = "snap")]
+            Self::Snappy(inner) => inner.flush(),
+            #[cfg(feature = "lz4_flex")]
+            Self::Lz4(inner) => inner.flush(),
         }
     }
 }
@@ -77,9 +304,12 @@ impl<'de> de::Deserialize<'de> for Compression {
                 match s {
                     "none" => Ok(Compression::None),
                     "gzip" => Ok(Compression::gzip_default()),
+                    "zstd" => Ok(Compression::zstd_default()),
+                    "snappy" => Ok(Compression::Snappy),
+                    "lz4" => Ok(Compression::Lz4),
                     _ => Err(de::Error::invalid_value(
                         de::Unexpected::Str(s),
-                        &r#""none" or "gzip""#,
+                        &r#""none", "gzip", "zstd", "snappy" or "lz4""#,
                     )),
                 }
             }
@@ -103,47 +333,58 @@ impl<'de> de::Deserialize<'de> for Compression {
                             if level.is_some() {
                                 return Err(de::Error::duplicate_field("level"));
                             }
-                            level = Some(match map.next_value::<Value>()? {
-                                Value::Number(level) => match level.as_u64() {
-                                    Some(value) if value <= 9 => value as usize,
-                                    Some(_) | None => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Other(&level.to_string()),
-                                            &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
-                                        ))
-                                    }
-                                },
-                                Value::String(level) => match level.as_str() {
-                                    "none" => GZIP_NONE,
-                                    "fast" => GZIP_FAST,
-                                    "default" => GZIP_DEFAULT,
-                                    "best" => GZIP_BEST,
-                                    level => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Str(level),
-                                            &r#""none", "fast", "best" or "default""#,
-                                        ))
-                                    }
-                                },
-                                value => {
-                                    return Err(de::Error::invalid_type(
-                                        de::Unexpected::Other(&value.to_string()),
-                                        &"integer or string",
-                                    ));
-                                }
-                            });
+                            level = Some(map.next_value::<Value>()?);
                         }
                         _ => return Err(de::Error::unknown_field(key, &["algorithm", "level"])),
                     };
                 }
 
+                // The meaning of a level depends on the algorithm, which may
+                // come after it, so levels are only checked once both are known.
                 match algorithm.ok_or_else(|| de::Error::missing_field("algorithm"))? {
-                    "none" => match level {
-                        Some(_) => Err(de::Error::unknown_field("level", &[])),
-                        None => Ok(Compression::None),
-                    },
-                    "gzip" => Ok(Compression::Gzip(level)),
-                    algorithm => Err(de::Error::unknown_variant(algorithm, &["none", "gzip"])),
+                    "none" | "snappy" | "lz4" if level.is_some() => {
+                        Err(de::Error::unknown_field("level", &[]))
+                    }
+                    "none" => Ok(Compression::None),
+                    "snappy" => Ok(Compression::Snappy),
+                    "lz4" => Ok(Compression::Lz4),
+                    "gzip" => level
+                        .map(|level| {
+                            parse_level(
+                                level,
+                                GZIP_NONE..=GZIP_BEST,
+                                "0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
+                                &[
+                                    ("none", GZIP_NONE),
+                                    ("fast", GZIP_FAST),
+                                    ("default", GZIP_DEFAULT),
+                                    ("best", GZIP_BEST),
+                                ],
+                                r#""none", "fast", "best" or "default""#,
+                            )
+                        })
+                        .transpose()
+                        .map(Compression::Gzip),
+                    "zstd" => level
+                        .map(|level| {
+                            parse_level(
+                                level,
+                                ZSTD_FAST..=ZSTD_MAX,
+                                "an integer from 1 to 21",
+                                &[
+                                    ("fast", ZSTD_FAST),
+                                    ("default", ZSTD_DEFAULT),
+                                    ("best", ZSTD_BEST),
+                                ],
+                                r#""fast", "best" or "default""#,
+                            )
+                        })
+                        .transpose()
+                        .map(Compression::Zstd),
+                    algorithm => Err(de::Error::unknown_variant(
+                        algorithm,
+                        &["none", "gzip", "zstd", "snappy", "lz4"],
+                    )),
                 }
             }
         }
@@ -152,6 +393,33 @@ impl<'de> de::Deserialize<'de> for Compression {
     }
 }
 
+fn parse_level<E: de::Error>(
+    level: Value,
+    range: RangeInclusive<usize>,
+    expected_number: &'static str,
+    names: &[(&str, usize)],
+    expected_name: &'static str,
+) -> Result<usize, E> {
+    match level {
+        Value::Number(level) => match level.as_u64() {
+            Some(value) if range.contains(&(value as usize)) => Ok(value as usize),
+            Some(_) | None => Err(de::Error::invalid_value(
+                de::Unexpected::Other(&level.to_string()),
+                &expected_number,
+            )),
+        },
+        Value::String(level) => names
+            .iter()
+            .find(|(name, _)| *name == level)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(&level), &expected_name)),
+        value => Err(de::Error::invalid_type(
+            de::Unexpected::Other(&value.to_string()),
+            &"integer or string",
+        )),
+    }
+}
+
 impl ser::Serialize for Compression {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -172,6 +440,17 @@ impl ser::Serialize for Compression {
                     level => map.serialize_entry("level", &level)?,
                 };
             }
+            Compression::Zstd(level) => {
+                map.serialize_entry("algorithm", "zstd")?;
+                match level.unwrap_or(ZSTD_DEFAULT) {
+                    ZSTD_FAST => map.serialize_entry("level", "fast")?,
+                    ZSTD_DEFAULT => map.serialize_entry("level", "default")?,
+                    ZSTD_BEST => map.serialize_entry("level", "best")?,
+                    level => map.serialize_entry("level", &level)?,
+                };
+            }
+            Compression::Snappy => map.serialize_entry("algorithm", "snappy")?,
+            Compression::Lz4 => map.serialize_entry("algorithm", "lz4")?,
         };
         map.end()
     }
@@ -179,7 +458,7 @@ impl ser::Serialize for Compression {
 
 #[cfg(test)]
 mod test {
-    use super::Compression;
+    use super::*;
 
     #[test]
     fn deserialization() {
@@ -195,6 +474,17 @@ mod test {
                 r#"{"algorithm": "gzip", "level": 8}"#,
                 Compression::Gzip(Some(8)),
             ),
+            (r#""zstd""#, Compression::Zstd(None)),
+            (
+                r#"{"level": 15, "algorithm": "zstd"}"#,
+                Compression::Zstd(Some(15)),
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": "fast"}"#,
+                Compression::Zstd(Some(1)),
+            ),
+            (r#""snappy""#, Compression::Snappy),
+            (r#"{"algorithm": "lz4"}"#, Compression::Lz4),
         ];
         for (sources, result) in fixtures_valid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(sources);
@@ -208,11 +498,11 @@ mod test {
             ),
             (
                 r#""b42""#,
-                r#"invalid value: string "b42", expected "none" or "gzip" at line 1 column 5"#,
+                r#"invalid value: string "b42", expected "none", "gzip", "zstd", "snappy" or "lz4" at line 1 column 5"#,
             ),
             (
                 r#"{"algorithm": "b42"}"#,
-                r#"unknown variant `b42`, expected `none` or `gzip` at line 1 column 20"#,
+                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zstd`, `snappy`, `lz4` at line 1 column 20"#,
             ),
             (
                 r#"{"algorithm": "none", "level": "default"}"#,
@@ -234,6 +524,14 @@ mod test {
                 r#"{"algorithm": "gzip", "level": "default", "key": 42}"#,
                 r#"unknown field `key`, expected `algorithm` or `level` at line 1 column 47"#,
             ),
+            (
+                r#"{"algorithm": "zstd", "level": 22}"#,
+                r#"invalid value: 22, expected an integer from 1 to 21 at line 1 column 34"#,
+            ),
+            (
+                r#"{"algorithm": "lz4", "level": "best"}"#,
+                r#"unknown field `level`, there are no fields at line 1 column 37"#,
+            ),
         ];
         for (source, result) in fixtures_invalid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(source);
@@ -241,4 +539,75 @@ mod test {
             assert_eq!(error.to_string().as_str(), *result);
         }
     }
+
+    #[test]
+    fn gzip_or_none() {
+        assert!(Compression::None.gzip_or_none("Splunk").is_ok());
+        assert!(Compression::Gzip(Some(8)).gzip_or_none("Splunk").is_ok());
+        assert_eq!(
+            Compression::zstd_default()
+                .gzip_or_none("Splunk")
+                .unwrap_err()
+                .to_string(),
+            r#"Compression zstd(3) is not supported by Splunk, use "gzip" or "none"."#
+        );
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        for compression in &[
+            Compression::None,
+            Compression::Gzip(Some(3)),
+            Compression::Zstd(Some(ZSTD_BEST)),
+            Compression::Zstd(Some(12)),
+            Compression::Snappy,
+            Compression::Lz4,
+        ] {
+            let serialized = serde_json::to_string(compression).unwrap();
+            let deserialized: Compression = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, *compression);
+        }
+    }
+
+    #[cfg(all(feature = "snap", feature = "lz4_flex"))]
+    #[test]
+    fn compresses_and_decompresses() {
+        let input = b"a payload which compresses well, a payload which compresses well".repeat(10);
+        for compression in &[
+            Compression::None,
+            Compression::gzip_default(),
+            Compression::zstd_default(),
+            Compression::Snappy,
+            Compression::Lz4,
+        ] {
+            let compressed = compression.compress(&input, GZIP_DEFAULT).unwrap();
+            let mut output = Vec::new();
+            match compression {
+                Compression::None => output = compressed,
+                Compression::Gzip(_) => {
+                    io::Read::read_to_end(
+                        &mut flate2::read::GzDecoder::new(&compressed[..]),
+                        &mut output,
+                    )
+                    .unwrap();
+                }
+                Compression::Zstd(_) => output = zstd::decode_all(&compressed[..]).unwrap(),
+                Compression::Snappy => {
+                    io::Read::read_to_end(
+                        &mut snap::read::FrameDecoder::new(&compressed[..]),
+                        &mut output,
+                    )
+                    .unwrap();
+                }
+                Compression::Lz4 => {
+                    io::Read::read_to_end(
+                        &mut lz4_flex::frame::FrameDecoder::new(&compressed[..]),
+                        &mut output,
+                    )
+                    .unwrap();
+                }
+            }
+            assert_eq!(output, input, "{}", compression);
+        }
+    }
 }
@@ -1,7 +1,6 @@
 use super::batch::{
     err_event_too_large, Batch, BatchConfig, BatchError, BatchSettings, BatchSize, PushResult,
 };
-use flate2::write::GzEncoder;
 use std::io::Write;
 
 pub mod compression;
@@ -12,24 +11,18 @@ pub mod metrics;
 pub mod partition;
 pub mod vec;
 
-pub use compression::{Compression, GZIP_FAST};
+pub use compression::{Compression, Compressor, GZIP_FAST};
 pub use partition::{Partition, PartitionBuffer, PartitionInnerBuffer};
 
 #[derive(Debug)]
 pub struct Buffer {
-    inner: Option<InnerBuffer>,
+    inner: Option<Compressor>,
     num_items: usize,
     num_bytes: usize,
     settings: BatchSize<Self>,
     compression: Compression,
 }
 
-#[derive(Debug)]
-pub enum InnerBuffer {
-    Plain(Vec<u8>),
-    Gzip(GzEncoder<Vec<u8>>),
-}
-
 impl Buffer {
     pub fn new(settings: BatchSize<Self>, compression: Compression) -> Self {
         Self {
@@ -41,44 +34,26 @@ impl Buffer {
         }
     }
 
-    fn buffer(&mut self) -> &mut InnerBuffer {
+    fn buffer(&mut self) -> &mut Compressor {
         let bytes = self.settings.bytes;
         let compression = self.compression;
         self.inner.get_or_insert_with(|| {
-            let buffer = Vec::with_capacity(bytes);
-            match compression {
-                Compression::None => InnerBuffer::Plain(buffer),
-                Compression::Gzip(level) => {
-                    let level = level.unwrap_or(GZIP_FAST);
-                    InnerBuffer::Gzip(GzEncoder::new(
-                        buffer,
-                        flate2::Compression::new(level as u32),
-                    ))
-                }
-            }
+            Compressor::new(compression, Vec::with_capacity(bytes), GZIP_FAST)
+                .expect("Creating an encoder over a Vec can't fail")
         })
     }
 
     pub fn push(&mut self, input: &[u8]) {
         self.num_items += 1;
-        match self.buffer() {
-            InnerBuffer::Plain(inner) => {
-                inner.extend_from_slice(input);
-            }
-            InnerBuffer::Gzip(inner) => {
-                inner.write_all(input).unwrap();
-            }
-        }
+        self.buffer()
+            .write_all(input)
+            .expect("Writing to Vec can't fail");
     }
 
     pub fn is_empty(&self) -> bool {
-        self.inner
-            .as_ref()
-            .map(|inner| match inner {
-                InnerBuffer::Plain(inner) => inner.is_empty(),
-                InnerBuffer::Gzip(inner) => inner.get_ref().is_empty(),
-            })
-            .unwrap_or(true)
+        // Most encoders hold on to their input until they are finished, so
+        // the compressed output can't tell whether anything was pushed.
+        self.num_items == 0
     }
 }
 
@@ -123,8 +98,7 @@ impl Batch for Buffer {
 
     fn finish(self) -> Self::Output {
         match self.inner {
-            Some(InnerBuffer::Plain(inner)) => inner,
-            Some(InnerBuffer::Gzip(inner)) => inner
+            Some(inner) => inner
                 .finish()
                 .expect("This can't fail because the inner writer is a Vec"),
             None => Vec::new(),
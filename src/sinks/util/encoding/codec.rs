@@ -0,0 +1,409 @@
+use crate::{
+    config::log_schema,
+    event::{proto, util::log::get_value, Event, LogEvent, PathComponent, Value},
+    sinks::util::encoding::EncodingConfiguration,
+};
+use prost::Message;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// The codecs shared by every sink which writes whole events as bytes.
+///
+/// Unless noted otherwise, events written to a stream or encoded as a batch
+/// are each followed by a newline.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq, Derivative, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum StandardEncodings {
+    /// The `message` field of log events, and the display format of metrics.
+    #[derivative(Default)]
+    Text,
+    /// A JSON object per event. Batches are encoded as a JSON array.
+    Json,
+    /// A JSON object per event. Batches are encoded as one object per line.
+    Ndjson,
+    /// The fields of log events as `key=value` pairs.
+    Logfmt,
+    /// The values of log events as a single CSV record. The columns are taken
+    /// from `only_fields`, in order, or all fields sorted by name otherwise.
+    Csv,
+    /// Vector's protobuf event format. In streams and batches, each event is
+    /// prefixed by its length as a 32-bit big-endian integer.
+    Native,
+}
+
+impl StandardEncodings {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Text | Self::Logfmt => "text/plain",
+            Self::Json => "application/json",
+            Self::Ndjson => "application/x-ndjson",
+            Self::Csv => "text/csv",
+            Self::Native => "application/octet-stream",
+        }
+    }
+
+    /// Encodes an event to which the rules of its config were already applied.
+    pub(crate) fn encode_event(
+        &self,
+        event: &Event,
+        columns: Option<&[Vec<PathComponent>]>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<usize> {
+        let bytes = match (self, event) {
+            (Self::Text, Event::Log(log)) => log
+                .get(log_schema().message_key())
+                .map(|value| value.as_bytes().to_vec())
+                .unwrap_or_default(),
+            (Self::Text, Event::Metric(metric)) => metric.to_string().into_bytes(),
+            (Self::Json, Event::Log(log)) | (Self::Ndjson, Event::Log(log)) => {
+                serde_json::to_vec(log)?
+            }
+            (Self::Json, Event::Metric(metric)) | (Self::Ndjson, Event::Metric(metric)) => {
+                serde_json::to_vec(metric)?
+            }
+            (Self::Logfmt, Event::Log(log)) => encode_logfmt(log).into_bytes(),
+            (Self::Csv, Event::Log(log)) => encode_csv(log, columns).into_bytes(),
+            (Self::Logfmt, Event::Metric(_)) | (Self::Csv, Event::Metric(_)) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Metrics can not be encoded as {:?}.", self),
+                ))
+            }
+            (Self::Native, event) => {
+                let mut bytes = Vec::new();
+                proto::EventWrapper::from(event.clone())
+                    .encode(&mut bytes)
+                    .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+                bytes
+            }
+        };
+        writer.write_all(&bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Encodes an event so that it can be written to a stream of events: each
+    /// event is followed by a newline, except for native events, which are
+    /// prefixed by their length.
+    fn encode_framed(
+        &self,
+        event: &Event,
+        columns: Option<&[Vec<PathComponent>]>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<usize> {
+        let mut bytes = Vec::new();
+        self.encode_event(event, columns, &mut bytes)?;
+        if *self == Self::Native {
+            writer.write_all(&(bytes.len() as u32).to_be_bytes())?;
+            writer.write_all(&bytes)?;
+            Ok(4 + bytes.len())
+        } else {
+            writer.write_all(&bytes)?;
+            writer.write_all(b"\n")?;
+            Ok(bytes.len() + 1)
+        }
+    }
+
+    fn encode_batch(
+        &self,
+        events: &[Event],
+        columns: Option<&[Vec<PathComponent>]>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<usize> {
+        let mut written = 0;
+        match self {
+            Self::Json => {
+                writer.write_all(b"[")?;
+                written += 1;
+                for (i, event) in events.iter().enumerate() {
+                    if i > 0 {
+                        writer.write_all(b",")?;
+                        written += 1;
+                    }
+                    written += self.encode_event(event, columns, writer)?;
+                }
+                writer.write_all(b"]")?;
+                written += 1;
+            }
+            Self::Text | Self::Ndjson | Self::Logfmt | Self::Csv | Self::Native => {
+                for event in events {
+                    written += self.encode_framed(event, columns, writer)?;
+                }
+            }
+        }
+        Ok(written)
+    }
+}
+
+/// Encodes some input into a writer.
+pub trait Encoder<T> {
+    /// Encodes the input into the writer, returning the number of bytes written.
+    fn encode_input(&self, input: T, writer: &mut dyn io::Write) -> io::Result<usize>;
+
+    fn encode_input_to_vec(&self, input: T) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.encode_input(input, &mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl Encoder<Event> for StandardEncodings {
+    fn encode_input(&self, event: Event, writer: &mut dyn io::Write) -> io::Result<usize> {
+        self.encode_event(&event, None, writer)
+    }
+}
+
+impl Encoder<Vec<Event>> for StandardEncodings {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        self.encode_batch(&events, None, writer)
+    }
+}
+
+/// Applies the encoding rules of the configuration before encoding a single
+/// event, without any framing.
+pub fn encode_event<C>(
+    config: &C,
+    mut event: Event,
+    writer: &mut dyn io::Write,
+) -> io::Result<usize>
+where
+    C: EncodingConfiguration<StandardEncodings>,
+{
+    config.apply_rules(&mut event);
+    config
+        .codec()
+        .encode_event(&event, config.only_fields().as_deref(), writer)
+}
+
+/// Applies the encoding rules of the configuration before encoding a single
+/// event for a stream of events, as described for `StandardEncodings`.
+pub fn encode_framed_event<C>(
+    config: &C,
+    mut event: Event,
+    writer: &mut dyn io::Write,
+) -> io::Result<usize>
+where
+    C: EncodingConfiguration<StandardEncodings>,
+{
+    config.apply_rules(&mut event);
+    config
+        .codec()
+        .encode_framed(&event, config.only_fields().as_deref(), writer)
+}
+
+/// Applies the encoding rules of the configuration to every event before
+/// encoding them as a single batch.
+pub fn encode_batch<C>(
+    config: &C,
+    mut events: Vec<Event>,
+    writer: &mut dyn io::Write,
+) -> io::Result<usize>
+where
+    C: EncodingConfiguration<StandardEncodings>,
+{
+    for event in &mut events {
+        config.apply_rules(event);
+    }
+    config
+        .codec()
+        .encode_batch(&events, config.only_fields().as_deref(), writer)
+}
+
+fn encode_logfmt(log: &LogEvent) -> String {
+    log.all_fields()
+        .map(|(key, value)| {
+            format!(
+                "{}={}",
+                logfmt_key(&key),
+                quote_logfmt(&value.to_string_lossy())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether `c` ends a logfmt key or unquoted value.
+fn is_logfmt_separator(c: char) -> bool {
+    c <= ' ' || c == '=' || c == '"' || c.is_control()
+}
+
+/// Keys can't be quoted, so the characters which would end them are replaced.
+fn logfmt_key(key: &str) -> String {
+    key.replace(is_logfmt_separator, "_")
+}
+
+/// Quotes values containing separators, escaping quotes, backslashes and
+/// control characters inside the quotes.
+fn quote_logfmt(value: &str) -> String {
+    if !value.is_empty() && !value.contains(is_logfmt_separator) {
+        return value.to_owned();
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn encode_csv(log: &LogEvent, columns: Option<&[Vec<PathComponent>]>) -> String {
+    let values = match columns {
+        Some(columns) => columns
+            .iter()
+            .map(|path| get_path(log, path).map(Value::to_string_lossy))
+            .collect::<Vec<_>>(),
+        None => log
+            .all_fields()
+            .map(|(_, value)| Some(value.to_string_lossy()))
+            .collect(),
+    };
+    values
+        .iter()
+        .map(|value| quote_csv(value.as_deref().unwrap_or_default()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn get_path<'a>(log: &'a LogEvent, path: &[PathComponent]) -> Option<&'a Value> {
+    match path.split_first() {
+        Some((PathComponent::Key(key), rest)) => log
+            .as_map()
+            .get(key)
+            .and_then(|value| get_value(value, rest.iter().cloned())),
+        _ => None,
+    }
+}
+
+fn quote_csv(value: &str) -> String {
+    if value.contains(|c: char| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::{Metric, MetricKind, MetricValue},
+        sinks::util::encoding::EncodingConfig,
+    };
+    use bytes::{Buf, Bytes};
+    use indoc::indoc;
+
+    fn log_event() -> Event {
+        let mut event = Event::from("hello world");
+        let log = event.as_mut_log();
+        log.remove(log_schema().timestamp_key());
+        log.insert("host", "localhost");
+        log.insert("count", 3);
+        event
+    }
+
+    fn encode(config: &EncodingConfig<StandardEncodings>, event: Event) -> String {
+        let mut bytes = Vec::new();
+        encode_event(config, event, &mut bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn encodes_text() {
+        let config = EncodingConfig::from(StandardEncodings::Text);
+        assert_eq!(encode(&config, log_event()), "hello world");
+    }
+
+    #[test]
+    fn encodes_json() {
+        let config = EncodingConfig::from(StandardEncodings::Json);
+        assert_eq!(
+            encode(&config, log_event()),
+            r#"{"count":3,"host":"localhost","message":"hello world"}"#
+        );
+    }
+
+    #[test]
+    fn encodes_logfmt() {
+        let config = EncodingConfig::from(StandardEncodings::Logfmt);
+        assert_eq!(
+            encode(&config, log_event()),
+            r#"count=3 host=localhost message="hello world""#
+        );
+    }
+
+    #[test]
+    fn quotes_logfmt() {
+        let config = EncodingConfig::from(StandardEncodings::Logfmt);
+        let mut event = Event::new_empty_log();
+        let log = event.as_mut_log();
+        log.insert("a", "");
+        log.insert("b", r#"say "hi" \ bye"#);
+        log.insert("c", "two\nlines\u{1b}");
+        log.insert("d", "é=1");
+        log.insert("e", "plain\\path");
+        assert_eq!(
+            encode(&config, event),
+            r#"a="" b="say \"hi\" \\ bye" c="two\nlines\u001b" d="é=1" e=plain\path"#
+        );
+        assert_eq!(logfmt_key("e f=g"), "e_f_g");
+    }
+
+    #[test]
+    fn encodes_csv_in_only_fields_order() {
+        let config: EncodingConfig<StandardEncodings> = toml::from_str(indoc! {r#"
+            codec = "csv"
+            only_fields = ["message", "count", "missing"]
+        "#})
+        .unwrap();
+        let mut event = log_event();
+        event.as_mut_log().insert("message", "hello, \"world\"");
+        assert_eq!(encode(&config, event), r#""hello, ""world""",3,"#);
+    }
+
+    #[test]
+    fn rejects_metrics_as_logfmt() {
+        let config = EncodingConfig::from(StandardEncodings::Logfmt);
+        let metric = Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+        assert!(encode_event(&config, metric.into(), &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn encodes_batches() {
+        let events = vec![log_event(), log_event()];
+
+        let json = StandardEncodings::Json
+            .encode_input_to_vec(events.clone())
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 2);
+
+        let text = StandardEncodings::Text
+            .encode_input_to_vec(events.clone())
+            .unwrap();
+        assert_eq!(text, b"hello world\nhello world\n");
+
+        let mut native = Bytes::from(
+            StandardEncodings::Native
+                .encode_input_to_vec(events)
+                .unwrap(),
+        );
+        let len = native.get_u32() as usize;
+        let event = proto::EventWrapper::decode(native.split_to(len)).unwrap();
+        assert_eq!(Event::from(event), log_event());
+    }
+}
@@ -5,6 +5,11 @@ use crate::{
         with_default::EncodingConfigWithDefault, EncodingConfiguration, TimestampFormat,
     },
 };
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{Schema, SchemaObject, SubschemaValidation},
+    JsonSchema,
+};
 use serde::{
     de::{self, DeserializeOwned, IntoDeserializer, MapAccess, Visitor},
     Deserialize, Deserializer, Serialize,
@@ -168,7 +173,28 @@ where
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+// Deserialized from the name of a codec, or from the fields of `Inner`.
+impl<E: JsonSchema> JsonSchema for EncodingConfig<E> {
+    fn schema_name() -> String {
+        format!("EncodingConfig_for_{}", E::schema_name())
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![
+                    gen.subschema_for::<E>(),
+                    gen.subschema_for::<Inner<E>>(),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, JsonSchema)]
 pub struct Inner<E> {
     codec: E,
     #[serde(default)]
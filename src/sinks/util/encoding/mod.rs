@@ -4,14 +4,20 @@
 //!   * `EncodingConfig<E>`: For sinks without a default `Encoding`.
 //!   * `EncodingConfigWithDefault<E: Default>`: For sinks that have a default `Encoding`.
 //!
-//! Your sink should define some `Encoding` enum that is used as the `E` parameter.
+//! Sinks which write whole events as bytes should use `StandardEncodings` as the `E` parameter,
+//! so that every destination offers the same set of codecs with the same output. Sinks with a
+//! protocol specific format may still define their own `Encoding` enum.
 //!
 //! You can use either of these for a sink! They both implement `EncodingConfiguration`, which you
 //! will need to import as well.
 //!
 //! # Using a configuration
 //!
-//! To use an `EncodingConfig` involves three steps:
+//! With `StandardEncodings`, call `encode_event(&config, event, &mut writer)` or
+//! `encode_batch(&config, events, &mut writer)`. These apply the configured rules before encoding,
+//! so they can't be forgotten.
+//!
+//! With a sink specific `Encoding`, using an `EncodingConfig` involves two steps:
 //!
 //!  1. Choose between `EncodingConfig` and `EncodingConfigWithDefault`.
 //!  2. Call `apply_rules(&mut event)` on this config **on each event** just before it gets sent.
@@ -24,11 +30,9 @@
 //! We don't require `E: Default` we can't always assume that, so we need to create statically
 //! distinct types! Having `EncodingConfigWithDefault` is a relatively straightforward way to
 //! accomplish this without a bunch of magic.
-//!
-// TODO: To avoid users forgetting to apply the rules, the `E` param should require a trait
-//       `Encoder` that defines some `encode` function which this config then calls internally as
-//       part of it's own (yet to be written) `encode() -> Vec<u8>` function.
 
+mod codec;
+pub use codec::{encode_batch, encode_event, encode_framed_event, Encoder, StandardEncodings};
 mod config;
 pub use config::EncodingConfig;
 mod with_default;
@@ -38,6 +42,7 @@ use crate::{
     event::{Event, PathComponent, PathIter, Value},
     Result,
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -152,7 +157,7 @@ pub trait EncodingConfiguration<E> {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TimestampFormat {
     Unix,
@@ -1,7 +1,7 @@
 use super::{
     retries::{RetryAction, RetryLogic},
-    sink, Batch, EncodedEvent, Partition, TowerBatchedSink, TowerPartitionSink, TowerRequestConfig,
-    TowerRequestSettings,
+    sink, Batch, BatchReconfigurations, EncodedEvent, Partition, PushResult, TowerBatchedSink,
+    TowerPartitionSink, TowerRequestConfig, TowerRequestSettings,
 };
 use crate::{
     buffers::Acker,
@@ -35,6 +35,27 @@ pub trait HttpSink: Send + Sync + 'static {
     async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Vec<u8>>>;
 }
 
+/// Builds the request `sink` would send for `events`, without sending it. All
+/// of the events have to fit in the single `batch`.
+pub async fn build_request_for_events<T, B>(
+    sink: &T,
+    mut batch: B,
+    events: Vec<Event>,
+) -> crate::Result<http::Request<Vec<u8>>>
+where
+    B: Batch,
+    T: HttpSink<Input = B::Input, Output = B::Output>,
+{
+    for event in events {
+        if let Some(encoded) = sink.encode_event(event) {
+            if let PushResult::Overflow(_) = batch.push(encoded.item) {
+                return Err("Events don't fit in a single batch.".into());
+            }
+        }
+    }
+    sink.build_request(batch.finish()).await
+}
+
 /// Provides a simple wrapper around internal tower and
 /// batching sinks for http.
 ///
@@ -128,6 +149,18 @@ where
             slot: None,
         }
     }
+
+    /// Starts new batches with the settings of `reconfigurations` as they
+    /// arrive. The request settings stay those the sink was built with.
+    pub fn with_reconfigurations(self, reconfigurations: BatchReconfigurations<B>) -> Self
+    where
+        B: Send + 'static,
+    {
+        Self {
+            inner: self.inner.with_reconfigurations(reconfigurations),
+            ..self
+        }
+    }
 }
 
 impl<T, B, L> Sink<Event> for BatchedHttpSink<T, B, L>
@@ -381,6 +414,10 @@ impl<T: fmt::Debug> sink::Response for http::Response<T> {
     fn is_transient(&self) -> bool {
         self.status().is_server_error()
     }
+
+    fn rejection_reason(&self) -> String {
+        format!("HTTP status {}: {:?}", self.status(), self.body())
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -18,8 +18,7 @@ pub mod uri;
 
 use crate::event::{Event, EventMetadata, LogEvent};
 use bytes::Bytes;
-use encoding::{EncodingConfig, EncodingConfiguration};
-use serde::{Deserialize, Serialize};
+use encoding::{EncodingConfig, StandardEncodings};
 use snafu::Snafu;
 use std::borrow::Cow;
 
@@ -27,12 +26,12 @@ pub use batch::{Batch, BatchConfig, BatchSettings, BatchSize, PushResult};
 pub use buffer::json::{BoxedRawValue, JsonArrayBuffer};
 pub use buffer::partition::Partition;
 pub use buffer::vec::{EncodedLength, VecBuffer};
-pub use buffer::{Buffer, Compression, PartitionBuffer, PartitionInnerBuffer};
+pub use buffer::{Buffer, Compression, Compressor, PartitionBuffer, PartitionInnerBuffer};
 pub use service::{
     Concurrency, ServiceBuilderExt, TowerBatchedSink, TowerPartitionSink, TowerRequestConfig,
     TowerRequestLayer, TowerRequestSettings,
 };
-pub use sink::{BatchSink, PartitionBatchSink, StreamSink};
+pub use sink::{BatchReconfigurations, BatchSink, PartitionBatchSink, StreamSink};
 pub use uri::UriSerde;
 
 #[derive(Debug, Snafu)]
@@ -84,16 +83,6 @@ impl<I> EncodedEvent<I> {
     }
 }
 
-/**
- * Enum representing different ways to encode events as they are sent into a Sink.
- */
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 /**
 * Encodes the given event into raw bytes that can be sent into a Sink, according to
 * the given encoding. If there are any errors encoding the event, logs a warning
@@ -101,32 +90,18 @@ pub enum Encoding {
 **/
 pub fn encode_event(
     mut event: Event,
-    encoding: &EncodingConfig<Encoding>,
+    encoding: &EncodingConfig<StandardEncodings>,
 ) -> Option<EncodedEvent<Bytes>> {
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
+    let metadata = std::mem::take(event.metadata_mut());
+    let mut bytes = Vec::new();
 
-    let b = match encoding.codec() {
-        Encoding::Json => serde_json::to_vec(&log),
-        Encoding::Text => {
-            let bytes = log
-                .get(crate::config::log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default();
-            Ok(bytes)
-        }
-    };
-    let (_fields, metadata) = log.into_parts();
-
-    b.map(|mut b| {
-        b.push(b'\n');
-        EncodedEvent {
-            item: Bytes::from(b),
+    encoding::encode_framed_event(encoding, event, &mut bytes)
+        .map(|_| EncodedEvent {
+            item: Bytes::from(bytes),
             metadata: Some(metadata),
-        }
-    })
-    .map_err(|error| error!(message = "Unable to encode.", %error))
-    .ok()
+        })
+        .map_err(|error| error!(message = "Unable to encode.", %error))
+        .ok()
 }
 
 /// Joins namespace with name via delimiter if namespace is present.
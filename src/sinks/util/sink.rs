@@ -46,7 +46,7 @@ use futures::{
     future::BoxFuture,
     ready,
     stream::{BoxStream, FuturesUnordered},
-    FutureExt, Sink, Stream, TryFutureExt,
+    FutureExt, Sink, Stream, StreamExt, TryFutureExt,
 };
 use pin_project::pin_project;
 use std::{
@@ -66,6 +66,10 @@ use tracing_futures::Instrument;
 
 // === StreamSink ===
 
+/// New batches for a batch sink to start its batches from, with their linger
+/// timeout, as the sink is reconfigured.
+pub type BatchReconfigurations<B> = BoxStream<'static, (B, Duration)>;
+
 #[async_trait]
 pub trait StreamSink {
     async fn run(&mut self, input: BoxStream<'_, Event>) -> Result<(), ()>;
@@ -117,6 +121,20 @@ where
         let inner = PartitionBatchSink::new(service, batch, timeout, acker);
         Self { inner }
     }
+
+    /// Starts new batches with the settings of `reconfigurations` as they
+    /// arrive. See `PartitionBatchSink::with_reconfigurations`.
+    pub fn with_reconfigurations(self, reconfigurations: BatchReconfigurations<B>) -> Self
+    where
+        B: Send + 'static,
+    {
+        let reconfigurations = reconfigurations
+            .map(|(batch, timeout)| (PartitionBuffer::new(batch), timeout))
+            .boxed();
+        Self {
+            inner: self.inner.with_reconfigurations(reconfigurations),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -191,6 +209,7 @@ where
     partitions: HashMap<K, StatefulBatch<MetadataBatch<B>>>,
     timeout: Duration,
     lingers: HashMap<K, Pin<Box<Sleep>>>,
+    reconfigurations: Option<BatchReconfigurations<B>>,
     closing: bool,
 }
 
@@ -214,9 +233,31 @@ where
             partitions: HashMap::new(),
             timeout,
             lingers: HashMap::new(),
+            reconfigurations: None,
             closing: false,
         }
     }
+
+    /// Starts new batches from the batches of `reconfigurations`, with their
+    /// timeout, as they arrive. Batches already in progress are sent with the
+    /// settings they were started with.
+    pub fn with_reconfigurations(mut self, reconfigurations: BatchReconfigurations<B>) -> Self {
+        self.reconfigurations = Some(reconfigurations);
+        self
+    }
+
+    fn poll_reconfigurations(&mut self, cx: &mut Context<'_>) {
+        while let Some(reconfigurations) = self.reconfigurations.as_mut() {
+            match reconfigurations.poll_next_unpin(cx) {
+                Poll::Ready(Some((batch, timeout))) => {
+                    self.batch = StatefulBatch::from(MetadataBatch::from(batch));
+                    self.timeout = timeout;
+                }
+                Poll::Ready(None) => self.reconfigurations = None,
+                Poll::Pending => break,
+            }
+        }
+    }
 }
 
 impl<S, B, K> Sink<EncodedEvent<B::Input>> for PartitionBatchSink<S, B, K>
@@ -232,6 +273,8 @@ where
     type Error = crate::Error;
 
     fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_reconfigurations(cx);
+
         if self.buffer.is_some() {
             match self.as_mut().poll_flush(cx) {
                 Poll::Ready(Ok(())) => {}
@@ -426,6 +469,7 @@ where
             .call(req)
             .err_into()
             .map(move |result| {
+                let mut reason = None;
                 let status = match result {
                     Ok(response) => {
                         if response.is_successful() {
@@ -436,6 +480,7 @@ where
                             EventStatus::Errored
                         } else {
                             error!(message = "Response failed.", ?response);
+                            reason = Some(response.rejection_reason());
                             EventStatus::Failed
                         }
                     }
@@ -445,7 +490,10 @@ where
                     }
                 };
                 for metadata in metadata {
-                    metadata.update_status(status);
+                    match &reason {
+                        Some(reason) => metadata.reject(reason),
+                        None => metadata.update_status(status),
+                    }
                 }
 
                 // If the rx end is dropped we still completed
@@ -505,6 +553,11 @@ pub trait Response: fmt::Debug {
     fn is_transient(&self) -> bool {
         true
     }
+
+    /// Describes why the request failed, for the events sent to dead-letter outputs.
+    fn rejection_reason(&self) -> String {
+        format!("{:?}", self)
+    }
 }
 
 impl Response for () {}
@@ -728,6 +781,33 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn batch_sink_reconfigures_batches() {
+        let (acker, _) = Acker::new_for_testing();
+        let sent_requests = Arc::new(Mutex::new(Vec::new()));
+
+        let svc = tower::service_fn(|req| {
+            let sent_requests = Arc::clone(&sent_requests);
+            sent_requests.lock().unwrap().push(req);
+            future::ok::<_, std::io::Error>(())
+        });
+        let batch = BatchSettings::default().bytes(9999).events(10);
+        let reconfigured = BatchSettings::default().bytes(9999).events(3);
+        let buffered = BatchSink::new(svc, VecBuffer::new(batch.size), TIMEOUT, acker)
+            .with_reconfigurations(
+                stream::iter(vec![(VecBuffer::new(reconfigured.size), TIMEOUT)]).boxed(),
+            );
+
+        let _ = buffered
+            .sink_map_err(drop)
+            .send_all(&mut stream::iter(0..7).map(|item| Ok(EncodedEvent::new(item))))
+            .await
+            .unwrap();
+
+        let output = sent_requests.lock().unwrap();
+        assert_eq!(&*output, &vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+    }
+
     #[tokio::test]
     async fn batch_sink_flushes_below_min_on_close() {
         let (acker, _) = Acker::new_for_testing();
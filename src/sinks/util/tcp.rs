@@ -22,6 +22,7 @@ use crate::{
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::{stream::BoxStream, task::noop_waker_ref, SinkExt, StreamExt};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{
@@ -50,7 +51,7 @@ enum TcpError {
     SendError { source: tokio::io::Error },
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TcpSinkConfig {
     address: String,
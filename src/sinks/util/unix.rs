@@ -19,6 +19,7 @@ use crate::{
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::{stream::BoxStream, SinkExt, StreamExt};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{path::PathBuf, pin::Pin, sync::Arc, time::Duration};
@@ -30,7 +31,7 @@ pub enum UnixError {
     ConnectError { source: tokio::io::Error },
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct UnixSinkConfig {
     pub path: PathBuf,
@@ -166,7 +167,7 @@ impl StreamSink for UnixSink {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sinks::util::{encode_event, Encoding};
+    use crate::sinks::util::{encode_event, encoding::StandardEncodings};
     use crate::test_util::{random_lines_with_stream, CountReceiver};
     use tokio::net::UnixListener;
 
@@ -205,7 +206,7 @@ mod tests {
         // Set up Sink
         let config = UnixSinkConfig::new(out_path);
         let cx = SinkContext::new_test();
-        let encoding = Encoding::Text.into();
+        let encoding = StandardEncodings::Text.into();
         let (sink, _healthcheck) = config
             .build(cx, move |event| encode_event(event, &encoding))
             .unwrap();
@@ -1,4 +1,5 @@
 use super::handlers::RecordDecodeError;
+use crate::sources::util::DecompressionError;
 use snafu::Snafu;
 use warp::http::StatusCode;
 
@@ -43,6 +44,11 @@ pub enum RequestError {
         source: crate::pipeline::ClosedError,
         request_id: String,
     },
+    #[snafu(display("Could not decompress request {}: {}", request_id, source))]
+    Decompress {
+        source: DecompressionError,
+        request_id: String,
+    },
     #[snafu(display("Unsupported encoding: {}", encoding))]
     UnsupportedEncoding {
         encoding: String,
@@ -61,6 +67,11 @@ impl RequestError {
             AccessKeyMissing { .. } => StatusCode::UNAUTHORIZED,
             AccessKeyInvalid { .. } => StatusCode::UNAUTHORIZED,
             Parse { .. } => StatusCode::UNAUTHORIZED,
+            Decompress {
+                source: DecompressionError::TooLarge { .. },
+                ..
+            } => StatusCode::PAYLOAD_TOO_LARGE,
+            Decompress { .. } => StatusCode::BAD_REQUEST,
             UnsupportedEncoding { .. } => StatusCode::BAD_REQUEST,
             ParseRecords { .. } => StatusCode::BAD_REQUEST,
             Decode { .. } => StatusCode::BAD_REQUEST,
@@ -75,6 +86,7 @@ impl RequestError {
             AccessKeyMissing { ref request_id, .. } => Some(request_id),
             AccessKeyInvalid { ref request_id, .. } => Some(request_id),
             Parse { ref request_id, .. } => Some(request_id),
+            Decompress { ref request_id, .. } => Some(request_id),
             UnsupportedEncoding { ref request_id, .. } => Some(request_id),
             ParseRecords { ref request_id, .. } => Some(request_id),
             Decode { ref request_id, .. } => Some(request_id),
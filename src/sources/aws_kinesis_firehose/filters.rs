@@ -6,19 +6,20 @@ use super::{
 };
 use crate::{
     internal_events::{AwsKinesisFirehoseRequestError, AwsKinesisFirehoseRequestReceived},
+    sources::util::{decompress, DecompressionConfig, DecompressionError},
     Pipeline,
 };
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 use chrono::Utc;
-use flate2::read::MultiGzDecoder;
 use snafu::ResultExt;
-use std::{convert::Infallible, io};
+use std::convert::Infallible;
 use warp::{http::StatusCode, Filter};
 
 /// Handles routing of incoming HTTP requests from AWS Kinesis Firehose
 pub fn firehose(
     access_key: Option<String>,
     record_compression: Compression,
+    decompression: DecompressionConfig,
     out: Pipeline,
 ) -> impl Filter<Extract = impl warp::Reply, Error = Infallible> + Clone {
     warp::post()
@@ -38,7 +39,7 @@ pub fn firehose(
                 })
                 .untuple_one(),
         )
-        .and(parse_body())
+        .and(parse_body(decompression))
         .and(warp::any().map(move || record_compression))
         .and(warp::any().map(move || out.clone()))
         .and_then(handlers::firehose)
@@ -48,33 +49,35 @@ pub fn firehose(
 /// Decode (if needed) and parse request body
 ///
 /// Firehose can be configured to gzip compress messages so we handle this here
-fn parse_body() -> impl Filter<Extract = (FirehoseRequest,), Error = warp::reject::Rejection> + Clone
-{
+fn parse_body(
+    decompression: DecompressionConfig,
+) -> impl Filter<Extract = (FirehoseRequest,), Error = warp::reject::Rejection> + Clone {
     warp::any()
         .and(warp::header::optional::<String>("Content-Encoding"))
         .and(warp::header("X-Amz-Firehose-Request-Id"))
         .and(warp::body::bytes())
         .and_then(
-            |encoding: Option<String>, request_id: String, body: Bytes| async move {
-                match encoding {
-                    Some(s) if s == "gzip" => {
-                        Ok(Box::new(MultiGzDecoder::new(body.reader())) as Box<dyn io::Read>)
-                    }
-                    Some(s) => Err(warp::reject::Rejection::from(
-                        RequestError::UnsupportedEncoding {
-                            encoding: s,
+            move |encoding: Option<String>, request_id: String, body: Bytes| {
+                let result = decompress(encoding.as_deref(), body, &decompression)
+                    .map_err(|error| match error {
+                        DecompressionError::UnsupportedEncoding { encoding } => {
+                            RequestError::UnsupportedEncoding {
+                                encoding,
+                                request_id: request_id.clone(),
+                            }
+                        }
+                        source => RequestError::Decompress {
+                            source,
                             request_id: request_id.clone(),
                         },
-                    )),
-                    None => Ok(Box::new(body.reader()) as Box<dyn io::Read>),
-                }
-                .and_then(|r| {
-                    serde_json::from_reader(r)
-                        .context(Parse {
+                    })
+                    .and_then(|body| {
+                        serde_json::from_slice(&body).context(Parse {
                             request_id: request_id.clone(),
                         })
-                        .map_err(warp::reject::custom)
-                })
+                    })
+                    .map_err(warp::reject::custom);
+                async move { result }
             },
         )
 }
@@ -1,5 +1,6 @@
 use crate::{
     config::{DataType, GenerateConfig, Resource, SourceConfig, SourceContext, SourceDescription},
+    sources::util::DecompressionConfig,
     tls::{MaybeTlsSettings, TlsConfig},
 };
 use futures::FutureExt;
@@ -17,6 +18,11 @@ pub struct AwsKinesisFirehoseConfig {
     access_key: Option<String>,
     tls: Option<TlsConfig>,
     record_compression: Option<Compression>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    decompression: DecompressionConfig,
 }
 
 #[derive(Derivative, Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -46,6 +52,7 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
         let svc = filters::firehose(
             self.access_key.clone(),
             self.record_compression.unwrap_or_default(),
+            self.decompression.clone(),
             cx.out,
         );
 
@@ -88,6 +95,7 @@ impl GenerateConfig for AwsKinesisFirehoseConfig {
             access_key: None,
             tls: None,
             record_compression: None,
+            decompression: Default::default(),
         })
         .unwrap()
     }
@@ -130,6 +138,7 @@ mod tests {
                 tls: None,
                 access_key,
                 record_compression,
+                decompression: Default::default(),
             }
             .build(SourceContext::new_test(sender))
             .await
@@ -70,9 +70,11 @@ impl SourceConfig for AwsS3Config {
 
         match self.strategy {
             Strategy::Sqs => Ok(Box::pin(
-                self.create_sqs_ingestor(multiline_config)
-                    .await?
-                    .run(cx.out, cx.shutdown),
+                self.create_sqs_ingestor(multiline_config).await?.run(
+                    cx.out,
+                    cx.shutdown,
+                    cx.acknowledgements,
+                ),
             )),
         }
     }
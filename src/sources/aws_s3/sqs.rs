@@ -1,10 +1,13 @@
 use crate::{
     config::log_schema,
-    event::Event,
-    internal_events::aws_s3::source::{
-        SqsMessageDeleteFailed, SqsMessageDeleteSucceeded, SqsMessageProcessingFailed,
-        SqsMessageProcessingSucceeded, SqsMessageReceiveFailed, SqsMessageReceiveSucceeded,
-        SqsS3EventReceived, SqsS3EventRecordInvalidEventIgnored,
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
+    internal_events::{
+        aws_s3::source::{
+            SqsMessageDeleteFailed, SqsMessageDeleteSucceeded, SqsMessageProcessingFailed,
+            SqsMessageProcessingSucceeded, SqsMessageReceiveFailed, SqsMessageReceiveSucceeded,
+            SqsS3EventReceived, SqsS3EventRecordInvalidEventIgnored,
+        },
+        BatchNotDelivered,
     },
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
@@ -13,7 +16,7 @@ use crate::{
 use bytes::Bytes;
 use chrono::{DateTime, TimeZone, Utc};
 use codec::BytesDelimitedCodec;
-use futures::{SinkExt, Stream, StreamExt, TryFutureExt};
+use futures::{stream::FuturesUnordered, SinkExt, Stream, StreamExt, TryFutureExt};
 use lazy_static::lazy_static;
 use rusoto_core::{Region, RusotoError};
 use rusoto_s3::{GetObjectError, GetObjectRequest, S3Client, S3};
@@ -23,7 +26,7 @@ use rusoto_sqs::{
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use snafu::{ResultExt, Snafu};
-use std::{future::ready, time::Duration};
+use std::{future::ready, sync::Arc, time::Duration};
 use tokio::time;
 use tokio_stream::wrappers::IntervalStream;
 use tokio_util::codec::FramedRead;
@@ -156,18 +159,23 @@ impl Ingestor {
         })
     }
 
-    pub(super) async fn run(self, mut out: Pipeline, shutdown: ShutdownSignal) -> Result<(), ()> {
+    pub(super) async fn run(
+        self,
+        mut out: Pipeline,
+        shutdown: ShutdownSignal,
+        acknowledgements: bool,
+    ) -> Result<(), ()> {
         let mut stream =
             IntervalStream::new(time::interval(self.poll_interval)).take_until(shutdown);
 
         while stream.next().await.is_some() {
-            self.run_once(&mut out).await
+            self.run_once(&mut out, acknowledgements).await
         }
 
         Ok(())
     }
 
-    async fn run_once(&self, out: &mut Pipeline) {
+    async fn run_once(&self, out: &mut Pipeline, acknowledgements: bool) {
         let messages = self
             .receive_messages()
             .inspect_ok(|messages| {
@@ -181,6 +189,9 @@ impl Ingestor {
             .await
             .unwrap_or_default();
 
+        // Messages wait for their events to be delivered concurrently, so one
+        // slow message doesn't hold back the deletion of the others.
+        let mut deletions = FuturesUnordered::new();
         for message in messages {
             let receipt_handle = match message.receipt_handle {
                 None => {
@@ -198,26 +209,23 @@ impl Ingestor {
                 .clone()
                 .unwrap_or_else(|| "<unknown>".to_owned());
 
-            match self.handle_sqs_message(message, out).await {
+            let (batch, receiver) = if acknowledgements {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                (Some(batch), Some(receiver))
+            } else {
+                (None, None)
+            };
+
+            match self.handle_sqs_message(message, out, batch).await {
                 Ok(()) => {
                     emit!(SqsMessageProcessingSucceeded {
                         message_id: &message_id
                     });
-                    if self.delete_message {
-                        match self.delete_message(receipt_handle).await {
-                            Ok(_) => {
-                                emit!(SqsMessageDeleteSucceeded {
-                                    message_id: &message_id
-                                });
-                            }
-                            Err(err) => {
-                                emit!(SqsMessageDeleteFailed {
-                                    error: &err,
-                                    message_id: &message_id,
-                                });
-                            }
-                        }
-                    }
+                    deletions.push(self.delete_once_delivered(
+                        receipt_handle,
+                        message_id,
+                        receiver,
+                    ));
                 }
                 Err(err) => {
                     emit!(SqsMessageProcessingFailed {
@@ -227,28 +235,67 @@ impl Ingestor {
                 }
             }
         }
+
+        while deletions.next().await.is_some() {}
+    }
+
+    /// Deletes the message once all sinks have delivered its events, otherwise
+    /// it becomes visible again after the timeout.
+    async fn delete_once_delivered(
+        &self,
+        receipt_handle: String,
+        message_id: String,
+        receiver: Option<BatchStatusReceiver>,
+    ) {
+        let delivered = match receiver {
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => true,
+                status => {
+                    emit!(BatchNotDelivered { status });
+                    false
+                }
+            },
+            None => true,
+        };
+        if self.delete_message && delivered {
+            match self.delete_message(receipt_handle).await {
+                Ok(_) => {
+                    emit!(SqsMessageDeleteSucceeded {
+                        message_id: &message_id
+                    });
+                }
+                Err(err) => {
+                    emit!(SqsMessageDeleteFailed {
+                        error: &err,
+                        message_id: &message_id,
+                    });
+                }
+            }
+        }
     }
 
     async fn handle_sqs_message(
         &self,
         message: Message,
         out: &mut Pipeline,
+        batch: Option<Arc<BatchNotifier>>,
     ) -> Result<(), ProcessingError> {
         let s3_event: S3Event = serde_json::from_str(message.body.unwrap_or_default().as_ref())
             .context(InvalidSqsMessage {
                 message_id: message.message_id.unwrap_or_else(|| "<empty>".to_owned()),
             })?;
 
-        self.handle_s3_event(s3_event, out).await
+        self.handle_s3_event(s3_event, out, batch).await
     }
 
     async fn handle_s3_event(
         &self,
         s3_event: S3Event,
         out: &mut Pipeline,
+        batch: Option<Arc<BatchNotifier>>,
     ) -> Result<(), ProcessingError> {
         for record in s3_event.records {
-            self.handle_s3_event_record(record, out).await?
+            self.handle_s3_event_record(record, out, &batch).await?
         }
         Ok(())
     }
@@ -257,6 +304,7 @@ impl Ingestor {
         &self,
         s3_event: S3EventRecord,
         out: &mut Pipeline,
+        batch: &Option<Arc<BatchNotifier>>,
     ) -> Result<(), ProcessingError> {
         let event_version: semver::Version = s3_event.event_version.clone().into();
         if !SUPPORTED_S3S_EVENT_VERSION.matches(&event_version) {
@@ -360,6 +408,9 @@ impl Ingestor {
                     });
 
                     let mut event = Event::from(line);
+                    if let Some(batch) = batch {
+                        event.add_batch_notifier(Arc::clone(batch));
+                    }
 
                     let log = event.as_mut_log();
                     log.insert("bucket", s3_event.s3.bucket.name.clone());
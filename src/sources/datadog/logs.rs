@@ -6,7 +6,10 @@ use crate::{
     event::Event,
     sources::{
         self,
-        util::{decode_body, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+        util::{
+            decode_body, DecompressionConfig, Encoding, ErrorMessage, HttpSource,
+            HttpSourceAuthConfig,
+        },
     },
     tls::TlsConfig,
 };
@@ -28,6 +31,11 @@ pub struct DatadogLogsConfig {
     address: SocketAddr,
     tls: Option<TlsConfig>,
     auth: Option<HttpSourceAuthConfig>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    decompression: DecompressionConfig,
     #[serde(default = "crate::serde::default_true")]
     store_api_key: bool,
 }
@@ -42,6 +50,7 @@ impl GenerateConfig for DatadogLogsConfig {
             address: "0.0.0.0:8080".parse().unwrap(),
             tls: None,
             auth: None,
+            decompression: Default::default(),
             store_api_key: true,
         })
         .unwrap()
@@ -56,7 +65,15 @@ impl SourceConfig for DatadogLogsConfig {
             store_api_key: self.store_api_key,
         };
         // We accept /v1/input & /v1/input/<API_KEY>
-        source.run(self.address, "/v1/input", false, &self.tls, &self.auth, cx)
+        source.run(
+            self.address,
+            "/v1/input",
+            false,
+            &self.tls,
+            &self.auth,
+            &self.decompression,
+            cx,
+        )
     }
 
     fn output_type(&self) -> DataType {
@@ -159,6 +176,7 @@ mod tests {
                 address,
                 tls: None,
                 auth: None,
+                decompression: Default::default(),
                 store_api_key,
             }
             .build(context)
@@ -1,7 +1,7 @@
 use crate::async_read::VecAsyncReadExt;
+use crate::codecs::{DecodingConfig, Framer};
 use crate::config::{DataType, SourceContext};
-use crate::event::LogEvent;
-use crate::internal_events::{ExecCommandExecuted, ExecTimeout};
+use crate::internal_events::{DecoderParseFailed, ExecCommandExecuted, ExecTimeout};
 use crate::{
     config::{log_schema, SourceConfig, SourceDescription},
     event::Event,
@@ -10,10 +10,9 @@ use crate::{
     Pipeline,
 };
 use bytes::Bytes;
-use chrono::Utc;
 use futures::{FutureExt, SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
-use snafu::Snafu;
+use snafu::{ResultExt, Snafu};
 use std::io::{Error, ErrorKind};
 use std::path::PathBuf;
 use std::process::ExitStatus;
@@ -40,6 +39,8 @@ pub struct ExecConfig {
     pub event_per_line: bool,
     #[serde(default = "default_maximum_buffer_size")]
     pub maximum_buffer_size_bytes: usize,
+    /// Without explicit framing, output is split according to `event_per_line`.
+    pub decoding: Option<DecodingConfig>,
 }
 
 // TODO: Would be nice to combine the scheduled and streaming config with the mode enum once
@@ -73,6 +74,8 @@ pub enum ExecConfigError {
     CommandEmpty,
     #[snafu(display("The maximum buffer size must be greater than zero"))]
     ZeroBuffer,
+    #[snafu(display("Invalid framing: {}", source))]
+    InvalidFraming { source: crate::codecs::FramingError },
 }
 
 impl Default for ExecConfig {
@@ -88,6 +91,7 @@ impl Default for ExecConfig {
             include_stderr: default_include_stderr(),
             event_per_line: default_events_per_line(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
+            decoding: None,
         }
     }
 }
@@ -141,10 +145,18 @@ impl ExecConfig {
         } else if self.maximum_buffer_size_bytes == 0 {
             Err(ExecConfigError::ZeroBuffer)
         } else {
-            Ok(())
+            self.framer().map(|_| ())
         }
     }
 
+    fn framer(&self) -> Result<Option<Framer>, ExecConfigError> {
+        self.decoding
+            .as_ref()
+            .and_then(|decoding| decoding.framing.as_ref())
+            .map(|framing| framing.build().context(InvalidFraming))
+            .transpose()
+    }
+
     fn command_line(&self) -> String {
         self.command.join(" ")
     }
@@ -204,7 +216,9 @@ impl SourceConfig for ExecConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding
+            .as_ref()
+            .map_or(DataType::Log, DecodingConfig::output_type)
     }
 
     fn source_type(&self) -> &'static str {
@@ -318,6 +332,10 @@ async fn run_command(
 ) -> Result<Option<ExitStatus>, Error> {
     debug!("Starting command run.");
     let mut command = build_command(&config);
+    let framer = config
+        .framer()
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, error))?;
+    let parser = config.decoding.clone().unwrap_or_default().build_parser();
 
     // Mark the start time just before spawning the process as
     // this seems to be the best approximation of exec duration
@@ -340,6 +358,7 @@ async fn run_command(
 
         spawn_reader_thread(
             stderr_reader,
+            framer.clone(),
             config.event_per_line,
             config.maximum_buffer_size_bytes,
             STDERR,
@@ -360,6 +379,7 @@ async fn run_command(
 
     spawn_reader_thread(
         stdout_reader,
+        framer,
         config.event_per_line,
         config.maximum_buffer_size_bytes,
         STDOUT,
@@ -367,14 +387,29 @@ async fn run_command(
     );
 
     while let Some((line, stream)) = receiver.recv().await {
-        let event = create_event(&config, &hostname, line, &Some(stream.to_string()), pid);
+        emit!(ExecEventReceived {
+            command: config.command_line().as_str(),
+            byte_size: line.len(),
+        });
+
+        let events = match parser.parse(line) {
+            Ok(events) => events,
+            Err(error) => {
+                emit!(DecoderParseFailed { error });
+                continue;
+            }
+        };
 
-        let _ = out
-            .send(event)
-            .await
-            .map_err(|_: crate::pipeline::ClosedError| {
-                error!(message = "Failed to forward events; downstream is closed.");
-            });
+        for event in events {
+            let event = create_event(&config, &hostname, event, &Some(stream.to_string()), pid);
+
+            let _ = out
+                .send(event)
+                .await
+                .map_err(|_: crate::pipeline::ClosedError| {
+                    error!(message = "Failed to forward events; downstream is closed.");
+                });
+        }
     }
 
     let elapsed = start.elapsed();
@@ -445,21 +480,14 @@ fn build_command(config: &ExecConfig) -> Command {
 fn create_event(
     config: &ExecConfig,
     hostname: &Option<String>,
-    line: Bytes,
+    mut event: Event,
     data_stream: &Option<String>,
     pid: Option<u32>,
 ) -> Event {
-    emit!(ExecEventReceived {
-        command: config.command_line().as_str(),
-        byte_size: line.len(),
-    });
-    let mut log_event = LogEvent::default();
-
-    // Add message
-    log_event.insert(log_schema().message_key(), line);
-
-    // Add timestamp
-    log_event.insert(log_schema().timestamp_key(), Utc::now());
+    let log_event = match &mut event {
+        Event::Log(log_event) => log_event,
+        Event::Metric(_) => return event,
+    };
 
     // Add source type
     log_event.insert(log_schema().source_type_key(), Bytes::from(EXEC));
@@ -482,11 +510,12 @@ fn create_event(
     // Add command
     log_event.insert(COMMAND_KEY, config.command.clone());
 
-    Event::Log(log_event)
+    event
 }
 
 fn spawn_reader_thread<R: 'static + AsyncRead + Unpin + std::marker::Send>(
     reader: BufReader<R>,
+    framer: Option<Framer>,
     event_per_line: bool,
     buf_size: usize,
     stream: &'static str,
@@ -496,7 +525,22 @@ fn spawn_reader_thread<R: 'static + AsyncRead + Unpin + std::marker::Send>(
     Box::pin(tokio::spawn(async move {
         debug!("Start capturing {} command output.", stream);
 
-        if event_per_line {
+        if let Some(framer) = framer {
+            let mut bytes_stream = FramedRead::new(reader, framer);
+            while let Some(result) = bytes_stream.next().await {
+                match result {
+                    Ok(frame) => {
+                        if sender.send((frame, stream)).await.is_err() {
+                            debug!("Receive channel closed, unable to send.");
+                            break;
+                        }
+                    }
+                    Err(error) => {
+                        error!(message = "Error decoding frames.", %error);
+                    }
+                }
+            }
+        } else if event_per_line {
             let codec = LinesCodec::new_with_max_length(buf_size);
             let mut bytes_stream = FramedRead::new(reader, codec);
             while let Some(result) = bytes_stream.next().await {
@@ -561,7 +605,7 @@ mod tests {
         let data_stream = Some(STDOUT.to_string());
         let pid = Some(8888_u32);
 
-        let event = create_event(&config, &hostname, line, &data_stream, pid);
+        let event = create_event(&config, &hostname, Event::from(line), &data_stream, pid);
         let log = event.into_log();
 
         assert_eq!(log[log_schema().host_key()], "Some.Machine".into());
@@ -581,7 +625,7 @@ mod tests {
         let data_stream = Some(STDOUT.to_string());
         let pid = Some(8888_u32);
 
-        let event = create_event(&config, &hostname, line, &data_stream, pid);
+        let event = create_event(&config, &hostname, Event::from(line), &data_stream, pid);
         let log = event.into_log();
 
         assert_eq!(log[log_schema().host_key()], "Some.Machine".into());
@@ -607,6 +651,7 @@ mod tests {
             include_stderr: default_include_stderr(),
             event_per_line: default_events_per_line(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
+            decoding: None,
         };
 
         let command = build_command(&config);
@@ -631,7 +676,7 @@ mod tests {
         let reader = BufReader::new(buf);
         let (sender, mut receiver) = channel(1024);
 
-        spawn_reader_thread(reader, true, 88888, STDOUT, sender);
+        spawn_reader_thread(reader, None, true, 88888, STDOUT, sender);
 
         let mut counter = 0;
         if let Some((line, stream)) = receiver.recv().await {
@@ -657,7 +702,7 @@ mod tests {
         let reader = BufReader::new(buf);
         let (sender, mut receiver) = channel(1024);
 
-        spawn_reader_thread(reader, true, 6, STDOUT, sender);
+        spawn_reader_thread(reader, None, true, 6, STDOUT, sender);
 
         let mut counter = 0;
 
@@ -685,7 +730,7 @@ mod tests {
         let reader = BufReader::new(buf);
         let (sender, mut receiver) = channel(1024);
 
-        spawn_reader_thread(reader, false, 88888, STDOUT, sender);
+        spawn_reader_thread(reader, None, false, 88888, STDOUT, sender);
 
         let mut counter = 0;
         if let Some((line, stream)) = receiver.recv().await {
@@ -705,7 +750,7 @@ mod tests {
         let reader = BufReader::new(buf);
         let (sender, mut receiver) = channel(1024);
 
-        spawn_reader_thread(reader, false, 6, STDOUT, sender);
+        spawn_reader_thread(reader, None, false, 6, STDOUT, sender);
 
         let mut counter = 0;
         if let Some((line, stream)) = receiver.recv().await {
@@ -784,6 +829,7 @@ mod tests {
             include_stderr: default_include_stderr(),
             event_per_line: default_events_per_line(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
+            decoding: None,
         }
     }
 }
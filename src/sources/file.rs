@@ -1,9 +1,13 @@
 use super::util::{EncodingConfig, MultilineConfig};
 use crate::{
+    codecs::DecodingConfig,
     config::{log_schema, DataType, SourceConfig, SourceContext, SourceDescription},
     encoding_transcode::{Decoder, Encoder},
-    event::Event,
-    internal_events::{FileEventReceived, FileOpen, FileSourceInternalEventsEmitter},
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{
+        BatchNotDelivered, DecoderParseFailed, FileEventReceived, FileOpen,
+        FileSourceInternalEventsEmitter,
+    },
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
     trace::{current_span, Instrument},
@@ -13,20 +17,24 @@ use bytes::Bytes;
 use chrono::Utc;
 use file_source::{
     paths_provider::glob::{Glob, MatchOptions},
-    FileServer, FingerprintStrategy, Fingerprinter, ReadFrom,
+    Checkpointer, CheckpointsView, FileFingerprint, FilePosition, FileServer, FingerprintStrategy,
+    Fingerprinter, Line, ReadFrom,
 };
 use futures::{
     future::TryFutureExt,
-    stream::{Stream, StreamExt},
+    stream::{self, Stream, StreamExt},
     SinkExt,
 };
 use regex::bytes::Regex;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
+use std::collections::HashSet;
 use std::convert::TryInto;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::task::spawn_blocking;
+use tokio::{sync::mpsc, task::spawn_blocking};
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -55,6 +63,10 @@ enum BuildError {
         indicator: String,
         source: regex::Error,
     },
+    #[snafu(display(
+        "The file source splits files with `line_delimiter` and does not support `decoding.framing`"
+    ))]
+    UnsupportedFraming,
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
@@ -88,6 +100,7 @@ pub struct FileConfig {
     pub remove_after_secs: Option<u64>,
     pub line_delimiter: String,
     pub encoding: Option<EncodingConfig>,
+    pub decoding: Option<DecodingConfig>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
@@ -174,6 +187,7 @@ impl Default for FileConfig {
             remove_after_secs: None,
             line_delimiter: "\n".to_string(),
             encoding: None,
+            decoding: None,
         }
     }
 }
@@ -207,13 +221,28 @@ impl SourceConfig for FileConfig {
                 Regex::new(indicator)
                     .with_context(|| InvalidMessageStartIndicator { indicator })?;
             }
+
+            if let Some(DecodingConfig {
+                framing: Some(_), ..
+            }) = self.decoding
+            {
+                return Err(BuildError::UnsupportedFraming.into());
+            }
         }
 
-        Ok(file_source(self, data_dir, cx.shutdown, cx.out))
+        Ok(file_source(
+            self,
+            data_dir,
+            cx.shutdown,
+            cx.out,
+            cx.acknowledgements,
+        ))
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding
+            .as_ref()
+            .map_or(DataType::Log, DecodingConfig::output_type)
     }
 
     fn source_type(&self) -> &'static str {
@@ -226,6 +255,7 @@ pub fn file_source(
     data_dir: PathBuf,
     shutdown: ShutdownSignal,
     mut out: Pipeline,
+    acknowledgements: bool,
 ) -> super::Source {
     let ignore_before = config
         .ignore_older_secs
@@ -262,7 +292,6 @@ pub fn file_source(
         ignore_before,
         max_line_bytes: config.max_line_bytes,
         line_delimiter: line_delimiter_as_bytes,
-        data_dir,
         glob_minimum_cooldown,
         fingerprinter: Fingerprinter {
             strategy: config.fingerprint.clone().into(),
@@ -271,6 +300,7 @@ pub fn file_source(
         },
         oldest_first: config.oldest_first,
         remove_after: config.remove_after_secs.map(Duration::from_secs),
+        acknowledgements,
         emitter: FileSourceInternalEventsEmitter,
         handle: tokio::runtime::Handle::current(),
     };
@@ -281,6 +311,7 @@ pub fn file_source(
         .clone()
         .unwrap_or_else(|| log_schema().host_key().to_string());
     let hostname = crate::get_hostname().ok();
+    let parser = config.decoding.clone().unwrap_or_default().build_parser();
 
     let include = config.include.clone();
     let exclude = config.exclude.clone();
@@ -288,25 +319,28 @@ pub fn file_source(
     let message_start_indicator = config.message_start_indicator.clone();
     let multi_line_timeout = config.multi_line_timeout;
 
+    let checkpointer = Checkpointer::new(&data_dir);
+    let checkpoints = checkpointer.view();
+
     Box::pin(async move {
         info!(message = "Starting file server.", include = ?include, exclude = ?exclude);
 
         let mut encoding_decoder = encoding_charset.map(|e| Decoder::new(e));
 
         // sizing here is just a guess
-        let (tx, rx) = futures::channel::mpsc::channel::<Vec<(Bytes, String)>>(2);
+        let (tx, rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
         let rx = rx
             .map(futures::stream::iter)
             .flatten()
-            .map(move |(line, src)| {
+            .map(move |mut line| {
                 // transcode each line from the file's encoding charset to utf8
-                match encoding_decoder.as_mut() {
-                    Some(d) => (d.decode_to_utf8(line), src),
-                    None => (line, src),
+                if let Some(d) = encoding_decoder.as_mut() {
+                    line.text = d.decode_to_utf8(line.text);
                 }
+                line
             });
 
-        let messages: Box<dyn Stream<Item = (Bytes, String)> + Send + std::marker::Unpin> =
+        let messages: Box<dyn Stream<Item = Line> + Send + std::marker::Unpin> =
             if let Some(ref multiline_config) = multiline_config {
                 wrap_with_line_agg(
                     rx,
@@ -324,22 +358,60 @@ pub fn file_source(
                 Box::new(rx)
             };
 
+        // With acknowledgements, the position after a line is checkpointed once
+        // its events are delivered.
+        let acks = acknowledgements.then(|| {
+            let (acks_tx, acks_rx) = mpsc::unbounded_channel();
+            let acks = UnboundedReceiverStream::new(acks_rx).then(
+                |(receiver, file_id, offset)| async move { (receiver.await, file_id, offset) },
+            );
+            tokio::spawn(checkpoint_acknowledged(acks, checkpoints));
+            acks_tx
+        });
+
         // Once file server ends this will run until it has finished processing remaining
         // logs in the queue.
         let span = current_span();
         let span2 = span.clone();
         let mut messages = messages
-            .map(move |(msg, file): (Bytes, String)| {
+            .map(move |line: Line| {
                 let _enter = span2.enter();
-                create_event(msg, file, &host_key, &hostname, &file_key)
+                emit!(FileEventReceived {
+                    file: &line.filename,
+                    byte_size: line.text.len(),
+                });
+
+                let batch = acks.as_ref().map(|acks| {
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    // The receiving task only ends once this sender is dropped.
+                    let _ = acks.send((receiver, line.file_id, line.offset));
+                    batch
+                });
+
+                let events = parser.parse(line.text).unwrap_or_else(|error| {
+                    emit!(DecoderParseFailed { error });
+                    Vec::new()
+                });
+                let events = events
+                    .into_iter()
+                    .map(|event| {
+                        let mut event =
+                            create_event(event, &line.filename, &host_key, &hostname, &file_key);
+                        if let Some(batch) = &batch {
+                            event.add_batch_notifier(Arc::clone(batch));
+                        }
+                        Ok(event)
+                    })
+                    .collect::<Vec<_>>();
+                stream::iter(events)
             })
-            .map(Ok);
+            .flatten();
         tokio::spawn(async move { out.send_all(&mut messages).instrument(span).await });
 
         let span = info_span!("file_server");
         spawn_blocking(move || {
             let _enter = span.enter();
-            let result = file_server.run(tx, shutdown);
+            let result = file_server.run(tx, shutdown, checkpointer);
             emit!(FileOpen { count: 0 });
             // Panic if we encounter any error originating from the file server.
             // We're at the `spawn_blocking` call, the panic will be caught and
@@ -375,50 +447,76 @@ fn reconcile_position_options(
 }
 
 fn wrap_with_line_agg(
-    rx: impl Stream<Item = (Bytes, String)> + Send + std::marker::Unpin + 'static,
+    rx: impl Stream<Item = Line> + Send + std::marker::Unpin + 'static,
     config: line_agg::Config,
-) -> Box<dyn Stream<Item = (Bytes, String)> + Send + std::marker::Unpin + 'static> {
+) -> Box<dyn Stream<Item = Line> + Send + std::marker::Unpin + 'static> {
     let logic = line_agg::Logic::new(config);
     Box::new(
-        LineAgg::new(rx.map(|(line, src)| (src, line, ())), logic)
-            .map(|(src, line, _context)| (line, src)),
+        LineAgg::new(
+            rx.map(|line| (line.filename, line.text, (line.file_id, line.offset))),
+            logic,
+        )
+        .map(|(filename, text, (file_id, offset))| Line {
+            text,
+            filename,
+            file_id,
+            offset,
+        }),
     )
 }
 
 fn create_event(
-    line: Bytes,
-    file: String,
+    mut event: Event,
+    file: &str,
     host_key: &str,
     hostname: &Option<String>,
     file_key: &Option<String>,
 ) -> Event {
-    emit!(FileEventReceived {
-        file: &file,
-        byte_size: line.len(),
-    });
-
-    let mut event = Event::from(line);
+    if let Event::Log(log) = &mut event {
+        // Add source type
+        log.insert(log_schema().source_type_key(), Bytes::from("file"));
 
-    // Add source type
-    event
-        .as_mut_log()
-        .insert(log_schema().source_type_key(), Bytes::from("file"));
-
-    if let Some(file_key) = &file_key {
-        event.as_mut_log().insert(file_key.clone(), file);
-    }
+        if let Some(file_key) = &file_key {
+            log.insert(file_key.clone(), file.to_owned());
+        }
 
-    if let Some(hostname) = &hostname {
-        event.as_mut_log().insert(host_key, hostname.clone());
+        if let Some(hostname) = &hostname {
+            log.insert(host_key, hostname.clone());
+        }
     }
 
     event
 }
 
+/// Checkpoints the position after each delivered line, in the order the lines
+/// were read.
+///
+/// Once a line of a file isn't delivered, the checkpoint of that file stops
+/// advancing, so the undelivered line is read again after a restart instead of
+/// being skipped by the checkpoint of a later line.
+async fn checkpoint_acknowledged(
+    acks: impl Stream<Item = (BatchStatus, FileFingerprint, FilePosition)>,
+    checkpoints: Arc<CheckpointsView>,
+) {
+    tokio::pin!(acks);
+    let mut undelivered = HashSet::new();
+    while let Some((status, file_id, offset)) = acks.next().await {
+        if status != BatchStatus::Delivered {
+            emit!(BatchNotDelivered { status });
+            undelivered.insert(file_id);
+        } else if !undelivered.contains(&file_id) {
+            checkpoints.update(file_id, offset);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{config::Config, shutdown::ShutdownSignal, sources::file};
+    use crate::{
+        config::Config, event::EventStatus, shutdown::ShutdownSignal, sources::file,
+        test_util::collect_n,
+    };
     use encoding_rs::UTF_16LE;
     use pretty_assertions::assert_eq;
     use std::{
@@ -559,7 +657,7 @@ mod tests {
         let hostname = Some("Some.Machine".to_string());
         let file_key = Some("file".to_string());
 
-        let event = create_event(line, file, &host_key, &hostname, &file_key);
+        let event = create_event(Event::from(line), &file, &host_key, &hostname, &file_key);
         let log = event.into_log();
 
         assert_eq!(log["file"], "some_file.rs".into());
@@ -580,7 +678,13 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         let path1 = dir.path().join("file1");
@@ -637,7 +741,13 @@ mod tests {
             include: vec![dir.path().join("*")],
             ..test_default_file_config(&dir)
         };
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -702,7 +812,13 @@ mod tests {
             include: vec![dir.path().join("*")],
             ..test_default_file_config(&dir)
         };
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -770,7 +886,13 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         let path1 = dir.path().join("a.txt");
@@ -826,7 +948,13 @@ mod tests {
                 ..test_default_file_config(&dir)
             };
 
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                shutdown,
+                tx,
+                false,
+            );
             tokio::spawn(source);
 
             let path = dir.path().join("file");
@@ -860,7 +988,13 @@ mod tests {
                 ..test_default_file_config(&dir)
             };
 
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                shutdown,
+                tx,
+                false,
+            );
             tokio::spawn(source);
 
             let path = dir.path().join("file");
@@ -894,7 +1028,13 @@ mod tests {
                 ..test_default_file_config(&dir)
             };
 
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                shutdown,
+                tx,
+                false,
+            );
             tokio::spawn(source);
 
             let path = dir.path().join("file");
@@ -924,6 +1064,59 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn file_start_position_server_restart_acknowledged() {
+        async fn run(config: &file::FileConfig, status: EventStatus) -> String {
+            let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
+            let (tx, rx) = Pipeline::new_test_finalize(status);
+            let source =
+                file::file_source(config, config.data_dir.clone().unwrap(), shutdown, tx, true);
+            let source = tokio::spawn(source);
+
+            let event = wait_with_timeout(collect_n(rx, 1)).await.remove(0);
+            sleep_500_millis().await;
+            drop(trigger_shutdown);
+            wait_with_timeout(source).await.unwrap().unwrap();
+
+            event.as_log()[log_schema().message_key()].to_string_lossy()
+        }
+
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+        writeln!(&mut file, "zeroth line").unwrap();
+        sleep_500_millis().await;
+
+        // Lines are only checkpointed once they are delivered.
+        assert_eq!(run(&config, EventStatus::Errored).await, "zeroth line");
+        assert_eq!(run(&config, EventStatus::Delivered).await, "zeroth line");
+
+        writeln!(&mut file, "first line").unwrap();
+        assert_eq!(run(&config, EventStatus::Delivered).await, "first line");
+    }
+
+    #[tokio::test]
+    async fn undelivered_lines_hold_back_their_file_checkpoint() {
+        let checkpoints = Arc::new(CheckpointsView::default());
+        let (failing, other) = (FileFingerprint::Unknown(1), FileFingerprint::Unknown(2));
+        let acks = stream::iter(vec![
+            (BatchStatus::Delivered, failing, 10),
+            (BatchStatus::Failed, failing, 20),
+            (BatchStatus::Delivered, failing, 30),
+            (BatchStatus::Delivered, other, 10),
+        ]);
+
+        checkpoint_acknowledged(acks, Arc::clone(&checkpoints)).await;
+
+        assert_eq!(checkpoints.get(failing), Some(10));
+        assert_eq!(checkpoints.get(other), Some(10));
+    }
+
     #[tokio::test]
     async fn file_start_position_server_restart() {
         let dir = tempdir().unwrap();
@@ -942,7 +1135,13 @@ mod tests {
             let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
 
             let (tx, rx) = Pipeline::new_test();
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                shutdown,
+                tx,
+                false,
+            );
             tokio::spawn(source);
 
             sleep_500_millis().await;
@@ -963,7 +1162,13 @@ mod tests {
             let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
 
             let (tx, rx) = Pipeline::new_test();
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                shutdown,
+                tx,
+                false,
+            );
             tokio::spawn(source);
 
             sleep_500_millis().await;
@@ -990,7 +1195,13 @@ mod tests {
                 ..test_default_file_config(&dir)
             };
             let (tx, rx) = Pipeline::new_test();
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                shutdown,
+                tx,
+                false,
+            );
             tokio::spawn(source);
 
             sleep_500_millis().await;
@@ -1026,7 +1237,13 @@ mod tests {
             let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
 
             let (tx, rx) = Pipeline::new_test();
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                shutdown,
+                tx,
+                false,
+            );
             tokio::spawn(source);
 
             let mut file = File::create(&path).unwrap();
@@ -1051,7 +1268,13 @@ mod tests {
             let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
 
             let (tx, rx) = Pipeline::new_test();
-            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            let source = file::file_source(
+                &config,
+                config.data_dir.clone().unwrap(),
+                shutdown,
+                tx,
+                false,
+            );
             tokio::spawn(source);
 
             let mut file = File::create(&path).unwrap();
@@ -1085,7 +1308,13 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         let before_path = dir.path().join("before");
@@ -1160,7 +1389,13 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -1218,7 +1453,13 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -1292,7 +1533,13 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -1380,7 +1627,13 @@ mod tests {
 
         sleep_500_millis().await;
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         sleep_500_millis().await;
@@ -1443,7 +1696,13 @@ mod tests {
 
         sleep_500_millis().await;
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         sleep_500_millis().await;
@@ -1494,7 +1753,13 @@ mod tests {
 
         sleep_500_millis().await;
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         sleep_500_millis().await;
@@ -1549,7 +1814,13 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         sleep_500_millis().await;
@@ -1592,7 +1863,13 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         sleep_500_millis().await;
@@ -1635,7 +1912,13 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
@@ -1691,7 +1974,13 @@ mod tests {
             ..test_default_file_config(&dir)
         };
 
-        let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+        let source = file::file_source(
+            &config,
+            config.data_dir.clone().unwrap(),
+            shutdown,
+            tx,
+            false,
+        );
         tokio::spawn(source);
 
         let path = dir.path().join("file");
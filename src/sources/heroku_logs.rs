@@ -5,7 +5,9 @@ use crate::{
     },
     event::Event,
     internal_events::{HerokuLogplexRequestReadError, HerokuLogplexRequestReceived},
-    sources::util::{add_query_parameters, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    sources::util::{
+        add_query_parameters, DecompressionConfig, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+    },
     tls::TlsConfig,
 };
 use bytes::{Buf, Bytes};
@@ -27,6 +29,11 @@ pub struct LogplexConfig {
     query_parameters: Vec<String>,
     tls: Option<TlsConfig>,
     auth: Option<HttpSourceAuthConfig>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    decompression: DecompressionConfig,
 }
 
 inventory::submit! {
@@ -44,6 +51,7 @@ impl GenerateConfig for LogplexConfig {
             query_parameters: Vec::new(),
             tls: None,
             auth: None,
+            decompression: Default::default(),
         })
         .unwrap()
     }
@@ -74,7 +82,15 @@ impl SourceConfig for LogplexConfig {
         let source = LogplexSource {
             query_parameters: self.query_parameters.clone(),
         };
-        source.run(self.address, "events", true, &self.tls, &self.auth, cx)
+        source.run(
+            self.address,
+            "events",
+            true,
+            &self.tls,
+            &self.auth,
+            &self.decompression,
+            cx,
+        )
     }
 
     fn output_type(&self) -> DataType {
@@ -252,6 +268,7 @@ mod tests {
                 query_parameters,
                 tls: None,
                 auth,
+                decompression: Default::default(),
             }
             .build(context)
             .await
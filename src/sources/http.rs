@@ -1,31 +1,42 @@
 use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
     config::{
         log_schema, DataType, GenerateConfig, Resource, SourceConfig, SourceContext,
         SourceDescription,
     },
     event::{Event, Value},
     sources::util::{
-        add_query_parameters, decode_body, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        add_query_parameters, decode_body, DecompressionConfig, Encoding, ErrorMessage, HttpSource,
+        HttpSourceAuthConfig,
     },
     tls::TlsConfig,
 };
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, net::SocketAddr};
 
-use warp::http::{HeaderMap, HeaderValue};
+use warp::http::{HeaderMap, HeaderValue, StatusCode};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SimpleHttpConfig {
     address: SocketAddr,
     #[serde(default)]
     encoding: Encoding,
+    /// Takes precedence over `encoding`. Without explicit framing, each
+    /// request body is parsed as a single frame.
+    #[serde(default)]
+    decoding: Option<DecodingConfig>,
     #[serde(default)]
     headers: Vec<String>,
     #[serde(default)]
     query_parameters: Vec<String>,
     tls: Option<TlsConfig>,
     auth: Option<HttpSourceAuthConfig>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    decompression: DecompressionConfig,
     #[serde(default = "crate::serde::default_true")]
     strict_path: bool,
     #[serde(default = "default_path")]
@@ -43,10 +54,12 @@ impl GenerateConfig for SimpleHttpConfig {
         toml::Value::try_from(Self {
             address: "0.0.0.0:8080".parse().unwrap(),
             encoding: Default::default(),
+            decoding: None,
             headers: Vec::new(),
             query_parameters: Vec::new(),
             tls: None,
             auth: None,
+            decompression: Default::default(),
             path_key: "path".to_string(),
             path: "/".to_string(),
             strict_path: true,
@@ -66,6 +79,7 @@ fn default_path_key() -> String {
 #[derive(Clone)]
 struct SimpleHttpSource {
     encoding: Encoding,
+    decoding: Option<Decoder>,
     headers: Vec<String>,
     query_parameters: Vec<String>,
     path_key: String,
@@ -79,7 +93,12 @@ impl HttpSource for SimpleHttpSource {
         query_parameters: HashMap<String, String>,
         request_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        decode_body(body, self.encoding)
+        let events = match &self.decoding {
+            Some(decoder) => decode_framed_body(body, decoder.clone()),
+            None => decode_body(body, self.encoding),
+        };
+
+        events
             .map(|events| add_headers(events, &self.headers, header_map))
             .map(|events| add_query_parameters(events, &self.query_parameters, query_parameters))
             .map(|events| add_path(events, self.path_key.as_str(), request_path))
@@ -98,18 +117,14 @@ impl HttpSource for SimpleHttpSource {
 #[typetag::serde(name = "http")]
 impl SourceConfig for SimpleHttpConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let source = SimpleHttpSource {
-            encoding: self.encoding,
-            headers: self.headers.clone(),
-            query_parameters: self.query_parameters.clone(),
-            path_key: self.path_key.clone(),
-        };
+        let source = self.source()?;
         source.run(
             self.address,
             &self.path.as_str(),
             self.strict_path,
             &self.tls,
             &self.auth,
+            &self.decompression,
             cx,
         )
     }
@@ -125,6 +140,45 @@ impl SourceConfig for SimpleHttpConfig {
     fn resources(&self) -> Vec<Resource> {
         vec![Resource::tcp(self.address)]
     }
+
+    fn decode_payload(&self, payload: Bytes) -> Option<crate::Result<Vec<Event>>> {
+        Some(self.source().and_then(|source| {
+            Ok(source.build_events(payload, HeaderMap::new(), HashMap::new(), &self.path)?)
+        }))
+    }
+}
+
+impl SimpleHttpConfig {
+    fn source(&self) -> crate::Result<SimpleHttpSource> {
+        let decoding = match &self.decoding {
+            Some(decoding) if decoding.output_type() != DataType::Log => {
+                return Err(format!(
+                    "The {:?} codec is not supported by the `http` source.",
+                    decoding.codec
+                )
+                .into())
+            }
+            Some(decoding) => Some(decoding.build(FramingConfig::bytes())?),
+            None => None,
+        };
+        Ok(SimpleHttpSource {
+            encoding: self.encoding,
+            decoding,
+            headers: self.headers.clone(),
+            query_parameters: self.query_parameters.clone(),
+            path_key: self.path_key.clone(),
+        })
+    }
+}
+
+/// Frames which fail to parse are reported and skipped by the decoder, so only
+/// a body which can't be framed rejects the request.
+fn decode_framed_body(body: Bytes, mut decoder: Decoder) -> Result<Vec<Event>, ErrorMessage> {
+    decoder
+        .decode_all(BytesMut::from(&body[..]))
+        .map_err(|error| {
+            ErrorMessage::new(StatusCode::BAD_REQUEST, format!("Bad request: {}", error))
+        })
 }
 
 fn add_path(mut events: Vec<Event>, key: &str, path: &str) -> Vec<Event> {
@@ -160,6 +214,7 @@ fn add_headers(
 mod tests {
     use super::{Encoding, SimpleHttpConfig};
     use crate::{
+        codecs::{DecodingConfig, FramingConfig, ParserConfig},
         config::{log_schema, SourceConfig, SourceContext},
         event::{Event, EventStatus, Value},
         test_util::{next_addr, spawn_collect_n, trace_init, wait_for_tcp},
@@ -201,10 +256,12 @@ mod tests {
             SimpleHttpConfig {
                 address,
                 encoding,
+                decoding: None,
                 headers,
                 query_parameters,
                 tls: None,
                 auth: None,
+                decompression: Default::default(),
                 strict_path,
                 path_key,
                 path,
@@ -446,6 +503,98 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_decoding_skips_invalid_frames() {
+        trace_init();
+
+        let (sender, rx) = Pipeline::new_test();
+        let address = next_addr();
+        let decoding = DecodingConfig::new(
+            Some(FramingConfig::NewlineDelimited { max_length: None }),
+            ParserConfig::Json,
+        );
+        tokio::spawn(async move {
+            SimpleHttpConfig {
+                address,
+                encoding: Encoding::Json,
+                decoding: Some(decoding),
+                headers: vec![],
+                query_parameters: vec![],
+                tls: None,
+                auth: None,
+                decompression: Default::default(),
+                strict_path: true,
+                path_key: "http_path".to_owned(),
+                path: "/".to_owned(),
+            }
+            .build(SourceContext::new_test(sender))
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        });
+        wait_for_tcp(address).await;
+
+        let body = "{\"key\":1}\nnot json\n{\"key\":2}";
+        let mut events = spawn_ok_collect_n(send(address, body), rx, 2).await;
+
+        assert_eq!(events.remove(0).as_log()["key"], 1.into());
+        assert_eq!(events.remove(0).as_log()["key"], 2.into());
+    }
+
+    #[cfg(feature = "codecs-syslog")]
+    #[tokio::test]
+    async fn http_decoding_syslog() {
+        trace_init();
+
+        let (sender, rx) = Pipeline::new_test();
+        let address = next_addr();
+        let decoding = DecodingConfig::new(
+            Some(FramingConfig::NewlineDelimited { max_length: None }),
+            ParserConfig::Syslog,
+        );
+        tokio::spawn(async move {
+            SimpleHttpConfig {
+                address,
+                encoding: Encoding::Json,
+                decoding: Some(decoding),
+                headers: vec![],
+                query_parameters: vec![],
+                tls: None,
+                auth: None,
+                decompression: Default::default(),
+                strict_path: true,
+                path_key: "http_path".to_owned(),
+                path: "/".to_owned(),
+            }
+            .build(SourceContext::new_test(sender))
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        });
+        wait_for_tcp(address).await;
+
+        let body = "<13>1 2019-02-13T19:48:34+00:00 host1 app 1 - - first\n\
+                    <13>1 2019-02-13T19:48:35+00:00 host2 app 2 - - second";
+        let mut events = spawn_ok_collect_n(send(address, body), rx, 2).await;
+
+        {
+            let event = events.remove(0);
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], "first".into());
+            assert_eq!(log["hostname"], "host1".into());
+            assert_eq!(log[log_schema().source_type_key()], "http".into());
+            assert_eq!(log["http_path"], "/".into());
+        }
+        {
+            let event = events.remove(0);
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], "second".into());
+            assert_eq!(log["hostname"], "host2".into());
+        }
+    }
+
     #[tokio::test]
     async fn http_json_dotted_keys() {
         trace_init();
@@ -661,6 +810,36 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_zstd() {
+        trace_init();
+
+        let body = zstd::encode_all(&b"test body"[..], 3).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Encoding", "zstd".parse().unwrap());
+
+        let (rx, addr) = source(
+            Encoding::default(),
+            vec![],
+            vec![],
+            "http_path",
+            "/",
+            true,
+            EventStatus::Delivered,
+            true,
+        )
+        .await;
+
+        let mut events = spawn_ok_collect_n(send_bytes(addr, body, headers), rx, 1).await;
+
+        let event = events.remove(0);
+        assert_eq!(
+            event.as_log()[log_schema().message_key()],
+            "test body".into()
+        );
+    }
+
     #[tokio::test]
     async fn http_path() {
         trace_init();
@@ -1,23 +1,28 @@
 use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
     config::{log_schema, DataType, SourceConfig, SourceContext, SourceDescription},
-    event::{Event, Value},
-    internal_events::{KafkaEventFailed, KafkaEventReceived, KafkaOffsetUpdateFailed},
+    event::{BatchNotifier, BatchStatus, Event, Value},
+    internal_events::{
+        BatchNotDelivered, DecoderFramingFailed, KafkaEventFailed, KafkaEventReceived,
+        KafkaOffsetUpdateFailed,
+    },
     kafka::{KafkaAuthConfig, KafkaStatisticsContext},
     shutdown::ShutdownSignal,
     Pipeline,
 };
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use chrono::{TimeZone, Utc};
-use futures::{SinkExt, StreamExt};
+use futures::{stream, stream::FuturesOrdered, FutureExt, SinkExt, StreamExt};
 use rdkafka::{
     config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
     message::Headers,
     message::Message,
+    Offset, TopicPartitionList,
 };
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
-use std::{collections::BTreeMap, collections::HashMap};
+use std::{collections::BTreeMap, collections::HashMap, sync::Arc};
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -56,6 +61,8 @@ pub struct KafkaSourceConfig {
     librdkafka_options: Option<HashMap<String, String>>,
     #[serde(flatten)]
     auth: KafkaAuthConfig,
+    /// Without explicit framing, each message payload is parsed as a single frame.
+    decoding: Option<DecodingConfig>,
 }
 
 fn default_session_timeout_ms() -> u64 {
@@ -109,9 +116,15 @@ impl_generate_config_from_default!(KafkaSourceConfig);
 impl SourceConfig for KafkaSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let consumer = create_consumer(self)?;
+        let decoder = self
+            .decoding
+            .clone()
+            .unwrap_or_default()
+            .build(FramingConfig::bytes())?;
 
         Ok(Box::pin(kafka_source(
             consumer,
+            decoder,
             self.key_field.clone(),
             self.topic_key.clone(),
             self.partition_key.clone(),
@@ -119,11 +132,14 @@ impl SourceConfig for KafkaSourceConfig {
             self.headers_key.clone(),
             cx.shutdown,
             cx.out,
+            cx.acknowledgements,
         )))
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding
+            .as_ref()
+            .map_or(DataType::Log, DecodingConfig::output_type)
     }
 
     fn source_type(&self) -> &'static str {
@@ -133,6 +149,7 @@ impl SourceConfig for KafkaSourceConfig {
 
 async fn kafka_source(
     consumer: StreamConsumer<KafkaStatisticsContext>,
+    decoder: Decoder,
     key_field: String,
     topic_key: String,
     partition_key: String,
@@ -140,10 +157,26 @@ async fn kafka_source(
     headers_key: String,
     shutdown: ShutdownSignal,
     mut out: Pipeline,
+    acknowledgements: bool,
 ) -> Result<(), ()> {
     let mut stream = consumer.stream().take_until(shutdown);
+    // Offsets are stored in the order messages were read, once all sinks
+    // have delivered their events.
+    let mut acks = FuturesOrdered::new();
+    let mut undelivered = Undelivered::default();
+
+    loop {
+        let message = tokio::select! {
+            Some((status, topic, partition, offset)) = acks.next(), if !acks.is_empty() => {
+                store_acked_offset(&consumer, &mut undelivered, status, &topic, partition, offset);
+                continue;
+            }
+            message = stream.next() => match message {
+                Some(message) => message,
+                None => break,
+            },
+        };
 
-    while let Some(message) = stream.next().await {
         match message {
             Err(error) => {
                 emit!(KafkaEventFailed { error });
@@ -152,18 +185,19 @@ async fn kafka_source(
                 emit!(KafkaEventReceived {
                     byte_size: msg.payload_len()
                 });
+                undelivered.consumed(msg.topic(), msg.partition(), msg.offset());
 
                 let payload = match msg.payload() {
                     None => continue, // skip messages with empty payload
                     Some(payload) => payload,
                 };
-                let mut event = Event::new_empty_log();
-                let log = event.as_mut_log();
-
-                log.insert(
-                    log_schema().message_key(),
-                    Value::from(Bytes::from(payload.to_owned())),
-                );
+                let mut events = match decoder.clone().decode_all(BytesMut::from(payload)) {
+                    Ok(events) => events,
+                    Err(error) => {
+                        emit!(DecoderFramingFailed { error });
+                        continue;
+                    }
+                };
 
                 // Extract timestamp from kafka message
                 let timestamp = msg
@@ -171,22 +205,11 @@ async fn kafka_source(
                     .to_millis()
                     .and_then(|millis| Utc.timestamp_millis_opt(millis).latest())
                     .unwrap_or_else(Utc::now);
-                log.insert(log_schema().timestamp_key(), timestamp);
-
-                // Add source type
-                log.insert(log_schema().source_type_key(), Bytes::from("kafka"));
 
                 let msg_key = msg
                     .key()
                     .map(|key| Value::from(String::from_utf8_lossy(key).to_string()))
                     .unwrap_or(Value::Null);
-                log.insert(&key_field, msg_key);
-
-                log.insert(&topic_key, Value::from(msg.topic().to_string()));
-
-                log.insert(&partition_key, Value::from(msg.partition()));
-
-                log.insert(&offset_key, Value::from(msg.offset()));
 
                 let mut headers_map = BTreeMap::new();
                 if let Some(headers) = msg.headers() {
@@ -201,23 +224,137 @@ async fn kafka_source(
                         }
                     }
                 }
-                log.insert(&headers_key, Value::from(headers_map));
+                let headers_map = Value::from(headers_map);
+
+                for event in &mut events {
+                    let log = match event {
+                        Event::Log(log) => log,
+                        Event::Metric(_) => continue,
+                    };
+
+                    log.insert(log_schema().timestamp_key(), timestamp);
+
+                    // Add source type
+                    log.insert(log_schema().source_type_key(), Bytes::from("kafka"));
 
-                match out.send(event).await {
+                    log.insert(&key_field, msg_key.clone());
+
+                    log.insert(&topic_key, Value::from(msg.topic().to_string()));
+
+                    log.insert(&partition_key, Value::from(msg.partition()));
+
+                    log.insert(&offset_key, Value::from(msg.offset()));
+
+                    log.insert(&headers_key, headers_map.clone());
+                }
+
+                let receiver = acknowledgements.then(|| {
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    for event in &mut events {
+                        event.add_batch_notifier(Arc::clone(&batch));
+                    }
+                    receiver
+                });
+
+                let mut events = stream::iter(events).map(Ok);
+                match out.send_all(&mut events).await {
                     Err(error) => error!(message = "Error sending to sink.", %error),
-                    Ok(_) => {
-                        if let Err(error) = consumer.store_offset(&msg) {
-                            emit!(KafkaOffsetUpdateFailed { error });
+                    Ok(_) => match receiver {
+                        Some(receiver) => {
+                            let (topic, partition, offset) =
+                                (msg.topic().to_owned(), msg.partition(), msg.offset());
+                            acks.push(
+                                receiver.map(move |status| (status, topic, partition, offset)),
+                            );
                         }
-                    }
+                        None => {
+                            if let Err(error) = consumer.store_offset(&msg) {
+                                emit!(KafkaOffsetUpdateFailed { error });
+                            }
+                        }
+                    },
                 }
             }
         }
     }
 
+    // Events still in flight are delivered while the topology shuts down.
+    while let Some((status, topic, partition, offset)) = acks.next().await {
+        store_acked_offset(
+            &consumer,
+            &mut undelivered,
+            status,
+            &topic,
+            partition,
+            offset,
+        );
+    }
+
     Ok(())
 }
 
+/// The first offset of each partition whose events weren't delivered.
+///
+/// Later offsets of the partition aren't stored, so the message is consumed
+/// again once Vector restarts or the partition is assigned again, instead of
+/// being committed past.
+#[derive(Debug, Default)]
+struct Undelivered(HashMap<(String, i32), i64>);
+
+impl Undelivered {
+    fn insert(&mut self, topic: &str, partition: i32, offset: i64) {
+        let first = self
+            .0
+            .entry((topic.to_owned(), partition))
+            .or_insert(offset);
+        *first = (*first).min(offset);
+    }
+
+    fn allows(&self, topic: &str, partition: i32, offset: i64) -> bool {
+        self.0
+            .get(&(topic.to_owned(), partition))
+            .map_or(true, |first| offset < *first)
+    }
+
+    /// Reading the undelivered message again resumes storing offsets.
+    fn consumed(&mut self, topic: &str, partition: i32, offset: i64) {
+        if self.0.is_empty() {
+            return;
+        }
+        let key = (topic.to_owned(), partition);
+        if self.0.get(&key).map_or(false, |first| offset <= *first) {
+            self.0.remove(&key);
+        }
+    }
+}
+
+fn store_acked_offset(
+    consumer: &StreamConsumer<KafkaStatisticsContext>,
+    undelivered: &mut Undelivered,
+    status: BatchStatus,
+    topic: &str,
+    partition: i32,
+    offset: i64,
+) {
+    if status != BatchStatus::Delivered {
+        emit!(BatchNotDelivered { status });
+        undelivered.insert(topic, partition, offset);
+        return;
+    }
+    if !undelivered.allows(topic, partition, offset) {
+        return;
+    }
+
+    // Like `store_offset`, store the offset of the next message to consume.
+    let mut offsets = TopicPartitionList::new();
+    let result = offsets
+        .add_partition_offset(topic, partition, Offset::Offset(offset + 1))
+        .and_then(|_| consumer.store_offsets(&offsets));
+    if let Err(error) = result {
+        emit!(KafkaOffsetUpdateFailed { error });
+    }
+}
+
 fn create_consumer(
     config: &KafkaSourceConfig,
 ) -> crate::Result<StreamConsumer<KafkaStatisticsContext>> {
@@ -265,6 +402,24 @@ mod test {
         crate::test_util::test_generate_config::<KafkaSourceConfig>();
     }
 
+    #[test]
+    fn undelivered_offsets_hold_back_their_partition() {
+        let mut undelivered = Undelivered::default();
+        undelivered.insert("topic", 0, 5);
+
+        assert!(undelivered.allows("topic", 0, 4));
+        assert!(!undelivered.allows("topic", 0, 5));
+        assert!(!undelivered.allows("topic", 0, 6));
+        assert!(undelivered.allows("topic", 1, 6));
+        assert!(undelivered.allows("other", 0, 6));
+
+        // Later messages don't release the partition, only reading it again does.
+        undelivered.consumed("topic", 0, 7);
+        assert!(!undelivered.allows("topic", 0, 6));
+        undelivered.consumed("topic", 0, 5);
+        assert!(undelivered.allows("topic", 0, 6));
+    }
+
     fn make_config() -> KafkaSourceConfig {
         KafkaSourceConfig {
             bootstrap_servers: "localhost:9092".to_string(),
@@ -305,6 +460,7 @@ mod test {
 mod integration_test {
     use super::*;
     use crate::{
+        event::EventStatus,
         shutdown::ShutdownSignal,
         test_util::{collect_n, random_string},
         Pipeline,
@@ -381,10 +537,14 @@ mod integration_test {
         .await;
 
         println!("Receiving event...");
-        let (tx, rx) = Pipeline::new_test();
+        let (tx, rx) = Pipeline::new_test_finalize(EventStatus::Delivered);
         let consumer = create_consumer(&config).unwrap();
+        let decoder = DecodingConfig::default()
+            .build(FramingConfig::bytes())
+            .unwrap();
         tokio::spawn(kafka_source(
             consumer,
+            decoder,
             config.key_field,
             config.topic_key,
             config.partition_key,
@@ -392,6 +552,7 @@ mod integration_test {
             config.headers_key,
             ShutdownSignal::noop(),
             tx,
+            true,
         ));
         let events = collect_n(rx, 1).await;
         println!("Received event  {:?}", events[0].as_log());
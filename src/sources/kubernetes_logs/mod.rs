@@ -20,7 +20,10 @@ use crate::{
     transforms::{FunctionTransform, TaskTransform},
 };
 use bytes::Bytes;
-use file_source::{FileServer, FileServerShutdown, FingerprintStrategy, Fingerprinter, ReadFrom};
+use file_source::{
+    Checkpointer, FileServer, FileServerShutdown, FingerprintStrategy, Fingerprinter, Line,
+    ReadFrom,
+};
 use k8s_openapi::api::core::v1::Pod;
 use serde::{Deserialize, Serialize};
 use shared::TimeZone;
@@ -281,8 +284,6 @@ impl Source {
             max_line_bytes,
             // Delimiter bytes that is used to read the file line-by-line
             line_delimiter: Bytes::from("\n"),
-            // The directory where to keep the checkpoints.
-            data_dir,
             // This value specifies not exactly the globbing, but interval
             // between the polling the files to watch from the `paths_provider`.
             glob_minimum_cooldown,
@@ -307,23 +308,27 @@ impl Source {
             oldest_first: false,
             // We do not remove the log files, `kubelet` is responsible for it.
             remove_after: None,
+            // Checkpoints are written as lines are read.
+            acknowledgements: false,
             // The standard emitter.
             emitter: FileSourceInternalEventsEmitter,
             // A handle to the current tokio runtime
             handle: tokio::runtime::Handle::current(),
         };
 
-        let (file_source_tx, file_source_rx) =
-            futures::channel::mpsc::channel::<Vec<(Bytes, String)>>(2);
+        let (file_source_tx, file_source_rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
+        // The directory where to keep the checkpoints.
+        let checkpointer = Checkpointer::new(&data_dir);
 
         let mut parser = parser::build(timezone);
         let partial_events_merger = Box::new(partial_events_merger::build(auto_partial_merge));
 
         let events = file_source_rx.map(futures::stream::iter);
         let events = events.flatten();
-        let events = events.map(move |(bytes, file)| {
-            let byte_size = bytes.len();
-            let mut event = create_event(bytes, &file, ingestion_timestamp_field.as_deref());
+        let events = events.map(move |line: Line| {
+            let byte_size = line.text.len();
+            let file = line.filename;
+            let mut event = create_event(line.text, &file, ingestion_timestamp_field.as_deref());
             let file_info = annotator.annotate(&mut event, &file);
 
             emit!(KubernetesLogsEventReceived {
@@ -362,12 +367,11 @@ impl Source {
         }
         {
             let (slot, shutdown) = lifecycle.add();
-            let fut = util::run_file_server(file_server, file_source_tx, shutdown).map(|result| {
-                match result {
+            let fut = util::run_file_server(file_server, file_source_tx, shutdown, checkpointer)
+                .map(|result| match result {
                     Ok(FileServerShutdown) => info!(message = "File server completed gracefully."),
                     Err(error) => error!(message = "File server exited with an error.", %error),
-                }
-            });
+                });
             slot.bind(Box::pin(fut));
         }
         {
@@ -5,7 +5,7 @@ use crate::{
     internal_events::{PrometheusRemoteWriteParseError, PrometheusRemoteWriteReceived},
     sources::{
         self,
-        util::{decode, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+        util::{decode, DecompressionConfig, ErrorMessage, HttpSource, HttpSourceAuthConfig},
     },
     tls::TlsConfig,
 };
@@ -25,6 +25,12 @@ struct PrometheusRemoteWriteConfig {
     tls: Option<TlsConfig>,
 
     auth: Option<HttpSourceAuthConfig>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    decompression: DecompressionConfig,
 }
 
 inventory::submit! {
@@ -37,6 +43,7 @@ impl GenerateConfig for PrometheusRemoteWriteConfig {
             address: "127.0.0.1:9090".parse().unwrap(),
             tls: None,
             auth: None,
+            decompression: Default::default(),
         })
         .unwrap()
     }
@@ -46,8 +53,18 @@ impl GenerateConfig for PrometheusRemoteWriteConfig {
 #[typetag::serde(name = "prometheus_remote_write")]
 impl SourceConfig for PrometheusRemoteWriteConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
-        let source = RemoteWriteSource;
-        source.run(self.address, "", true, &self.tls, &self.auth, cx)
+        let source = RemoteWriteSource {
+            decompression: self.decompression.clone(),
+        };
+        source.run(
+            self.address,
+            "",
+            true,
+            &self.tls,
+            &self.auth,
+            &self.decompression,
+            cx,
+        )
     }
 
     fn output_type(&self) -> crate::config::DataType {
@@ -60,7 +77,9 @@ impl SourceConfig for PrometheusRemoteWriteConfig {
 }
 
 #[derive(Clone)]
-struct RemoteWriteSource;
+struct RemoteWriteSource {
+    decompression: DecompressionConfig,
+}
 
 impl RemoteWriteSource {
     fn decode_body(&self, body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
@@ -97,7 +116,7 @@ impl HttpSource for RemoteWriteSource {
             .map(|header| header.as_ref())
             != Some(b"snappy")
         {
-            body = decode(&Some("snappy".to_string()), body)?;
+            body = decode(&Some("snappy".to_string()), body, &self.decompression)?;
         }
         let events = self.decode_body(body)?;
         let count = events.len();
@@ -142,6 +161,7 @@ mod test {
             address,
             auth: None,
             tls: tls.clone(),
+            decompression: Default::default(),
         };
         let source = source.build(SourceContext::new_test(tx)).await.unwrap();
         tokio::spawn(source);
@@ -231,6 +251,7 @@ mod integration_tests {
             address: PROMETHEUS_RECEIVE_ADDRESS.parse().unwrap(),
             auth: None,
             tls: None,
+            decompression: Default::default(),
         };
 
         let (tx, rx) = Pipeline::new_test();
@@ -5,24 +5,30 @@ mod unix;
 
 use super::util::TcpSource;
 use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
     config::{
-        log_schema, DataType, GenerateConfig, Resource, SourceConfig, SourceContext,
+        log_schema, schema, DataType, GenerateConfig, Resource, SourceConfig, SourceContext,
         SourceDescription,
     },
+    event::Event,
     tls::MaybeTlsSettings,
 };
+use bytes::{Bytes, BytesMut};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 // TODO: add back when https://github.com/serde-rs/serde/issues/1358 is addressed
 // #[serde(deny_unknown_fields)]
 pub struct SocketConfig {
     #[serde(flatten)]
     pub mode: Mode,
+    #[serde(default)]
+    pub decoding: Option<DecodingConfig>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(tag = "mode", rename_all = "snake_case")]
 pub enum Mode {
     Tcp(tcp::TcpConfig),
@@ -42,12 +48,73 @@ impl SocketConfig {
     pub fn make_basic_tcp_config(addr: SocketAddr) -> Self {
         tcp::TcpConfig::from_address(addr.into()).into()
     }
+
+    /// The framing used when `decoding` doesn't configure one.
+    fn default_framing(&self) -> FramingConfig {
+        match &self.mode {
+            Mode::Tcp(config) => FramingConfig::newline_delimited(config.max_length()),
+            Mode::Udp(_) => FramingConfig::NewlineDelimited { max_length: None },
+            #[cfg(unix)]
+            Mode::UnixDatagram(config) => FramingConfig::newline_delimited(config.max_length),
+            #[cfg(unix)]
+            Mode::UnixStream(config) => FramingConfig::newline_delimited(config.max_length),
+        }
+    }
+
+    /// The framing of `decoding`, or the default one. Streams only end a
+    /// `bytes` frame when they're closed, so it's capped at the `max_length`
+    /// of their mode.
+    fn framing(&self) -> FramingConfig {
+        let framing = self
+            .decoding
+            .as_ref()
+            .and_then(|decoding| decoding.framing.clone())
+            .unwrap_or_else(|| self.default_framing());
+        match &self.mode {
+            Mode::Tcp(config) => framing.with_stream_max_length(config.max_length()),
+            #[cfg(unix)]
+            Mode::UnixStream(config) => framing.with_stream_max_length(config.max_length),
+            _ => framing,
+        }
+    }
+
+    fn decoder(&self) -> crate::Result<Decoder> {
+        let decoding = self.decoding.clone().unwrap_or_default();
+        Ok(Decoder::new(
+            self.framing().build()?,
+            decoding.build_parser(),
+        ))
+    }
+
+    fn host_key(&self) -> String {
+        let host_key = match &self.mode {
+            Mode::Tcp(config) => config.host_key().clone(),
+            Mode::Udp(config) => config.host_key().clone(),
+            #[cfg(unix)]
+            Mode::UnixDatagram(config) => config.host_key.clone(),
+            #[cfg(unix)]
+            Mode::UnixStream(config) => config.host_key.clone(),
+        };
+        host_key.unwrap_or_else(|| log_schema().host_key().to_string())
+    }
+}
+
+/// Adds the fields the source sets on every event: its type, and the address
+/// of the peer it was received from when known.
+fn enrich_event(event: &mut Event, host_key: &str, host: Option<Bytes>) {
+    if let Event::Log(log) = event {
+        log.insert(log_schema().source_type_key(), Bytes::from("socket"));
+        if let Some(host) = host {
+            log.insert(host_key, host);
+        }
+    }
 }
 
 impl From<tcp::TcpConfig> for SocketConfig {
     fn from(config: tcp::TcpConfig) -> Self {
         SocketConfig {
             mode: Mode::Tcp(config),
+            decoding: None,
         }
     }
 }
@@ -56,6 +123,7 @@ impl From<udp::UdpConfig> for SocketConfig {
     fn from(config: udp::UdpConfig) -> Self {
         SocketConfig {
             mode: Mode::Udp(config),
+            decoding: None,
         }
     }
 }
@@ -72,16 +140,23 @@ impl GenerateConfig for SocketConfig {
         )
         .unwrap()
     }
+
+    fn generate_schema() -> serde_json::Value {
+        schema::derive::<Self>()
+    }
 }
 
 #[async_trait::async_trait]
 #[typetag::serde(name = "socket")]
 impl SourceConfig for SocketConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let decoder = self.decoder()?;
+        let host_key = self.host_key();
         match self.mode.clone() {
             Mode::Tcp(config) => {
                 let tcp = tcp::RawTcpSource {
                     config: config.clone(),
+                    decoder,
                 };
                 let tls = MaybeTlsSettings::from_config(&config.tls(), true)?;
                 tcp.run(
@@ -94,57 +169,58 @@ impl SourceConfig for SocketConfig {
                     cx.out,
                 )
             }
-            Mode::Udp(config) => {
-                let host_key = config
-                    .host_key()
-                    .clone()
-                    .unwrap_or_else(|| log_schema().host_key().to_string());
-                Ok(udp::udp(
-                    config.address(),
-                    config.max_length(),
-                    host_key,
-                    config.receive_buffer_bytes(),
-                    cx.shutdown,
-                    cx.out,
-                ))
-            }
+            Mode::Udp(config) => Ok(udp::udp(
+                config.address(),
+                config.max_length(),
+                host_key,
+                config.receive_buffer_bytes(),
+                decoder,
+                cx.shutdown,
+                cx.out,
+            )),
             #[cfg(unix)]
-            Mode::UnixDatagram(config) => {
-                let host_key = config
-                    .host_key
-                    .unwrap_or_else(|| log_schema().host_key().to_string());
-                Ok(unix::unix_datagram(
-                    config.path,
-                    config.max_length,
-                    host_key,
-                    cx.shutdown,
-                    cx.out,
-                ))
-            }
+            Mode::UnixDatagram(config) => Ok(unix::unix_datagram(
+                config.path,
+                config.max_length,
+                host_key,
+                decoder,
+                cx.shutdown,
+                cx.out,
+            )),
             #[cfg(unix)]
-            Mode::UnixStream(config) => {
-                let host_key = config
-                    .host_key
-                    .unwrap_or_else(|| log_schema().host_key().to_string());
-                Ok(unix::unix_stream(
-                    config.path,
-                    config.max_length,
-                    host_key,
-                    cx.shutdown,
-                    cx.out,
-                ))
-            }
+            Mode::UnixStream(config) => Ok(unix::unix_stream(
+                config.path,
+                host_key,
+                decoder,
+                cx.shutdown,
+                cx.out,
+            )),
         }
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding
+            .as_ref()
+            .map_or(DataType::Log, DecodingConfig::output_type)
     }
 
     fn source_type(&self) -> &'static str {
         "socket"
     }
 
+    /// Decodes the payload as a single datagram or connection. There is no
+    /// peer, so events don't get a host.
+    fn decode_payload(&self, payload: Bytes) -> Option<crate::Result<Vec<Event>>> {
+        let host_key = self.host_key();
+        Some(self.decoder().and_then(|mut decoder| {
+            let mut events = decoder.decode_all(BytesMut::from(&payload[..]))?;
+            for event in &mut events {
+                enrich_event(event, &host_key, None);
+            }
+            Ok(events)
+        }))
+    }
+
     fn resources(&self) -> Vec<Resource> {
         match self.mode.clone() {
             Mode::Tcp(tcp) => vec![tcp.address().into()],
@@ -161,6 +237,7 @@ impl SourceConfig for SocketConfig {
 mod test {
     use super::{tcp::TcpConfig, udp::UdpConfig, SocketConfig};
     use crate::{
+        codecs::{DecodingConfig, ParserConfig},
         config::{log_schema, GlobalOptions, SinkContext, SourceConfig, SourceContext},
         event::Event,
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
@@ -204,6 +281,29 @@ mod test {
         crate::test_util::test_generate_config::<SocketConfig>();
     }
 
+    #[test]
+    fn decode_payload_caps_bytes_framing_of_tcp() {
+        let config: SocketConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+            max_length = 4
+            decoding.framing.method = "bytes"
+          "#,
+        )
+        .unwrap();
+
+        let events = config.decode_payload(Bytes::from("foobar")).unwrap();
+        assert!(events.unwrap().is_empty());
+
+        let events = config.decode_payload(Bytes::from("foo")).unwrap().unwrap();
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "foo".into());
+        assert_eq!(log[log_schema().source_type_key()], "socket".into());
+        assert!(!log.contains(log_schema().host_key()));
+    }
+
     //////// TCP TESTS ////////
     #[tokio::test]
     async fn tcp_it_includes_host() {
@@ -225,6 +325,32 @@ mod test {
         assert_eq!(event.as_log()[log_schema().host_key()], "127.0.0.1".into());
     }
 
+    #[tokio::test]
+    async fn tcp_decodes_json() {
+        let (tx, mut rx) = Pipeline::new_test();
+        let addr = next_addr();
+
+        let mut config = SocketConfig::from(TcpConfig::from_address(addr.into()));
+        config.decoding = Some(DecodingConfig::new(None, ParserConfig::Json));
+        let server = config.build(SourceContext::new_test(tx)).await.unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        send_lines(
+            addr,
+            vec![r#"{"foo": "bar", "answer": 42}"#.to_owned()].into_iter(),
+        )
+        .await
+        .unwrap();
+
+        let event = rx.next().await.unwrap();
+        let log = event.as_log();
+        assert_eq!(log["foo"], "bar".into());
+        assert_eq!(log["answer"], 42.into());
+        assert_eq!(log[log_schema().host_key()], "127.0.0.1".into());
+        assert_eq!(log[log_schema().source_type_key()], "socket".into());
+    }
+
     #[tokio::test]
     async fn tcp_it_includes_source_type() {
         let (tx, mut rx) = Pipeline::new_test();
@@ -681,10 +807,13 @@ mod test {
         } else {
             Mode::UnixDatagram(config)
         };
-        let server = SocketConfig { mode }
-            .build(SourceContext::new_test(sender))
-            .await
-            .unwrap();
+        let server = SocketConfig {
+            mode,
+            decoding: None,
+        }
+        .build(SourceContext::new_test(sender))
+        .await
+        .unwrap();
         tokio::spawn(server);
 
         // Wait for server to accept traffic
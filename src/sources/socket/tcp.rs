@@ -1,4 +1,5 @@
 use crate::{
+    codecs::Decoder,
     event::Event,
     internal_events::{SocketEventReceived, SocketMode},
     sources::util::{SocketListenAddr, TcpSource},
@@ -6,13 +7,14 @@ use crate::{
     tls::TlsConfig,
 };
 use bytes::Bytes;
-use codec::BytesDelimitedCodec;
 use getset::{CopyGetters, Getters, Setters};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug, Clone, Getters, CopyGetters, Setters)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, CopyGetters, Setters, JsonSchema)]
 pub struct TcpConfig {
     #[get_copy = "pub"]
+    #[schemars(with = "String")]
     address: SocketListenAddr,
     #[get_copy = "pub"]
     keepalive: Option<TcpKeepaliveConfig>,
@@ -75,29 +77,23 @@ impl TcpConfig {
 #[derive(Debug, Clone)]
 pub struct RawTcpSource {
     pub config: TcpConfig,
+    pub decoder: Decoder,
 }
 
 impl TcpSource for RawTcpSource {
     type Error = std::io::Error;
-    type Decoder = BytesDelimitedCodec;
+    type Decoder = Decoder;
 
     fn decoder(&self) -> Self::Decoder {
-        BytesDelimitedCodec::new_with_max_length(b'\n', self.config.max_length)
+        self.decoder.clone()
     }
 
-    fn build_event(&self, frame: Bytes, host: Bytes) -> Option<Event> {
-        let byte_size = frame.len();
-        let mut event = Event::from(frame);
-
-        event.as_mut_log().insert(
-            crate::config::log_schema().source_type_key(),
-            Bytes::from("socket"),
-        );
+    fn build_event(&self, decoded: (Event, usize), host: Bytes) -> Option<Event> {
+        let (mut event, byte_size) = decoded;
 
         let host_key = (self.config.host_key.clone())
             .unwrap_or_else(|| crate::config::log_schema().host_key().to_string());
-
-        event.as_mut_log().insert(host_key, host);
+        super::enrich_event(&mut event, &host_key, Some(host));
 
         emit!(SocketEventReceived {
             byte_size,
@@ -1,22 +1,23 @@
 use crate::udp;
 use crate::{
-    event::Event,
+    codecs::Decoder,
     internal_events::{SocketEventReceived, SocketMode, SocketReceiveError},
     shutdown::ShutdownSignal,
     sources::Source,
     Pipeline,
 };
-use bytes::{Bytes, BytesMut};
-use codec::BytesDelimitedCodec;
+use bytes::BytesMut;
 use futures::SinkExt;
 use getset::{CopyGetters, Getters};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use tokio::net::UdpSocket;
-use tokio_util::codec::Decoder;
+use tokio_util::codec::Decoder as _;
 
-/// UDP processes messages per packet, where messages are separated by newline.
-#[derive(Deserialize, Serialize, Debug, Clone, Getters, CopyGetters)]
+/// UDP processes messages per packet, where messages are separated by newline unless
+/// another framing is configured through `decoding`.
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, CopyGetters, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct UdpConfig {
     #[get_copy = "pub"]
@@ -50,6 +51,7 @@ pub fn udp(
     max_length: usize,
     host_key: String,
     receive_buffer_bytes: Option<usize>,
+    decoder: Decoder,
     mut shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> Source {
@@ -88,18 +90,11 @@ pub fn udp(
 
                     let mut payload = buf.split_to(byte_size);
 
-                    // UDP processes messages per payload, where messages are separated by newline
-                    // and stretch to end of payload.
-                    let mut decoder = BytesDelimitedCodec::new(b'\n');
-                    while let Ok(Some(line)) = decoder.decode_eof(&mut payload) {
-                        let mut event = Event::from(line);
-
-                        event
-                            .as_mut_log()
-                            .insert(crate::config::log_schema().source_type_key(), Bytes::from("socket"));
-                        event
-                            .as_mut_log()
-                            .insert(host_key.clone(), address.to_string());
+                    // UDP processes messages per payload, where messages are framed by the
+                    // decoder and stretch to end of payload.
+                    let mut decoder = decoder.clone();
+                    while let Ok(Some((mut event, byte_size))) = decoder.decode_eof(&mut payload) {
+                        super::enrich_event(&mut event, &host_key, Some(address.to_string().into()));
 
                         emit!(SocketEventReceived { byte_size,mode:SocketMode::Udp });
 
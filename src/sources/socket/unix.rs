@@ -1,4 +1,5 @@
 use crate::{
+    codecs::Decoder,
     event::Event,
     internal_events::{SocketEventReceived, SocketMode},
     shutdown::ShutdownSignal,
@@ -9,11 +10,11 @@ use crate::{
     Pipeline,
 };
 use bytes::Bytes;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use tokio_util::codec::LinesCodec;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct UnixConfig {
     pub path: PathBuf,
@@ -38,18 +39,11 @@ impl UnixConfig {
 
 /**
 * Function to pass to build_unix_*_source, specific to the basic unix source.
-* Takes a single line of a received message and builds an Event object.
+* Takes a single decoded event of a received message and enriches it.
 **/
-fn build_event(host_key: &str, received_from: Option<Bytes>, line: &str) -> Event {
-    let byte_size = line.len();
-    let mut event = Event::from(line);
-    event.as_mut_log().insert(
-        crate::config::log_schema().source_type_key(),
-        Bytes::from("socket"),
-    );
-    if let Some(host) = received_from {
-        event.as_mut_log().insert(host_key, host);
-    }
+fn build_event(host_key: &str, received_from: Option<Bytes>, decoded: (Event, usize)) -> Event {
+    let (mut event, byte_size) = decoded;
+    super::enrich_event(&mut event, host_key, received_from);
     emit!(SocketEventReceived {
         byte_size,
         mode: SocketMode::Unix
@@ -61,6 +55,7 @@ pub(super) fn unix_datagram(
     path: PathBuf,
     max_length: usize,
     host_key: String,
+    decoder: Decoder,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> Source {
@@ -68,26 +63,26 @@ pub(super) fn unix_datagram(
         path,
         max_length,
         host_key,
-        LinesCodec::new_with_max_length(max_length),
+        decoder,
         shutdown,
         out,
-        |host_key, received_from, line| Some(build_event(host_key, received_from, line)),
+        |host_key, received_from, decoded| Some(build_event(host_key, received_from, decoded)),
     )
 }
 
 pub(super) fn unix_stream(
     path: PathBuf,
-    max_length: usize,
     host_key: String,
+    decoder: Decoder,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> Source {
     build_unix_stream_source(
         path,
-        LinesCodec::new_with_max_length(max_length),
+        decoder,
         host_key,
         shutdown,
         out,
-        |host_key, received_from, line| Some(build_event(host_key, received_from, line)),
+        |host_key, received_from, decoded| Some(build_event(host_key, received_from, decoded)),
     )
 }
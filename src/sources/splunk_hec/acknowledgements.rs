@@ -0,0 +1,148 @@
+//! Splunk HEC indexer acknowledgements
+//!
+//! Each request on a channel is answered with an `ackId`. Clients poll the
+//! `ack` endpoint with these ids and learn which of their requests had all of
+//! their events delivered by the sinks, resending the others after a timeout.
+
+use super::ApiError;
+use crate::internal_events::BatchNotDelivered;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot::error::TryRecvError;
+use vector_core::event::{BatchNotifier, BatchStatus, BatchStatusReceiver};
+
+/// Channels unused for this long are forgotten along with their pending acks.
+const MAX_IDLE_CHANNEL: Duration = Duration::from_secs(600);
+
+/// Requests on a channel are refused while it has this many pending acks.
+const MAX_PENDING_ACKS_PER_CHANNEL: usize = 1_000_000;
+
+#[derive(Default)]
+struct Channel {
+    next_ack_id: u64,
+    pending: HashMap<u64, BatchStatusReceiver>,
+}
+
+#[derive(Default)]
+pub(super) struct IndexerAcknowledgements {
+    channels: Mutex<HashMap<String, (Channel, Instant)>>,
+}
+
+/// Body of a request to the `ack` endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+pub(super) struct AckRequest {
+    pub(super) acks: Vec<u64>,
+}
+
+/// Body of a response of the `ack` endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+pub(super) struct AckResponse {
+    pub(super) acks: HashMap<u64, bool>,
+}
+
+impl IndexerAcknowledgements {
+    /// Creates a batch for the events of a request on `channel`, returning it
+    /// along with the request's ack id.
+    pub(super) fn create_batch(
+        &self,
+        channel: &str,
+    ) -> Result<(Arc<BatchNotifier>, u64), ApiError> {
+        let mut channels = self.channels.lock().unwrap();
+        if !channels.contains_key(channel) {
+            channels.retain(|_, (_, last_used)| last_used.elapsed() < MAX_IDLE_CHANNEL);
+        }
+
+        let (channel, last_used) = channels
+            .entry(channel.to_owned())
+            .or_insert_with(|| (Channel::default(), Instant::now()));
+        *last_used = Instant::now();
+
+        if channel.pending.len() >= MAX_PENDING_ACKS_PER_CHANNEL {
+            return Err(ApiError::ServerBusy);
+        }
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let ack_id = channel.next_ack_id;
+        channel.next_ack_id += 1;
+        channel.pending.insert(ack_id, receiver);
+        Ok((batch, ack_id))
+    }
+
+    /// Looks up which of the requests with `ack_ids` were delivered. Ids are
+    /// forgotten once they are reported as delivered or have failed.
+    pub(super) fn query(&self, channel: &str, ack_ids: &[u64]) -> AckResponse {
+        let mut channels = self.channels.lock().unwrap();
+        let mut channel = channels.get_mut(channel).map(|(channel, last_used)| {
+            *last_used = Instant::now();
+            channel
+        });
+
+        let acks = ack_ids
+            .iter()
+            .map(|&ack_id| {
+                let receiver = channel
+                    .as_mut()
+                    .and_then(|channel| channel.pending.get_mut(&ack_id));
+                let status = match receiver.map(BatchStatusReceiver::try_recv) {
+                    None | Some(Err(TryRecvError::Empty)) => return (ack_id, false),
+                    Some(Ok(status)) => status,
+                    Some(Err(TryRecvError::Closed)) => BatchStatus::Errored,
+                };
+
+                if let Some(channel) = channel.as_mut() {
+                    channel.pending.remove(&ack_id);
+                }
+                if status != BatchStatus::Delivered {
+                    emit!(BatchNotDelivered { status });
+                }
+                (ack_id, status == BatchStatus::Delivered)
+            })
+            .collect();
+
+        AckResponse { acks }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Event, EventStatus};
+
+    #[test]
+    fn reports_delivered_requests_once() {
+        let acks = IndexerAcknowledgements::default();
+        let (batch, first) = acks.create_batch("channel").unwrap();
+        let (_pending, second) = acks.create_batch("channel").unwrap();
+        assert_eq!((first, second), (0, 1));
+
+        let mut event = Event::from("message").with_batch_notifier(&batch);
+        drop(batch);
+        assert!(!acks.query("channel", &[first]).acks[&first]);
+
+        event.metadata_mut().update_status(EventStatus::Delivered);
+        drop(event);
+        let response = acks.query("channel", &[first, second]);
+        assert!(response.acks[&first]);
+        assert!(!response.acks[&second]);
+
+        assert!(!acks.query("channel", &[first]).acks[&first]);
+        assert!(!acks.query("other", &[first]).acks[&first]);
+    }
+
+    #[test]
+    fn reports_failed_requests_as_not_delivered() {
+        let acks = IndexerAcknowledgements::default();
+        let (batch, ack_id) = acks.create_batch("channel").unwrap();
+
+        let event = Event::from("message").with_batch_notifier(&batch);
+        drop(batch);
+        event.metadata().update_status(EventStatus::Failed);
+        drop(event);
+
+        assert!(!acks.query("channel", &[ack_id]).acks[&ack_id]);
+    }
+}
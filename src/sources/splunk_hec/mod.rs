@@ -1,16 +1,16 @@
 use crate::{
     config::{log_schema, DataType, Resource, SourceConfig, SourceContext, SourceDescription},
-    event::{Event, LogEvent, Value},
+    event::{BatchNotifier, Event, LogEvent, Value},
     internal_events::{
         SplunkHecEventReceived, SplunkHecRequestBodyInvalid, SplunkHecRequestError,
         SplunkHecRequestReceived,
     },
+    sources::util::{decompress, DecompressionConfig, DecompressionError},
     tls::{MaybeTlsSettings, TlsConfig},
     Pipeline,
 };
 use bytes::{Buf, Bytes};
 use chrono::{DateTime, TimeZone, Utc};
-use flate2::read::MultiGzDecoder;
 use futures::{stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
 use http::StatusCode;
 use serde::{de, Deserialize, Serialize};
@@ -21,10 +21,15 @@ use std::{
     future,
     io::Read,
     net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
 };
 
 use warp::{filters::BoxedFilter, path, reject::Rejection, reply::Response, Filter, Reply};
 
+mod acknowledgements;
+
+use acknowledgements::{AckRequest, IndexerAcknowledgements};
+
 // Event fields unique to splunk_hec source
 pub const CHANNEL: &str = "splunk_channel";
 pub const INDEX: &str = "splunk_index";
@@ -41,6 +46,12 @@ pub struct SplunkConfig {
     /// Splunk HEC token
     token: Option<String>,
     tls: Option<TlsConfig>,
+    /// Limits on decompressed request bodies
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    decompression: DecompressionConfig,
 }
 
 inventory::submit! {
@@ -65,6 +76,7 @@ impl Default for SplunkConfig {
             address: default_socket_address(),
             token: None,
             tls: None,
+            decompression: Default::default(),
         }
     }
 }
@@ -77,10 +89,11 @@ fn default_socket_address() -> SocketAddr {
 #[typetag::serde(name = "splunk_hec")]
 impl SourceConfig for SplunkConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let source = SplunkSource::new(self);
+        let source = SplunkSource::new(self, cx.acknowledgements);
 
         let event_service = source.event_service(cx.out.clone());
         let raw_service = source.raw_service(cx.out);
+        let ack_service = source.ack_service();
         let health_service = source.health_service();
         let options = SplunkSource::options();
 
@@ -98,6 +111,8 @@ impl SourceConfig for SplunkConfig {
                 event_service
                     .or(raw_service)
                     .unify()
+                    .or(ack_service)
+                    .unify()
                     .or(health_service)
                     .unify()
                     .or(options)
@@ -132,20 +147,53 @@ impl SourceConfig for SplunkConfig {
     fn resources(&self) -> Vec<Resource> {
         vec![Resource::tcp(self.address)]
     }
+
+    /// Decodes the body of a request to the `event` endpoint.
+    fn decode_payload(&self, payload: Bytes) -> Option<crate::Result<Vec<Event>>> {
+        Some(
+            EventIterator::new(payload.reader(), None, None)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|rejection| match rejection.find::<ApiError>() {
+                    Some(error) => format!("Invalid request body: {:?}.", error).into(),
+                    None => "Invalid request body.".into(),
+                }),
+        )
+    }
 }
 
 /// Shared data for responding to requests.
 struct SplunkSource {
     credentials: Option<Bytes>,
+    decompression: DecompressionConfig,
+    /// Set when the source is configured with `acknowledgements`.
+    acknowledgements: Option<Arc<IndexerAcknowledgements>>,
 }
 
 impl SplunkSource {
-    fn new(config: &SplunkConfig) -> Self {
+    fn new(config: &SplunkConfig, acknowledgements: bool) -> Self {
         SplunkSource {
             credentials: config
                 .token
                 .as_ref()
                 .map(|token| format!("Splunk {}", token).into()),
+            decompression: config.decompression.clone(),
+            acknowledgements: acknowledgements.then(Default::default),
+        }
+    }
+
+    /// Creates a batch for the events of a request on `channel` when
+    /// acknowledgements are enabled, along with the ack id of the request.
+    fn batch(
+        acknowledgements: &Option<Arc<IndexerAcknowledgements>>,
+        channel: Option<&str>,
+    ) -> Result<(Option<Arc<BatchNotifier>>, Option<u64>), Rejection> {
+        match (acknowledgements, channel) {
+            (None, _) => Ok((None, None)),
+            (Some(acknowledgements), Some(channel)) => {
+                let (batch, ack_id) = acknowledgements.create_batch(channel)?;
+                Ok((Some(batch), Some(ack_id)))
+            }
+            (Some(_), None) => Err(ApiError::MissingChannel.into()),
         }
     }
 
@@ -158,31 +206,30 @@ impl SplunkSource {
             .and(splunk_channel_query_param)
             .map(|header: Option<String>, query_param| header.or(query_param));
 
+        let acknowledgements = self.acknowledgements.clone();
         warp::post()
             .and(path!("event").or(path!("event" / "1.0")))
             .and(self.authorization())
             .and(splunk_channel)
             .and(warp::addr::remote())
-            .and(self.gzip())
-            .and(warp::body::bytes())
+            .and(self.body())
             .and_then(
-                move |_,
-                      _,
-                      channel: Option<String>,
-                      remote: Option<SocketAddr>,
-                      gzip: bool,
-                      body: Bytes| {
+                move |_, _, channel: Option<String>, remote: Option<SocketAddr>, body: Bytes| {
                     let mut out = out
                         .clone()
                         .sink_map_err(|_| Rejection::from(ApiError::ServerShutdown));
+                    let batch = Self::batch(&acknowledgements, channel.as_deref());
                     async move {
-                        let reader: Box<dyn Read + Send> = if gzip {
-                            Box::new(MultiGzDecoder::new(body.reader()))
-                        } else {
-                            Box::new(body.reader())
-                        };
-
-                        let events = stream::iter(EventIterator::new(reader, channel, remote));
+                        let (batch, ack_id) = batch?;
+                        let events = stream::iter(EventIterator::new(
+                            body.reader(),
+                            channel,
+                            remote,
+                        ))
+                        .map(move |event| match &batch {
+                            Some(batch) => event.map(|event| event.with_batch_notifier(batch)),
+                            None => event,
+                        });
 
                         // `fn send_all` can be used once https://github.com/rust-lang/futures-rs/issues/2402
                         // is resolved.
@@ -190,7 +237,7 @@ impl SplunkSource {
 
                         out.flush().await?;
 
-                        res
+                        res.map(|_| ack_id)
                     }
                 },
             )
@@ -211,29 +258,29 @@ impl SplunkSource {
                     .ok_or_else(|| Rejection::from(ApiError::MissingChannel))
             });
 
+        let acknowledgements = self.acknowledgements.clone();
         warp::post()
             .and(path!("raw" / "1.0").or(path!("raw")))
             .and(self.authorization())
             .and(splunk_channel)
             .and(warp::addr::remote())
-            .and(self.gzip())
-            .and(warp::body::bytes())
+            .and(self.body())
             .and_then(
-                move |_,
-                      _,
-                      channel: String,
-                      remote: Option<SocketAddr>,
-                      gzip: bool,
-                      body: Bytes| {
+                move |_, _, channel: String, remote: Option<SocketAddr>, body: Bytes| {
                     let out = out.clone();
+                    let batch = Self::batch(&acknowledgements, Some(&channel));
                     async move {
+                        let (batch, ack_id) = batch?;
                         // Construct event parser
-                        let event = future::ready(raw_event(body, gzip, channel, remote));
-                        futures::stream::once(event)
+                        let event = raw_event(body, channel, remote).map(|event| match &batch {
+                            Some(batch) => event.with_batch_notifier(batch),
+                            None => event,
+                        });
+                        futures::stream::once(future::ready(event))
                             .forward(
                                 out.sink_map_err(|_| Rejection::from(ApiError::ServerShutdown)),
                             )
-                            .map_ok(|_| ())
+                            .map_ok(|_| ack_id)
                             .await
                     }
                 },
@@ -242,6 +289,38 @@ impl SplunkSource {
             .boxed()
     }
 
+    fn ack_service(&self) -> BoxedFilter<(Response,)> {
+        let splunk_channel_query_param = warp::query::<HashMap<String, String>>()
+            .map(|qs: HashMap<String, String>| qs.get("channel").map(|v| v.to_owned()));
+        let splunk_channel_header = warp::header::optional::<String>("x-splunk-request-channel");
+
+        let splunk_channel = splunk_channel_header
+            .and(splunk_channel_query_param)
+            .and_then(|header: Option<String>, query_param| async move {
+                header
+                    .or(query_param)
+                    .ok_or_else(|| Rejection::from(ApiError::MissingChannel))
+            });
+
+        let acknowledgements = self.acknowledgements.clone();
+        warp::post()
+            .and(path!("ack" / "1.0").or(path!("ack")))
+            .and(self.authorization())
+            .and(splunk_channel)
+            .and(self.body())
+            .and_then(move |_, _, channel: String, body: Bytes| {
+                let response = match &acknowledgements {
+                    Some(acknowledgements) => serde_json::from_slice::<AckRequest>(&body)
+                        .map(|request| acknowledgements.query(&channel, &request.acks))
+                        .map_err(|_| Rejection::from(ApiError::BadRequest)),
+                    None => Err(Rejection::from(ApiError::AckIsDisabled)),
+                };
+                future::ready(response)
+            })
+            .map(|response| response_json(StatusCode::OK, response))
+            .boxed()
+    }
+
     fn health_service(&self) -> BoxedFilter<(Response,)> {
         let credentials = self.credentials.clone();
         let authorize =
@@ -271,7 +350,9 @@ impl SplunkSource {
                 path!("event")
                     .or(path!("event" / "1.0"))
                     .or(path!("raw" / "1.0"))
-                    .or(path!("raw")),
+                    .or(path!("raw"))
+                    .or(path!("ack" / "1.0"))
+                    .or(path!("ack")),
             )
             .map(|_| warp::reply::with_header(warp::reply(), "Allow", "POST").into_response());
 
@@ -302,15 +383,27 @@ impl SplunkSource {
             .boxed()
     }
 
-    /// Is body encoded with gzip
-    fn gzip(&self) -> BoxedFilter<(bool,)> {
+    /// Body decompressed according to its `Content-Encoding`
+    fn body(&self) -> BoxedFilter<(Bytes,)> {
+        let decompression = self.decompression.clone();
         warp::header::optional::<String>("Content-Encoding")
-            .and_then(|encoding: Option<String>| async move {
-                match encoding {
-                    Some(s) if s.as_bytes() == b"gzip" => Ok(true),
-                    Some(_) => Err(Rejection::from(ApiError::UnsupportedEncoding)),
-                    None => Ok(false),
-                }
+            .and(warp::body::bytes())
+            .and_then(move |encoding: Option<String>, body: Bytes| {
+                let body = decompress(encoding.as_deref(), body, &decompression).map_err(|error| {
+                    match error {
+                        DecompressionError::UnsupportedEncoding { .. } => {
+                            Rejection::from(ApiError::UnsupportedEncoding)
+                        }
+                        DecompressionError::Failed { source, .. } => {
+                            emit!(SplunkHecRequestBodyInvalid { error: source });
+                            Rejection::from(ApiError::InvalidDataFormat { event: 0 })
+                        }
+                        DecompressionError::TooLarge { .. } => {
+                            Rejection::from(ApiError::PayloadTooLarge)
+                        }
+                    }
+                });
+                future::ready(body)
             })
             .boxed()
     }
@@ -577,24 +670,13 @@ enum Time {
 /// Creates event from raw request
 fn raw_event(
     bytes: Bytes,
-    gzip: bool,
     channel: String,
     remote: Option<SocketAddr>,
 ) -> Result<Event, Rejection> {
-    // Process gzip
-    let message: Value = if gzip {
-        let mut data = Vec::new();
-        match MultiGzDecoder::new(bytes.reader()).read_to_end(&mut data) {
-            Ok(0) => return Err(ApiError::NoData.into()),
-            Ok(_) => Value::from(Bytes::from(data)),
-            Err(error) => {
-                emit!(SplunkHecRequestBodyInvalid { error });
-                return Err(ApiError::InvalidDataFormat { event: 0 }.into());
-            }
-        }
-    } else {
-        bytes.into()
-    };
+    if bytes.is_empty() {
+        return Err(ApiError::NoData.into());
+    }
+    let message = Value::from(bytes);
 
     // Construct event
     let mut event = Event::new_empty_log();
@@ -629,6 +711,7 @@ pub(crate) enum ApiError {
     MissingAuthorization,
     InvalidAuthorization,
     UnsupportedEncoding,
+    PayloadTooLarge,
     MissingChannel,
     NoData,
     InvalidDataFormat { event: usize },
@@ -636,6 +719,8 @@ pub(crate) enum ApiError {
     EmptyEventField { event: usize },
     MissingEventField { event: usize },
     BadRequest,
+    AckIsDisabled,
+    ServerBusy,
 }
 
 impl warp::reject::Reject for ApiError {}
@@ -663,13 +748,25 @@ mod splunk_response {
             json_to_bytes(json!({"text":"Server is shutting down","code":9}));
         pub static ref UNSUPPORTED_MEDIA_TYPE: Bytes =
             json_to_bytes(json!({"text":"unsupported content encoding"}));
+        pub static ref PAYLOAD_TOO_LARGE: Bytes =
+            json_to_bytes(json!({"text":"decompressed content too large"}));
         pub static ref NO_CHANNEL: Bytes =
             json_to_bytes(json!({"text":"Data channel is missing","code":10}));
+        pub static ref ACK_IS_DISABLED: Bytes =
+            json_to_bytes(json!({"text":"ACK is disabled","code":14}));
+        pub static ref SERVER_BUSY: Bytes =
+            json_to_bytes(json!({"text":"Server is busy","code":9}));
     }
 }
 
-fn finish_ok(_: ()) -> Response {
-    response_json(StatusCode::OK, splunk_response::SUCCESS.as_ref())
+fn finish_ok(ack_id: Option<u64>) -> Response {
+    match ack_id {
+        Some(ack_id) => response_json(
+            StatusCode::OK,
+            json!({"text":"Success","code":0,"ackId":ack_id}),
+        ),
+        None => response_json(StatusCode::OK, splunk_response::SUCCESS.as_ref()),
+    }
 }
 
 async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
@@ -688,6 +785,10 @@ async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
                 StatusCode::UNSUPPORTED_MEDIA_TYPE,
                 splunk_response::UNSUPPORTED_MEDIA_TYPE.as_ref(),
             ),
+            ApiError::PayloadTooLarge => response_json(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                splunk_response::PAYLOAD_TOO_LARGE.as_ref(),
+            ),
             ApiError::MissingChannel => response_json(
                 StatusCode::BAD_REQUEST,
                 splunk_response::NO_CHANNEL.as_ref(),
@@ -707,6 +808,14 @@ async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
                 event_error("Event field is required", 12, event)
             }
             ApiError::BadRequest => empty_response(StatusCode::BAD_REQUEST),
+            ApiError::AckIsDisabled => response_json(
+                StatusCode::BAD_REQUEST,
+                splunk_response::ACK_IS_DISABLED.as_ref(),
+            ),
+            ApiError::ServerBusy => response_json(
+                StatusCode::SERVICE_UNAVAILABLE,
+                splunk_response::SERVER_BUSY.as_ref(),
+            ),
         },))
     } else {
         Err(rejection)
@@ -751,10 +860,13 @@ mod tests {
     use super::{parse_timestamp, SplunkConfig};
     use crate::{
         config::{log_schema, SinkConfig, SinkContext, SourceConfig, SourceContext},
-        event::Event,
+        event::{Event, EventStatus},
         sinks::{
-            splunk_hec::{Encoding, HecSinkConfig},
-            util::{encoding::EncodingConfig, BatchConfig, Compression, TowerRequestConfig},
+            splunk_hec::HecSinkConfig,
+            util::{
+                encoding::{EncodingConfig, StandardEncodings},
+                BatchConfig, Compression, TowerRequestConfig,
+            },
             Healthcheck, VectorSink,
         },
         test_util::{collect_n, next_addr, trace_init, wait_for_tcp},
@@ -784,6 +896,7 @@ mod tests {
                 address,
                 token,
                 tls: None,
+                decompression: Default::default(),
             }
             .build(SourceContext::new_test(sender))
             .await
@@ -797,7 +910,7 @@ mod tests {
 
     async fn sink(
         address: SocketAddr,
-        encoding: impl Into<EncodingConfig<Encoding>>,
+        encoding: impl Into<EncodingConfig<StandardEncodings>>,
         compression: Compression,
     ) -> (VectorSink, Healthcheck) {
         HecSinkConfig {
@@ -820,7 +933,7 @@ mod tests {
     }
 
     async fn start(
-        encoding: impl Into<EncodingConfig<Encoding>>,
+        encoding: impl Into<EncodingConfig<StandardEncodings>>,
         compression: Compression,
     ) -> (VectorSink, mpsc::Receiver<Event>) {
         let (source, address) = source().await;
@@ -890,7 +1003,7 @@ mod tests {
         trace_init();
 
         let message = "gzip_text_event";
-        let (sink, source) = start(Encoding::Text, Compression::None).await;
+        let (sink, source) = start(StandardEncodings::Text, Compression::None).await;
 
         let event = channel_n(vec![message], sink, source).await.remove(0);
 
@@ -907,7 +1020,7 @@ mod tests {
         trace_init();
 
         let message = "one_simple_text_event";
-        let (sink, source) = start(Encoding::Text, Compression::gzip_default()).await;
+        let (sink, source) = start(StandardEncodings::Text, Compression::gzip_default()).await;
 
         let event = channel_n(vec![message], sink, source).await.remove(0);
 
@@ -924,7 +1037,7 @@ mod tests {
         trace_init();
 
         let n = 200;
-        let (sink, source) = start(Encoding::Text, Compression::None).await;
+        let (sink, source) = start(StandardEncodings::Text, Compression::None).await;
 
         let messages = (0..n)
             .map(|i| format!("multiple_simple_text_event_{}", i))
@@ -946,7 +1059,7 @@ mod tests {
         trace_init();
 
         let message = "one_simple_json_event";
-        let (sink, source) = start(Encoding::Json, Compression::gzip_default()).await;
+        let (sink, source) = start(StandardEncodings::Json, Compression::gzip_default()).await;
 
         let event = channel_n(vec![message], sink, source).await.remove(0);
 
@@ -963,7 +1076,7 @@ mod tests {
         trace_init();
 
         let n = 200;
-        let (sink, source) = start(Encoding::Json, Compression::gzip_default()).await;
+        let (sink, source) = start(StandardEncodings::Json, Compression::gzip_default()).await;
 
         let messages = (0..n)
             .map(|i| format!("multiple_simple_json_event{}", i))
@@ -984,7 +1097,7 @@ mod tests {
     async fn json_event() {
         trace_init();
 
-        let (sink, source) = start(Encoding::Json, Compression::gzip_default()).await;
+        let (sink, source) = start(StandardEncodings::Json, Compression::gzip_default()).await;
 
         let mut event = Event::new_empty_log();
         event.as_mut_log().insert("greeting", "hello");
@@ -1005,7 +1118,7 @@ mod tests {
     async fn line_to_message() {
         trace_init();
 
-        let (sink, source) = start(Encoding::Json, Compression::gzip_default()).await;
+        let (sink, source) = start(StandardEncodings::Json, Compression::gzip_default()).await;
 
         let mut event = Event::new_empty_log();
         event.as_mut_log().insert("line", "hello");
@@ -1108,13 +1221,65 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn indexer_acknowledgements() {
+        trace_init();
+
+        let (sender, source) = Pipeline::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let mut context = SourceContext::new_test(sender);
+        context.acknowledgements = true;
+        tokio::spawn(async move {
+            SplunkConfig::on(address)
+                .build(context)
+                .await
+                .unwrap()
+                .await
+                .unwrap()
+        });
+        wait_for_tcp(address).await;
+
+        let client = reqwest::Client::new();
+        let request = |api: &str, body: &str| {
+            client
+                .post(&format!("http://{}/{}", address, api))
+                .header("x-splunk-request-channel", "channel")
+                .body(body.to_owned())
+                .send()
+        };
+
+        let response: serde_json::Value =
+            request("services/collector/event", r#"{"event":"first"}"#)
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+        assert_eq!(response["ackId"], 0);
+        drop(collect_n(source, 1).await);
+
+        let response: serde_json::Value = request("services/collector/ack", r#"{"acks":[0,1]}"#)
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(response["acks"]["0"], true);
+        assert_eq!(response["acks"]["1"], false);
+    }
+
     #[tokio::test]
     async fn no_authorization() {
         trace_init();
 
         let message = "no_authorization";
         let (source, address) = source_with(None).await;
-        let (sink, health) = sink(address, Encoding::Text, Compression::gzip_default()).await;
+        let (sink, health) = sink(
+            address,
+            StandardEncodings::Text,
+            Compression::gzip_default(),
+        )
+        .await;
         assert!(health.await.is_ok());
 
         let event = channel_n(vec![message], sink, source).await.remove(0);
@@ -1218,7 +1383,7 @@ mod tests {
         trace_init();
 
         let message = "for the host";
-        let (sink, source) = start(Encoding::Text, Compression::gzip_default()).await;
+        let (sink, source) = start(StandardEncodings::Text, Compression::gzip_default()).await;
 
         let event = channel_n(vec![message], sink, source).await.remove(0);
 
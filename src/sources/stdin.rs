@@ -1,14 +1,16 @@
 use crate::{
+    codecs::{DecodingConfig, FramingConfig},
     config::{log_schema, DataType, Resource, SourceConfig, SourceContext, SourceDescription},
     event::Event,
     internal_events::{StdinEventReceived, StdinReadFailed},
     shutdown::ShutdownSignal,
     Pipeline,
 };
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use futures::{channel::mpsc, executor, FutureExt, SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use std::{io, thread};
+use tokio_util::codec::Decoder;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields, default)]
@@ -16,6 +18,7 @@ pub struct StdinConfig {
     #[serde(default = "default_max_length")]
     pub max_length: usize,
     pub host_key: Option<String>,
+    pub decoding: Option<DecodingConfig>,
 }
 
 impl Default for StdinConfig {
@@ -23,6 +26,7 @@ impl Default for StdinConfig {
         StdinConfig {
             max_length: default_max_length(),
             host_key: None,
+            decoding: None,
         }
     }
 }
@@ -50,7 +54,9 @@ impl SourceConfig for StdinConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Log
+        self.decoding
+            .as_ref()
+            .map_or(DataType::Log, DecodingConfig::output_type)
     }
 
     fn source_type(&self) -> &'static str {
@@ -60,10 +66,31 @@ impl SourceConfig for StdinConfig {
     fn resources(&self) -> Vec<Resource> {
         vec![Resource::Stdin]
     }
+
+    /// Decodes the payload like a line read from stdin. The hostname is left
+    /// out, so the events don't depend on the host running the tests.
+    fn decode_payload(&self, payload: Bytes) -> Option<crate::Result<Vec<Event>>> {
+        let host_key = self
+            .host_key
+            .clone()
+            .unwrap_or_else(|| log_schema().host_key().to_string());
+        Some(
+            self.decoding
+                .clone()
+                .unwrap_or_default()
+                .decode_payload(FramingConfig::newline_delimited(self.max_length), payload)
+                .map(|events| {
+                    events
+                        .into_iter()
+                        .map(|event| create_event(event, &host_key, &None))
+                        .collect()
+                }),
+        )
+    }
 }
 
 pub fn stdin_source<R>(
-    stdin: R,
+    mut stdin: R,
     config: StdinConfig,
     shutdown: ShutdownSignal,
     out: Pipeline,
@@ -75,6 +102,10 @@ where
         .host_key
         .unwrap_or_else(|| log_schema().host_key().to_string());
     let hostname = crate::get_hostname().ok();
+    let mut decoder = config
+        .decoding
+        .unwrap_or_default()
+        .build(FramingConfig::newline_delimited(config.max_length))?;
 
     let (mut sender, receiver) = mpsc::channel(1024);
 
@@ -82,9 +113,39 @@ where
     thread::spawn(move || {
         info!("Capturing STDIN.");
 
-        for line in stdin.lines() {
-            if executor::block_on(sender.send(line)).is_err() {
-                // receiver has closed so we should shutdown
+        let mut buffer = BytesMut::new();
+        loop {
+            let read = match stdin.fill_buf() {
+                Ok(chunk) => {
+                    buffer.extend_from_slice(chunk);
+                    chunk.len()
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    let _ = executor::block_on(sender.send(Err(error)));
+                    return;
+                }
+            };
+            stdin.consume(read);
+            let eof = read == 0;
+
+            loop {
+                let decoded = if eof {
+                    decoder.decode_eof(&mut buffer)
+                } else {
+                    decoder.decode(&mut buffer)
+                };
+                let decoded = match decoded.transpose() {
+                    Some(decoded) => decoded,
+                    None => break,
+                };
+                if executor::block_on(sender.send(decoded)).is_err() {
+                    // receiver has closed so we should shutdown
+                    return;
+                }
+            }
+
+            if eof {
                 return;
             }
         }
@@ -97,11 +158,9 @@ where
         let res = receiver
             .take_until(shutdown)
             .map_err(|error| emit!(StdinReadFailed { error }))
-            .map_ok(move |line| {
-                emit!(StdinEventReceived {
-                    byte_size: line.len()
-                });
-                create_event(Bytes::from(line), &host_key, &hostname)
+            .map_ok(move |(event, byte_size)| {
+                emit!(StdinEventReceived { byte_size });
+                create_event(event, &host_key, &hostname)
             })
             .forward(&mut out)
             .inspect(|_| info!("Finished sending."))
@@ -113,16 +172,14 @@ where
     }))
 }
 
-fn create_event(line: Bytes, host_key: &str, hostname: &Option<String>) -> Event {
-    let mut event = Event::from(line);
+fn create_event(mut event: Event, host_key: &str, hostname: &Option<String>) -> Event {
+    if let Event::Log(log) = &mut event {
+        // Add source type
+        log.insert(log_schema().source_type_key(), Bytes::from("stdin"));
 
-    // Add source type
-    event
-        .as_mut_log()
-        .insert(log_schema().source_type_key(), Bytes::from("stdin"));
-
-    if let Some(hostname) = &hostname {
-        event.as_mut_log().insert(host_key, hostname.clone());
+        if let Some(hostname) = &hostname {
+            log.insert(host_key, hostname.clone());
+        }
     }
 
     event
@@ -131,7 +188,7 @@ fn create_event(line: Bytes, host_key: &str, hostname: &Option<String>) -> Event
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_util::trace_init, Pipeline};
+    use crate::{codecs::ParserConfig, test_util::trace_init, Pipeline};
     use std::io::Cursor;
 
     #[test]
@@ -145,7 +202,7 @@ mod tests {
         let host_key = "host".to_string();
         let hostname = Some("Some.Machine".to_string());
 
-        let event = create_event(line, &host_key, &hostname);
+        let event = create_event(Event::from(line), &host_key, &hostname);
         let log = event.into_log();
 
         assert_eq!(log["host"], "Some.Machine".into());
@@ -183,4 +240,30 @@ mod tests {
         let event = stream.next().await;
         assert!(event.is_none());
     }
+
+    #[tokio::test]
+    async fn stdin_decodes_json() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            decoding: Some(DecodingConfig::new(None, ParserConfig::Json)),
+            ..StdinConfig::default()
+        };
+        let buf = Cursor::new("{\"a\": 1}\nnot json\n{\"a\": 2}");
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let events = rx.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()["a"], 1.into());
+        assert_eq!(events[1].as_log()["a"], 2.into());
+        assert_eq!(
+            events[1].as_log()[log_schema().source_type_key()],
+            "stdin".into()
+        );
+    }
 }
@@ -3,11 +3,12 @@ use super::util::{SocketListenAddr, TcpSource};
 use crate::sources::util::build_unix_stream_source;
 use crate::udp;
 use crate::{
+    codecs::{insert_fields_from_syslog, resolve_year},
     config::{
         log_schema, DataType, GenerateConfig, Resource, SourceConfig, SourceContext,
         SourceDescription,
     },
-    event::{Event, Value},
+    event::Event,
     internal_events::{SyslogEventReceived, SyslogUdpReadError, SyslogUdpUtf8Error},
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
@@ -15,14 +16,13 @@ use crate::{
     Pipeline,
 };
 use bytes::{Buf, Bytes, BytesMut};
-use chrono::{Datelike, Utc};
+use chrono::Utc;
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::io;
 use std::net::SocketAddr;
 #[cfg(unix)]
 use std::path::PathBuf;
-use syslog_loose::{IncompleteDate, Message, ProcId, Protocol};
 use tokio::net::UdpSocket;
 use tokio_util::{
     codec::{BytesCodec, Decoder, LinesCodec, LinesCodecError},
@@ -142,7 +142,7 @@ impl SourceConfig for SyslogConfig {
                 host_key,
                 cx.shutdown,
                 cx.out,
-                |host_key, default_host, line| Some(event_from_str(host_key, default_host, line)),
+                |host_key, default_host, line| Some(event_from_str(host_key, default_host, &line)),
             )),
         }
     }
@@ -163,6 +163,36 @@ impl SourceConfig for SyslogConfig {
             Mode::Unix { .. } => vec![],
         }
     }
+
+    fn decode_payload(&self, payload: Bytes) -> Option<crate::Result<Vec<Event>>> {
+        let host_key = self
+            .host_key
+            .clone()
+            .unwrap_or_else(|| log_schema().host_key().to_string());
+        if let Mode::Udp { .. } = self.mode {
+            return Some(Ok(event_from_datagram(&host_key, None, &payload)
+                .into_iter()
+                .collect()));
+        }
+        Some(decode_lines(self.max_length, payload).map(|lines| {
+            lines
+                .iter()
+                .map(|line| event_from_str(&host_key, None, line))
+                .collect()
+        }))
+    }
+}
+
+/// Splits a stream into messages the way the source does, either octet
+/// counted or newline delimited.
+fn decode_lines(max_length: usize, payload: Bytes) -> crate::Result<Vec<String>> {
+    let mut decoder = SyslogDecoder::new(max_length);
+    let mut buf = BytesMut::from(&payload[..]);
+    let mut lines = Vec::new();
+    while let Some(line) = decoder.decode_eof(&mut buf)? {
+        lines.push(line);
+    }
+    Ok(lines)
 }
 
 #[derive(Debug, Clone)]
@@ -427,11 +457,7 @@ pub fn udp(
                     match frame {
                         Ok((bytes, received_from)) => {
                             let received_from = received_from.ip().to_string().into();
-
-                            std::str::from_utf8(&bytes)
-                                .map_err(|error| emit!(SyslogUdpUtf8Error { error }))
-                                .ok()
-                                .map(|s| Ok(event_from_str(&host_key, Some(received_from), s)))
+                            event_from_datagram(&host_key, Some(received_from), &bytes).map(Ok)
                         }
                         Err(error) => {
                             emit!(SyslogUdpReadError { error });
@@ -448,16 +474,17 @@ pub fn udp(
     })
 }
 
-/// Function used to resolve the year for syslog messages that don't include the year.
-/// If the current month is January, and the syslog message is for December, it will take the previous year.
-/// Otherwise, take the current year.
-fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
-    let now = Utc::now();
-    if now.month() == 1 && month == 12 {
-        now.year() - 1
-    } else {
-        now.year()
-    }
+/// Parses a UDP datagram, which holds a single message. Datagrams which aren't
+/// valid UTF-8 are reported and dropped.
+fn event_from_datagram(
+    host_key: &str,
+    received_from: Option<Bytes>,
+    bytes: &[u8],
+) -> Option<Event> {
+    std::str::from_utf8(bytes)
+        .map_err(|error| emit!(SyslogUdpUtf8Error { error }))
+        .ok()
+        .map(|line| event_from_str(host_key, received_from, line))
 }
 
 /**
@@ -494,7 +521,7 @@ fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Ev
         .as_mut_log()
         .insert(log_schema().timestamp_key(), timestamp);
 
-    insert_fields_from_syslog(&mut event, parsed);
+    insert_fields_from_syslog(event.as_mut_log(), parsed);
 
     emit!(SyslogEventReceived {
         byte_size: line.len()
@@ -508,43 +535,6 @@ fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Ev
     event
 }
 
-fn insert_fields_from_syslog(event: &mut Event, parsed: Message<&str>) {
-    let log = event.as_mut_log();
-
-    if let Some(host) = parsed.hostname {
-        log.insert("hostname", host.to_string());
-    }
-    if let Some(severity) = parsed.severity {
-        log.insert("severity", severity.as_str().to_owned());
-    }
-    if let Some(facility) = parsed.facility {
-        log.insert("facility", facility.as_str().to_owned());
-    }
-    if let Protocol::RFC5424(version) = parsed.protocol {
-        log.insert("version", version as i64);
-    }
-    if let Some(app_name) = parsed.appname {
-        log.insert("appname", app_name.to_owned());
-    }
-    if let Some(msg_id) = parsed.msgid {
-        log.insert("msgid", msg_id.to_owned());
-    }
-    if let Some(procid) = parsed.procid {
-        let value: Value = match procid {
-            ProcId::PID(pid) => pid.into(),
-            ProcId::Name(name) => name.to_string().into(),
-        };
-        log.insert("procid", value);
-    }
-
-    for element in parsed.structured_data.into_iter() {
-        for (name, value) in element.params.into_iter() {
-            let key = format!("{}.{}", element.id, name);
-            log.insert(key, value.to_string());
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -570,6 +560,25 @@ mod test {
         assert!(matches!(config.mode, Mode::Tcp { .. }));
     }
 
+    #[test]
+    fn decode_payload_octet_counted() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+          "#,
+        )
+        .unwrap();
+
+        let payload = Bytes::from("27 <13>1 - host1 app - - - one27 <13>1 - host2 app - - - two");
+        let events = config.decode_payload(payload).unwrap().unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "one".into());
+        assert_eq!(events[0].as_log()[log_schema().host_key()], "host1".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "two".into());
+    }
+
     #[test]
     fn config_tcp_with_receive_buffer_size() {
         let config: SyslogConfig = toml::from_str(
@@ -0,0 +1,290 @@
+//! Decompression of request bodies according to their `Content-Encoding`.
+//!
+//! Every decoding step is bounded by a maximum decompressed size, so that a
+//! small compressed request can't exhaust the memory of the source.
+
+use bytes::{Buf, Bytes};
+use flate2::read::{DeflateDecoder, MultiGzDecoder};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, Read},
+    str::FromStr,
+};
+
+/// 100MiB, well above the default body size of any of the HTTP based sources.
+const fn default_max_bytes() -> usize {
+    100 * 1024 * 1024
+}
+
+/// The `decompression` option of HTTP based sources.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DecompressionConfig {
+    /// The maximum size of a body after each decoding step.
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: usize,
+    /// Overrides `max_bytes` for individual encodings.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub max_bytes_per_encoding: BTreeMap<ContentEncoding, usize>,
+}
+
+impl Default for DecompressionConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: default_max_bytes(),
+            max_bytes_per_encoding: BTreeMap::new(),
+        }
+    }
+}
+
+impl DecompressionConfig {
+    fn max_bytes(&self, encoding: ContentEncoding) -> usize {
+        self.max_bytes_per_encoding
+            .get(&encoding)
+            .copied()
+            .unwrap_or(self.max_bytes)
+    }
+}
+
+/// A `Content-Encoding` which a source can decompress.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentEncoding {
+    Gzip,
+    Deflate,
+    Zstd,
+    /// The raw snappy block format, as used by Prometheus remote write.
+    Snappy,
+    /// The snappy framing format, as sent by Vector's sinks.
+    #[serde(rename = "x-snappy-framed")]
+    SnappyFramed,
+    Lz4,
+}
+
+impl ContentEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+            Self::Zstd => "zstd",
+            Self::Snappy => "snappy",
+            Self::SnappyFramed => "x-snappy-framed",
+            Self::Lz4 => "lz4",
+        }
+    }
+
+    fn decompress(self, body: Bytes, max_bytes: usize) -> Result<Bytes, DecompressionError> {
+        let reader: Box<dyn Read> = match self {
+            Self::Gzip => Box::new(MultiGzDecoder::new(body.reader())),
+            Self::Deflate => Box::new(DeflateDecoder::new(body.reader())),
+            Self::Zstd => Box::new(
+                zstd::stream::read::Decoder::new(body.reader())
+                    .context(Failed { encoding: self })?,
+            ),
+            Self::Snappy => {
+                // The block format can't be streamed, but it records the
+                // decompressed length up front.
+                let len = snap::raw::decompress_len(&body)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+                    .context(Failed { encoding: self })?;
+                if len > max_bytes {
+                    return Err(DecompressionError::TooLarge {
+                        encoding: self,
+                        max_bytes,
+                    });
+                }
+                return snap::raw::Decoder::new()
+                    .decompress_vec(&body)
+                    .map(Into::into)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+                    .context(Failed { encoding: self });
+            }
+            Self::SnappyFramed => Box::new(snap::read::FrameDecoder::new(body.reader())),
+            Self::Lz4 => Box::new(lz4_flex::frame::FrameDecoder::new(body.reader())),
+        };
+
+        let mut decoded = Vec::new();
+        reader
+            .take(max_bytes as u64 + 1)
+            .read_to_end(&mut decoded)
+            .context(Failed { encoding: self })?;
+        if decoded.len() > max_bytes {
+            return Err(DecompressionError::TooLarge {
+                encoding: self,
+                max_bytes,
+            });
+        }
+        Ok(decoded.into())
+    }
+}
+
+impl FromStr for ContentEncoding {
+    type Err = DecompressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gzip" | "x-gzip" => Ok(Self::Gzip),
+            "deflate" => Ok(Self::Deflate),
+            "zstd" => Ok(Self::Zstd),
+            "snappy" => Ok(Self::Snappy),
+            "x-snappy-framed" => Ok(Self::SnappyFramed),
+            "lz4" => Ok(Self::Lz4),
+            encoding => Err(DecompressionError::UnsupportedEncoding {
+                encoding: encoding.to_owned(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for ContentEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum DecompressionError {
+    #[snafu(display("Unsupported encoding {}", encoding))]
+    UnsupportedEncoding { encoding: String },
+    #[snafu(display("Failed decompressing payload with {} decoder: {}", encoding, source))]
+    Failed {
+        encoding: ContentEncoding,
+        source: io::Error,
+    },
+    #[snafu(display(
+        "Payload decompressed with {} decoder exceeds {} bytes",
+        encoding,
+        max_bytes
+    ))]
+    TooLarge {
+        encoding: ContentEncoding,
+        max_bytes: usize,
+    },
+}
+
+/// Decodes a body according to a `Content-Encoding` header, in which the
+/// encodings are listed in the order they were applied.
+pub fn decompress(
+    header: Option<&str>,
+    mut body: Bytes,
+    config: &DecompressionConfig,
+) -> Result<Bytes, DecompressionError> {
+    if let Some(encodings) = header {
+        for encoding in encodings.rsplit(',').map(str::trim) {
+            if encoding == "identity" || encoding.is_empty() {
+                continue;
+            }
+            let encoding = encoding.parse::<ContentEncoding>()?;
+            body = encoding.decompress(body, config.max_bytes(encoding))?;
+        }
+    }
+
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    const PAYLOAD: &[u8] = b"a payload which compresses well, a payload which compresses well";
+
+    fn gzip(input: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(input).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn decompresses_every_encoding() {
+        let config = DecompressionConfig::default();
+        let cases: Vec<(&str, Vec<u8>)> = vec![
+            ("gzip", gzip(PAYLOAD)),
+            ("zstd", zstd::encode_all(PAYLOAD, 3).unwrap()),
+            (
+                "snappy",
+                snap::raw::Encoder::new().compress_vec(PAYLOAD).unwrap(),
+            ),
+            ("x-snappy-framed", {
+                let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+                encoder.write_all(PAYLOAD).unwrap();
+                encoder.into_inner().unwrap()
+            }),
+            ("lz4", {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+                encoder.write_all(PAYLOAD).unwrap();
+                encoder.finish().unwrap()
+            }),
+            ("identity", PAYLOAD.to_vec()),
+        ];
+        for (encoding, body) in cases {
+            let decoded = decompress(Some(encoding), body.into(), &config).unwrap();
+            assert_eq!(&decoded[..], PAYLOAD, "{}", encoding);
+        }
+    }
+
+    #[test]
+    fn decompresses_in_reverse_order() {
+        let body = zstd::encode_all(&gzip(PAYLOAD)[..], 3).unwrap();
+        let decoded = decompress(
+            Some("gzip, zstd"),
+            body.into(),
+            &DecompressionConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(&decoded[..], PAYLOAD);
+    }
+
+    #[test]
+    fn rejects_unsupported_encoding() {
+        let error =
+            decompress(Some("br"), PAYLOAD.into(), &DecompressionConfig::default()).unwrap_err();
+        assert!(matches!(
+            error,
+            DecompressionError::UnsupportedEncoding { .. }
+        ));
+    }
+
+    #[test]
+    fn limits_decompressed_size_per_encoding() {
+        let bomb = vec![0; 1024 * 1024];
+        let config: DecompressionConfig = toml::from_str(
+            r#"
+            max_bytes = 2048
+            max_bytes_per_encoding.gzip = 2097152
+            "#,
+        )
+        .unwrap();
+
+        let decoded = decompress(Some("gzip"), gzip(&bomb).into(), &config).unwrap();
+        assert_eq!(decoded.len(), bomb.len());
+
+        for (encoding, body) in vec![
+            ("zstd", zstd::encode_all(&bomb[..], 3).unwrap()),
+            (
+                "snappy",
+                snap::raw::Encoder::new().compress_vec(&bomb).unwrap(),
+            ),
+        ] {
+            let error = decompress(Some(encoding), body.into(), &config).unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    DecompressionError::TooLarge {
+                        max_bytes: 2048,
+                        ..
+                    }
+                ),
+                "{}",
+                encoding
+            );
+        }
+    }
+}
@@ -1,19 +1,16 @@
 use crate::{
     config::SourceContext,
     internal_events::{HttpBadRequest, HttpDecompressError, HttpEventsReceived},
+    sources::util::{decompress, DecompressionConfig, DecompressionError},
     tls::{MaybeTlsSettings, TlsConfig},
     Pipeline,
 };
 use async_trait::async_trait;
-use bytes::{Buf, Bytes};
-use flate2::read::{DeflateDecoder, MultiGzDecoder};
+use bytes::Bytes;
 use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt};
 use headers::{Authorization, HeaderMapExt};
 use serde::{Deserialize, Serialize};
-use snap::raw::Decoder as SnappyDecoder;
-use std::{
-    collections::HashMap, convert::TryFrom, error::Error, fmt, io::Read, net::SocketAddr, sync::Arc,
-};
+use std::{collections::HashMap, convert::TryFrom, error::Error, fmt, net::SocketAddr, sync::Arc};
 use tracing_futures::Instrument;
 use vector_core::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event};
 use warp::{
@@ -128,51 +125,31 @@ impl HttpSourceAuth {
     }
 }
 
-pub fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMessage> {
-    if let Some(encodings) = header {
-        for encoding in encodings.rsplit(',').map(str::trim) {
-            body = match encoding {
-                "identity" => body,
-                "gzip" => {
-                    let mut decoded = Vec::new();
-                    MultiGzDecoder::new(body.reader())
-                        .read_to_end(&mut decoded)
-                        .map_err(|error| handle_decode_error(encoding, error))?;
-                    decoded.into()
-                }
-                "deflate" => {
-                    let mut decoded = Vec::new();
-                    DeflateDecoder::new(body.reader())
-                        .read_to_end(&mut decoded)
-                        .map_err(|error| handle_decode_error(encoding, error))?;
-                    decoded.into()
-                }
-                "snappy" => SnappyDecoder::new()
-                    .decompress_vec(&body)
-                    .map_err(|error| handle_decode_error(encoding, error))?
-                    .into(),
-                encoding => {
-                    return Err(ErrorMessage::new(
-                        StatusCode::UNSUPPORTED_MEDIA_TYPE,
-                        format!("Unsupported encoding {}", encoding),
-                    ))
-                }
+pub fn decode(
+    header: &Option<String>,
+    body: Bytes,
+    config: &DecompressionConfig,
+) -> Result<Bytes, ErrorMessage> {
+    decompress(header.as_deref(), body, config).map_err(|error| {
+        let code = match error {
+            DecompressionError::UnsupportedEncoding { .. } => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            DecompressionError::Failed { encoding, .. } => {
+                emit!(HttpDecompressError {
+                    encoding: encoding.as_str(),
+                    error: &error
+                });
+                StatusCode::UNPROCESSABLE_ENTITY
             }
-        }
-    }
-
-    Ok(body)
-}
-
-fn handle_decode_error(encoding: &str, error: impl std::error::Error) -> ErrorMessage {
-    emit!(HttpDecompressError {
-        encoding,
-        error: &error
-    });
-    ErrorMessage::new(
-        StatusCode::UNPROCESSABLE_ENTITY,
-        format!("Failed decompressing payload with {} decoder.", encoding),
-    )
+            DecompressionError::TooLarge { encoding, .. } => {
+                emit!(HttpDecompressError {
+                    encoding: encoding.as_str(),
+                    error: &error
+                });
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+        };
+        ErrorMessage::new(code, error.to_string())
+    })
 }
 
 #[async_trait]
@@ -192,10 +169,12 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         strict_path: bool,
         tls: &Option<TlsConfig>,
         auth: &Option<HttpSourceAuthConfig>,
+        decompression: &DecompressionConfig,
         cx: SourceContext,
     ) -> crate::Result<crate::sources::Source> {
         let tls = MaybeTlsSettings::from_config(tls, true)?;
         let auth = HttpSourceAuth::try_from(auth.as_ref())?;
+        let decompression = decompression.clone();
         let path = path.to_owned();
         let out = cx.out;
         let shutdown = cx.shutdown;
@@ -238,7 +217,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
 
                         let events = auth
                             .is_valid(&auth_header)
-                            .and_then(|()| decode(&encoding_header, body))
+                            .and_then(|()| decode(&encoding_header, body, &decompression))
                             .and_then(|body| {
                                 let body_len = body.len();
                                 self.build_events(body, headers, query_parameters, path.as_str())
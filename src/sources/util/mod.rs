@@ -1,5 +1,11 @@
 #[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
 mod body_decoding;
+#[cfg(any(
+    feature = "sources-utils-http",
+    feature = "sources-aws_kinesis_firehose",
+    feature = "sources-splunk_hec"
+))]
+mod decompression;
 mod encoding_config;
 #[cfg(feature = "sources-utils-http")]
 mod http;
@@ -13,6 +19,12 @@ mod unix_stream;
 
 #[cfg(any(feature = "sources-http", feature = "sources-datadog"))]
 pub(crate) use self::body_decoding::{decode_body, Encoding};
+#[cfg(any(
+    feature = "sources-utils-http",
+    feature = "sources-aws_kinesis_firehose",
+    feature = "sources-splunk_hec"
+))]
+pub(crate) use self::decompression::{decompress, DecompressionConfig, DecompressionError};
 #[cfg(any(feature = "sources-http", feature = "sources-heroku_logs"))]
 pub(crate) use self::http::add_query_parameters;
 #[cfg(feature = "sources-prometheus")]
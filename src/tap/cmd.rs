@@ -1,7 +1,11 @@
 use crate::config;
 use tokio_stream::StreamExt;
 use url::Url;
-use vector_api_client::{connect_subscription_client, gql::TapSubscriptionExt, Client};
+use vector_api_client::{
+    connect_subscription_client,
+    gql::{TapFilter, TapSubscriptionExt},
+    Client,
+};
 
 /// CLI command func for issuing 'tap' queries, and communicating with a local/remote
 /// Vector API server via HTTP/WebSockets.
@@ -36,9 +40,21 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
         }
     };
 
+    // Tap the output of all components, unless only inputs were asked for.
+    let components = if opts.components.is_empty() && opts.inputs_of.is_empty() {
+        vec!["*".to_string()]
+    } else {
+        opts.components.clone()
+    };
+
     // Issue the 'tap' request, printing to stdout.
     let res = subscription_client.output_events_subscription(
-        opts.components.clone(),
+        components,
+        opts.inputs_of.clone(),
+        TapFilter {
+            condition: opts.filter.clone(),
+            rate_limit: opts.rate_limit.map(i64::from),
+        },
         opts.format,
         opts.limit as i64,
         opts.interval as i64,
@@ -48,14 +64,18 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
         let stream = res.stream();
     };
 
-    // Loop over the returned results, printing out log events.
+    // Loop over the returned results, printing out log and metric events.
     // NOTE: This will currently ignore notifications. A later `--verbose` option is planned
     // to include these.
     // TODO: https://github.com/timberio/vector/issues/6870
     while let Some(Some(res)) = stream.next().await {
         if let Some(d) = res.data {
-            for log_event in d.output_events.iter().filter_map(|ev| ev.as_log()) {
-                println!("{}", log_event.string);
+            for event in d.output_events.iter() {
+                if let Some(log_event) = event.as_log() {
+                    println!("{}", log_event.string);
+                } else if let Some(metric_event) = event.as_metric() {
+                    println!("{}", metric_event.string);
+                }
             }
         }
     }
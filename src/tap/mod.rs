@@ -9,7 +9,7 @@ pub use cmd::cmd;
 #[derive(StructOpt, Debug, Clone)]
 #[structopt(rename_all = "kebab-case")]
 pub struct Opts {
-    /// Interval to sample metrics at, in milliseconds
+    /// Interval to sample events at, in milliseconds
     #[structopt(default_value = "500", short = "i", long)]
     interval: u32,
 
@@ -17,15 +17,28 @@ pub struct Opts {
     #[structopt(short, long)]
     url: Option<Url>,
 
-    /// Sample log events to the provided limit
+    /// Sample events to the provided limit
     #[structopt(default_value = "100", short = "l", long)]
     limit: u32,
 
-    /// Encoding format for logs printed to screen
+    /// Encoding format for events printed to screen
     #[structopt(default_value = "json", possible_values = &["json", "yaml"], short = "f", long)]
     format: TapEncodingFormat,
 
-    /// Components to observe (comma-separated; accepts glob patterns)
-    #[structopt(default_value = "*", use_delimiter(true))]
+    /// VRL condition evaluated by the server; only events it returns `true` for are sent
+    #[structopt(long)]
+    filter: Option<String>,
+
+    /// Maximum number of events sent by the server each second from each component
+    #[structopt(long)]
+    rate_limit: Option<u32>,
+
+    /// Components whose input is observed (comma-separated; accepts glob patterns)
+    #[structopt(long, use_delimiter(true))]
+    inputs_of: Vec<String>,
+
+    /// Components whose output is observed (comma-separated; accepts glob patterns). Defaults
+    /// to all components unless `--inputs-of` is given
+    #[structopt(use_delimiter(true))]
     components: Vec<String>,
 }
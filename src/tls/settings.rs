@@ -10,6 +10,7 @@ use openssl::{
     stack::Stack,
     x509::{store::X509StoreBuilder, X509},
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use std::path::{Path, PathBuf};
@@ -24,7 +25,7 @@ pub const TEST_PEM_CRT_PATH: &str = "tests/data/localhost.crt";
 #[cfg(test)]
 pub const TEST_PEM_KEY_PATH: &str = "tests/data/localhost.key";
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
 pub struct TlsConfig {
     pub enabled: Option<bool>,
     #[serde(flatten)]
@@ -49,7 +50,7 @@ impl TlsConfig {
 }
 
 /// Standard TLS options
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
 pub struct TlsOptions {
     pub verify_certificate: Option<bool>,
     pub verify_hostname: Option<bool>,
@@ -62,8 +62,12 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
     // Subscribe to updated metrics
     metrics::subscribe(subscription_client, tx.clone(), opts.interval as i64);
 
+    // Query the history of the component in the detail pane, once one is opened
+    let (detail_tx, detail_rx) = tokio::sync::watch::channel(None);
+    metrics::detail(Client::new(url.clone()), tx, detail_rx);
+
     // Initialize the dashboard
-    match init_dashboard(url.as_str(), opts, sender).await {
+    match init_dashboard(url.as_str(), opts, sender, detail_tx).await {
         Ok(_) => exitcode::OK,
         _ => {
             eprintln!("Your terminal doesn't support building a dashboard. Exiting.");
@@ -1,6 +1,10 @@
-use super::{events::capture_key_press, state};
+use super::{
+    events::capture_key_press,
+    state,
+    view::{Action, Column, View},
+};
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture, KeyCode},
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     tty::IsTty,
@@ -9,12 +13,13 @@ use crossterm::{
 use num_format::{Locale, ToFormattedString};
 use number_prefix::NumberPrefix;
 use std::io::stdout;
+use tokio::sync::watch;
 use tui::{
     backend::{Backend, CrosstermBackend},
-    layout::{Alignment, Constraint, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Sparkline, Table, TableState, Wrap},
     Frame, Terminal,
 };
 
@@ -82,16 +87,6 @@ impl HumanFormatter for i64 {
     }
 }
 
-static HEADER: [&str; 7] = [
-    "Name",
-    "Kind",
-    "Type",
-    "Events In",
-    "Events Out",
-    "Bytes",
-    "Errors",
-];
-
 struct Widgets<'a> {
     constraints: Vec<Constraint>,
     url_string: &'a str,
@@ -136,16 +131,41 @@ impl<'a> Widgets<'a> {
     }
 
     /// Renders a components table, showing sources, transforms and sinks in tabular form, with
-    /// statistics pulled from `ComponentsState`,
-    fn components_table<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, area: Rect) {
-        // Header columns
-        let header = HEADER
+    /// statistics pulled from `ComponentsState`, in the order and with the filter of `view`.
+    fn components_table<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        state: &state::State,
+        view: &View,
+        area: Rect,
+    ) {
+        // Header columns, marking the one rows are sorted by
+        let header = Column::ALL
             .iter()
-            .map(|s| Cell::from(*s).style(Style::default().add_modifier(Modifier::BOLD)))
+            .map(|c| {
+                let title = match (*c == view.sort, view.descending) {
+                    (true, true) => format!("{} ▼", c.title()),
+                    (true, false) => format!("{} ▲", c.title()),
+                    (false, _) => c.title().to_owned(),
+                };
+                Cell::from(title).style(Style::default().add_modifier(Modifier::BOLD))
+            })
             .collect::<Vec<_>>();
 
+        let rows = view.rows(state);
+        let mut table_state = TableState::default();
+        table_state.select(view.selected_index(&rows));
+
+        let mut title = format!("Components ({} of {})", rows.len(), state.len());
+        if !view.filter.is_empty() {
+            title.push_str(&format!(" | Filter: {}", view.filter));
+        }
+        if view.errors_only {
+            title.push_str(" | Errors only");
+        }
+
         // Data columns
-        let items = state.iter().map(|(_, r)| {
+        let items = rows.into_iter().map(|r| {
             let mut data = vec![r.name.clone(), r.kind.clone(), r.component_type.clone()];
 
             let formatted_metrics = [
@@ -193,12 +213,17 @@ impl<'a> Widgets<'a> {
             ];
 
             data.extend_from_slice(&formatted_metrics);
-            Row::new(data).style(Style::default())
+            let style = match r.errors {
+                0 => Style::default(),
+                _ => Style::default().fg(Color::Red),
+            };
+            Row::new(data).style(style)
         });
 
         let w = Table::new(items)
             .header(Row::new(header).bottom_margin(1))
-            .block(Block::default().borders(Borders::ALL).title("Components"))
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
             .column_spacing(2)
             .widths(&[
                 Constraint::Percentage(19),
@@ -210,9 +235,123 @@ impl<'a> Widgets<'a> {
                 Constraint::Percentage(8),
             ]);
 
+        f.render_stateful_widget(w, area, &mut table_state);
+    }
+
+    /// Renders the recent throughput of a component, keeping to the latest samples that fit.
+    fn sparkline<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        title: &str,
+        data: &[u64],
+        color: Color,
+        area: Rect,
+    ) {
+        let latest = data.last().copied().unwrap_or(0) as i64;
+        let fits = area.width.saturating_sub(2) as usize;
+        let data = &data[data.len().saturating_sub(fits)..];
+
+        let w = Sparkline::default()
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "{} ({}/s)",
+                title,
+                latest.human_format()
+            )))
+            .data(data)
+            .style(Style::default().fg(color));
+
         f.render_widget(w, area);
     }
 
+    /// Renders the detail pane of a component: its throughput history, the fill level of its
+    /// buffer, its concurrency, and its errors by type.
+    fn component_detail<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        row: &state::ComponentRow,
+        area: Rect,
+    ) {
+        let detail = row.detail.clone().unwrap_or_default();
+        let block = Block::default().borders(Borders::ALL).title(format!(
+            "{} ({} {})",
+            row.name, row.component_type, row.kind
+        ));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(inner);
+        let charts = Layout::default()
+            .constraints(vec![Constraint::Ratio(1, 3); 3])
+            .split(columns[0]);
+
+        self.sparkline(
+            f,
+            "Events In",
+            &detail.events_in_throughputs,
+            Color::Green,
+            charts[0],
+        );
+        self.sparkline(
+            f,
+            "Events Out",
+            &detail.events_out_throughputs,
+            Color::Cyan,
+            charts[1],
+        );
+        self.sparkline(f, "Errors", &detail.errors, Color::Red, charts[2]);
+
+        let figures = Layout::default()
+            .constraints(vec![Constraint::Length(3), Constraint::Min(0)])
+            .split(columns[1]);
+
+        let gauge = Block::default().borders(Borders::ALL).title("Buffer");
+        let gauge = match detail.buffer {
+            Some((events, max_events)) if max_events > 0 => Gauge::default()
+                .block(gauge)
+                .gauge_style(Style::default().fg(Color::Yellow))
+                .ratio((events as f64 / max_events as f64).max(0.0).min(1.0))
+                .label(format!(
+                    "{} / {} events",
+                    events.thousands_format(),
+                    max_events.thousands_format()
+                )),
+            _ => Gauge::default().block(gauge).ratio(0.0).label("N/A"),
+        };
+        f.render_widget(gauge, figures[0]);
+
+        let float =
+            |value: Option<f64>| value.map_or_else(|| "N/A".to_owned(), |v| format!("{:.1}", v));
+        let mut text = vec![
+            Spans::from(format!(
+                "In-flight requests: {}",
+                float(detail.in_flight_requests)
+            )),
+            Spans::from(format!(
+                "Concurrency limit: {}",
+                float(detail.concurrency_limit)
+            )),
+            Spans::from(""),
+        ];
+        if detail.errors_by_type.is_empty() {
+            text.push(Spans::from("No errors"));
+        } else {
+            text.push(Spans::from(Span::styled(
+                "Errors",
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            text.extend(detail.errors_by_type.iter().map(|(name, count)| {
+                Spans::from(format!("{}: {}", name, count.thousands_format()))
+            }));
+        }
+        let w = Paragraph::new(text)
+            .block(Block::default().borders(Borders::ALL).title("Concurrency"))
+            .wrap(Wrap { trim: true });
+        f.render_widget(w, figures[1]);
+    }
+
     /// Alerts the user to resize the window to view columns
     fn components_resize_window<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
         let block = Block::default().borders(Borders::ALL).title("Components");
@@ -223,9 +362,21 @@ impl<'a> Widgets<'a> {
         f.render_widget(w, area);
     }
 
-    /// Renders a box showing instructions on how to exit from `vector top`.
-    fn quit_box<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
-        let text = vec![Spans::from("To quit, press ESC or 'q'")];
+    /// Renders a box showing the keys to use `vector top`, or the filter being typed in.
+    fn help_box<B: Backend>(&self, f: &mut Frame<B>, view: &View, area: Rect) {
+        let text = if view.editing_filter {
+            vec![Spans::from(vec![
+                Span::styled(
+                    format!("Filter: {}_", view.filter),
+                    Style::default().fg(Color::White),
+                ),
+                Span::from(" | name:, kind: or type: narrow a term | Apply: Enter | Clear: ESC"),
+            ])]
+        } else {
+            vec![Spans::from(
+                "Quit: q or ESC | Select: ↑/↓ | Detail: Enter | Sort: ←/→ or 1-7, reverse: r | Filter: / | Errors only: e",
+            )]
+        };
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -239,7 +390,7 @@ impl<'a> Widgets<'a> {
     }
 
     /// Draw a single frame. Creates a layout and renders widgets into it.
-    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: state::State) {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, view: &View) {
         let size = f.size();
         let rects = Layout::default()
             .constraints(self.constraints.as_ref())
@@ -249,12 +400,21 @@ impl<'a> Widgets<'a> {
 
         // Require a minimum of 80 chars of line width to display the table
         if size.width >= 80 {
-            self.components_table(f, &state, rects[1]);
+            match view.selected_row(state).filter(|_| view.detail) {
+                Some(row) => {
+                    let panes = Layout::default()
+                        .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .split(rects[1]);
+                    self.components_table(f, state, view, panes[0]);
+                    self.component_detail(f, row, panes[1]);
+                }
+                None => self.components_table(f, state, view, rects[1]),
+            }
         } else {
             self.components_resize_window(f, rects[1]);
         }
 
-        self.quit_box(f, rects[2]);
+        self.help_box(f, view, rects[2]);
     }
 }
 
@@ -271,6 +431,7 @@ pub async fn init_dashboard<'a>(
     url: &'a str,
     opts: &'a super::Opts,
     mut state_rx: state::StateRx,
+    detail_tx: watch::Sender<Option<String>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Capture key presses, to determine when to quit
     let (mut key_press_rx, key_press_kill_tx) = capture_key_press();
@@ -292,19 +453,34 @@ pub async fn init_dashboard<'a>(
     terminal.clear()?;
 
     let widgets = Widgets::new(url, opts);
+    let mut state = state::State::new();
+    let mut view = View::default();
+    let mut detail = None;
 
     loop {
         tokio::select! {
-            Some(state) = state_rx.recv() => {
-                terminal.draw(|f| widgets.draw(f, state))?;
+            Some(new_state) = state_rx.recv() => {
+                state = new_state;
             },
             k = key_press_rx.recv() => {
-                if let KeyCode::Esc | KeyCode::Char('q') = k.unwrap() {
+                if view.handle_key(k.unwrap(), &state) == Action::Quit {
                     let _ = key_press_kill_tx.send(());
                     break
                 }
+
+                // Only the component in the detail pane needs its history.
+                let new_detail = view
+                    .selected_row(&state)
+                    .filter(|_| view.detail)
+                    .map(|r| r.name.clone());
+                if new_detail != detail {
+                    detail = new_detail;
+                    let _ = detail_tx.send(detail.clone());
+                }
             }
         }
+
+        terminal.draw(|f| widgets.draw(f, &state, &view))?;
     }
 
     // Clean-up terminal
@@ -1,8 +1,12 @@
 use super::state;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::{sync::watch, time};
 use tokio_stream::StreamExt;
 use vector_api_client::{
-    gql::{ComponentsQueryExt, ComponentsSubscriptionExt, MetricsSubscriptionExt},
+    gql::{
+        component_metric_history_query, ComponentsQueryExt, ComponentsSubscriptionExt,
+        MetricsQueryExt, MetricsSubscriptionExt,
+    },
     Client, SubscriptionClient,
 };
 
@@ -29,6 +33,7 @@ async fn component_added(client: Arc<SubscriptionClient>, tx: state::EventTx) {
                     processed_bytes_total: 0,
                     processed_bytes_throughput_sec: 0,
                     errors: 0,
+                    detail: None,
                 }))
                 .await;
         }
@@ -186,6 +191,27 @@ async fn processed_bytes_throughputs(
     }
 }
 
+async fn errors_totals(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
+    let res = client.component_errors_totals_subscription(interval);
+
+    tokio::pin! {
+        let stream = res.stream();
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            // Components report one metric for each kind of error they count.
+            let mut totals = BTreeMap::new();
+            for c in d.component_errors_totals {
+                *totals.entry(c.name).or_insert(0) += c.metric.errors_total as i64;
+            }
+            let _ = tx
+                .send(state::EventType::ErrorsTotals(totals.into_iter().collect()))
+                .await;
+        }
+    }
+}
+
 /// Subscribe to each metrics channel through a separate client. This is a temporary workaround
 /// until client multiplexing is fixed. In future, we should be able to use a single client
 pub fn subscribe(client: SubscriptionClient, tx: state::EventTx, interval: i64) {
@@ -212,9 +238,82 @@ pub fn subscribe(client: SubscriptionClient, tx: state::EventTx, interval: i64)
     ));
     tokio::spawn(processed_bytes_throughputs(
         Arc::clone(&client),
-        tx,
+        tx.clone(),
         interval,
     ));
+    tokio::spawn(errors_totals(Arc::clone(&client), tx, interval));
+}
+
+fn component_detail(
+    history: component_metric_history_query::ComponentMetricHistoryQueryComponentMetricHistory,
+) -> state::ComponentDetail {
+    let samples = history.samples;
+    let last = samples.last();
+
+    state::ComponentDetail {
+        events_in_throughputs: samples
+            .iter()
+            .map(|s| s.events_in_throughput.max(0) as u64)
+            .collect(),
+        events_out_throughputs: samples
+            .iter()
+            .map(|s| s.events_out_throughput.max(0) as u64)
+            .collect(),
+        errors: samples.iter().map(|s| s.errors.max(0) as u64).collect(),
+        buffer: last.and_then(|s| s.buffer_events.zip(s.buffer_max_events)),
+        in_flight_requests: last.and_then(|s| s.in_flight_requests),
+        concurrency_limit: last.and_then(|s| s.concurrency_limit),
+        errors_by_type: history
+            .errors_by_type
+            .into_iter()
+            .map(|e| {
+                let name = match e.error_type {
+                    Some(error_type) => format!("{} ({})", e.metric, error_type),
+                    None => e.metric,
+                };
+                (name, e.count as i64)
+            })
+            .collect(),
+    }
+}
+
+/// Periodically query the history of the component selected for the detail pane. Only the
+/// selected component is queried, as the history of every component would be sizeable.
+pub fn detail(client: Client, tx: state::EventTx, mut selected: watch::Receiver<Option<String>>) {
+    tokio::spawn(async move {
+        let mut interval = time::interval(time::Duration::from_secs(1));
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {},
+                changed = selected.changed() => {
+                    // The dashboard is gone.
+                    if changed.is_err() {
+                        return;
+                    }
+                },
+            }
+
+            let name = match selected.borrow().clone() {
+                Some(name) => name,
+                None => continue,
+            };
+
+            let history = client
+                .component_metric_history_query(Some(vec![name.clone()]))
+                .await
+                .ok()
+                .and_then(|res| res.data)
+                .and_then(|d| d.component_metric_history.into_iter().next());
+            if let Some(history) = history {
+                let _ = tx
+                    .send(state::EventType::ComponentDetail(
+                        name,
+                        component_detail(history),
+                    ))
+                    .await;
+            }
+        }
+    });
 }
 
 /// Retrieve the initial components/metrics for first paint. Further updating the metrics
@@ -247,8 +346,8 @@ pub async fn init_components(client: &Client) -> Result<state::State, ()> {
                         events_out_throughput_sec: 0,
                         processed_bytes_total: d.on.processed_bytes_total(),
                         processed_bytes_throughput_sec: 0,
-
                         errors: 0,
+                        detail: None,
                     },
                 ))
             })
@@ -3,6 +3,7 @@ mod dashboard;
 mod events;
 mod metrics;
 mod state;
+mod view;
 
 use structopt::StructOpt;
 use url::Url;
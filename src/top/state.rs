@@ -14,6 +14,9 @@ pub enum EventType {
     ProcessedBytesTotals(Vec<NamedMetric>),
     /// Interval + named metric
     ProcessedBytesThroughputs(i64, Vec<NamedMetric>),
+    ErrorsTotals(Vec<NamedMetric>),
+    /// Recent history of the component shown in the detail pane
+    ComponentDetail(String, ComponentDetail),
     ComponentAdded(ComponentRow),
     ComponentRemoved(String),
 }
@@ -35,6 +38,22 @@ pub struct ComponentRow {
     pub events_out_total: i64,
     pub events_out_throughput_sec: i64,
     pub errors: i64,
+    pub detail: Option<ComponentDetail>,
+}
+
+/// Recent per-second samples of a component, oldest first, along with its latest buffer and
+/// concurrency figures.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComponentDetail {
+    pub events_in_throughputs: Vec<u64>,
+    pub events_out_throughputs: Vec<u64>,
+    pub errors: Vec<u64>,
+    /// Buffered events, and the capacity of the buffer
+    pub buffer: Option<(i64, i64)>,
+    pub in_flight_requests: Option<f64>,
+    pub concurrency_limit: Option<f64>,
+    /// Errors since the component started, by metric and error type
+    pub errors_by_type: Vec<(String, i64)>,
 }
 
 /// Takes the receiver `EventRx` channel, and returns a `StateTx` state transmitter. This
@@ -95,6 +114,18 @@ pub async fn updater(mut state: State, mut event_rx: EventRx) -> StateRx {
                             }
                         }
                     }
+                    EventType::ErrorsTotals(rows) => {
+                        for (name, v) in rows {
+                            if let Some(r) = state.get_mut(&name) {
+                                r.errors = v;
+                            }
+                        }
+                    }
+                    EventType::ComponentDetail(name, detail) => {
+                        if let Some(r) = state.get_mut(&name) {
+                            r.detail = Some(detail);
+                        }
+                    }
                     EventType::ComponentAdded(c) => {
                         let _ = state.insert(c.name.clone(), c);
                     }
@@ -0,0 +1,353 @@
+use super::state::{ComponentRow, State};
+use crossterm::event::KeyCode;
+use std::cmp::Ordering;
+
+/// Columns of the components table, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Name,
+    Kind,
+    Type,
+    EventsIn,
+    EventsOut,
+    Bytes,
+    Errors,
+}
+
+impl Column {
+    pub const ALL: [Column; 7] = [
+        Column::Name,
+        Column::Kind,
+        Column::Type,
+        Column::EventsIn,
+        Column::EventsOut,
+        Column::Bytes,
+        Column::Errors,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Column::Name => "Name",
+            Column::Kind => "Kind",
+            Column::Type => "Type",
+            Column::EventsIn => "Events In",
+            Column::EventsOut => "Events Out",
+            Column::Bytes => "Bytes",
+            Column::Errors => "Errors",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|c| *c == self).unwrap()
+    }
+
+    /// Text columns sort ascending at first, metrics sort descending so the busiest
+    /// components come first.
+    fn descending_by_default(self) -> bool {
+        !matches!(self, Column::Name | Column::Kind | Column::Type)
+    }
+
+    fn compare(self, a: &ComponentRow, b: &ComponentRow) -> Ordering {
+        match self {
+            Column::Name => a.name.cmp(&b.name),
+            Column::Kind => a.kind.cmp(&b.kind),
+            Column::Type => a.component_type.cmp(&b.component_type),
+            Column::EventsIn => a.events_in_total.cmp(&b.events_in_total),
+            Column::EventsOut => a.events_out_total.cmp(&b.events_out_total),
+            Column::Bytes => a.processed_bytes_total.cmp(&b.processed_bytes_total),
+            Column::Errors => a.errors.cmp(&b.errors),
+        }
+    }
+}
+
+/// How the dashboard presents the components: their order, which of them are shown, and
+/// which one is selected. Kept apart from `State` as it only changes with key presses.
+#[derive(Debug, Clone)]
+pub struct View {
+    pub sort: Column,
+    pub descending: bool,
+    /// Whitespace separated terms that all have to match. A term matches the name, kind or
+    /// type of a component, unless it's restricted to one of them by a `name:`, `kind:` or
+    /// `type:` prefix.
+    pub filter: String,
+    /// Set while the filter is typed in.
+    pub editing_filter: bool,
+    pub errors_only: bool,
+    /// Name of the selected component, so the selection sticks as rows move around.
+    pub selected: Option<String>,
+    /// Set while the detail pane of the selected component is open.
+    pub detail: bool,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        Self {
+            sort: Column::Name,
+            descending: false,
+            filter: String::new(),
+            editing_filter: false,
+            errors_only: false,
+            selected: None,
+            detail: false,
+        }
+    }
+}
+
+/// Whether the dashboard keeps running after a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Continue,
+    Quit,
+}
+
+fn term_matches(term: &str, row: &ComponentRow) -> bool {
+    let contains = |field: &str| field.to_lowercase().contains(term);
+
+    if let Some(name) = term.strip_prefix("name:") {
+        row.name.to_lowercase().contains(name)
+    } else if let Some(kind) = term.strip_prefix("kind:") {
+        row.kind.to_lowercase().contains(kind)
+    } else if let Some(component_type) = term.strip_prefix("type:") {
+        row.component_type.to_lowercase().contains(component_type)
+    } else {
+        contains(&row.name) || contains(&row.kind) || contains(&row.component_type)
+    }
+}
+
+impl View {
+    fn matches(&self, row: &ComponentRow) -> bool {
+        if self.errors_only && row.errors == 0 {
+            return false;
+        }
+
+        self.filter
+            .to_lowercase()
+            .split_whitespace()
+            .all(|term| term_matches(term, row))
+    }
+
+    /// The components to show, in order.
+    pub fn rows<'a>(&self, state: &'a State) -> Vec<&'a ComponentRow> {
+        let mut rows = state
+            .values()
+            .filter(|row| self.matches(row))
+            .collect::<Vec<_>>();
+
+        rows.sort_by(|a, b| {
+            let ordering = self.sort.compare(a, b);
+            let ordering = if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            ordering.then_with(|| a.name.cmp(&b.name))
+        });
+
+        rows
+    }
+
+    /// Position of the selected component among `rows`, if it's shown.
+    pub fn selected_index(&self, rows: &[&ComponentRow]) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+        rows.iter().position(|row| &row.name == selected)
+    }
+
+    /// The selected component, if it's shown.
+    pub fn selected_row<'a>(&self, state: &'a State) -> Option<&'a ComponentRow> {
+        let rows = self.rows(state);
+        self.selected_index(&rows).map(|index| rows[index])
+    }
+
+    fn sort_by(&mut self, column: Column) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            self.descending = column.descending_by_default();
+        }
+    }
+
+    fn shift_sort(&mut self, offset: isize) {
+        let count = Column::ALL.len() as isize;
+        let index = (self.sort.index() as isize + offset).rem_euclid(count);
+        self.sort_by(Column::ALL[index as usize]);
+    }
+
+    fn move_selection(&mut self, state: &State, offset: isize) {
+        let rows = self.rows(state);
+        if rows.is_empty() {
+            return;
+        }
+
+        let index = match self.selected_index(&rows) {
+            Some(index) => (index as isize + offset)
+                .max(0)
+                .min(rows.len() as isize - 1),
+            None if offset < 0 => rows.len() as isize - 1,
+            None => 0,
+        };
+        self.selected = Some(rows[index as usize].name.clone());
+    }
+
+    /// Updates the view for a key press. `state` is what's currently shown.
+    pub fn handle_key(&mut self, key: KeyCode, state: &State) -> Action {
+        if self.editing_filter {
+            match key {
+                KeyCode::Char(c) => self.filter.push(c),
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Enter => self.editing_filter = false,
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.editing_filter = false;
+                }
+                _ => (),
+            }
+            return Action::Continue;
+        }
+
+        match key {
+            KeyCode::Char('q') => return Action::Quit,
+            KeyCode::Esc if self.detail => self.detail = false,
+            KeyCode::Esc if !self.filter.is_empty() => self.filter.clear(),
+            KeyCode::Esc => return Action::Quit,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(state, -1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(state, 1),
+            KeyCode::PageUp => self.move_selection(state, -10),
+            KeyCode::PageDown => self.move_selection(state, 10),
+            KeyCode::Left | KeyCode::Char('<') => self.shift_sort(-1),
+            KeyCode::Right | KeyCode::Char('>') => self.shift_sort(1),
+            KeyCode::Char('r') => self.descending = !self.descending,
+            KeyCode::Char(c @ '1'..='7') => {
+                self.sort_by(Column::ALL[c as usize - '1' as usize]);
+            }
+            KeyCode::Char('/') => self.editing_filter = true,
+            KeyCode::Char('e') => self.errors_only = !self.errors_only,
+            KeyCode::Enter => {
+                if self.selected_row(state).is_some() {
+                    self.detail = !self.detail;
+                } else {
+                    self.move_selection(state, 0);
+                    self.detail = self.selected.is_some();
+                }
+            }
+            _ => (),
+        }
+
+        Action::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(
+        name: &str,
+        kind: &str,
+        component_type: &str,
+        events_in: i64,
+        errors: i64,
+    ) -> ComponentRow {
+        ComponentRow {
+            name: name.to_owned(),
+            kind: kind.to_owned(),
+            component_type: component_type.to_owned(),
+            processed_bytes_total: 0,
+            processed_bytes_throughput_sec: 0,
+            events_in_total: events_in,
+            events_in_throughput_sec: 0,
+            events_out_total: 0,
+            events_out_throughput_sec: 0,
+            errors,
+            detail: None,
+        }
+    }
+
+    fn state() -> State {
+        vec![
+            row("in", "source", "kafka", 30, 0),
+            row("parse", "transform", "remap", 20, 5),
+            row("out", "sink", "elasticsearch", 10, 0),
+            row("archive", "sink", "aws_s3", 10, 2),
+        ]
+        .into_iter()
+        .map(|row| (row.name.clone(), row))
+        .collect()
+    }
+
+    fn names(view: &View, state: &State) -> Vec<String> {
+        view.rows(state)
+            .into_iter()
+            .map(|row| row.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn sorts_by_columns() {
+        let state = state();
+        let mut view = View::default();
+        assert_eq!(names(&view, &state), vec!["archive", "in", "out", "parse"]);
+
+        view.handle_key(KeyCode::Char('4'), &state);
+        assert_eq!(names(&view, &state), vec!["in", "parse", "archive", "out"]);
+
+        view.handle_key(KeyCode::Char('4'), &state);
+        assert_eq!(names(&view, &state), vec!["archive", "out", "parse", "in"]);
+
+        view.handle_key(KeyCode::Left, &state);
+        assert_eq!(view.sort, Column::Type);
+        assert_eq!(names(&view, &state), vec!["archive", "out", "in", "parse"]);
+    }
+
+    #[test]
+    fn filters_by_name_kind_and_type() {
+        let state = state();
+        let mut view = View::default();
+        for key in vec!['/', 'k', 'i', 'n', 'd', ':', 's', 'i', 'n', 'k'] {
+            view.handle_key(KeyCode::Char(key), &state);
+        }
+        view.handle_key(KeyCode::Enter, &state);
+        assert_eq!(names(&view, &state), vec!["archive", "out"]);
+
+        view.filter = "sink S3".to_owned();
+        assert_eq!(names(&view, &state), vec!["archive"]);
+
+        view.filter = "name:in".to_owned();
+        assert_eq!(names(&view, &state), vec!["in"]);
+
+        assert_eq!(view.handle_key(KeyCode::Esc, &state), Action::Continue);
+        assert!(view.filter.is_empty());
+        assert_eq!(view.handle_key(KeyCode::Esc, &state), Action::Quit);
+    }
+
+    #[test]
+    fn shows_only_errors() {
+        let state = state();
+        let mut view = View::default();
+        view.handle_key(KeyCode::Char('e'), &state);
+        view.handle_key(KeyCode::Char('7'), &state);
+        assert_eq!(names(&view, &state), vec!["parse", "archive"]);
+    }
+
+    #[test]
+    fn selects_and_opens_detail() {
+        let state = state();
+        let mut view = View::default();
+        view.handle_key(KeyCode::Down, &state);
+        view.handle_key(KeyCode::Down, &state);
+        assert_eq!(view.selected.as_deref(), Some("in"));
+
+        // The selection follows the component when the order changes.
+        view.handle_key(KeyCode::Char('r'), &state);
+        assert_eq!(view.selected.as_deref(), Some("in"));
+        view.handle_key(KeyCode::Down, &state);
+        assert_eq!(view.selected.as_deref(), Some("archive"));
+
+        view.handle_key(KeyCode::Enter, &state);
+        assert!(view.detail);
+        assert_eq!(view.handle_key(KeyCode::Esc, &state), Action::Continue);
+        assert!(!view.detail);
+    }
+}
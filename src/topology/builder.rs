@@ -5,11 +5,12 @@ use super::{
 };
 use crate::{
     buffers,
-    config::{DataType, SinkContext, SourceContext},
+    config::{output_name, DataType, SinkContext, SourceContext},
+    dead_letter,
     event::Event,
     internal_events::{EventIn, EventOut, EventZeroIn},
     shutdown::SourceShutdownCoordinator,
-    transforms::Transform,
+    transforms::{NamedOutputs, Transform},
     Pipeline,
 };
 use futures::{future, stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
@@ -20,18 +21,28 @@ use std::{
     sync::{Arc, Mutex},
 };
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
-use tokio::time::{timeout, Duration};
+use tokio::{
+    sync::watch,
+    time::{timeout, Duration},
+};
 
 pub struct Pieces {
     pub inputs: HashMap<String, (buffers::BufferInputCloner<Event>, Vec<String>)>,
     pub outputs: HashMap<String, fanout::ControlChannel>,
+    /// Named outputs, with their full names, by component.
+    pub named_outputs: HashMap<String, Vec<(String, fanout::ControlChannel)>>,
     pub tasks: HashMap<String, Task>,
     pub source_tasks: HashMap<String, Task>,
-    pub healthchecks: HashMap<String, Task>,
+    pub healthchecks: HashMap<String, Healthcheck>,
     pub shutdown_coordinator: SourceShutdownCoordinator,
     pub detach_triggers: HashMap<String, Trigger>,
+    /// Send the new configurations of sinks reconfigured in place.
+    pub reconfigure: HashMap<String, watch::Sender<serde_json::Value>>,
 }
 
+/// Resolves to why the healthcheck of a sink failed, if it did.
+pub type Healthcheck = future::BoxFuture<'static, Result<(), String>>;
+
 /// Builds only the new pieces, and doesn't check their topology.
 pub async fn build_pieces(
     config: &super::Config,
@@ -40,11 +51,13 @@ pub async fn build_pieces(
 ) -> Result<Pieces, Vec<String>> {
     let mut inputs = HashMap::new();
     let mut outputs = HashMap::new();
+    let mut named_outputs = HashMap::new();
     let mut tasks = HashMap::new();
     let mut source_tasks = HashMap::new();
     let mut healthchecks = HashMap::new();
     let mut shutdown_coordinator = SourceShutdownCoordinator::default();
     let mut detach_triggers = HashMap::new();
+    let mut reconfigure = HashMap::new();
 
     let mut errors = vec![];
 
@@ -109,6 +122,7 @@ pub async fn build_pieces(
         .filter(|(name, _)| diff.transforms.contains_new(&name))
     {
         let trans_inputs = &transform.inputs;
+        let transform_outputs = transform.inner.named_outputs();
 
         let typetag = transform.inner.transform_type();
 
@@ -124,14 +138,62 @@ pub async fn build_pieces(
         let (input_tx, input_rx, _) =
             vector_core::buffers::build(vector_core::buffers::Variant::Memory {
                 max_events: 100,
+                max_bytes: None,
                 when_full: vector_core::buffers::WhenFull::Block,
             })
             .unwrap();
         let input_rx = crate::utilization::wrap(Pin::new(input_rx));
 
         let (output, control) = Fanout::new();
+        let mut named = HashMap::new();
+        let mut controls = Vec::new();
+        for transform_output in transform_outputs {
+            let (fanout, control) = Fanout::new();
+            controls.push((output_name(name, &transform_output.name), control));
+            named.insert(transform_output.name, fanout);
+        }
+        named_outputs.insert(name.clone(), controls);
 
         let transform = match transform {
+            Transform::Function(mut t) if !named.is_empty() => {
+                let mut input_rx = Box::pin(
+                    input_rx
+                        .filter(move |event| ready(filter_event_type(event, input_type)))
+                        .inspect(|_| emit!(EventIn)),
+                );
+                let mut output = output;
+                let name = name.clone();
+                async move {
+                    while let Some(event) = input_rx.next().await {
+                        let mut buf = Vec::with_capacity(1);
+                        let mut named_buf = NamedOutputs::default();
+                        t.transform_with_outputs(&mut buf, &mut named_buf, event);
+                        let mut count = buf.len();
+                        output.send_all(&mut stream::iter(buf).map(Ok)).await?;
+                        for (named_output, mut events) in named_buf.drain() {
+                            // Dropped events aren't counted as sent on.
+                            if named_output == dead_letter::DROPPED {
+                                for event in &mut events {
+                                    dead_letter::set_component(event, "transform", &name, typetag);
+                                }
+                            } else {
+                                count += events.len();
+                            }
+                            // Outputs the transform didn't declare have no consumers.
+                            if let Some(fanout) = named.get_mut(&named_output) {
+                                fanout.send_all(&mut stream::iter(events).map(Ok)).await?;
+                            }
+                        }
+                        emit!(EventOut { count });
+                    }
+                    output.close().await?;
+                    for fanout in named.values_mut() {
+                        fanout.close().await?;
+                    }
+                    Ok(())
+                }
+                .boxed()
+            }
             Transform::Function(mut t) => input_rx
                 .filter(move |event| ready(filter_event_type(event, input_type)))
                 .inspect(|_| emit!(EventIn))
@@ -180,6 +242,12 @@ pub async fn build_pieces(
         let typetag = sink.inner.sink_type();
         let input_type = sink.inner.input_type();
 
+        let rejected_output = sink.dead_letter_output(name).map(|dead_letter_output| {
+            let (rejected_output, control) = Fanout::new();
+            named_outputs.insert(name.clone(), vec![(dead_letter_output, control)]);
+            rejected_output
+        });
+
         let (tx, rx, acker) = if let Some(buffer) = buffers.remove(name) {
             buffer
         } else {
@@ -193,10 +261,13 @@ pub async fn build_pieces(
             }
         };
 
+        let (reconfigure_tx, reconfigure_rx) =
+            watch::channel(serde_json::to_value(&sink.inner).unwrap());
         let cx = SinkContext {
             acker: acker.clone(),
             healthcheck,
             globals: config.global.clone(),
+            reconfigure: reconfigure_rx,
         };
 
         let (sink, healthcheck) = match sink.inner.build(cx).await {
@@ -209,6 +280,7 @@ pub async fn build_pieces(
 
         let (trigger, tripwire) = Tripwire::new();
 
+        let component_name = name.clone();
         let sink = async move {
             // Why is this Arc<Mutex<Option<_>>> needed you ask.
             // In case when this function build_pieces errors
@@ -224,14 +296,21 @@ pub async fn build_pieces(
 
             let mut rx = Box::pin(crate::utilization::wrap(rx));
 
-            sink.run(
-                rx.by_ref()
-                    .filter(|event| ready(filter_event_type(event, input_type)))
-                    .inspect(|_| emit!(EventIn))
-                    .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
+            let input = rx
+                .by_ref()
+                .filter(|event| ready(filter_event_type(event, input_type)))
+                .inspect(|_| emit!(EventIn))
+                .take_until_if(tripwire);
+            let result = match rejected_output {
+                Some(rejected_output) => {
+                    let (input, rejected) =
+                        dead_letter::track_rejected(input, component_name, typetag);
+                    let rejected = rejected.map(Ok).forward(rejected_output);
+                    future::join(sink.run(input), rejected).await.0
+                }
+                None => sink.run(input).await,
+            };
+            result.map(|_| {
                 debug!("Finished.");
                 TaskOutput::Sink(rx, acker)
             })
@@ -246,7 +325,7 @@ pub async fn build_pieces(
                     .map(|result| match result {
                         Ok(Ok(_)) => {
                             info!("Healthcheck: Passed.");
-                            Ok(TaskOutput::Healthcheck)
+                            Ok(())
                         }
                         Ok(Err(error)) => {
                             error!(
@@ -256,7 +335,7 @@ pub async fn build_pieces(
                                 component_type = typetag,
                                 ?component_name,
                             );
-                            Err(())
+                            Err(error.to_string())
                         }
                         Err(_) => {
                             error!(
@@ -265,32 +344,35 @@ pub async fn build_pieces(
                                 component_type = typetag,
                                 ?component_name,
                             );
-                            Err(())
+                            Err(format!("timed out after {} seconds", duration.as_secs()))
                         }
                     })
                     .await
             } else {
                 info!("Healthcheck: Disabled.");
-                Ok(TaskOutput::Healthcheck)
+                Ok(())
             }
-        };
-        let healthcheck_task = Task::new(name, typetag, healthcheck_task);
+        }
+        .boxed();
 
         inputs.insert(name.clone(), (tx, sink_inputs.clone()));
         healthchecks.insert(name.clone(), healthcheck_task);
         tasks.insert(name.clone(), task);
         detach_triggers.insert(name.clone(), trigger);
+        reconfigure.insert(name.clone(), reconfigure_tx);
     }
 
     if errors.is_empty() {
         let pieces = Pieces {
             inputs,
             outputs,
+            named_outputs,
             tasks,
             source_tasks,
             healthchecks,
             shutdown_coordinator,
             detach_triggers,
+            reconfigure,
         };
 
         Ok(pieces)
@@ -16,12 +16,12 @@ use crate::{
     event::Event,
     shutdown::SourceShutdownCoordinator,
     topology::{
-        builder::Pieces,
+        builder::{Healthcheck, Pieces},
         task::{Task, TaskOutput},
     },
     trigger::DisabledTrigger,
 };
-use futures::{future, Future, FutureExt, SinkExt};
+use futures::{future, Future, FutureExt, SinkExt, TryFutureExt};
 use std::{
     collections::{HashMap, HashSet},
     panic::AssertUnwindSafe,
@@ -44,20 +44,29 @@ type BuiltBuffer = (
 
 type Outputs = HashMap<String, fanout::ControlChannel>;
 
-// Watcher types for topology changes. These are currently specific to receiving
-// `Outputs`. This could be expanded in the future to send an enum of types if, for example,
-// this included a new 'Inputs' type.
-type WatchTx = watch::Sender<Outputs>;
-pub type WatchRx = watch::Receiver<Outputs>;
+/// The parts of the running topology that taps connect to.
+#[derive(Debug, Clone, Default)]
+pub struct TapResource {
+    /// Outputs of every component, which tap sinks are added to
+    pub outputs: Outputs,
+    /// Names of the inputs of every transform and sink, to tap what they receive
+    pub inputs: HashMap<String, Vec<String>>,
+}
+
+// Watcher types for topology changes.
+type WatchTx = watch::Sender<TapResource>;
+pub type WatchRx = watch::Receiver<TapResource>;
 
 #[allow(dead_code)]
 pub struct RunningTopology {
     inputs: HashMap<String, buffers::BufferInputCloner<Event>>,
+    input_names: HashMap<String, Vec<String>>,
     outputs: HashMap<String, fanout::ControlChannel>,
     source_tasks: HashMap<String, TaskHandle>,
     tasks: HashMap<String, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
     detach_triggers: HashMap<String, DisabledTrigger>,
+    reconfigure: HashMap<String, watch::Sender<serde_json::Value>>,
     config: Config,
     abort_tx: mpsc::UnboundedSender<()>,
     watch: (WatchTx, WatchRx),
@@ -72,19 +81,22 @@ pub async fn start_validated(
 
     let mut running_topology = RunningTopology {
         inputs: HashMap::new(),
+        input_names: HashMap::new(),
         outputs: HashMap::new(),
         config,
         shutdown_coordinator: SourceShutdownCoordinator::default(),
         detach_triggers: HashMap::new(),
+        reconfigure: HashMap::new(),
         source_tasks: HashMap::new(),
         tasks: HashMap::new(),
         abort_tx,
-        watch: watch::channel(HashMap::new()),
+        watch: watch::channel(TapResource::default()),
     };
 
-    if !running_topology
+    if running_topology
         .run_healthchecks(&diff, &mut pieces, running_topology.config.healthchecks)
         .await
+        .is_err()
     {
         return None;
     }
@@ -110,7 +122,7 @@ pub async fn build_or_log_errors(
     }
 }
 
-pub fn take_healthchecks(diff: &ConfigDiff, pieces: &mut Pieces) -> Vec<(String, Task)> {
+pub fn take_healthchecks(diff: &ConfigDiff, pieces: &mut Pieces) -> Vec<(String, Healthcheck)> {
     (&diff.sinks.to_change | &diff.sinks.to_add)
         .into_iter()
         .filter_map(|name| {
@@ -217,12 +229,25 @@ impl RunningTopology {
     /// On Error, topology is in invalid state.
     /// May change componenets even if reload fails.
     pub async fn reload_config_and_respawn(&mut self, new_config: Config) -> Result<bool, ()> {
+        self.reload_config_with_errors(new_config)
+            .await
+            .map(|errors| errors.is_empty())
+    }
+
+    /// Like `reload_config_and_respawn`, but returns why the new config was
+    /// rejected, in which case the old one is kept or rebuilt.
+    pub async fn reload_config_with_errors(
+        &mut self,
+        new_config: Config,
+    ) -> Result<Vec<String>, ()> {
         if self.config.global != new_config.global {
             error!(
                 message =
                 "Global options can't be changed while reloading config file; reload aborted. Please restart vector to reload the configuration file."
             );
-            return Ok(false);
+            return Ok(vec![
+                "Global options can't be changed while reloading.".to_owned()
+            ]);
         }
 
         let diff = ConfigDiff::new(&self.config, &new_config);
@@ -239,19 +264,30 @@ impl RunningTopology {
         }
 
         // Now let's actually build the new pieces.
-        if let Some(mut new_pieces) = build_or_log_errors(&new_config, &diff, buffers.clone()).await
-        {
-            if self
-                .run_healthchecks(&diff, &mut new_pieces, new_config.healthchecks)
-                .await
-            {
-                self.connect_diff(&diff, &mut new_pieces).await;
-                self.spawn_diff(&diff, new_pieces);
-                self.config = new_config;
-                // We have successfully changed to new config.
-                return Ok(true);
+        let errors = match builder::build_pieces(&new_config, &diff, buffers.clone()).await {
+            Ok(mut new_pieces) => {
+                match self
+                    .run_healthchecks(&diff, &mut new_pieces, new_config.healthchecks)
+                    .await
+                {
+                    Ok(()) => {
+                        self.connect_diff(&diff, &mut new_pieces).await;
+                        self.spawn_diff(&diff, new_pieces);
+                        self.reconfigure_diff(&diff, &new_config);
+                        self.config = new_config;
+                        // We have successfully changed to new config.
+                        return Ok(Vec::new());
+                    }
+                    Err(errors) => errors,
+                }
             }
-        }
+            Err(errors) => {
+                for error in &errors {
+                    error!(message = "Configuration error.", %error);
+                }
+                errors
+            }
+        };
 
         // We need to rebuild the removed.
         info!("Rebuilding old configuration.");
@@ -260,11 +296,12 @@ impl RunningTopology {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, self.config.healthchecks)
                 .await
+                .is_ok()
             {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
                 // We have successfully returned to old config.
-                return Ok(false);
+                return Ok(errors);
             }
         }
 
@@ -274,35 +311,79 @@ impl RunningTopology {
         Err(())
     }
 
+    /// Builds the components that reloading `new_config` would start, like
+    /// `vector validate` does, leaving the running topology untouched. Their
+    /// healthchecks aren't run, since the config may not have its secrets.
+    pub async fn check_config(&self, new_config: &Config) -> Result<ConfigDiff, Vec<String>> {
+        if self.config.global != new_config.global {
+            return Err(vec![
+                "Global options can't be changed while reloading.".to_owned()
+            ]);
+        }
+
+        let diff = ConfigDiff::new(&self.config, new_config);
+
+        // Sinks get in-memory buffers instead of their own, which either belong
+        // to the running sinks or would be created on disk.
+        let buffers = diff
+            .sinks
+            .changed_and_added()
+            .map(|name| {
+                let (tx, rx, acker) = buffers::BufferConfig::default()
+                    .build(&None, name)
+                    .expect("Memory buffers can always be built.");
+                (
+                    name.clone(),
+                    (tx, Arc::new(Mutex::new(Some(rx.into()))), acker),
+                )
+            })
+            .collect();
+
+        builder::build_pieces(new_config, &diff, buffers).await?;
+
+        Ok(diff)
+    }
+
+    /// Fails with the error of every unhealthy sink, if they are required to
+    /// be healthy.
     async fn run_healthchecks(
         &mut self,
         diff: &ConfigDiff,
         pieces: &mut Pieces,
         options: HealthcheckOptions,
-    ) -> bool {
+    ) -> Result<(), Vec<String>> {
         if options.enabled {
-            let healthchecks = take_healthchecks(diff, pieces)
-                .into_iter()
-                .map(|(_, task)| task);
-            let healthchecks = future::try_join_all(healthchecks);
+            let healthchecks =
+                take_healthchecks(diff, pieces)
+                    .into_iter()
+                    .map(|(name, healthcheck)| {
+                        healthcheck.map_err(move |error| {
+                            format!("Sink \"{}\": Healthcheck failed: {}", name, error)
+                        })
+                    });
+            let healthchecks = future::join_all(healthchecks);
 
             info!("Running healthchecks.");
             if options.require_healthy {
-                let success = healthchecks.await;
+                let errors = healthchecks
+                    .await
+                    .into_iter()
+                    .filter_map(Result::err)
+                    .collect::<Vec<_>>();
 
-                if success.is_ok() {
+                if errors.is_empty() {
                     info!("All healthchecks passed.");
-                    true
+                    Ok(())
                 } else {
                     error!("Sinks unhealthy.");
-                    false
+                    Err(errors)
                 }
             } else {
                 tokio::spawn(healthchecks);
-                true
+                Ok(())
             }
         } else {
-            true
+            Ok(())
         }
     }
 
@@ -429,6 +510,8 @@ impl RunningTopology {
         for name in &diff.sinks.to_remove {
             info!(message = "Removing sink.", name = ?name);
             self.remove_inputs(&name).await;
+            self.remove_outputs(&name);
+            self.reconfigure.remove(name);
         }
 
         // Detach changed sinks
@@ -492,6 +575,12 @@ impl RunningTopology {
         // it as an input
         for name in diff.transforms.changed_and_added() {
             self.setup_outputs(&name, new_pieces).await;
+            self.setup_named_outputs(&name, new_pieces).await;
+        }
+
+        // Sinks only have dead-letter outputs, which may be inputs of transforms.
+        for name in diff.sinks.changed_and_added() {
+            self.setup_named_outputs(&name, new_pieces).await;
         }
 
         for name in &diff.transforms.to_change {
@@ -515,7 +604,10 @@ impl RunningTopology {
         if !self.watch.0.is_closed() {
             self.watch
                 .0
-                .send(self.outputs.clone())
+                .send(TapResource {
+                    outputs: self.outputs.clone(),
+                    inputs: self.input_names.clone(),
+                })
                 .expect("Couldn't broadcast config changes.");
         }
     }
@@ -556,7 +648,21 @@ impl RunningTopology {
         }
     }
 
+    /// Sends their new configuration to the sinks reconfigured in place.
+    fn reconfigure_diff(&mut self, diff: &ConfigDiff, new_config: &Config) {
+        for name in &diff.sinks.to_reconfigure {
+            info!(message = "Reconfiguring sink.", name = ?name);
+            let config = serde_json::to_value(&new_config.sinks[name].inner).unwrap();
+            if self.reconfigure[name].send(config).is_err() {
+                warn!(message = "Sink is not running, so it can't be reconfigured.", name = ?name);
+            }
+        }
+    }
+
     fn spawn_sink(&mut self, name: &str, new_pieces: &mut builder::Pieces) {
+        if let Some(reconfigure) = new_pieces.reconfigure.remove(name) {
+            self.reconfigure.insert(name.to_string(), reconfigure);
+        }
         let task = new_pieces.tasks.remove(name).unwrap();
         let span = error_span!(
             "sink",
@@ -611,10 +717,18 @@ impl RunningTopology {
 
     fn remove_outputs(&mut self, name: &str) {
         self.outputs.remove(name);
+        self.remove_named_outputs(name);
+    }
+
+    fn remove_named_outputs(&mut self, name: &str) {
+        for output in self.config.named_outputs(name) {
+            self.outputs.remove(&output);
+        }
     }
 
     async fn remove_inputs(&mut self, name: &str) {
         self.inputs.remove(name);
+        self.input_names.remove(name);
         self.detach_triggers.remove(name);
 
         let sink_inputs = self.config.sinks.get(name).map(|s| &s.inputs);
@@ -635,8 +749,12 @@ impl RunningTopology {
     }
 
     async fn setup_outputs(&mut self, name: &str, new_pieces: &mut builder::Pieces) {
-        let mut output = new_pieces.outputs.remove(name).unwrap();
+        let output = new_pieces.outputs.remove(name).unwrap();
+        self.add_output(name, output).await;
+    }
 
+    /// Connects the output to its consumers and makes it available to new ones.
+    async fn add_output(&mut self, name: &str, mut output: fanout::ControlChannel) {
         for (sink_name, sink) in &self.config.sinks {
             if sink.inputs.iter().any(|i| i == name) {
                 // Sink may have been removed with the new config so it may not be present.
@@ -664,8 +782,18 @@ impl RunningTopology {
         self.outputs.insert(name.to_string(), output);
     }
 
+    /// Replaces the named outputs the component had before with the ones of
+    /// the new pieces.
+    async fn setup_named_outputs(&mut self, name: &str, new_pieces: &mut builder::Pieces) {
+        self.remove_named_outputs(name);
+        for (output_name, output) in new_pieces.named_outputs.remove(name).unwrap_or_default() {
+            self.add_output(&output_name, output).await;
+        }
+    }
+
     async fn setup_inputs(&mut self, name: &str, new_pieces: &mut builder::Pieces) {
         let (tx, inputs) = new_pieces.inputs.remove(name).unwrap();
+        self.input_names.insert(name.to_string(), inputs.clone());
 
         for input in inputs {
             // This can only fail if we are disconnected, which is a valid situation.
@@ -686,6 +814,7 @@ impl RunningTopology {
 
     async fn replace_inputs(&mut self, name: &str, new_pieces: &mut builder::Pieces) {
         let (tx, inputs) = new_pieces.inputs.remove(name).unwrap();
+        self.input_names.insert(name.to_string(), inputs.clone());
 
         let sink_inputs = self.config.sinks.get(name).map(|s| &s.inputs);
         let trans_inputs = self.config.transforms.get(name).map(|t| &t.inputs);
@@ -764,10 +893,9 @@ impl RunningTopology {
         &self.config
     }
 
-    /// Subscribe to topology changes. This will receive an `Outputs` currently, but may be
-    /// expanded in the future to accommodate `Inputs`. This is used by the 'tap' API to observe
-    /// config changes, and re-wire tap sinks.
-    pub fn watch(&self) -> watch::Receiver<Outputs> {
+    /// Subscribe to topology changes, receiving the outputs and inputs of components. This is
+    /// used by the 'tap' API to observe config changes, and re-wire tap sinks.
+    pub fn watch(&self) -> WatchRx {
         self.watch.1.clone()
     }
 }
@@ -803,7 +931,10 @@ fn retain<T>(vec: &mut Vec<T>, mut retain_filter: impl FnMut(&mut T) -> bool) {
 mod tests {
     use crate::{
         config::Config,
-        sinks::console::{ConsoleSinkConfig, Encoding, Target},
+        sinks::{
+            console::{ConsoleSinkConfig, Target},
+            util::encoding::StandardEncodings,
+        },
         sources::socket::SocketConfig,
         test_util::{next_addr, start_topology},
     };
@@ -818,7 +949,7 @@ mod tests {
             &[&"in"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
             },
         );
         old_config.global.data_dir = Some(Path::new("/asdf").to_path_buf());
@@ -853,8 +984,11 @@ mod tests {
 mod reload_tests {
     use crate::buffers::{BufferConfig, WhenFull};
     use crate::config::Config;
-    use crate::sinks::console::{ConsoleSinkConfig, Encoding, Target};
     use crate::sinks::prometheus::exporter::PrometheusExporterConfig;
+    use crate::sinks::{
+        console::{ConsoleSinkConfig, Target},
+        util::encoding::StandardEncodings,
+    };
     use crate::sources::generator::GeneratorConfig;
     use crate::sources::splunk_hec::SplunkConfig;
     use crate::test_util::{next_addr, start_topology, temp_dir, wait_for_tcp};
@@ -876,7 +1010,7 @@ mod reload_tests {
             &[&"in1"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
             },
         );
 
@@ -887,7 +1021,7 @@ mod reload_tests {
             &[&"in2"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
             },
         );
 
@@ -910,7 +1044,7 @@ mod reload_tests {
             &[&"in1"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
             },
         );
 
@@ -921,7 +1055,7 @@ mod reload_tests {
             &[&"in1"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
             },
         );
 
@@ -946,7 +1080,7 @@ mod reload_tests {
             &[&"in1"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
             },
         );
 
@@ -1152,7 +1286,10 @@ mod reload_tests {
 mod source_finished_tests {
     use crate::{
         config::Config,
-        sinks::console::{ConsoleSinkConfig, Encoding, Target},
+        sinks::{
+            console::{ConsoleSinkConfig, Target},
+            util::encoding::StandardEncodings,
+        },
         sources::generator::GeneratorConfig,
         test_util::start_topology,
     };
@@ -1168,7 +1305,7 @@ mod source_finished_tests {
             &[&"in"],
             ConsoleSinkConfig {
                 target: Target::Stdout,
-                encoding: Encoding::Text.into(),
+                encoding: StandardEncodings::Text.into(),
             },
         );
 
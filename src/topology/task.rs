@@ -13,7 +13,6 @@ pub enum TaskOutput {
     Transform,
     /// Buffer of sink
     Sink(Pin<EventStream>, Acker),
-    Healthcheck,
 }
 
 /// High level topology task.
@@ -1,6 +1,7 @@
 use crate::{
     config::{
-        log_schema, DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription,
+        log_schema, DataType, GenerateConfig, GlobalOptions, Output, TransformConfig,
+        TransformDescription,
     },
     event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
     event::{Event, Value},
@@ -9,7 +10,7 @@ use crate::{
         LogToMetricTemplateParseError, TemplateRenderingFailed,
     },
     template::{Template, TemplateParseError, TemplateRenderingError},
-    transforms::{FunctionTransform, Transform},
+    transforms::{FunctionTransform, NamedOutputs, Transform},
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,9 @@ use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::num::ParseFloatError;
 
+/// Name of the output of the log events no metric was extracted from.
+const UNMATCHED: &str = "unmatched";
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct LogToMetricConfig {
@@ -133,6 +137,10 @@ impl TransformConfig for LogToMetricConfig {
     fn transform_type(&self) -> &'static str {
         "log_to_metric"
     }
+
+    fn named_outputs(&self) -> Vec<Output> {
+        vec![Output::new(UNMATCHED, DataType::Log)]
+    }
 }
 
 impl LogToMetric {
@@ -367,8 +375,8 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
     }
 }
 
-impl FunctionTransform for LogToMetric {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+impl LogToMetric {
+    fn to_metrics(&self, output: &mut Vec<Event>, event: &Event) {
         for config in self.config.metrics.iter() {
             match to_metric(&config, &event) {
                 Ok(metric) => {
@@ -401,6 +409,25 @@ impl FunctionTransform for LogToMetric {
     }
 }
 
+impl FunctionTransform for LogToMetric {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        self.to_metrics(output, &event);
+    }
+
+    fn transform_with_outputs(
+        &mut self,
+        output: &mut Vec<Event>,
+        named: &mut NamedOutputs,
+        event: Event,
+    ) {
+        let count = output.len();
+        self.to_metrics(output, &event);
+        if output.len() == count {
+            named.push(UNMATCHED, event);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -853,4 +880,28 @@ mod tests {
             .with_timestamp(Some(ts()))
         );
     }
+
+    #[test]
+    fn sends_unmatched_events_to_output() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "counter"
+            field = "status"
+            "#,
+        );
+
+        let mut transform = LogToMetric::new(config);
+        let mut output = Vec::new();
+        let mut named = NamedOutputs::default();
+        transform.transform_with_outputs(&mut output, &mut named, create_event("status", "42"));
+        transform.transform_with_outputs(&mut output, &mut named, create_event("other", "42"));
+
+        assert_eq!(output.len(), 1);
+        let named = named.drain().collect::<Vec<_>>();
+        assert_eq!(named.len(), 1);
+        assert_eq!(named[0].0, UNMATCHED);
+        assert_eq!(named[0].1.len(), 1);
+        assert_eq!(named[0].1[0].as_log()["other"], "42".into());
+    }
 }
@@ -63,7 +63,7 @@ pub mod tokenizer;
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
-pub use vector_core::transform::{FunctionTransform, TaskTransform, Transform};
+pub use vector_core::transform::{FunctionTransform, NamedOutputs, TaskTransform, Transform};
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -12,8 +12,9 @@ use serde::{Deserialize, Serialize};
 use std::{
     collections::{hash_map, HashMap},
     pin::Pin,
-    time::{Duration, Instant},
+    time::Duration,
 };
+use tokio::time::Instant;
 
 mod merge_strategy;
 
@@ -1,8 +1,9 @@
 use crate::{
-    config::{DataType, GlobalOptions, TransformConfig, TransformDescription},
+    config::{DataType, GlobalOptions, Output, TransformConfig, TransformDescription},
+    dead_letter,
     event::{Event, VrlTarget},
     internal_events::{RemapMappingAbort, RemapMappingError},
-    transforms::{FunctionTransform, Transform},
+    transforms::{FunctionTransform, NamedOutputs, Transform},
     Result,
 };
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,7 @@ pub struct RemapConfig {
     pub drop_on_error: bool,
     #[serde(default = "crate::serde::default_true")]
     pub drop_on_abort: bool,
+    pub reroute_dropped: bool,
 }
 
 inventory::submit! {
@@ -43,6 +45,14 @@ impl TransformConfig for RemapConfig {
     fn transform_type(&self) -> &'static str {
         "remap"
     }
+
+    fn named_outputs(&self) -> Vec<Output> {
+        if self.reroute_dropped {
+            vec![Output::new(dead_letter::DROPPED, DataType::Any)]
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -50,6 +60,7 @@ pub struct Remap {
     program: Program,
     drop_on_error: bool,
     drop_on_abort: bool,
+    reroute_dropped: bool,
 }
 
 impl Remap {
@@ -64,12 +75,16 @@ impl Remap {
             program,
             drop_on_error: config.drop_on_error,
             drop_on_abort: config.drop_on_abort,
+            reroute_dropped: config.reroute_dropped,
         })
     }
-}
 
-impl FunctionTransform for Remap {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+    fn run(
+        &mut self,
+        output: &mut Vec<Event>,
+        mut dropped: Option<&mut NamedOutputs>,
+        event: Event,
+    ) {
         // If a program can fail or abort at runtime, we need to clone the
         // original event and keep it around, to allow us to discard any
         // mutations made to the event while the VRL program runs, before it
@@ -78,10 +93,13 @@ impl FunctionTransform for Remap {
         // The `drop_on_{error, abort}` transform config allows operators to
         // ignore events if their failed/aborted, in which case we can skip the
         // cloning, since any mutations made by VRL will be ignored regardless.
+        // Unless the dropped events are rerouted, which sends them on unchanged.
+        let keep_failed = !self.drop_on_error || dropped.is_some();
+        let keep_aborted = !self.drop_on_abort || dropped.is_some();
         #[allow(clippy::if_same_then_else)]
-        let original_event = if !self.drop_on_error && self.program.can_fail() {
+        let original_event = if keep_failed && self.program.can_fail() {
             Some(event.clone())
-        } else if !self.drop_on_abort && self.program.can_abort() {
+        } else if keep_aborted && self.program.can_abort() {
             Some(event.clone())
         } else {
             None
@@ -106,22 +124,49 @@ impl FunctionTransform for Remap {
 
                 if !self.drop_on_abort {
                     output.push(original_event.expect("event will be set"))
+                } else if let Some(dropped) = dropped.as_mut() {
+                    let mut event = original_event.expect("event will be set");
+                    dead_letter::set_reason(&mut event, "abort", "Event mapping aborted.");
+                    dropped.push(dead_letter::DROPPED, event);
                 }
             }
             Err(Terminate::Error(error)) => {
+                if !self.drop_on_error {
+                    output.push(original_event.expect("event will be set"))
+                } else if let Some(dropped) = dropped.as_mut() {
+                    let mut event = original_event.expect("event will be set");
+                    dead_letter::set_reason(&mut event, "error", &error);
+                    dropped.push(dead_letter::DROPPED, event);
+                }
+
                 emit!(RemapMappingError {
                     error,
                     event_dropped: self.drop_on_error,
                 });
-
-                if !self.drop_on_error {
-                    output.push(original_event.expect("event will be set"))
-                }
             }
         }
     }
 }
 
+impl FunctionTransform for Remap {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        self.run(output, None, event)
+    }
+
+    fn transform_with_outputs(
+        &mut self,
+        output: &mut Vec<Event>,
+        named: &mut NamedOutputs,
+        event: Event,
+    ) {
+        if self.reroute_dropped {
+            self.run(output, Some(named), event)
+        } else {
+            self.run(output, None, event)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,6 +207,7 @@ mod tests {
             .to_string(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -193,6 +239,7 @@ mod tests {
             .to_owned(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -221,6 +268,7 @@ mod tests {
             "#},
             drop_on_error: false,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -247,6 +295,7 @@ mod tests {
             "#},
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -268,6 +317,7 @@ mod tests {
             "#},
             drop_on_error: false,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -294,6 +344,7 @@ mod tests {
             "#},
             drop_on_error: false,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -320,12 +371,45 @@ mod tests {
             "#},
             drop_on_error: false,
             drop_on_abort: true,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
         assert!(transform_one(&mut tform, event).is_none())
     }
 
+    #[test]
+    fn check_remap_reroute_dropped() {
+        let conf = RemapConfig {
+            source: formatdoc! {r#"
+                .foo = "foo"
+                .not_an_int = int!(.bar)
+            "#},
+            drop_on_error: true,
+            drop_on_abort: true,
+            reroute_dropped: true,
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        let mut event = Event::from("augment me");
+        event.as_mut_log().insert("bar", "is a string");
+        let mut output = Vec::new();
+        let mut named = NamedOutputs::default();
+        tform.transform_with_outputs(&mut output, &mut named, event);
+
+        assert!(output.is_empty());
+        let mut named = named.drain().collect::<Vec<_>>();
+        assert_eq!(named.len(), 1);
+        let (name, dropped) = named.remove(0);
+        assert_eq!(name, dead_letter::DROPPED);
+        assert_eq!(dropped.len(), 1);
+        let log = dropped[0].as_log();
+        assert!(log.get("foo").is_none());
+        assert_eq!(get_field_string(&dropped[0], "bar"), "is a string");
+        assert_eq!(get_field_string(&dropped[0], "dead_letter.reason"), "error");
+        assert!(log.get("dead_letter.message").is_some());
+    }
+
     #[test]
     fn check_remap_metric() {
         let metric = Event::Metric(Metric::new(
@@ -343,6 +427,7 @@ mod tests {
                 .to_string(),
             drop_on_error: true,
             drop_on_abort: false,
+            reroute_dropped: false,
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -1,60 +1,52 @@
 use crate::{
     conditions::{AnyCondition, Condition},
-    config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
+    config::{
+        DataType, GenerateConfig, GlobalOptions, Output, TransformConfig, TransformDescription,
+    },
     event::Event,
     internal_events::RouteEventDiscarded,
-    transforms::{FunctionTransform, Transform},
+    transforms::{FunctionTransform, NamedOutputs, Transform},
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 //------------------------------------------------------------------------------
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct LaneConfig {
-    condition: AnyCondition,
-}
-
-#[async_trait::async_trait]
-#[typetag::serde(name = "lane")]
-impl TransformConfig for LaneConfig {
-    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
-        Ok(Transform::function(Lane::new(self.condition.build()?)))
-    }
-
-    fn input_type(&self) -> DataType {
-        DataType::Any
-    }
-
-    fn output_type(&self) -> DataType {
-        DataType::Any
-    }
-
-    fn transform_type(&self) -> &'static str {
-        "lane"
-    }
-}
-
 #[derive(Clone, Derivative)]
 #[derivative(Debug)]
-pub struct Lane {
+pub struct Route {
     #[derivative(Debug = "ignore")]
-    condition: Box<dyn Condition>,
+    routes: Vec<(String, Box<dyn Condition>)>,
 }
 
-impl Lane {
-    pub fn new(condition: Box<dyn Condition>) -> Self {
-        Self { condition }
+impl Route {
+    pub fn new(config: &RouteConfig) -> crate::Result<Self> {
+        let routes = config
+            .route
+            .iter()
+            .map(|(name, condition)| Ok((name.clone(), condition.build()?)))
+            .collect::<crate::Result<_>>()?;
+        Ok(Self { routes })
     }
 }
 
-impl FunctionTransform for Lane {
+impl FunctionTransform for Route {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
-        if self.condition.check(&event) {
-            output.push(event);
-        } else {
-            emit!(RouteEventDiscarded);
+        self.transform_with_outputs(output, &mut NamedOutputs::default(), event);
+    }
+
+    fn transform_with_outputs(
+        &mut self,
+        _output: &mut Vec<Event>,
+        named: &mut NamedOutputs,
+        event: Event,
+    ) {
+        for (name, condition) in &self.routes {
+            if condition.check(&event) {
+                named.push(name, event.clone());
+            } else {
+                emit!(RouteEventDiscarded);
+            }
         }
     }
 }
@@ -90,21 +82,10 @@ impl GenerateConfig for RouteConfig {
 #[typetag::serde(name = "route")]
 impl TransformConfig for RouteConfig {
     async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
-        Err("this transform must be expanded".into())
-    }
-
-    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
-        let mut map: IndexMap<String, Box<dyn TransformConfig>> = IndexMap::new();
-
-        while let Some((k, v)) = self.route.pop() {
-            map.insert(k.clone(), Box::new(LaneConfig { condition: v }));
-        }
-
-        if !map.is_empty() {
-            Ok(Some(map))
-        } else {
-            Err("must specify at least one lane".into())
+        if self.route.is_empty() {
+            return Err("must specify at least one route".into());
         }
+        Ok(Transform::function(Route::new(self)?))
     }
 
     fn input_type(&self) -> DataType {
@@ -118,6 +99,13 @@ impl TransformConfig for RouteConfig {
     fn transform_type(&self) -> &'static str {
         "route"
     }
+
+    fn named_outputs(&self) -> Vec<Output> {
+        self.route
+            .keys()
+            .map(|name| Output::new(name.clone(), DataType::Any))
+            .collect()
+    }
 }
 
 // Add a compatibility alias to avoid breaking existing configs
@@ -131,8 +119,8 @@ impl TransformConfig for RouteCompatConfig {
         self.0.build(globals).await
     }
 
-    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
-        self.0.expand()
+    fn named_outputs(&self) -> Vec<Output> {
+        self.0.named_outputs()
     }
 
     fn input_type(&self) -> DataType {
@@ -171,17 +159,36 @@ mod test {
     }
 
     #[test]
-    fn can_serialize_remap() {
-        // We need to serialize the config to check if a config has
-        // changed when reloading.
-        let config = LaneConfig {
-            condition: AnyCondition::String("foo".to_string()),
-        };
-
+    fn routes_events_to_outputs() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.first.type = "check_fields"
+            route.first."message.eq" = "foo"
+            route.second.type = "check_fields"
+            route.second."message.contains" = "o"
+        "#,
+        )
+        .unwrap();
         assert_eq!(
-            serde_json::to_string(&config).unwrap(),
-            r#"{"condition":"foo"}"#
+            config.named_outputs(),
+            vec![
+                Output::new("first", DataType::Any),
+                Output::new("second", DataType::Any)
+            ]
         );
+
+        let mut transform = Route::new(&config).unwrap();
+        let mut output = Vec::new();
+        let mut named = NamedOutputs::default();
+        transform.transform_with_outputs(&mut output, &mut named, Event::from("foo"));
+        transform.transform_with_outputs(&mut output, &mut named, Event::from("bob"));
+
+        assert!(output.is_empty());
+        let named = named
+            .drain()
+            .map(|(name, events)| (name, events.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(named, vec![("first".into(), 1), ("second".into(), 2)]);
     }
 
     #[test]
@@ -194,14 +201,11 @@ mod test {
             lanes.first."message.eq" = "foo"
         "#,
         )
-        .unwrap()
-        .expand()
-        .unwrap()
         .unwrap();
 
         assert_eq!(
             serde_json::to_string(&config).unwrap(),
-            r#"{"first":{"type":"lane","condition":{"type":"check_fields","message.eq":"foo"}}}"#
+            r#"{"route":{"first":{"type":"check_fields","message.eq":"foo"}}}"#
         );
     }
 }
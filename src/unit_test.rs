@@ -1,6 +1,13 @@
-use crate::config;
+use crate::config::{self, TestSnapshot};
 use colored::*;
-use std::path::PathBuf;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Serialize;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -18,6 +25,23 @@ pub struct Opts {
     #[structopt(name = "config-yaml", long, use_delimiter(true))]
     paths_yaml: Vec<PathBuf>,
 
+    /// Write a JUnit XML report of the test results to this file.
+    #[structopt(long)]
+    junit_report: Option<PathBuf>,
+
+    /// Write a JSON report of the test results to this file.
+    #[structopt(long)]
+    json_report: Option<PathBuf>,
+
+    /// Compare the outputs of each test with the snapshot recorded in this
+    /// directory. Snapshots missing from it are recorded instead.
+    #[structopt(long)]
+    snapshot_dir: Option<PathBuf>,
+
+    /// Record the snapshots of all tests again, instead of comparing with them.
+    #[structopt(long, requires = "snapshot-dir")]
+    update_snapshots: bool,
+
     /// Any number of Vector config files to test. If none are specified the
     /// default config path `/etc/vector/vector.toml` will be targeted.
     #[structopt(use_delimiter(true))]
@@ -35,9 +59,138 @@ impl Opts {
     }
 }
 
+/// The result of a single test, as written to reports.
+#[derive(Debug, Serialize)]
+struct TestReport {
+    name: String,
+    passed: bool,
+    duration_secs: f64,
+    errors: Vec<String>,
+    inspections: Vec<String>,
+}
+
+/// The JSON report of a test run.
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    passed: usize,
+    failed: usize,
+    tests: &'a [TestReport],
+}
+
+/// Renders the results as a single JUnit test suite.
+fn junit_report(reports: &[TestReport]) -> String {
+    let failures = reports.iter().filter(|report| !report.passed).count();
+    let time = reports
+        .iter()
+        .map(|report| report.duration_secs)
+        .sum::<f64>();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"vector\" tests=\"{}\" failures=\"{}\" time=\"{:.6}\">\n",
+        reports.len(),
+        failures,
+        time
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"vector\" tests=\"{}\" failures=\"{}\" time=\"{:.6}\">\n",
+        reports.len(),
+        failures,
+        time
+    ));
+    for report in reports {
+        xml.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"vector\" time=\"{:.6}\"",
+            escape_xml(&report.name),
+            report.duration_secs
+        ));
+        if report.passed && report.inspections.is_empty() {
+            xml.push_str("/>\n");
+            continue;
+        }
+        xml.push_str(">\n");
+        if !report.passed {
+            xml.push_str(&format!(
+                "      <failure message=\"{} check(s) failed\">{}</failure>\n",
+                report.errors.len(),
+                escape_xml(&report.errors.join("\n\n"))
+            ));
+        }
+        if !report.inspections.is_empty() {
+            xml.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape_xml(&report.inspections.join("\n\n"))
+            ));
+        }
+        xml.push_str("    </testcase>\n");
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Characters of test names that are escaped in the names of their snapshots.
+const SNAPSHOT_NAME_ESCAPED: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_');
+
+/// The snapshot file of a test, named after the test. The name is
+/// percent-encoded, so that tests with different names don't share a file.
+fn snapshot_path(dir: &Path, test_name: &str) -> PathBuf {
+    let name = utf8_percent_encode(test_name, SNAPSHOT_NAME_ESCAPED);
+    dir.join(format!("{}.json", name))
+}
+
+/// Compares `snapshot` with the one recorded for the test, recording it instead
+/// if there's none yet or `update` is set. Returns the errors of the test.
+fn check_snapshot(
+    dir: &Path,
+    test_name: &str,
+    snapshot: &TestSnapshot,
+    update: bool,
+) -> Vec<String> {
+    let path = snapshot_path(dir, test_name);
+    if !update && path.exists() {
+        return match fs::read(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|bytes| {
+                serde_json::from_slice::<TestSnapshot>(&bytes).map_err(|error| error.to_string())
+            }) {
+            Ok(recorded) => recorded.diff(snapshot),
+            Err(error) => vec![format!("failed to read snapshot {:?}: {}", path, error)],
+        };
+    }
+
+    let written = fs::create_dir_all(dir).and_then(|_| {
+        let json = serde_json::to_vec_pretty(snapshot).expect("Snapshots serialize to JSON.");
+        fs::write(&path, json)
+    });
+    match written {
+        Ok(()) => {
+            println!("recorded snapshot {:?}", path);
+            Vec::new()
+        }
+        Err(error) => vec![format!("failed to write snapshot {:?}: {}", path, error)],
+    }
+}
+
+fn write_report(path: &Path, contents: &[u8]) -> bool {
+    match fs::write(path, contents) {
+        Ok(()) => true,
+        Err(error) => {
+            error!(message = "Failed to write test report.", path = ?path, %error);
+            false
+        }
+    }
+}
+
 pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
-    let mut aggregated_test_inspections = Vec::new();
-    let mut aggregated_test_errors = Vec::new();
+    let mut reports = Vec::new();
 
     let paths = opts.paths_with_formats();
     let paths = match config::process_paths(&paths) {
@@ -48,17 +201,48 @@ pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
     println!("Running tests");
     match config::build_unit_tests(&paths).await {
         Ok(mut tests) => {
+            // Snapshot file names by test, lowercased since file systems may
+            // not be case sensitive.
+            let mut snapshot_names = HashMap::new();
             tests.iter_mut().for_each(|t| {
-                let (test_inspections, test_errors) = t.run();
-                if !test_inspections.is_empty() {
-                    aggregated_test_inspections.push((t.name.clone(), test_inspections));
+                let start = Instant::now();
+                let mut result = t.run_with_snapshot();
+                if let Some(dir) = &opts.snapshot_dir {
+                    let name = snapshot_path(dir, &t.name)
+                        .to_string_lossy()
+                        .to_lowercase();
+                    match snapshot_names.entry(name) {
+                        Entry::Occupied(entry) => result.errors.push(format!(
+                            "test '{}' has the same snapshot file as test '{}', rename one of them.",
+                            t.name,
+                            entry.get()
+                        )),
+                        Entry::Vacant(entry) => {
+                            entry.insert(t.name.clone());
+                            result.errors.extend(check_snapshot(
+                                dir,
+                                &t.name,
+                                &result.snapshot,
+                                opts.update_snapshots,
+                            ));
+                        }
+                    }
                 }
-                if !test_errors.is_empty() {
-                    println!("test {} ... {}", t.name, "failed".red());
-                    aggregated_test_errors.push((t.name.clone(), test_errors));
-                } else {
+                let duration_secs = start.elapsed().as_secs_f64();
+
+                let passed = result.errors.is_empty();
+                if passed {
                     println!("test {} ... {}", t.name, "passed".green());
+                } else {
+                    println!("test {} ... {}", t.name, "failed".red());
                 }
+                reports.push(TestReport {
+                    name: t.name.clone(),
+                    passed,
+                    duration_secs,
+                    errors: result.errors,
+                    inspections: result.inspections,
+                });
             });
             if tests.is_empty() {
                 println!("{}", "No tests found.".yellow());
@@ -70,27 +254,127 @@ pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
         }
     }
 
-    if !aggregated_test_inspections.is_empty() {
+    let mut reports_written = true;
+    if let Some(path) = &opts.junit_report {
+        reports_written &= write_report(path, junit_report(&reports).as_bytes());
+    }
+    if let Some(path) = &opts.json_report {
+        let failed = reports.iter().filter(|report| !report.passed).count();
+        let report = Report {
+            passed: reports.len() - failed,
+            failed,
+            tests: &reports,
+        };
+        let json = serde_json::to_vec_pretty(&report).expect("Reports serialize to JSON.");
+        reports_written &= write_report(path, &json);
+    }
+
+    if reports.iter().any(|report| !report.inspections.is_empty()) {
         println!("\ninspections:");
-        for (test_name, inspection) in aggregated_test_inspections {
-            println!("\ntest {}:\n", test_name);
-            for inspect in inspection {
+        for report in reports
+            .iter()
+            .filter(|report| !report.inspections.is_empty())
+        {
+            println!("\ntest {}:\n", report.name);
+            for inspect in &report.inspections {
                 println!("{}\n", inspect);
             }
         }
     }
 
-    if !aggregated_test_errors.is_empty() {
+    if reports.iter().any(|report| !report.passed) {
         println!("\nfailures:");
-        for (test_name, fails) in aggregated_test_errors {
-            println!("\ntest {}:\n", test_name);
-            for fail in fails {
+        for report in reports.iter().filter(|report| !report.passed) {
+            println!("\ntest {}:\n", report.name);
+            for fail in &report.errors {
                 println!("{}\n", fail);
             }
         }
 
         exitcode::CONFIG
+    } else if !reports_written {
+        exitcode::IOERR
     } else {
         exitcode::OK
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn junit_report_escapes_results() {
+        let reports = vec![
+            TestReport {
+                name: "passes".into(),
+                passed: true,
+                duration_secs: 0.5,
+                errors: vec![],
+                inspections: vec![],
+            },
+            TestReport {
+                name: "fails <here>".into(),
+                passed: false,
+                duration_secs: 0.25,
+                errors: vec![r#"expected "a" & got 'b'"#.into()],
+                inspections: vec![],
+            },
+        ];
+
+        assert_eq!(
+            junit_report(&reports),
+            indoc::indoc! {r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites name="vector" tests="2" failures="1" time="0.750000">
+                  <testsuite name="vector" tests="2" failures="1" time="0.750000">
+                    <testcase name="passes" classname="vector" time="0.500000"/>
+                    <testcase name="fails &lt;here&gt;" classname="vector" time="0.250000">
+                      <failure message="1 check(s) failed">expected &quot;a&quot; &amp; got &apos;b&apos;</failure>
+                    </testcase>
+                  </testsuite>
+                </testsuites>
+            "#}
+        );
+    }
+
+    #[test]
+    fn snapshot_names_dont_collide() {
+        let dir = Path::new("snapshots");
+        assert_eq!(snapshot_path(dir, "my-test_1"), dir.join("my-test_1.json"));
+        assert_ne!(snapshot_path(dir, "my test"), snapshot_path(dir, "my/test"));
+        assert_eq!(
+            snapshot_path(dir, "../a b"),
+            dir.join("%2E%2E%2Fa%20b.json")
+        );
+    }
+
+    #[test]
+    fn records_and_compares_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut snapshot = TestSnapshot::default();
+        snapshot
+            .outputs
+            .insert("foo".into(), vec![serde_json::json!({"message": "a"})]);
+
+        assert!(check_snapshot(dir.path(), "my test", &snapshot, false).is_empty());
+        assert!(dir.path().join("my%20test.json").exists());
+        assert!(check_snapshot(dir.path(), "my test", &snapshot, false).is_empty());
+
+        let mut changed = snapshot.clone();
+        changed
+            .outputs
+            .insert("foo".into(), vec![serde_json::json!({"message": "b"})]);
+        assert_eq!(
+            check_snapshot(dir.path(), "my test", &changed, false),
+            vec![indoc::indoc! {r#"
+                check 'foo' failed, outputs don't match the snapshot:
+                  - {"message":"a"}
+                  + {"message":"b"}"#}
+            .to_owned()]
+        );
+
+        assert!(check_snapshot(dir.path(), "my test", &changed, true).is_empty());
+        assert!(check_snapshot(dir.path(), "my test", &changed, false).is_empty());
+    }
+}
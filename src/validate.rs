@@ -197,7 +197,11 @@ async fn validate_healthchecks(
                     validated &= !opts.deny_warnings;
                 }
             }
-            Ok(Err(())) => failed(format!("Health check for `{}` failed", name.as_str())),
+            Ok(Err(error)) => failed(format!(
+                "Health check for `{}` failed: {}",
+                name.as_str(),
+                error
+            )),
             Err(error) if error.is_cancelled() => failed(format!(
                 "Health check for `{}` was cancelled",
                 name.as_str()
@@ -349,8 +353,9 @@ impl Formatter {
     }
 
     fn print(&mut self, print: impl AsRef<str>) {
+        // Errors of components may quote the secrets they are configured with.
+        let print = config::secret::redact(print.as_ref());
         let width = print
-            .as_ref()
             .lines()
             .map(|line| {
                 String::from_utf8_lossy(&strip_ansi_escapes::strip(line).unwrap())
@@ -361,6 +366,6 @@ impl Formatter {
             .unwrap_or(0);
         self.max_line_width = width.max(self.max_line_width);
         self.print_space = true;
-        print!("{}", print.as_ref())
+        print!("{}", print)
     }
 }
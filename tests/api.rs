@@ -54,7 +54,7 @@ mod tests {
         net::SocketAddr,
         time::{Duration, Instant},
     };
-    use tokio::sync::oneshot;
+    use tokio::sync::{mpsc, oneshot};
     use tokio_stream::wrappers::IntervalStream;
     use url::Url;
     use vector::{
@@ -133,8 +133,9 @@ mod tests {
     }
 
     fn start_server_with_config(config: &Config) -> Server {
-        let (_, shutdown_rx) = watch::channel(HashMap::new());
-        api::Server::start(&config, shutdown_rx)
+        let (_, watch_rx) = watch::channel(HashMap::new());
+        let (signal_tx, _) = mpsc::channel(1);
+        api::Server::start(&config, watch_rx, signal_tx)
     }
 
     fn make_client(addr: SocketAddr) -> Client {
@@ -567,7 +568,8 @@ mod tests {
 
             tokio::time::delay_for(tokio::time::Duration::from_millis(500)).await;
 
-            let server = api::Server::start(topology.config());
+            let (signal_tx, _) = mpsc::channel(1);
+            let server = api::Server::start(topology.config(), topology.watch(), signal_tx);
             let client = new_subscription_client(server.addr()).await;
             let subscription = client.component_events_out_totals_subscription(500);
 
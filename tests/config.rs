@@ -826,3 +826,196 @@ async fn route() {
 
     assert_eq!(0, warnings.len());
 }
+
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "transforms-remap",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn dead_letter_outputs() {
+    let config = |reroute_dropped: bool| {
+        format!(
+            r#"
+            [sources.in]
+            type = "socket"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+
+            [transforms.parse]
+            type = "remap"
+            inputs = ["in"]
+            source = ". = parse_json!(.message)"
+            drop_on_error = true
+            reroute_dropped = {}
+
+            [sinks.out]
+            type = "socket"
+            mode = "tcp"
+            inputs = ["parse"]
+            encoding = "text"
+            address = "127.0.0.1:9999"
+            dead_letter = true
+
+            [sinks.failed]
+            type = "socket"
+            mode = "tcp"
+            inputs = ["parse.dropped", "out.dead_letter"]
+            encoding = "json"
+            address = "127.0.0.1:9998"
+            "#,
+            reroute_dropped
+        )
+    };
+
+    let warnings = load(&config(true), Some(Format::Toml)).await.unwrap();
+    assert_eq!(0, warnings.len());
+
+    let err = load(&config(false), Some(Format::Toml)).await.unwrap_err();
+    assert_eq!(
+        err,
+        vec!["Input \"parse.dropped\" for sink \"failed\" doesn't exist."]
+    );
+}
+
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "transforms-route",
+    feature = "transforms-log_to_metric",
+    feature = "transforms-metric_to_log",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn named_outputs() {
+    let config = |unmatched_into: &str| {
+        format!(
+            r#"
+            [sources.in]
+            type = "socket"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+
+            [transforms.split]
+            type = "route"
+            inputs = ["in"]
+            route.errors.type = "check_fields"
+            route.errors."level.eq" = "error"
+
+            [transforms.metrics]
+            type = "log_to_metric"
+            inputs = ["split.errors"]
+            metrics = [{{ type = "counter", field = "code" }}]
+
+            [transforms.to_log]
+            type = "metric_to_log"
+            inputs = ["metrics"]
+
+            [sinks.out]
+            type = "socket"
+            mode = "tcp"
+            inputs = ["to_log", "{}"]
+            encoding = "json"
+            address = "127.0.0.1:9999"
+            "#,
+            unmatched_into
+        )
+    };
+
+    let warnings = load(&config("metrics.unmatched"), Some(Format::Toml))
+        .await
+        .unwrap();
+    assert_eq!(0, warnings.len());
+
+    let err = load(&config("metrics.missing"), Some(Format::Toml))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        vec!["Input \"metrics.missing\" for sink \"out\" doesn't exist."]
+    );
+}
+
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "transforms-log_to_metric",
+    feature = "transforms-metric_to_log",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn named_output_type_mismatch() {
+    let err = load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+
+        [transforms.metrics]
+        type = "log_to_metric"
+        inputs = ["in"]
+        metrics = [{ type = "counter", field = "code" }]
+
+        [transforms.to_log]
+        type = "metric_to_log"
+        inputs = ["metrics.unmatched"]
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["to_log"]
+        encoding = "json"
+        address = "127.0.0.1:9999"
+        "#,
+        Some(Format::Toml),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        vec!["Data type mismatch between metrics.unmatched (Log) and to_log (Metric)"]
+    );
+}
+
+#[cfg(all(feature = "sources-socket", feature = "sinks-socket"))]
+#[tokio::test]
+async fn secrets() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("address"), "127.0.0.1:1235\n").unwrap();
+    let config = format!(
+        r#"
+        [secret.files]
+        type = "directory"
+        path = "{}"
+
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "SECRET[files.address]"
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["in"]
+        address = "127.0.0.1:9999"
+        encoding = "text"
+        "#,
+        dir.path().display()
+    );
+
+    let loaded = config::load_from_str(&config, Some(Format::Toml)).unwrap();
+    let debug = format!("{:?}", loaded);
+    assert!(!debug.contains("127.0.0.1:1235"));
+    assert!(debug.contains("**REDACTED**"));
+
+    let err = load(
+        &config.replace("SECRET[files.address]", "SECRET[vault.address]"),
+        Some(Format::Toml),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err,
+        vec![r#"Unknown secret backend in config. name = "vault""#]
+    );
+}
@@ -193,6 +193,79 @@ async fn topology_transform_chain() {
     assert_eq!(vec!["this first second"], res);
 }
 
+#[cfg(feature = "transforms-remap")]
+#[tokio::test]
+async fn topology_transform_dropped_output() {
+    let (mut in1, source1) = source();
+    let (out1, sink1) = sink(10);
+    let (dropped1, sink2) = sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_transform(
+        "t1",
+        &["in1"],
+        vector::transforms::remap::RemapConfig {
+            source: ".number = int!(.message)".into(),
+            drop_on_error: true,
+            drop_on_abort: true,
+            reroute_dropped: true,
+        },
+    );
+    config.add_sink("out1", &["t1"], sink1);
+    config.add_sink("dropped1", &["t1.dropped"], sink2);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    in1.send(Event::from("1")).await.unwrap();
+    in1.send(Event::from("one")).await.unwrap();
+
+    topology.stop().await;
+
+    let res = out1.map(into_message).collect::<Vec<_>>().await;
+    assert_eq!(vec!["1"], res);
+
+    let res = dropped1.collect::<Vec<_>>().await;
+    assert_eq!(res.len(), 1);
+    assert_eq!(into_message(res[0].clone()), "one");
+    assert_eq!(res[0].as_log()["dead_letter.reason"], "error".into());
+    assert_eq!(res[0].as_log()["dead_letter.component_name"], "t1".into());
+}
+
+#[tokio::test]
+async fn topology_sink_dead_letter_output() {
+    let (mut in1, source1) = source();
+    let (mut out1, sink1) = sink(10);
+    let (dead_letter1, sink2) = sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+    config.sinks["out1"].dead_letter = true;
+    config.add_sink("dead_letter1", &["out1.dead_letter"], sink2);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    in1.send(Event::from("delivered")).await.unwrap();
+    in1.send(Event::from("rejected")).await.unwrap();
+
+    let delivered = out1.next().await.unwrap();
+    let rejected = out1.next().await.unwrap();
+    rejected.metadata().reject("Bad request.");
+    drop((delivered, rejected));
+
+    topology.stop().await;
+
+    let res = dead_letter1.collect::<Vec<_>>().await;
+    assert_eq!(res.len(), 1);
+    assert_eq!(into_message(res[0].clone()), "rejected");
+    assert_eq!(
+        res[0].as_log()["dead_letter.message"],
+        "Bad request.".into()
+    );
+    assert_eq!(res[0].as_log()["dead_letter.component_name"], "out1".into());
+}
+
 #[tokio::test]
 async fn topology_remove_one_source() {
     let (mut in1, source1) = source();